@@ -0,0 +1,232 @@
+//! Abbreviations — `:abbreviate`, `:iabbrev`, `:cabbrev` and their
+//! `:unabbreviate` / `:abclear` forms.
+//!
+//! An abbreviation replaces a typed word with other text: after
+//! `:iabbrev teh the`, typing `teh ` in insert mode leaves `the `. The
+//! word is checked when a character that can't be part of it is typed
+//! (a space, punctuation, `Enter`, `Esc`), and replaced only if it starts
+//! the line or follows a non-word character, so `steh ` is left alone.
+//!
+//! | Command                 | Modes                     |
+//! |-------------------------|---------------------------|
+//! | `:ab[breviate]`         | Insert and command line   |
+//! | `:ia[bbrev]`            | Insert                    |
+//! | `:ca[bbrev]`            | Command line              |
+//!
+//! The left-hand side must be a word — letters, digits and `_`. The
+//! replacement is inserted as typed text but is never expanded again, so
+//! `:ab a a b` doesn't loop.
+//!
+//! The editor starts with [`DEFAULTS`] defined, so `teh` is fixed out of
+//! the box; `:iunabbreviate teh` removes it.
+
+/// The modes an abbreviation applies in, or (for `Insert` and `Command`)
+/// the mode the editor is in when a word is typed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbbrevMode {
+    /// Insert mode and the command line (`:abbreviate`).
+    All,
+    /// Insert mode (`:iabbrev`).
+    Insert,
+    /// The command line (`:cabbrev`).
+    Command,
+}
+
+impl AbbrevMode {
+    /// Whether an abbreviation for `self` applies when the editor is in
+    /// `current`.
+    #[must_use]
+    pub fn applies_in(self, current: Self) -> bool {
+        self == Self::All || self == current
+    }
+
+    /// The mode's mark in an `:abbreviate` listing.
+    #[must_use]
+    pub const fn letter(self) -> char {
+        match self {
+            Self::All => '!',
+            Self::Insert => 'i',
+            Self::Command => 'c',
+        }
+    }
+}
+
+/// One abbreviation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Abbrev {
+    /// The modes it applies in.
+    pub mode: AbbrevMode,
+    /// The word that is replaced.
+    pub lhs: String,
+    /// The text it's replaced with.
+    pub rhs: String,
+}
+
+/// The insert-mode abbreviations every editor starts with, as
+/// `(lhs, rhs)`.
+pub const DEFAULTS: &[(&str, &str)] = &[("teh", "the")];
+
+/// A set of abbreviations, at most one per word and mode.
+#[derive(Debug, Default)]
+pub struct AbbrevTable {
+    abbrevs: Vec<Abbrev>,
+}
+
+impl AbbrevTable {
+    /// An empty table.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { abbrevs: Vec::new() }
+    }
+
+    /// A table holding the [`DEFAULTS`].
+    #[must_use]
+    pub fn with_defaults() -> Self {
+        let mut table = Self::new();
+        for (lhs, rhs) in DEFAULTS {
+            table.add(AbbrevMode::Insert, lhs, rhs);
+        }
+        table
+    }
+
+    /// Add an abbreviation, replacing any for the same word and mode.
+    /// Returns `false` (adding nothing) if `lhs` isn't a word.
+    pub fn add(&mut self, mode: AbbrevMode, lhs: &str, rhs: &str) -> bool {
+        if lhs.is_empty() || !lhs.chars().all(is_word_char) {
+            return false;
+        }
+        let abbrev = Abbrev { mode, lhs: lhs.to_string(), rhs: rhs.to_string() };
+        if let Some(existing) = self.abbrevs.iter_mut().find(|a| a.mode == mode && a.lhs == lhs) {
+            *existing = abbrev;
+        } else {
+            self.abbrevs.push(abbrev);
+        }
+        true
+    }
+
+    /// Remove the abbreviations for `lhs` that apply in any of `mode`'s
+    /// modes. Returns `false` if there were none.
+    pub fn remove(&mut self, mode: AbbrevMode, lhs: &str) -> bool {
+        let before = self.abbrevs.len();
+        self.abbrevs.retain(|a| !(a.lhs == lhs && overlaps(a.mode, mode)));
+        self.abbrevs.len() != before
+    }
+
+    /// Remove every abbreviation that applies in any of `mode`'s modes.
+    pub fn clear(&mut self, mode: AbbrevMode) {
+        self.abbrevs.retain(|a| !overlaps(a.mode, mode));
+    }
+
+    /// Whether there are no abbreviations.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.abbrevs.is_empty()
+    }
+
+    /// The replacement for `word` typed in mode `current`. An abbreviation
+    /// for just that mode wins over one for all modes.
+    #[must_use]
+    pub fn lookup(&self, word: &str, current: AbbrevMode) -> Option<&str> {
+        self.abbrevs
+            .iter()
+            .filter(|a| a.lhs == word && a.mode.applies_in(current))
+            .max_by_key(|a| a.mode == current)
+            .map(|a| a.rhs.as_str())
+    }
+
+    /// The replacement for the word that `before` (the text before the
+    /// cursor) ends with, and the word's length in chars.
+    #[must_use]
+    pub fn expansion(&self, before: &str, current: AbbrevMode) -> Option<(&str, usize)> {
+        let word = word_before(before)?;
+        self.lookup(word, current).map(|rhs| (rhs, word.chars().count()))
+    }
+
+    /// The `:abbreviate` listing of abbreviations that apply in any of
+    /// `mode`'s modes and start with `prefix`.
+    #[must_use]
+    pub fn listing(&self, mode: AbbrevMode, prefix: &str) -> Vec<String> {
+        self.abbrevs
+            .iter()
+            .filter(|a| overlaps(a.mode, mode) && a.lhs.starts_with(prefix))
+            .map(|a| format!("{}  {:<12} {}", a.mode.letter(), a.lhs, a.rhs))
+            .collect()
+    }
+}
+
+/// Whether `ch` can be part of an abbreviated word.
+#[must_use]
+pub fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// The word at the end of `before`, if there is one.
+fn word_before(before: &str) -> Option<&str> {
+    let start = before
+        .char_indices()
+        .rev()
+        .take_while(|&(_, ch)| is_word_char(ch))
+        .last()
+        .map(|(i, _)| i)?;
+    Some(&before[start..])
+}
+
+/// Whether `a` and `b` apply in some mode in common.
+fn overlaps(a: AbbrevMode, b: AbbrevMode) -> bool {
+    [AbbrevMode::Insert, AbbrevMode::Command]
+        .iter()
+        .any(|&m| a.applies_in(m) && b.applies_in(m))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_respects_modes() {
+        let mut t = AbbrevTable::new();
+        assert!(t.add(AbbrevMode::Insert, "teh", "the"));
+        assert!(t.add(AbbrevMode::All, "adn", "and"));
+        assert_eq!(t.lookup("teh", AbbrevMode::Insert), Some("the"));
+        assert_eq!(t.lookup("teh", AbbrevMode::Command), None);
+        assert_eq!(t.lookup("adn", AbbrevMode::Command), Some("and"));
+        assert!(t.add(AbbrevMode::Insert, "adn", "AND"));
+        assert_eq!(t.lookup("adn", AbbrevMode::Insert), Some("AND"));
+        assert_eq!(t.lookup("adn", AbbrevMode::Command), Some("and"));
+        assert!(!t.add(AbbrevMode::Insert, "a-b", "x"));
+    }
+
+    #[test]
+    fn expansion_takes_the_word_before_the_cursor() {
+        let mut t = AbbrevTable::new();
+        t.add(AbbrevMode::Insert, "teh", "the");
+        assert_eq!(t.expansion("see teh", AbbrevMode::Insert), Some(("the", 3)));
+        assert_eq!(t.expansion("(teh", AbbrevMode::Insert), Some(("the", 3)));
+        assert_eq!(t.expansion("steh", AbbrevMode::Insert), None);
+        assert_eq!(t.expansion("teh ", AbbrevMode::Insert), None);
+    }
+
+    #[test]
+    fn remove_and_clear() {
+        let mut t = AbbrevTable::new();
+        t.add(AbbrevMode::Insert, "teh", "the");
+        t.add(AbbrevMode::Command, "teh", "tee");
+        assert!(!t.remove(AbbrevMode::Insert, "nope"));
+        assert!(t.remove(AbbrevMode::Insert, "teh"));
+        assert_eq!(t.lookup("teh", AbbrevMode::Command), Some("tee"));
+        assert_eq!(t.listing(AbbrevMode::All, ""), vec!["c  teh          tee"]);
+        t.clear(AbbrevMode::All);
+        assert!(t.is_empty());
+    }
+
+    #[test]
+    fn defaults_fix_teh_in_insert_mode() {
+        let t = AbbrevTable::with_defaults();
+        assert_eq!(t.lookup("teh", AbbrevMode::Insert), Some("the"));
+        assert_eq!(t.lookup("teh", AbbrevMode::Command), None);
+    }
+}
@@ -12,22 +12,33 @@
 //!
 //! - **Columns are char offsets**, not byte offsets. This means column 3 of
 //!   `"café"` is `'é'`, not a byte in the middle of its UTF-8 encoding. Byte
-//!   offsets never leak into the public API.
+//!   offsets never leak into the public API. Cursor motion steps over
+//!   whole grapheme clusters ([`Buffer::grapheme_boundaries`]), so an emoji
+//!   ZWJ sequence or a letter with combining marks is never split.
 //!
 //! - **Line endings are detected on load** and preserved on save. Internally
-//!   the rope stores whatever bytes are in the file. The `line_ending` field
-//!   records the dominant style for use when saving or inserting new lines.
+//!   the rope always uses `\n` — `\r\n` and lone `\r` are normalized away on
+//!   load so a stray `\r` never shows up as content. The `line_ending` field
+//!   records the file's style and is re-applied when saving.
+//!
+//! - **Saves are atomic.** Content goes to a sibling temp file that is
+//!   renamed over the original, so a crash mid-write can't truncate it.
 //!
 //! - **No undo/redo here.** Edit history is a separate concern that will wrap
 //!   Buffer operations with transaction tracking.
 
+use std::collections::HashMap;
 use std::fmt;
 use std::fs;
-use std::io;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use ropey::{Rope, RopeSlice};
+use unicode_segmentation::UnicodeSegmentation;
 
+use crate::expr::Value;
+use crate::options::LARGE_FILE_THRESHOLD;
 use crate::position::{Position, Range};
 
 // ---------------------------------------------------------------------------
@@ -62,19 +73,25 @@ impl LineEnding {
 
     /// Detect the dominant line ending in a string by finding the first
     /// occurrence. Returns `Lf` if no line endings are found.
+    ///
+    /// Only the first [`DETECT_SAMPLE_BYTES`](Self::DETECT_SAMPLE_BYTES) are
+    /// examined, so a huge file without newlines doesn't cost a full scan.
     #[must_use]
     pub fn detect(text: &str) -> Self {
-        for (i, byte) in text.bytes().enumerate() {
+        let bytes = text.as_bytes();
+        let sample = &bytes[..bytes.len().min(Self::DETECT_SAMPLE_BYTES)];
+        for (i, &byte) in sample.iter().enumerate() {
             if byte == b'\n' {
                 // Check if preceded by \r → CrLf.
-                if i > 0 && text.as_bytes()[i - 1] == b'\r' {
+                if i > 0 && sample[i - 1] == b'\r' {
                     return Self::CrLf;
                 }
                 return Self::Lf;
             }
             if byte == b'\r' {
-                // Check if followed by \n → CrLf.
-                if text.as_bytes().get(i + 1) == Some(&b'\n') {
+                // Check if followed by \n → CrLf. Peek past the sample so a
+                // \r\n straddling the boundary is still recognized.
+                if bytes.get(i + 1) == Some(&b'\n') {
                     return Self::CrLf;
                 }
                 return Self::Cr;
@@ -84,6 +101,30 @@ impl LineEnding {
         Self::Lf
     }
 
+    /// How many leading bytes [`detect`](Self::detect) looks at.
+    pub const DETECT_SAMPLE_BYTES: usize = 8 * 1024;
+
+    /// Parse a Vim `fileformat` name: `unix`, `dos`, or `mac`.
+    #[must_use]
+    pub fn from_fileformat(name: &str) -> Option<Self> {
+        match name {
+            "unix" => Some(Self::Lf),
+            "dos" => Some(Self::CrLf),
+            "mac" => Some(Self::Cr),
+            _ => None,
+        }
+    }
+
+    /// The Vim `fileformat` name for this line ending.
+    #[must_use]
+    pub const fn fileformat(self) -> &'static str {
+        match self {
+            Self::Lf => "unix",
+            Self::CrLf => "dos",
+            Self::Cr => "mac",
+        }
+    }
+
     /// Byte length of this line ending.
     #[inline]
     #[must_use]
@@ -106,6 +147,252 @@ impl fmt::Display for LineEnding {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Writing
+// ---------------------------------------------------------------------------
+
+/// How a save reached the disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteMethod {
+    /// Written to a sibling temp file, synced, then renamed over the target.
+    /// The original was never in a partially-written state.
+    Atomic,
+    /// Overwritten in place on request (`:set backupcopy=yes`).
+    InPlace,
+    /// The atomic path failed (e.g. the directory isn't writable), so the
+    /// target was overwritten in place.
+    Direct,
+}
+
+/// Options controlling how a save reaches the disk — the `:set backup`
+/// family. Passed to [`Buffer::save_with`] / [`Buffer::save_as_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Copy the existing file aside before overwriting it (`backup`).
+    pub backup: bool,
+    /// Overwrite the original in place instead of renaming a temp file
+    /// over it (`backupcopy=yes`).
+    pub backupcopy: bool,
+    /// Suffix appended to the file name to form the backup name
+    /// (`backupext`).
+    pub backupext: String,
+    /// Where backups go (`backupdir`). `.` means next to the file, other
+    /// relative paths resolve against the file's directory, and a leading
+    /// `~/` expands to `$HOME`.
+    pub backupdir: String,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            backup: false,
+            backupcopy: false,
+            backupext: "~".to_string(),
+            backupdir: ".".to_string(),
+        }
+    }
+}
+
+impl WriteOptions {
+    /// The backup file for `path`: `{backupdir}/{file name}{backupext}`.
+    #[must_use]
+    pub fn backup_path(&self, path: &Path) -> PathBuf {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(&self.backupext);
+        let parent = path.parent().unwrap_or_else(|| Path::new(""));
+        let dir = if self.backupdir == "." {
+            parent.to_path_buf()
+        } else if let Some(rest) = self.backupdir.strip_prefix("~/") {
+            std::env::var_os("HOME").map_or_else(|| parent.join(&self.backupdir), |home| {
+                PathBuf::from(home).join(rest)
+            })
+        } else {
+            parent.join(&self.backupdir)
+        };
+        dir.join(name)
+    }
+}
+
+/// The modification time of `path`, or `None` if it can't be read.
+fn file_mtime(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Write `content` to `path` according to `opts`.
+///
+/// Makes the backup first (if enabled), then writes atomically unless
+/// `backupcopy` asks for an in-place overwrite.
+fn write_file(path: &Path, content: &str, opts: &WriteOptions) -> io::Result<WriteMethod> {
+    // Renaming over a symlink would replace the link itself with a regular
+    // file. Write through to the real file instead.
+    let target = if fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink()) {
+        fs::canonicalize(path)?
+    } else {
+        path.to_path_buf()
+    };
+
+    if opts.backup && target.is_file() {
+        write_backup(&target, opts).map_err(|e| {
+            io::Error::new(e.kind(), format!("can't make backup file: {e}"))
+        })?;
+    }
+
+    if opts.backupcopy {
+        fs::write(&target, content)?;
+        return Ok(WriteMethod::InPlace);
+    }
+    if write_atomic(&target, content).is_ok() {
+        return Ok(WriteMethod::Atomic);
+    }
+    fs::write(&target, content)?;
+    Ok(WriteMethod::Direct)
+}
+
+/// Copy `path` to its backup location, replacing any previous backup.
+fn write_backup(path: &Path, opts: &WriteOptions) -> io::Result<()> {
+    let backup = opts.backup_path(path);
+    if let Some(dir) = backup.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::copy(path, &backup)?;
+    Ok(())
+}
+
+/// Write to `{path}.tmp-{pid}`, sync it, and rename it over `path`.
+///
+/// The temp file is removed if any step fails.
+fn write_atomic(path: &Path, content: &str) -> io::Result<()> {
+    let tmp = temp_path(path);
+    let result = (|| {
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        // The renamed file replaces the original inode — carry its
+        // permissions over so e.g. executable scripts stay executable.
+        if let Ok(meta) = fs::metadata(path) {
+            fs::set_permissions(&tmp, meta.permissions())?;
+        }
+        fs::rename(&tmp, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// The sibling temp file used by [`write_atomic`]: `{path}.tmp-{pid}`.
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".tmp-{}", std::process::id()));
+    path.with_file_name(name)
+}
+
+// ---------------------------------------------------------------------------
+// BufType
+// ---------------------------------------------------------------------------
+
+/// What kind of buffer it is (Vim's `buftype`).
+///
+/// Only [`Normal`](Self::Normal) buffers are written to their file; the
+/// others are never reported as modified, so quitting doesn't stop for
+/// them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BufType {
+    /// A buffer for a file.
+    #[default]
+    Normal,
+    /// A scratch buffer, not tied to a file (`:set buftype=nofile`).
+    NoFile,
+    /// A buffer that is never written, though it may have a file name.
+    NoWrite,
+    /// A list of quickfix entries.
+    QuickFix,
+    /// A terminal running a job (`:terminal`).
+    Terminal,
+}
+
+impl BufType {
+    /// Parse a `buftype` value: empty for a normal buffer, `nofile`,
+    /// `nowrite`, `quickfix` or `terminal`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "" => Some(Self::Normal),
+            "nofile" => Some(Self::NoFile),
+            "nowrite" => Some(Self::NoWrite),
+            "quickfix" => Some(Self::QuickFix),
+            "terminal" => Some(Self::Terminal),
+            _ => None,
+        }
+    }
+
+    /// The `buftype` value for this type.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Normal => "",
+            Self::NoFile => "nofile",
+            Self::NoWrite => "nowrite",
+            Self::QuickFix => "quickfix",
+            Self::Terminal => "terminal",
+        }
+    }
+
+    /// Whether the buffer's text is saved to its file.
+    #[must_use]
+    pub const fn is_written(self) -> bool {
+        matches!(self, Self::Normal)
+    }
+
+    /// Whether the text can't be edited: quickfix lists and terminals
+    /// belong to the editor, not the user.
+    #[must_use]
+    pub const fn is_read_only(self) -> bool {
+        matches!(self, Self::QuickFix | Self::Terminal)
+    }
+
+    /// The name shown for a buffer of this type that has no file name,
+    /// instead of `[No Name]`.
+    #[must_use]
+    pub const fn unnamed_label(self) -> &'static str {
+        match self {
+            Self::NoFile => "[Scratch]",
+            Self::QuickFix => "[Quickfix List]",
+            Self::Normal | Self::NoWrite | Self::Terminal => "[No Name]",
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// IndentStyle
+// ---------------------------------------------------------------------------
+
+/// How a file is indented, as detected from its text
+/// ([`Buffer::detect_indent`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndentStyle {
+    /// Whether lines are indented with tabs rather than spaces.
+    pub use_tabs: bool,
+    /// Columns per indent level. 0 means one tab stop, as with Vim's
+    /// `shiftwidth=0`.
+    pub width: usize,
+}
+
+/// A buffer's own values of the indent options. `None` follows the
+/// editor-wide value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LocalIndent {
+    /// Columns a tab character takes up (`tabstop`).
+    pub tabstop: Option<u8>,
+    /// Columns per indent level; 0 means one tab stop (`shiftwidth`).
+    pub shiftwidth: Option<usize>,
+    /// Indent with spaces rather than tabs (`expandtab`).
+    pub expandtab: Option<bool>,
+}
+
+/// Non-blank lines [`Buffer::detect_indent`] looks at.
+const INDENT_SAMPLE_LINES: usize = 100;
+
 // ---------------------------------------------------------------------------
 // Buffer
 // ---------------------------------------------------------------------------
@@ -119,6 +406,14 @@ impl fmt::Display for LineEnding {
 /// - The file path (if backed by a file)
 /// - Whether the content has been modified since last save
 /// - The line ending style (for consistent saves)
+/// - Whether the file is large enough to warrant large file mode
+/// - The file's modification time when last read or written (`:checktime`)
+/// - A change counter ([`changedtick`](Self::changedtick))
+/// - Its [`BufType`] — a scratch buffer or terminal isn't saved
+/// - Whether it may be edited (`modifiable`) and written (`readonly`)
+/// - The indentation detected when the file was read ([`IndentStyle`])
+/// - Its own `tabstop`, `shiftwidth` and `expandtab` ([`LocalIndent`])
+/// - Its `b:` variables ([`local_vars`](Self::local_vars)), set by `:let`
 ///
 /// # Coordinate system
 ///
@@ -126,11 +421,26 @@ impl fmt::Display for LineEnding {
 /// scalar values (chars). Use [`pos_to_char_idx`](Self::pos_to_char_idx) and
 /// [`char_idx_to_pos`](Self::char_idx_to_pos) for conversion to rope-native
 /// char indices.
+///
+/// Cloning is cheap: the copy shares the rope's nodes until one side is
+/// edited. The `:s` preview (`inccommand`) relies on this.
+#[derive(Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Buffer {
     rope: Rope,
     path: Option<PathBuf>,
     modified: bool,
     line_ending: LineEnding,
+    is_large: bool,
+    mtime: Option<SystemTime>,
+    changedtick: u64,
+    buf_type: BufType,
+    listed: bool,
+    modifiable: bool,
+    readonly: bool,
+    indent: Option<IndentStyle>,
+    local_indent: LocalIndent,
+    local_vars: HashMap<String, Value>,
 }
 
 impl Buffer {
@@ -144,37 +454,78 @@ impl Buffer {
             path: None,
             modified: false,
             line_ending: LineEnding::Lf,
+            is_large: false,
+            mtime: None,
+            changedtick: 1,
+            buf_type: BufType::Normal,
+            listed: true,
+            modifiable: true,
+            readonly: false,
+            indent: None,
+            local_indent: LocalIndent::default(),
+            local_vars: HashMap::new(),
         }
     }
 
     /// Create a buffer from a string.
+    ///
+    /// The line ending style is detected from `text`, then all line endings
+    /// are normalized to `\n` in the rope.
     #[must_use]
     pub fn from_text(text: &str) -> Self {
+        let line_ending = LineEnding::detect(text);
+        let rope = if text.contains('\r') {
+            Rope::from_str(&normalize_line_endings(text, "\n"))
+        } else {
+            Rope::from_str(text)
+        };
         Self {
-            line_ending: LineEnding::detect(text),
-            rope: Rope::from_str(text),
+            line_ending,
+            rope,
             path: None,
             modified: false,
+            is_large: false,
+            mtime: None,
+            changedtick: 1,
+            buf_type: BufType::Normal,
+            listed: true,
+            modifiable: true,
+            readonly: false,
+            indent: None,
+            local_indent: LocalIndent::default(),
+            local_vars: HashMap::new(),
         }
     }
 
     /// Load a buffer from a file.
     ///
-    /// Detects line endings from the file content. The buffer starts in an
-    /// unmodified state.
+    /// Detects line endings and [indentation](Self::indent) from the file
+    /// content. The buffer starts in an unmodified state. Files larger than [`LARGE_FILE_THRESHOLD`] are
+    /// flagged with [`is_large`](Self::is_large), and files without write
+    /// permission are [`readonly`](Self::is_readonly).
     ///
     /// # Errors
     ///
     /// Returns an error if the file cannot be read or contains invalid UTF-8.
     pub fn from_file(path: &Path) -> io::Result<Self> {
+        Self::from_file_with_threshold(path, LARGE_FILE_THRESHOLD)
+    }
+
+    /// Load a buffer from a file, flagging it as large when its byte size
+    /// exceeds `threshold`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or contains invalid UTF-8.
+    pub fn from_file_with_threshold(path: &Path, threshold: u64) -> io::Result<Self> {
         let text = fs::read_to_string(path)?;
-        let line_ending = LineEnding::detect(&text);
-        Ok(Self {
-            rope: Rope::from_str(&text),
-            path: Some(path.to_path_buf()),
-            modified: false,
-            line_ending,
-        })
+        let mut buf = Self::from_text(&text);
+        buf.path = Some(path.to_path_buf());
+        buf.is_large = text.len() as u64 > threshold;
+        buf.mtime = file_mtime(path);
+        buf.readonly = fs::metadata(path).is_ok_and(|m| m.permissions().readonly());
+        buf.indent = buf.detect_indent();
+        Ok(buf)
     }
 
     // -- Text access --------------------------------------------------------
@@ -210,6 +561,24 @@ impl Buffer {
         self.rope.len_bytes()
     }
 
+    /// Number of words: runs of non-whitespace characters, as `wc -w`
+    /// counts them. Computed on demand by walking the rope's chunks.
+    #[must_use]
+    pub fn word_count(&self) -> usize {
+        let mut words = 0;
+        let mut in_word = false;
+        for chunk in self.rope.chunks() {
+            for ch in chunk.chars() {
+                let word_char = !ch.is_whitespace();
+                if word_char && !in_word {
+                    words += 1;
+                }
+                in_word = word_char;
+            }
+        }
+        words
+    }
+
     /// True when the buffer contains no text.
     #[inline]
     #[must_use]
@@ -275,6 +644,57 @@ impl Buffer {
         self.pos_to_char_idx(pos).map(|idx| self.rope.char(idx))
     }
 
+    /// Column boundaries of the grapheme clusters in a line: the start
+    /// column of each cluster, followed by the content length. An emoji
+    /// ZWJ sequence or a letter with combining marks is one cluster of
+    /// several chars. Empty if the line doesn't exist.
+    #[must_use]
+    pub fn grapheme_boundaries(&self, line: usize) -> Vec<usize> {
+        let Some(content_len) = self.line_content_len(line) else {
+            return Vec::new();
+        };
+        let text: String = self.rope.line(line).chars().take(content_len).collect();
+        let mut cols = Vec::with_capacity(content_len + 1);
+        if text.is_ascii() {
+            cols.extend(0..=content_len);
+            return cols;
+        }
+        let mut col = 0;
+        for grapheme in text.graphemes(true) {
+            cols.push(col);
+            col += grapheme.chars().count();
+        }
+        cols.push(col);
+        cols
+    }
+
+    /// The start column of the grapheme cluster containing `pos`. A column
+    /// at or past the end of the line's content is returned unchanged.
+    #[must_use]
+    pub fn grapheme_start_col(&self, pos: Position) -> usize {
+        let cols = self.grapheme_boundaries(pos.line);
+        match cols.binary_search(&pos.col) {
+            Ok(_) | Err(0) => pos.col,
+            Err(i) if i == cols.len() => pos.col,
+            Err(i) => cols[i - 1],
+        }
+    }
+
+    /// The grapheme cluster containing `pos` — one or more chars that
+    /// display as a single character. Returns `None` if the position is
+    /// past the line's content.
+    #[must_use]
+    pub fn grapheme_at(&self, pos: Position) -> Option<String> {
+        let cols = self.grapheme_boundaries(pos.line);
+        let i = match cols.binary_search(&pos.col) {
+            Ok(i) => i,
+            Err(i) => i.checked_sub(1)?,
+        };
+        let (&start, &end) = (cols.get(i)?, cols.get(i + 1)?);
+        let line = self.rope.line(pos.line);
+        Some(line.slice(start..end).to_string())
+    }
+
     /// Get a slice of text for the given range. Returns `None` if either
     /// endpoint is out of bounds.
     #[must_use]
@@ -367,6 +787,7 @@ impl Buffer {
             .expect("insert position out of bounds");
         self.rope.insert(idx, text);
         self.modified = true;
+        self.changedtick += 1;
     }
 
     /// Insert a single character at a position.
@@ -380,6 +801,7 @@ impl Buffer {
             .expect("insert_char position out of bounds");
         self.rope.insert_char(idx, ch);
         self.modified = true;
+        self.changedtick += 1;
     }
 
     /// Delete the text in a range.
@@ -402,6 +824,7 @@ impl Buffer {
             .expect("delete range end out of bounds");
         self.rope.remove(start..end);
         self.modified = true;
+        self.changedtick += 1;
     }
 
     /// Replace the text in a range with new text.
@@ -422,6 +845,7 @@ impl Buffer {
         self.rope.remove(start..end);
         self.rope.insert(start, text);
         self.modified = true;
+        self.changedtick += 1;
     }
 
     // -- Metadata -----------------------------------------------------------
@@ -439,11 +863,152 @@ impl Buffer {
         self.path = Some(path);
     }
 
-    /// True if the buffer has been modified since the last save (or creation).
+    /// Give the buffer a new file name without writing it (`:file`).
+    ///
+    /// The path is kept exactly as given. The buffer counts as modified —
+    /// the new file may not exist or may hold something else — and the
+    /// old file's modification time is forgotten, so
+    /// [`check_time`](Self::check_time) doesn't compare it to the new file.
+    pub fn rename(&mut self, path: PathBuf) {
+        self.path = Some(path);
+        self.mtime = None;
+        self.modified = true;
+    }
+
+    /// True if the buffer has been modified since the last save (or
+    /// creation). Always false for a buffer that isn't written
+    /// ([`BufType::is_written`]), as there is nothing to save.
     #[inline]
     #[must_use]
     pub const fn is_modified(&self) -> bool {
-        self.modified
+        self.modified && self.buf_type.is_written()
+    }
+
+    /// What kind of buffer this is.
+    #[inline]
+    #[must_use]
+    pub const fn buf_type(&self) -> BufType {
+        self.buf_type
+    }
+
+    /// Change the kind of buffer (`:set buftype`).
+    #[inline]
+    pub const fn set_buf_type(&mut self, buf_type: BufType) {
+        self.buf_type = buf_type;
+    }
+
+    /// Whether the buffer shows in `:ls` (the `buflisted` option).
+    #[inline]
+    #[must_use]
+    pub const fn is_listed(&self) -> bool {
+        self.listed
+    }
+
+    /// Show or hide the buffer in `:ls` (`:set buflisted`).
+    #[inline]
+    pub const fn set_listed(&mut self, listed: bool) {
+        self.listed = listed;
+    }
+
+    /// Whether the text may be changed (the `modifiable` option). The
+    /// editor refuses edits to a buffer that isn't.
+    #[inline]
+    #[must_use]
+    pub const fn is_modifiable(&self) -> bool {
+        self.modifiable
+    }
+
+    /// Allow or forbid changes to the text (`:set modifiable`).
+    #[inline]
+    pub const fn set_modifiable(&mut self, modifiable: bool) {
+        self.modifiable = modifiable;
+    }
+
+    /// Whether writing the buffer to its file needs `!` (the `readonly`
+    /// option). The text can still be changed.
+    #[inline]
+    #[must_use]
+    pub const fn is_readonly(&self) -> bool {
+        self.readonly
+    }
+
+    /// Set or clear the `readonly` option.
+    #[inline]
+    pub const fn set_readonly(&mut self, readonly: bool) {
+        self.readonly = readonly;
+    }
+
+    /// The indentation detected in the file, if it had indented lines.
+    #[inline]
+    #[must_use]
+    pub const fn indent(&self) -> Option<IndentStyle> {
+        self.indent
+    }
+
+    /// Set or forget the buffer's indentation style.
+    #[inline]
+    pub const fn set_indent(&mut self, indent: Option<IndentStyle>) {
+        self.indent = indent;
+    }
+
+    /// The buffer's own `tabstop`, `shiftwidth` and `expandtab`.
+    #[inline]
+    #[must_use]
+    pub const fn local_indent(&self) -> LocalIndent {
+        self.local_indent
+    }
+
+    /// Mutable access to the buffer's own indent options, for `:set` and
+    /// `:setlocal`.
+    #[inline]
+    pub const fn local_indent_mut(&mut self) -> &mut LocalIndent {
+        &mut self.local_indent
+    }
+
+    /// The buffer's `b:` variables, by name without the `b:` prefix. They
+    /// live as long as the buffer does.
+    #[inline]
+    #[must_use]
+    pub const fn local_vars(&self) -> &HashMap<String, Value> {
+        &self.local_vars
+    }
+
+    /// Mutable access to the `b:` variables, for `:let` and `:unlet`.
+    #[inline]
+    pub const fn local_vars_mut(&mut self) -> &mut HashMap<String, Value> {
+        &mut self.local_vars
+    }
+
+    /// Work out how the text is indented from its first
+    /// [`INDENT_SAMPLE_LINES`] non-blank lines: tabs if more lines start
+    /// with a tab than with a space, otherwise spaces, as many per level as
+    /// the greatest common divisor of the space indents. `None` when no
+    /// sampled line is indented.
+    #[must_use]
+    pub fn detect_indent(&self) -> Option<IndentStyle> {
+        let mut tab_lines = 0;
+        let mut space_lines = 0;
+        let mut width = 0;
+        let sample = self.rope.lines().filter(|l| l.chars().any(|c| !c.is_whitespace()));
+        for line in sample.take(INDENT_SAMPLE_LINES) {
+            match line.chars().next() {
+                Some('\t') => tab_lines += 1,
+                Some(' ') => {
+                    let spaces = line.chars().take_while(|&c| c == ' ').count();
+                    // Spaces before a tab are alignment, not an indent.
+                    if line.chars().nth(spaces) != Some('\t') {
+                        space_lines += 1;
+                        width = gcd(width, spaces);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if tab_lines == 0 && space_lines == 0 {
+            return None;
+        }
+        let use_tabs = tab_lines > space_lines;
+        Some(IndentStyle { use_tabs, width: if use_tabs { 0 } else { width } })
     }
 
     /// Mark the buffer as saved (not modified). Called after a successful
@@ -460,6 +1025,26 @@ impl Buffer {
         self.modified = true;
     }
 
+    /// Counter bumped by every change to the text (Vim's `b:changedtick`).
+    ///
+    /// Starts at 1 and never goes down, so it doubles as a document
+    /// version number — e.g. for language servers.
+    #[inline]
+    #[must_use]
+    pub const fn changedtick(&self) -> u64 {
+        self.changedtick
+    }
+
+    /// True if the file exceeded the large file threshold when loaded.
+    ///
+    /// The editor uses this to skip undo history and syntax highlighting,
+    /// both of which scale with file size.
+    #[inline]
+    #[must_use]
+    pub const fn is_large(&self) -> bool {
+        self.is_large
+    }
+
     /// The detected (or configured) line ending style.
     #[inline]
     #[must_use]
@@ -467,30 +1052,110 @@ impl Buffer {
         self.line_ending
     }
 
-    /// Override the line ending style. Affects future saves but does not
-    /// modify the current buffer content.
+    /// Override the line ending style (`:set fileformat`). Affects future
+    /// saves but does not modify the current buffer content.
     #[inline]
     pub const fn set_line_ending(&mut self, ending: LineEnding) {
         self.line_ending = ending;
     }
 
+    // -- External changes ---------------------------------------------------
+
+    /// True if the file on disk was modified since this buffer last read or
+    /// wrote it.
+    ///
+    /// Returns `false` for buffers without a path, files that were never
+    /// loaded from disk, and files that can no longer be read.
+    #[must_use]
+    pub fn check_time(&self) -> bool {
+        let Some(path) = self.path.as_deref() else {
+            return false;
+        };
+        match (self.mtime, file_mtime(path)) {
+            (Some(known), Some(current)) => known != current,
+            _ => false,
+        }
+    }
+
+    /// Accept the file's current modification time as seen, so
+    /// [`check_time`](Self::check_time) stops reporting the change.
+    pub fn sync_mtime(&mut self) {
+        if let Some(path) = self.path.as_deref() {
+            self.mtime = file_mtime(path);
+        }
+    }
+
+    /// Re-read the buffer from its file, discarding any changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no path is set or the file cannot be read.
+    pub fn reload(&mut self) -> io::Result<()> {
+        self.reload_with_threshold(LARGE_FILE_THRESHOLD)
+    }
+
+    /// Re-read the buffer from its file, re-flagging it as
+    /// [large](Self::is_large) when its byte size now exceeds `threshold`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no path is set or the file cannot be read.
+    pub fn reload_with_threshold(&mut self, threshold: u64) -> io::Result<()> {
+        let path = self
+            .path
+            .as_ref()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "buffer has no file path"))?
+            .clone();
+        let text = fs::read_to_string(&path)?;
+        self.line_ending = LineEnding::detect(&text);
+        self.rope = Rope::from_str(&normalize_line_endings(&text, "\n"));
+        self.is_large = text.len() as u64 > threshold;
+        self.modified = false;
+        self.mtime = file_mtime(&path);
+        self.changedtick += 1;
+        self.indent = self.detect_indent();
+        Ok(())
+    }
+
     // -- File I/O -----------------------------------------------------------
 
+    /// Save the buffer to its associated file path with default
+    /// [`WriteOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no path is set or the write fails.
+    pub fn save(&mut self) -> io::Result<WriteMethod> {
+        self.save_with(&WriteOptions::default())
+    }
+
     /// Save the buffer to its associated file path.
     ///
     /// Converts line endings to match the buffer's [`line_ending`](Self::line_ending)
     /// style before writing. Marks the buffer as unmodified on success.
+    /// See [`save_as_with`](Self::save_as_with) for how the write reaches
+    /// the disk.
     ///
     /// # Errors
     ///
     /// Returns an error if no path is set or the write fails.
-    pub fn save(&mut self) -> io::Result<()> {
+    pub fn save_with(&mut self, opts: &WriteOptions) -> io::Result<WriteMethod> {
         let path = self
             .path
             .as_ref()
             .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "buffer has no file path"))?
             .clone();
-        self.save_as(&path)
+        self.save_as_with(&path, opts)
+    }
+
+    /// Save the buffer to a specific path with default [`WriteOptions`],
+    /// updating the stored path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the write fails.
+    pub fn save_as(&mut self, path: &Path) -> io::Result<WriteMethod> {
+        self.save_as_with(path, &WriteOptions::default())
     }
 
     /// Save the buffer to a specific path, updating the stored path.
@@ -498,15 +1163,25 @@ impl Buffer {
     /// Converts line endings to match the buffer's [`line_ending`](Self::line_ending)
     /// style before writing. Marks the buffer as unmodified on success.
     ///
+    /// With `opts.backup`, the existing file is first copied to
+    /// [`WriteOptions::backup_path`]. The content is then written to a
+    /// sibling temp file which is renamed over `path`, so a crash mid-write
+    /// leaves the original intact. If that fails, the file is written in
+    /// place and [`WriteMethod::Direct`] is returned so the caller can warn
+    /// about it. `opts.backupcopy` skips the rename and always writes in
+    /// place.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the write fails.
-    pub fn save_as(&mut self, path: &Path) -> io::Result<()> {
+    /// Returns an error if the backup or the write fails. A failed backup
+    /// leaves the original file untouched.
+    pub fn save_as_with(&mut self, path: &Path, opts: &WriteOptions) -> io::Result<WriteMethod> {
         let content = self.text_with_line_endings();
-        fs::write(path, &content)?;
+        let method = write_file(path, &content, opts)?;
         self.path = Some(path.to_path_buf());
         self.modified = false;
-        Ok(())
+        self.mtime = file_mtime(path);
+        Ok(method)
     }
 
     /// Produce the full buffer text with line endings converted to the
@@ -545,6 +1220,9 @@ impl fmt::Debug for Buffer {
             .field("chars", &self.len_chars())
             .field("modified", &self.modified)
             .field("line_ending", &self.line_ending)
+            .field("is_large", &self.is_large)
+            .field("buf_type", &self.buf_type)
+            .field("listed", &self.listed)
             .field("path", &self.path)
             .finish_non_exhaustive()
     }
@@ -554,6 +1232,11 @@ impl fmt::Debug for Buffer {
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Greatest common divisor, with `gcd(0, n) == n`.
+const fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
 /// Normalize all line endings in `text` to `target`. Handles \r\n, \r, and \n
 /// in any combination, converting all to the target ending.
 fn normalize_line_endings(text: &str, target: &str) -> String {
@@ -656,6 +1339,28 @@ mod tests {
         assert_eq!(LineEnding::detect("a\r\nb\nc"), LineEnding::CrLf);
     }
 
+    #[test]
+    fn line_ending_detect_only_samples_prefix() {
+        let mut text = "x".repeat(LineEnding::DETECT_SAMPLE_BYTES);
+        text.push_str("\r\n");
+        assert_eq!(LineEnding::detect(&text), LineEnding::Lf);
+        // A \r\n straddling the sample boundary is still CrLf.
+        let mut text = "x".repeat(LineEnding::DETECT_SAMPLE_BYTES - 1);
+        text.push_str("\r\n");
+        assert_eq!(LineEnding::detect(&text), LineEnding::CrLf);
+    }
+
+    #[test]
+    fn line_ending_fileformat_names() {
+        for ending in [LineEnding::Lf, LineEnding::CrLf, LineEnding::Cr] {
+            assert_eq!(LineEnding::from_fileformat(ending.fileformat()), Some(ending));
+        }
+        assert_eq!(LineEnding::Lf.fileformat(), "unix");
+        assert_eq!(LineEnding::CrLf.fileformat(), "dos");
+        assert_eq!(LineEnding::Cr.fileformat(), "mac");
+        assert_eq!(LineEnding::from_fileformat("windows"), None);
+    }
+
     #[test]
     fn line_ending_as_str() {
         assert_eq!(LineEnding::Lf.as_str(), "\n");
@@ -707,6 +1412,17 @@ mod tests {
         assert_eq!(buf.line_ending(), LineEnding::CrLf);
     }
 
+    #[test]
+    fn from_text_strips_cr() {
+        let buf = Buffer::from_text("hello\r\nworld\r\n");
+        assert_eq!(buf.contents(), "hello\nworld\n");
+        assert_eq!(buf.line(0).unwrap().to_string(), "hello\n");
+
+        let buf = Buffer::from_text("hello\rworld\r");
+        assert_eq!(buf.line_ending(), LineEnding::Cr);
+        assert_eq!(buf.contents(), "hello\nworld\n");
+    }
+
     #[test]
     fn from_text_no_trailing_newline() {
         let buf = Buffer::from_text("hello");
@@ -783,6 +1499,15 @@ mod tests {
         assert_eq!(buf.line_content_len(5), None);
     }
 
+    #[test]
+    fn word_count_counts_whitespace_separated_runs() {
+        assert_eq!(Buffer::new().word_count(), 0);
+        assert_eq!(Buffer::from_text("  \n\t ").word_count(), 0);
+        assert_eq!(Buffer::from_text("one two\nthree").word_count(), 3);
+        assert_eq!(Buffer::from_text("  a,b  c\n\n d.  ").word_count(), 3);
+        assert_eq!(Buffer::from_text("héllo wörld").word_count(), 2);
+    }
+
     // -- Character access ---------------------------------------------------
 
     #[test]
@@ -806,6 +1531,21 @@ mod tests {
         assert_eq!(buf.char_at(Position::new(1, 0)), None);
     }
 
+    #[test]
+    fn grapheme_boundaries_keep_clusters_together() {
+        // The family emoji is three people joined by two ZWJs: 5 chars.
+        let buf = Buffer::from_text("a\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F466}b\ne\u{301}x\n");
+        assert_eq!(buf.grapheme_boundaries(0), vec![0, 1, 6, 7]);
+        assert_eq!(buf.grapheme_boundaries(1), vec![0, 2, 3]);
+        assert_eq!(buf.grapheme_boundaries(5), Vec::<usize>::new());
+        assert_eq!(buf.grapheme_start_col(Position::new(0, 3)), 1);
+        assert_eq!(buf.grapheme_start_col(Position::new(0, 6)), 6);
+        assert_eq!(buf.grapheme_start_col(Position::new(1, 1)), 0);
+        assert_eq!(buf.grapheme_at(Position::new(1, 1)).as_deref(), Some("e\u{301}"));
+        assert_eq!(buf.grapheme_at(Position::new(0, 6)).as_deref(), Some("b"));
+        assert_eq!(buf.grapheme_at(Position::new(0, 7)), None);
+    }
+
     // -- Slice access -------------------------------------------------------
 
     #[test]
@@ -1172,6 +1912,21 @@ mod tests {
         assert_eq!(buf.path(), Some(Path::new("/tmp/test.txt")));
     }
 
+    #[test]
+    fn changedtick_counts_edits() {
+        let mut buf = Buffer::from_text("hello");
+        assert_eq!(buf.changedtick(), 1);
+        buf.insert_char(Position::new(0, 0), 'x');
+        buf.insert(Position::new(0, 0), "ab");
+        buf.delete(Range::new(Position::new(0, 0), Position::new(0, 1)));
+        buf.replace(Range::new(Position::new(0, 0), Position::new(0, 1)), "z");
+        assert_eq!(buf.changedtick(), 5);
+        // An empty delete changes nothing.
+        buf.delete(Range::new(Position::new(0, 2), Position::new(0, 2)));
+        buf.mark_saved();
+        assert_eq!(buf.changedtick(), 5);
+    }
+
     #[test]
     fn modified_tracking() {
         let mut buf = Buffer::from_text("hello");
@@ -1195,6 +1950,20 @@ mod tests {
         assert!(buf.is_modified());
     }
 
+    #[test]
+    fn unwritten_buffers_are_never_modified() {
+        let mut buf = Buffer::from_text("notes");
+        buf.set_buf_type(BufType::NoFile);
+        buf.insert(Position::ZERO, "x");
+        assert!(!buf.is_modified());
+        buf.set_buf_type(BufType::Normal);
+        assert!(buf.is_modified());
+        for bt in [BufType::Normal, BufType::NoFile, BufType::NoWrite, BufType::QuickFix, BufType::Terminal] {
+            assert_eq!(BufType::from_name(bt.name()), Some(bt));
+        }
+        assert_eq!(BufType::from_name("help"), None);
+    }
+
     #[test]
     fn line_ending_configurable() {
         let mut buf = Buffer::new();
@@ -1242,6 +2011,326 @@ mod tests {
         let _ = fs::remove_dir(&dir);
     }
 
+    #[test]
+    fn crlf_file_round_trips_unchanged() {
+        let dir = std::env::temp_dir().join("n_editor_test_crlf_roundtrip");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("dos.txt");
+        let original = "first\r\nsecond\r\n\r\nlast\r\n";
+        fs::write(&path, original).unwrap();
+
+        let mut buf = Buffer::from_file(&path).unwrap();
+        assert_eq!(buf.line_ending(), LineEnding::CrLf);
+        assert!(!buf.contents().contains('\r'));
+        assert_eq!(buf.line_content_len(0), Some(5));
+        buf.save().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn unix_format_strips_cr_on_save() {
+        let dir = std::env::temp_dir().join("n_editor_test_crlf_to_unix");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("dos.txt");
+        fs::write(&path, "a\r\nb\r\nc").unwrap();
+
+        let mut buf = Buffer::from_file(&path).unwrap();
+        buf.set_line_ending(LineEnding::Lf);
+        buf.save().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a\nb\nc");
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn save_takes_atomic_rename_path() {
+        let dir = std::env::temp_dir().join("n_editor_test_atomic");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("atomic.txt");
+        fs::write(&path, "old contents").unwrap();
+
+        let mut buf = Buffer::from_file(&path).unwrap();
+        buf.insert(Position::ZERO, "new ");
+        assert_eq!(buf.save().unwrap(), WriteMethod::Atomic);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new old contents");
+        // The temp file was renamed away, not left behind.
+        assert!(!temp_path(&path).exists());
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_preserves_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join("n_editor_test_atomic_perms");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("script.sh");
+        fs::write(&path, "echo hi\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut buf = Buffer::from_file(&path).unwrap();
+        buf.save().unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_writes_through_symlink() {
+        let dir = std::env::temp_dir().join("n_editor_test_atomic_link");
+        let _ = fs::create_dir_all(&dir);
+        let real = dir.join("real.txt");
+        let link = dir.join("link.txt");
+        let _ = fs::remove_file(&link);
+        fs::write(&real, "before").unwrap();
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        let mut buf = Buffer::from_text("after");
+        buf.save_as(&link).unwrap();
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(&real).unwrap(), "after");
+
+        let _ = fs::remove_file(&link);
+        let _ = fs::remove_file(&real);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn failed_save_cleans_up_temp_file() {
+        // Renaming a file over a directory fails, and so does the direct
+        // fallback — the temp file must not be left behind.
+        let dir = std::env::temp_dir().join("n_editor_test_atomic_fail");
+        let target = dir.join("is_a_dir");
+        let _ = fs::create_dir_all(&target);
+
+        let mut buf = Buffer::from_text("hello");
+        assert!(buf.save_as(&target).is_err());
+        assert!(buf.path().is_none());
+        assert!(!temp_path(&target).exists());
+
+        let _ = fs::remove_dir(&target);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn backup_created_before_save() {
+        let dir = std::env::temp_dir().join("n_editor_test_backup");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("notes.txt");
+        fs::write(&path, "version 1").unwrap();
+        let opts = WriteOptions { backup: true, ..WriteOptions::default() };
+        let backup = dir.join("notes.txt~");
+        assert_eq!(opts.backup_path(&path), backup);
+
+        let mut buf = Buffer::from_file(&path).unwrap();
+        buf.replace(Range::new(Position::new(0, 8), Position::new(0, 9)), "2");
+        buf.save_with(&opts).unwrap();
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "version 1");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "version 2");
+
+        // Re-saving overwrites the backup with the previous version.
+        buf.replace(Range::new(Position::new(0, 8), Position::new(0, 9)), "3");
+        buf.save_with(&opts).unwrap();
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "version 2");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "version 3");
+
+        let _ = fs::remove_file(&backup);
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn no_backup_by_default() {
+        let dir = std::env::temp_dir().join("n_editor_test_nobackup");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("plain.txt");
+        fs::write(&path, "old").unwrap();
+
+        let mut buf = Buffer::from_text("new");
+        buf.save_as(&path).unwrap();
+        assert!(!dir.join("plain.txt~").exists());
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn backup_ext_and_dir() {
+        let dir = std::env::temp_dir().join("n_editor_test_backupdir");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("code.rs");
+        fs::write(&path, "old").unwrap();
+        let opts = WriteOptions {
+            backup: true,
+            backupext: ".bak".to_string(),
+            backupdir: "backups".to_string(),
+            ..WriteOptions::default()
+        };
+        let backup = dir.join("backups").join("code.rs.bak");
+        assert_eq!(opts.backup_path(&path), backup);
+
+        let mut buf = Buffer::from_text("new");
+        buf.save_as_with(&path, &opts).unwrap();
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "old");
+
+        let _ = fs::remove_file(&backup);
+        let _ = fs::remove_dir(dir.join("backups"));
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn backup_skipped_for_new_file() {
+        let dir = std::env::temp_dir().join("n_editor_test_backup_new");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("fresh.txt");
+        let _ = fs::remove_file(&path);
+        let opts = WriteOptions { backup: true, ..WriteOptions::default() };
+
+        let mut buf = Buffer::from_text("hello");
+        buf.save_as_with(&path, &opts).unwrap();
+        assert!(!opts.backup_path(&path).exists());
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn backupcopy_writes_in_place() {
+        let dir = std::env::temp_dir().join("n_editor_test_backupcopy");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("inplace.txt");
+        fs::write(&path, "old").unwrap();
+        let opts = WriteOptions { backup: true, backupcopy: true, ..WriteOptions::default() };
+
+        let mut buf = Buffer::from_text("new");
+        assert_eq!(buf.save_as_with(&path, &opts).unwrap(), WriteMethod::InPlace);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(fs::read_to_string(opts.backup_path(&path)).unwrap(), "old");
+
+        let _ = fs::remove_file(opts.backup_path(&path));
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn temp_path_is_sibling() {
+        let tmp = temp_path(Path::new("/some/dir/file.txt"));
+        assert_eq!(tmp.parent(), Some(Path::new("/some/dir")));
+        let name = tmp.file_name().unwrap().to_str().unwrap();
+        assert_eq!(name, format!("file.txt.tmp-{}", std::process::id()));
+    }
+
+    #[test]
+    fn check_time_detects_external_change() {
+        let dir = std::env::temp_dir().join("n_editor_test_checktime");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("watched.txt");
+        fs::write(&path, "one").unwrap();
+
+        let mut buf = Buffer::from_file(&path).unwrap();
+        assert!(!buf.check_time());
+
+        // Push the mtime forward explicitly — filesystem timestamps can be
+        // too coarse to distinguish two writes in quick succession.
+        fs::write(&path, "two").unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+        assert!(buf.check_time());
+
+        buf.reload().unwrap();
+        assert_eq!(buf.contents(), "two");
+        assert!(!buf.check_time());
+
+        // Our own saves don't count as external changes.
+        buf.insert(Position::ZERO, "x");
+        buf.save().unwrap();
+        assert!(!buf.check_time());
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn check_time_without_file() {
+        assert!(!Buffer::from_text("scratch").check_time());
+        let mut buf = Buffer::new();
+        buf.set_path(PathBuf::from("/nonexistent/file.txt"));
+        assert!(!buf.check_time());
+    }
+
+    #[test]
+    fn sync_mtime_acknowledges_change() {
+        let dir = std::env::temp_dir().join("n_editor_test_sync_mtime");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("acked.txt");
+        fs::write(&path, "one").unwrap();
+
+        let mut buf = Buffer::from_file(&path).unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+        assert!(buf.check_time());
+        buf.sync_mtime();
+        assert!(!buf.check_time());
+        assert_eq!(buf.contents(), "one");
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn file_without_write_permission_is_readonly() {
+        let dir = std::env::temp_dir().join("n_editor_test_readonly");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("locked.txt");
+        let _ = fs::remove_file(&path);
+        fs::write(&path, "text").unwrap();
+        assert!(!Buffer::from_file(&path).unwrap().is_readonly());
+
+        let mut perms = fs::metadata(&path).unwrap().permissions();
+        perms.set_readonly(true);
+        fs::set_permissions(&path, perms).unwrap();
+        let buf = Buffer::from_file(&path).unwrap();
+        assert!(buf.is_readonly());
+        assert!(buf.is_modifiable());
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn rename_marks_modified_and_forgets_mtime() {
+        let dir = std::env::temp_dir().join("n_editor_test_rename");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("old.txt");
+        let other = dir.join("other.txt");
+        fs::write(&path, "one").unwrap();
+        fs::write(&other, "two").unwrap();
+
+        let mut buf = Buffer::from_file(&path).unwrap();
+        buf.rename(PathBuf::from("../other.txt"));
+        assert_eq!(buf.path(), Some(Path::new("../other.txt")));
+        assert!(buf.is_modified());
+        buf.set_path(other.clone());
+        assert!(!buf.check_time());
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&other);
+        let _ = fs::remove_dir(&dir);
+    }
+
     #[test]
     fn save_no_path_errors() {
         let mut buf = Buffer::from_text("hello");
@@ -1249,6 +2338,51 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn from_file_flags_large_files() {
+        let dir = std::env::temp_dir().join("n_editor_test_large");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("large.txt");
+        fs::write(&path, "0123456789").unwrap();
+
+        let small = Buffer::from_file_with_threshold(&path, 10).unwrap();
+        assert!(!small.is_large());
+        let large = Buffer::from_file_with_threshold(&path, 9).unwrap();
+        assert!(large.is_large());
+        // Large buffers remain fully editable.
+        let mut large = large;
+        large.insert(Position::ZERO, "x");
+        assert_eq!(large.contents(), "x0123456789");
+
+        assert!(!Buffer::from_file(&path).unwrap().is_large());
+        assert!(!Buffer::from_text("hello").is_large());
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn reload_reflags_large_files() {
+        let dir = std::env::temp_dir().join("n_editor_test_reload_large");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("growing.txt");
+        fs::write(&path, "01234").unwrap();
+
+        let mut buf = Buffer::from_file_with_threshold(&path, 9).unwrap();
+        assert!(!buf.is_large());
+
+        fs::write(&path, "0123456789").unwrap();
+        buf.reload_with_threshold(9).unwrap();
+        assert!(buf.is_large());
+
+        fs::write(&path, "01").unwrap();
+        buf.reload_with_threshold(9).unwrap();
+        assert!(!buf.is_large());
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_dir(&dir);
+    }
+
     #[test]
     fn from_file_nonexistent() {
         let result = Buffer::from_file(Path::new("/nonexistent/path/file.txt"));
@@ -1281,6 +2415,34 @@ mod tests {
         assert_eq!(result, "café\r\nnaïve\r\n");
     }
 
+    // -- Indent detection ---------------------------------------------------
+
+    #[test]
+    fn detect_indent_spaces_by_gcd() {
+        let buf = Buffer::from_text("a\n  b\n    c\n\n      d\ne\n");
+        assert_eq!(buf.detect_indent(), Some(IndentStyle { use_tabs: false, width: 2 }));
+        let buf = Buffer::from_text("fn f() {\n    x;\n        y;\n}\n");
+        assert_eq!(buf.detect_indent(), Some(IndentStyle { use_tabs: false, width: 4 }));
+    }
+
+    #[test]
+    fn detect_indent_tabs_by_majority() {
+        let buf = Buffer::from_text("a\n\tb\n\t\tc\n  d\n");
+        assert_eq!(buf.detect_indent(), Some(IndentStyle { use_tabs: true, width: 0 }));
+    }
+
+    #[test]
+    fn detect_indent_none_without_indented_lines() {
+        assert_eq!(Buffer::from_text("a\nb\n\n").detect_indent(), None);
+    }
+
+    #[test]
+    fn detect_indent_samples_the_first_lines() {
+        let mut text = "x\n   y\n".repeat(50);
+        text.push_str(&"x\n  y\n".repeat(50));
+        assert_eq!(Buffer::from_text(&text).detect_indent(), Some(IndentStyle { use_tabs: false, width: 3 }));
+    }
+
     // -- Unicode handling ---------------------------------------------------
 
     #[test]
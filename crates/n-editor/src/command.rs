@@ -9,26 +9,81 @@
 //! | Command                    | Action                                  |
 //! |----------------------------|-----------------------------------------|
 //! | `:w`                       | Save to current file path               |
+//! | `:w!`                      | Save, even a `readonly` buffer          |
 //! | `:w <path>`                | Save to a specific path (save-as)       |
 //! | `:q`                       | Quit (fails if buffer is modified)       |
-//! | `:q!`                      | Force quit (discard changes)             |
+//! | `:q!`                      | Close window or buffer, drop changes     |
 //! | `:wq`                      | Save and quit                           |
+//! | `:wq!`                     | Save, then close like `:q!`             |
 //! | `:x`                       | Save (only if modified) and quit         |
+//! | `:qa[ll]`                  | Quit (fails if any buffer is modified)  |
+//! | `:qa[ll]!`                 | Quit, discarding all changes            |
+//! | `:wqa[ll]` / `:xa[ll]`     | Save all modified buffers and quit      |
 //! | `:s/pat/rep/flags`         | Substitute on current line              |
 //! | `:%s/pat/rep/flags`        | Substitute on all lines                 |
 //! | `:N,Ms/pat/rep/flags`      | Substitute on line range                |
 //! | `:'<,'>s/pat/rep/flags`    | Substitute on visual selection          |
+//! | `:{range}!{cmd}`           | Filter lines through `cmd` (`:%!sort`)  |
 //! | `:s`                       | Repeat last substitution                |
 //! | `:e <path>`                | Open file in new buffer                 |
+//! | `:ene[w]`                  | Edit a new, unnamed buffer              |
+//! | `:f[ile] [name]`           | Show file status, or rename the buffer  |
 //! | `:bn` / `:bnext`           | Switch to next buffer                   |
 //! | `:bp` / `:bprev`           | Switch to previous buffer               |
 //! | `:bd` / `:bdelete`         | Close current buffer                    |
 //! | `:bd!`                     | Force-close current buffer               |
-//! | `:ls` / `:buffers`         | List all open buffers                   |
+//! | `:ls` / `:buffers`         | List the listed buffers                 |
+//! | `:ls!`                     | List every buffer, unlisted ones too    |
 //! | `:sp` / `:split`           | Horizontal split (top/bottom)           |
 //! | `:vsp` / `:vsplit`         | Vertical split (left/right)             |
 //! | `:close`                   | Close the current window                |
 //! | `:only`                    | Close all windows except current        |
+//! | `:only!`                   | The same, even hiding unsaved changes   |
+//! | `:tabn[ext] [n]`           | Next tab page (tab page `n`)            |
+//! | `:tabp[revious] [n]`       | Previous tab page (`n` back)            |
+//! | `:tabc[lose]`              | Close the current tab page              |
+//! | `:tabd[o] {cmd}`           | Run `cmd` in each tab page              |
+//! | `:checktime`               | Check open files for external changes   |
+//! | `:wc`                      | Show line, word, char and byte counts   |
+//! | `:h[elp] [topic]`          | Open the built-in help (contents)       |
+//! | `:spellgood {word}`        | Add a word to the user spell dictionary |
+//! | `:dig` / `:digraphs`       | List digraphs (`Ctrl+K` mnemonics)      |
+//! | `:dig {ab} {number}`       | Define a custom digraph                 |
+//! | `:exporttheme {fmt} {path}`| Write the theme for alacritty / wezterm |
+//! | `:float {w} {h} {row} {col}`| Open a floating window on the buffer   |
+//! | `:fclose` / `:fclose!`     | Close the topmost / every float         |
+//! | `:GitDiff` / `:GitDiff!`   | Refresh the git change signs            |
+//! | `:term` / `:terminal [cmd]`| Run a shell (or `cmd`) in a new buffer  |
+//! | `:vimgrep /pat/[g][j] {files}`| Search files into the quickfix list  |
+//! | `:grep[!] {pat} {files}`   | Run `grepprg` into the quickfix list    |
+//! | `:grepadd[!] {pat} {files}`| Same, adding to the quickfix list       |
+//! | `:cn` / `:cp` / `:cc [n]`  | Next / previous / nth quickfix entry    |
+//! | `:cfirst` / `:clast`       | First / last quickfix entry             |
+//! | `:cl` / `:clist`           | List the quickfix entries               |
+//! | `:cdo {cmd}`               | Run `cmd` at every quickfix entry       |
+//! | `:cfdo {cmd}`              | Run `cmd` once in each quickfix file    |
+//! | `:ta {name}` / `:tag`      | Jump to the definition of tag `name`    |
+//! | `:tags`                    | List the tag stack                      |
+//! | `:mes` / `:messages`       | Show the message history                |
+//! | `:messages clear`          | Empty the message history               |
+//! | `:verb[ose] {cmd}`         | Run `cmd`, its message prefixed with it |
+//! | `:map {lhs} {rhs}`         | Map keys (`:noremap`: without remapping)|
+//! | `:map [lhs]`               | List mappings (starting with `lhs`)     |
+//! | `:unm[ap] {lhs}`           | Remove a mapping                        |
+//! | `:mapc[lear]`              | Remove all mappings                     |
+//! | `:map <buffer> …`          | The same, for the current buffer only   |
+//! | `:nmap`, `:imap`, …        | Per-mode forms ([`crate::keymap`])      |
+//! | `:ab[breviate] {lhs} {rhs}`| Abbreviate `lhs` ([`crate::abbrev`])    |
+//! | `:ab [lhs]`                | List abbreviations                      |
+//! | `:una[bbreviate] {lhs}`    | Remove an abbreviation                  |
+//! | `:abc[lear]`               | Remove all abbreviations                |
+//! | `:ia[bbrev]`, `:ca[bbrev]` | Insert / command-line only forms        |
+//! | `:hi[ghlight] [group] [key=value…]` | Show or override a highlight group |
+//! | `:hi clear [group]`        | Drop the `:highlight` overrides         |
+//! | `:wsh[ada] [file]`         | Write the shared data file now          |
+//! | `:rsh[ada][!] [file]`      | Read the shared data file (`!` replaces)|
+//! | `:let {var} = {expr}`      | Set a `g:` or `b:` variable             |
+//! | `:ec[ho] {expr}`           | Show the value of an expression         |
 //!
 //! # Substitution flags
 //!
@@ -37,6 +92,7 @@
 //! | `g`  | Replace all matches per line          |
 //! | `i`  | Case-insensitive matching             |
 //! | `n`  | Count matches only (don't replace)    |
+//! | `c`  | Confirm each replacement (`y/n/a/q/l`) |
 //!
 //! # Architecture
 //!
@@ -46,6 +102,13 @@
 
 use std::path::PathBuf;
 
+use n_theme::export::ExportFormat;
+
+use crate::abbrev::AbbrevMode;
+use crate::digraph;
+use crate::expr;
+use crate::keymap::MapMode;
+use crate::split::BorderStyle;
 use crate::options::{self, SetDirective};
 
 // ---------------------------------------------------------------------------
@@ -78,6 +141,7 @@ pub enum CmdRange {
 
 /// Flags for the `:s` substitution command.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct SubFlags {
     /// `g` — replace all matches on each line (not just the first).
     pub global: bool,
@@ -87,6 +151,9 @@ pub struct SubFlags {
 
     /// `n` — count matches only, don't actually replace.
     pub count_only: bool,
+
+    /// `c` — ask before each replacement.
+    pub confirm: bool,
 }
 
 // ---------------------------------------------------------------------------
@@ -99,8 +166,12 @@ pub struct SubFlags {
 /// matches on this enum to execute the appropriate action.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Command {
-    /// `:w` — save to the current path.
-    Write,
+    /// `:w` — save to the current path. `:w!` writes even a `readonly`
+    /// buffer.
+    Write {
+        /// `!` was given.
+        force: bool,
+    },
 
     /// `:w <path>` — save to a specific path.
     WriteAs(PathBuf),
@@ -108,15 +179,29 @@ pub enum Command {
     /// `:q` — quit (refuses if buffer is modified).
     Quit,
 
-    /// `:q!` — force quit (discards unsaved changes).
-    ForceQuit,
+    /// `:q!` — close the current window; in the last one, drop the
+    /// current buffer and its unsaved changes, quitting when it was the
+    /// only buffer. (`:qa!` is [`Command::QuitAll`] with `force`.)
+    ForceQuitBuffer,
 
-    /// `:wq` — save and quit.
-    WriteQuit,
+    /// `:wq` — save and quit. With `!` (`:wq!`), save and then close the
+    /// window or buffer as `:q!` does.
+    WriteQuit {
+        /// `!` was given.
+        force: bool,
+    },
 
     /// `:x` — save if modified, then quit.
     ExitSave,
 
+    /// `:qa` / `:qa!` / `:wqa` — quit, closing every window and buffer.
+    QuitAll {
+        /// `:qa!` — discard unsaved changes.
+        force: bool,
+        /// `:wqa` — save every modified buffer first.
+        write: bool,
+    },
+
     /// `:[range]s/pattern/replacement/[flags]` — substitute.
     Substitute {
         range: CmdRange,
@@ -133,6 +218,13 @@ pub enum Command {
     /// `:e <path>` — open a file (or switch to it if already open).
     Edit(PathBuf),
 
+    /// `:enew` — edit a new, unnamed buffer.
+    Enew,
+
+    /// `:file` — show the file status; `:file {name}` renames the buffer
+    /// without writing it.
+    File(Option<PathBuf>),
+
     /// `:bn` / `:bnext` — switch to the next buffer.
     BufNext,
 
@@ -145,8 +237,12 @@ pub enum Command {
     /// `:bd!` / `:bdelete!` — force-close the current buffer (discard changes).
     BufDeleteForce,
 
-    /// `:ls` / `:buffers` — list all open buffers.
-    BufList,
+    /// `:ls` / `:buffers` — list the open buffers; with `!`, the unlisted
+    /// ones (`nobuflisted`) too.
+    BufList {
+        /// `!` was given.
+        all: bool,
+    },
 
     /// `:sp` / `:split` — horizontal split (current window becomes top half).
     Split,
@@ -160,12 +256,229 @@ pub enum Command {
     /// `:only` — close all windows except the current one.
     WinOnly,
 
+    /// `:only!` — close all other windows even if a buffer shown only in
+    /// them has unsaved changes.
+    WinOnlyForce,
+
+    /// `:tabnext [n]` — go to the next tab page, or to tab page `n`
+    /// (1-based).
+    TabNext(Option<usize>),
+
+    /// `:tabprevious [n]` / `:tabNext [n]` — go `n` tab pages back
+    /// (default 1).
+    TabPrevious(usize),
+
+    /// `:tabclose` — close the current tab page and its windows.
+    TabClose,
+
+    /// `:tabdo {cmd}` — run `cmd` in the active window of each tab page.
+    Tabdo(String),
+
     /// `:set [option[=value] ...]` — get or set editor options.
     Set(Vec<SetDirective>),
 
-    /// `:colorscheme <name>` — switch the editor theme.
+    /// `:setlocal [option[=value] ...]` — like `:set`, but `tabstop`,
+    /// `shiftwidth` and `expandtab` change for the current buffer only.
+    SetLocal(Vec<SetDirective>),
+
+    /// `:highlight [group] [key=value ...]` (or `:hi`) — the arguments,
+    /// parsed with the theme's [`n_theme::highlight::HighlightSpec`].
+    Highlight(String),
+
+    /// `:colorscheme <name>` — switch the editor theme. Empty for plain
+    /// `:colorscheme`, which shows the current one.
     Colorscheme(String),
 
+    /// `:{range}!{cmd}` — pipe the lines of `range` through the shell
+    /// command `cmd` and replace them with its output.
+    Filter {
+        /// The lines to filter; never [`CmdRange::CurrentLine`].
+        range: CmdRange,
+        /// The shell command line.
+        cmd: String,
+    },
+
+    /// `:let {var} = {expr}` — set a variable. `name` is the full name,
+    /// prefix included (`g:x`, `b:x`); `expr` is evaluated when run.
+    Let {
+        /// The variable, as [`crate::expr::variable_name`] gives it.
+        name: String,
+        /// The expression whose value is stored.
+        expr: String,
+    },
+
+    /// `:echo {expr}` — show the value of an expression.
+    Echo(String),
+
+    /// `:checktime` — check whether open files changed on disk.
+    Checktime,
+
+    /// `:wc` — show the buffer's line, word, character and byte counts.
+    WordCount,
+
+    /// `:help [topic]` — open the built-in help page for `topic`, or the
+    /// table of contents.
+    Help(String),
+
+    /// `:spellgood {word}` — mark a word as correctly spelled.
+    SpellGood(String),
+
+    /// `:digraphs` — list the digraph table when empty, otherwise define
+    /// each `({char1}{char2}, char)` pair.
+    Digraphs(Vec<([char; 2], char)>),
+
+    /// `:exporttheme {format} {path}` — write the current theme as a
+    /// terminal color scheme.
+    ExportTheme { format: ExportFormat, path: PathBuf },
+
+    /// `:float {width} {height} {row} {col} [border]` — open a floating
+    /// window on the current buffer. The size includes the border, which
+    /// defaults to `single`.
+    Float { width: u16, height: u16, row: u16, col: u16, border: BorderStyle },
+
+    /// `:fclose` closes the topmost floating window; `:fclose!` closes all.
+    FloatClose { all: bool },
+
+    /// `:GitDiff` (or `:GitDiff!`) — recompute the git change signs for the
+    /// current buffer.
+    GitDiff,
+
+    /// `:terminal [cmd]` (or `:term`) — run `cmd`, or the user's shell when
+    /// empty, in a new terminal buffer.
+    Terminal(String),
+
+    /// `:[count]vimgrep /{pattern}/[g][j] {file}…` — search files (globs
+    /// allowed, `%` for the current buffer) and load the matches into the
+    /// quickfix list. `g` finds every match on a line rather than the
+    /// first; `j` doesn't jump to the first match. `count` caps the number
+    /// of matches.
+    Vimgrep { pattern: String, global: bool, no_jump: bool, files: Vec<String>, max: Option<usize> },
+
+    /// `:grep {pattern} {files}` — run `grepprg` and load its matches into
+    /// the quickfix list. `add` (`:grepadd`) appends to the list instead;
+    /// `force` (`!`) doesn't jump to the first match. Both parts are
+    /// passed to the shell as typed.
+    Grep { pattern: String, files: String, force: bool, add: bool },
+
+    /// `:cnext` — go to the next quickfix entry.
+    QfNext,
+
+    /// `:cprevious` (or `:cNext`) — go to the previous quickfix entry.
+    QfPrev,
+
+    /// `:cfirst` / `:crewind` — go to the first quickfix entry.
+    QfFirst,
+
+    /// `:clast` — go to the last quickfix entry.
+    QfLast,
+
+    /// `:cc [n]` — go to quickfix entry `n` (1-based), or back to the
+    /// current one.
+    QfGo(Option<usize>),
+
+    /// `:clist` — list the quickfix entries.
+    QfList,
+
+    /// `:cdo {cmd}` — run `cmd` with the cursor on each quickfix entry in
+    /// turn.
+    Cdo(String),
+
+    /// `:cfdo {cmd}` — run `cmd` once in each file of the quickfix list.
+    Cfdo(String),
+
+    /// `:tag {name}` — jump to the tag's definition, pushing the tag stack.
+    Tag(String),
+
+    /// `:tags` — list the tag stack.
+    Tags,
+
+    /// `:messages` — show the message history; `:messages clear` empties
+    /// it.
+    Messages {
+        /// `clear` was given.
+        clear: bool,
+    },
+
+    /// `:verbose {cmd}` — run `cmd`, prefixing its message with the
+    /// command.
+    Verbose(String),
+
+    /// `:map {lhs} {rhs}` / `:noremap {lhs} {rhs}` — map keys. Without
+    /// `rhs`, list the mappings whose left-hand side starts with `lhs`
+    /// (all of them when `lhs` is empty too). `:nmap`, `:imap` and the
+    /// rest do the same for their [`MapMode`].
+    Map {
+        /// The modes the mapping applies in.
+        mode: MapMode,
+        /// Left-hand side, in key notation.
+        lhs: String,
+        /// Right-hand side, in key notation.
+        rhs: Option<String>,
+        /// `:noremap` — the right-hand side isn't mapped again.
+        noremap: bool,
+        /// `<expr>` — the right-hand side is an expression giving the keys.
+        expr: bool,
+        /// `<buffer>` — the mapping is for the current buffer only.
+        buffer: bool,
+    },
+
+    /// `:unmap {lhs}` (`:nunmap`, …) — remove a mapping.
+    Unmap {
+        /// The modes to remove it from.
+        mode: MapMode,
+        /// Left-hand side, in key notation.
+        lhs: String,
+        /// `<buffer>` — remove the current buffer's mapping, not a global
+        /// one.
+        buffer: bool,
+    },
+
+    /// `:mapclear` (`:nmapclear`, …) — remove every mapping for a mode.
+    MapClear {
+        /// The modes to clear.
+        mode: MapMode,
+        /// `<buffer>` — clear the current buffer's mappings only.
+        buffer: bool,
+    },
+
+    /// `:abbreviate {lhs} {rhs}` — abbreviate the word `lhs`. Without
+    /// `rhs`, list the abbreviations starting with `lhs`. `:iabbrev` and
+    /// `:cabbrev` do the same for one [`AbbrevMode`].
+    Abbreviate {
+        /// The modes the abbreviation applies in.
+        mode: AbbrevMode,
+        /// The word to replace.
+        lhs: String,
+        /// The replacement.
+        rhs: Option<String>,
+    },
+
+    /// `:unabbreviate {lhs}` (`:iunabbrev`, `:cunabbrev`) — remove an
+    /// abbreviation.
+    Unabbreviate {
+        /// The modes to remove it from.
+        mode: AbbrevMode,
+        /// The abbreviated word.
+        lhs: String,
+    },
+
+    /// `:abclear` (`:iabclear`, `:cabclear`) — remove every abbreviation
+    /// for a mode.
+    AbbrevClear(AbbrevMode),
+
+    /// `:wshada [file]` — write the shared data (jumps, marks, registers,
+    /// history) to `file`, or the default file.
+    WriteShada(Option<String>),
+
+    /// `:rshada[!] [file]` — read the shared data, merging it into the
+    /// current state, or with `!` replacing it.
+    ReadShada {
+        /// The file, or `None` for the default.
+        path: Option<String>,
+        /// `!` was given.
+        force: bool,
+    },
+
     /// Unknown command — contains the full input for error reporting.
     Unknown(String),
 }
@@ -316,7 +629,9 @@ impl Default for CommandLine {
 // ---------------------------------------------------------------------------
 
 /// Parse a command string (without the leading `:`) into a [`Command`].
-fn parse_command(input: &str) -> Command {
+#[must_use]
+#[allow(clippy::too_many_lines)] // One arm per command.
+pub fn parse_command(input: &str) -> Command {
     let trimmed = input.trim();
 
     if trimmed.is_empty() {
@@ -334,6 +649,18 @@ fn parse_command(input: &str) -> Command {
         }
     }
 
+    // `:{range}!{cmd}` filters lines; without a range there is nothing
+    // to filter.
+    if let Some(cmd) = rest.strip_prefix('!') {
+        if !matches!(range, CmdRange::CurrentLine) {
+            let cmd = cmd.trim();
+            if cmd.is_empty() {
+                return Command::Unknown("E471: Argument required".to_string());
+            }
+            return Command::Filter { range, cmd: cmd.to_string() };
+        }
+    }
+
     // A range with no command following it is invalid.
     if !matches!(range, CmdRange::CurrentLine) && rest.is_empty() {
         return Command::Unknown(trimmed.to_string());
@@ -350,10 +677,23 @@ fn parse_command(input: &str) -> Command {
             (&cmd_str[..pos], cmd_str[pos..].trim_start())
         });
 
+    // `:{count}vimgrep` — the count caps the number of matches.
+    let digits = cmd.bytes().take_while(u8::is_ascii_digit).count();
+    if matches!(&cmd[digits..], "vimgrep" | "vim" | "vimgrep!" | "vim!") {
+        return parse_vimgrep(arg, cmd[..digits].parse().ok());
+    }
+
+    if let Some(map) = parse_map_command(cmd, arg) {
+        return map;
+    }
+    if let Some(abbrev) = parse_abbrev_command(cmd, arg) {
+        return abbrev;
+    }
+
     match cmd {
-        "w" => {
+        "w" | "w!" => {
             if arg.is_empty() {
-                Command::Write
+                Command::Write { force: cmd == "w!" }
             } else {
                 Command::WriteAs(PathBuf::from(arg))
             }
@@ -365,31 +705,363 @@ fn parse_command(input: &str) -> Command {
                 Command::Edit(PathBuf::from(arg))
             }
         }
+        "enew" | "ene" => Command::Enew,
+        "file" | "f" => Command::File((!arg.is_empty()).then(|| PathBuf::from(arg))),
         "q" => Command::Quit,
-        "q!" => Command::ForceQuit,
-        "wq" => Command::WriteQuit,
+        "q!" => Command::ForceQuitBuffer,
+        "wq" => Command::WriteQuit { force: false },
+        "wq!" => Command::WriteQuit { force: true },
         "x" => Command::ExitSave,
+        "qa" | "qall" | "quita" | "quitall" => Command::QuitAll { force: false, write: false },
+        "qa!" | "qall!" | "quita!" | "quitall!" => Command::QuitAll { force: true, write: false },
+        "wqa" | "wqall" | "xa" | "xall" => Command::QuitAll { force: false, write: true },
         "bn" | "bnext" => Command::BufNext,
         "bp" | "bprev" | "bprevious" => Command::BufPrev,
         "bd" | "bdelete" => Command::BufDelete,
         "bd!" | "bdelete!" => Command::BufDeleteForce,
-        "ls" | "buffers" => Command::BufList,
+        "ls" | "buffers" => Command::BufList { all: false },
+        "ls!" | "buffers!" => Command::BufList { all: true },
         "sp" | "split" => Command::Split,
         "vsp" | "vsplit" => Command::VSplit,
         "close" | "clo" => Command::WinClose,
         "only" | "on" => Command::WinOnly,
+        "only!" | "on!" => Command::WinOnlyForce,
+        "tabnext" | "tabn" => match arg.parse::<usize>() {
+            _ if arg.is_empty() => Command::TabNext(None),
+            Ok(n) => Command::TabNext(Some(n)),
+            Err(_) => Command::Unknown(format!("E474: Invalid argument: {arg}")),
+        },
+        "tabprevious" | "tabp" | "tabNext" | "tabN" => match arg.parse::<usize>() {
+            _ if arg.is_empty() => Command::TabPrevious(1),
+            Ok(n) => Command::TabPrevious(n),
+            Err(_) => Command::Unknown(format!("E474: Invalid argument: {arg}")),
+        },
+        "tabclose" | "tabc" => Command::TabClose,
+        "tabdo" | "tabd" => {
+            if arg.is_empty() {
+                Command::Unknown("E471: Argument required".to_string())
+            } else {
+                Command::Tabdo(arg.to_string())
+            }
+        }
         "set" | "se" => Command::Set(options::parse_set(arg)),
-        "colorscheme" | "colo" => {
+        "setlocal" | "setl" => Command::SetLocal(options::parse_set(arg)),
+        "checktime" | "checkt" => Command::Checktime,
+        "wc" => Command::WordCount,
+        "h" | "he" | "hel" | "help" => Command::Help(arg.to_string()),
+        "dig" | "digr" | "digraph" | "digraphs" => match digraph::parse_definitions(arg) {
+            Ok(defs) => Command::Digraphs(defs),
+            Err(msg) => Command::Unknown(msg),
+        },
+        "spellgood" | "spe" => {
+            if arg.is_empty() {
+                Command::Unknown("E471: Argument required".to_string())
+            } else {
+                Command::SpellGood(arg.to_string())
+            }
+        }
+        "exporttheme" => {
+            let (name, path) = arg
+                .split_once(char::is_whitespace)
+                .map_or((arg, ""), |(n, p)| (n, p.trim()));
+            if path.is_empty() {
+                Command::Unknown("E471: Argument required".to_string())
+            } else if let Some(format) = ExportFormat::from_name(name) {
+                Command::ExportTheme { format, path: PathBuf::from(path) }
+            } else {
+                Command::Unknown(format!("E475: Invalid argument: {name}"))
+            }
+        }
+        "float" => parse_float(arg),
+        "fclose" | "fc" => Command::FloatClose { all: false },
+        "fclose!" | "fc!" => Command::FloatClose { all: true },
+        "GitDiff" | "GitDiff!" => Command::GitDiff,
+        "terminal" | "term" | "ter" => Command::Terminal(arg.to_string()),
+        "grep" | "gr" | "grep!" | "gr!" | "grepadd" | "grepa" | "grepadd!" | "grepa!" => {
+            parse_grep(arg, cmd.ends_with('!'), cmd.starts_with("grepa"))
+        }
+        "cnext" | "cn" => Command::QfNext,
+        "cprevious" | "cprev" | "cp" | "cNext" | "cN" => Command::QfPrev,
+        "cfirst" | "cfir" | "crewind" | "cr" => Command::QfFirst,
+        "clast" | "cla" => Command::QfLast,
+        "clist" | "cl" => Command::QfList,
+        "tag" | "ta" => {
             if arg.is_empty() {
                 Command::Unknown("E471: Argument required".to_string())
             } else {
-                Command::Colorscheme(arg.to_string())
+                Command::Tag(arg.to_string())
+            }
+        }
+        "tags" => Command::Tags,
+        "messages" | "mes" | "msgs" => match arg {
+            "" => Command::Messages { clear: false },
+            "clear" => Command::Messages { clear: true },
+            _ => Command::Unknown(format!("E488: Trailing characters: {arg}")),
+        },
+        "wshada" | "wsh" | "wshada!" | "wsh!" => {
+            Command::WriteShada((!arg.is_empty()).then(|| arg.to_string()))
+        }
+        "rshada" | "rsh" | "rshada!" | "rsh!" => Command::ReadShada {
+            path: (!arg.is_empty()).then(|| arg.to_string()),
+            force: cmd.ends_with('!'),
+        },
+        "verbose" | "verb" => {
+            if arg.is_empty() {
+                Command::Unknown("E471: Argument required".to_string())
+            } else {
+                Command::Verbose(arg.to_string())
+            }
+        }
+        "cdo" | "cfdo" => {
+            if arg.is_empty() {
+                Command::Unknown("E471: Argument required".to_string())
+            } else if cmd == "cdo" {
+                Command::Cdo(arg.to_string())
+            } else {
+                Command::Cfdo(arg.to_string())
+            }
+        }
+        "cc" => {
+            if arg.is_empty() {
+                Command::QfGo(None)
+            } else {
+                match arg.parse::<usize>() {
+                    Ok(n) if n > 0 => Command::QfGo(Some(n)),
+                    _ => Command::Unknown(format!("E16: Invalid range: {arg}")),
+                }
+            }
+        }
+        "colorscheme" | "colo" => Command::Colorscheme(arg.to_string()),
+        "highlight" | "hi" => Command::Highlight(arg.to_string()),
+        "let" => parse_let(arg),
+        "echo" | "ec" => {
+            if arg.is_empty() {
+                Command::Unknown("E15: Invalid expression: \"\"".to_string())
+            } else {
+                Command::Echo(arg.to_string())
             }
         }
         _ => Command::Unknown(trimmed.to_string()),
     }
 }
 
+/// Parse the argument of `:let {var} = {expr}`.
+fn parse_let(arg: &str) -> Command {
+    let Some((var, expr)) = arg.split_once('=') else {
+        return Command::Unknown(format!("E15: Invalid expression: \"{arg}\""));
+    };
+    let var = var.trim();
+    expr::variable_name(var).map_or_else(
+        || Command::Unknown(format!("E461: Illegal variable name: {var}")),
+        |name| Command::Let { name, expr: expr.trim().to_string() },
+    )
+}
+
+/// Parse the arguments of `:vimgrep`: `/{pattern}/[g][j] {file}…`, or
+/// `{pattern} {file}…` when the pattern has no spaces. Any non-word
+/// character works as the delimiter; `\{delim}` includes it in the pattern.
+fn parse_vimgrep(arg: &str, max: Option<usize>) -> Command {
+    let mut chars = arg.chars();
+    let (pattern, flags, files) = match chars.next() {
+        Some(delim) if !delim.is_alphanumeric() && !matches!(delim, '_' | '"' | '|') => {
+            let mut pattern = String::new();
+            let mut closed = None;
+            let body = &arg[delim.len_utf8()..];
+            let mut iter = body.char_indices();
+            while let Some((i, ch)) = iter.next() {
+                if ch == '\\' {
+                    match iter.next() {
+                        Some((_, next)) if next == delim => pattern.push(delim),
+                        Some((_, next)) => {
+                            pattern.push('\\');
+                            pattern.push(next);
+                        }
+                        None => pattern.push('\\'),
+                    }
+                } else if ch == delim {
+                    closed = Some(i + ch.len_utf8());
+                    break;
+                } else {
+                    pattern.push(ch);
+                }
+            }
+            let Some(end) = closed else {
+                return Command::Unknown("E682: Invalid search pattern or delimiter".to_string());
+            };
+            let rest = &body[end..];
+            let flags_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            (pattern, &rest[..flags_end], &rest[flags_end..])
+        }
+        _ => {
+            let (pattern, files) = arg.split_once(char::is_whitespace).unwrap_or((arg, ""));
+            (pattern.to_string(), "", files)
+        }
+    };
+    if let Some(bad) = flags.chars().find(|c| !matches!(c, 'g' | 'j')) {
+        return Command::Unknown(format!("E488: Trailing characters: {bad}"));
+    }
+    let files: Vec<String> = files.split_whitespace().map(str::to_string).collect();
+    if pattern.is_empty() || files.is_empty() {
+        return Command::Unknown("E683: File name missing or invalid pattern".to_string());
+    }
+    Command::Vimgrep {
+        pattern,
+        global: flags.contains('g'),
+        no_jump: flags.contains('j'),
+        files,
+        max,
+    }
+}
+
+/// Parse the `:map` family: `{mode}map`, `{mode}noremap`, `{mode}unmap`
+/// and `{mode}mapclear`, where `{mode}` is empty or one of `nivxoc`.
+/// Returns `None` if `cmd` isn't one of them.
+fn parse_map_command(cmd: &str, arg: &str) -> Option<Command> {
+    // The shortest abbreviation of `map`, `noremap`, `unmap` and
+    // `mapclear` after each prefix, as in Vim (`:no`, `:nn`, `:ino`,
+    // `:unm`, `:nun`, `:iu`) — shorter ones are other commands.
+    const MODES: [(&str, MapMode, [usize; 4]); 7] = [
+        ("", MapMode::All, [3, 2, 3, 4]),
+        ("n", MapMode::Normal, [1, 1, 2, 4]),
+        ("i", MapMode::Insert, [1, 2, 1, 4]),
+        ("v", MapMode::Visual, [1, 1, 1, 4]),
+        ("x", MapMode::VisualChar, [1, 1, 1, 4]),
+        ("o", MapMode::OperatorPending, [1, 2, 1, 4]),
+        ("c", MapMode::Command, [1, 2, 1, 4]),
+    ];
+    // `<expr>` and `<buffer>` come before the left-hand side, in either
+    // order.
+    let (mut arg, mut expr, mut buffer) = (arg, false, false);
+    loop {
+        if let Some(rest) = arg.strip_prefix("<expr>") {
+            expr = true;
+            arg = rest.trim_start();
+        } else if let Some(rest) = arg.strip_prefix("<buffer>") {
+            buffer = true;
+            arg = rest.trim_start();
+        } else {
+            break;
+        }
+    }
+    for (prefix, mode, [map, noremap, unmap, clear]) in MODES {
+        let Some(word) = cmd.strip_prefix(prefix) else { continue };
+        let is = |full: &str, min: usize| word.len() >= min && full.starts_with(word);
+        if is("map", map) || is("noremap", noremap) {
+            let (lhs, rhs) = arg.split_once(char::is_whitespace).unwrap_or((arg, ""));
+            let rhs = rhs.trim_start();
+            return Some(Command::Map {
+                mode,
+                lhs: lhs.to_string(),
+                rhs: (!rhs.is_empty()).then(|| rhs.to_string()),
+                noremap: word.starts_with('n'),
+                expr,
+                buffer,
+            });
+        }
+        if is("unmap", unmap) {
+            return Some(if arg.is_empty() {
+                Command::Unknown("E474: Invalid argument".to_string())
+            } else {
+                Command::Unmap { mode, lhs: arg.to_string(), buffer }
+            });
+        }
+        if is("mapclear", clear) {
+            return Some(if arg.is_empty() {
+                Command::MapClear { mode, buffer }
+            } else {
+                Command::Unknown(format!("E488: Trailing characters: {arg}"))
+            });
+        }
+    }
+    None
+}
+
+/// Parse the abbreviation commands: `{mode}abbreviate`, `{mode}unabbreviate`
+/// and `{mode}abclear`, where `{mode}` is empty, `i` or `c`. Returns `None`
+/// if `cmd` isn't one of them.
+fn parse_abbrev_command(cmd: &str, arg: &str) -> Option<Command> {
+    // The shortest abbreviation of each command after each prefix, as in
+    // Vim (`:ab`, `:ia`, `:una`, `:iuna`, `:abc`, `:iabc`).
+    const MODES: [(&str, AbbrevMode, &str, [usize; 3]); 3] = [
+        ("", AbbrevMode::All, "abbreviate", [2, 3, 3]),
+        ("i", AbbrevMode::Insert, "abbrev", [1, 3, 3]),
+        ("c", AbbrevMode::Command, "abbrev", [1, 3, 3]),
+    ];
+    for (prefix, mode, full, [add, remove, clear]) in MODES {
+        let Some(word) = cmd.strip_prefix(prefix) else { continue };
+        let is = |full: &str, min: usize| word.len() >= min && full.starts_with(word);
+        if is("abclear", clear) {
+            return Some(if arg.is_empty() {
+                Command::AbbrevClear(mode)
+            } else {
+                Command::Unknown(format!("E488: Trailing characters: {arg}"))
+            });
+        }
+        if is(full, add) {
+            let (lhs, rhs) = arg.split_once(char::is_whitespace).unwrap_or((arg, ""));
+            let rhs = rhs.trim_start();
+            return Some(Command::Abbreviate {
+                mode,
+                lhs: lhs.to_string(),
+                rhs: (!rhs.is_empty()).then(|| rhs.to_string()),
+            });
+        }
+        if is("unabbreviate", remove) {
+            return Some(if arg.is_empty() {
+                Command::Unknown("E474: Invalid argument".to_string())
+            } else {
+                Command::Unabbreviate { mode, lhs: arg.to_string() }
+            });
+        }
+    }
+    None
+}
+
+/// Parse the arguments of `:grep`: a pattern (one word, or quoted with
+/// `'` or `"` — quotes are kept for the shell) and then the files.
+fn parse_grep(arg: &str, force: bool, add: bool) -> Command {
+    let end = match arg.chars().next() {
+        None => return Command::Unknown("E471: Argument required".to_string()),
+        Some(quote @ ('\'' | '"')) => arg[1..]
+            .find(quote)
+            .map_or(arg.len(), |i| i + 2),
+        Some(_) => arg.find(char::is_whitespace).unwrap_or(arg.len()),
+    };
+    Command::Grep {
+        pattern: arg[..end].to_string(),
+        files: arg[end..].trim().to_string(),
+        force,
+        add,
+    }
+}
+
+/// Parse the arguments of `:float {width} {height} {row} {col} [border]`.
+fn parse_float(arg: &str) -> Command {
+    let args: Vec<&str> = arg.split_whitespace().collect();
+    if args.len() < 4 {
+        return Command::Unknown("E471: Argument required".to_string());
+    }
+    let mut nums = [0u16; 4];
+    for (n, a) in nums.iter_mut().zip(&args) {
+        match a.parse() {
+            Ok(v) => *n = v,
+            Err(_) => return Command::Unknown(format!("E475: Invalid argument: {a}")),
+        }
+    }
+    let [width, height, row, col] = nums;
+    if width == 0 || height == 0 {
+        return Command::Unknown(format!("E475: Invalid argument: {arg}"));
+    }
+    let border = match args.get(4) {
+        None => BorderStyle::Single,
+        Some(name) => match BorderStyle::from_name(name) {
+            Some(b) if args.len() == 5 => b,
+            _ => return Command::Unknown(format!("E475: Invalid argument: {name}")),
+        },
+    };
+    Command::Float { width, height, row, col, border }
+}
+
 /// Parse a range prefix from the start of a command string.
 ///
 /// Returns `(range, rest)` where `rest` is the command string after the range.
@@ -547,6 +1219,7 @@ fn parse_sub_flags(s: &str) -> SubFlags {
             'g' => flags.global = true,
             'i' => flags.case_insensitive = true,
             'n' => flags.count_only = true,
+            'c' => flags.confirm = true,
             _ => {} // Unknown flags silently ignored (like Vim).
         }
     }
@@ -727,7 +1400,8 @@ mod tests {
 
     #[test]
     fn parse_write() {
-        assert_eq!(parse_command("w"), Command::Write);
+        assert_eq!(parse_command("w"), Command::Write { force: false });
+        assert_eq!(parse_command("w!"), Command::Write { force: true });
     }
 
     #[test]
@@ -753,12 +1427,13 @@ mod tests {
 
     #[test]
     fn parse_force_quit() {
-        assert_eq!(parse_command("q!"), Command::ForceQuit);
+        assert_eq!(parse_command("q!"), Command::ForceQuitBuffer);
     }
 
     #[test]
     fn parse_write_quit() {
-        assert_eq!(parse_command("wq"), Command::WriteQuit);
+        assert_eq!(parse_command("wq"), Command::WriteQuit { force: false });
+        assert_eq!(parse_command("wq!"), Command::WriteQuit { force: true });
     }
 
     #[test]
@@ -766,6 +1441,15 @@ mod tests {
         assert_eq!(parse_command("x"), Command::ExitSave);
     }
 
+    #[test]
+    fn parse_quit_all() {
+        assert_eq!(parse_command("qa"), Command::QuitAll { force: false, write: false });
+        assert_eq!(parse_command("quitall"), Command::QuitAll { force: false, write: false });
+        assert_eq!(parse_command("qa!"), Command::QuitAll { force: true, write: false });
+        assert_eq!(parse_command("wqa"), Command::QuitAll { force: false, write: true });
+        assert_eq!(parse_command("xall"), Command::QuitAll { force: false, write: true });
+    }
+
     #[test]
     fn parse_unknown() {
         assert_eq!(
@@ -786,8 +1470,8 @@ mod tests {
 
     #[test]
     fn parse_leading_trailing_whitespace() {
-        assert_eq!(parse_command("  w  "), Command::Write);
-        assert_eq!(parse_command("  q!  "), Command::ForceQuit);
+        assert_eq!(parse_command("  w  "), Command::Write { force: false });
+        assert_eq!(parse_command("  q!  "), Command::ForceQuitBuffer);
     }
 
     #[test]
@@ -795,7 +1479,7 @@ mod tests {
         let mut cl = CommandLine::new();
         cl.insert_char('w');
         cl.insert_char('q');
-        assert_eq!(cl.parse(), Command::WriteQuit);
+        assert_eq!(cl.parse(), Command::WriteQuit { force: false });
     }
 
     // -- CommandResult variants exist ---------------------------------------
@@ -1147,8 +1831,8 @@ mod tests {
     #[test]
     fn flags_all() {
         assert_eq!(
-            parse_sub_flags("gin"),
-            SubFlags { global: true, case_insensitive: true, count_only: true }
+            parse_sub_flags("ginc"),
+            SubFlags { global: true, case_insensitive: true, count_only: true, confirm: true }
         );
     }
 
@@ -1184,6 +1868,22 @@ mod tests {
         assert!(matches!(parse_command("e"), Command::Unknown(_)));
     }
 
+    #[test]
+    fn parse_enew_and_setlocal() {
+        assert_eq!(parse_command("enew"), Command::Enew);
+        assert_eq!(parse_command("ene"), Command::Enew);
+        assert_eq!(
+            parse_command("setl bt=nofile"),
+            Command::SetLocal(vec![SetDirective::Assign("bt".into(), "nofile".into())])
+        );
+    }
+
+    #[test]
+    fn parse_file() {
+        assert_eq!(parse_command("file"), Command::File(None));
+        assert_eq!(parse_command("f new name.rs"), Command::File(Some(PathBuf::from("new name.rs"))));
+    }
+
     #[test]
     fn parse_buf_next() {
         assert_eq!(parse_command("bn"), Command::BufNext);
@@ -1211,8 +1911,9 @@ mod tests {
 
     #[test]
     fn parse_buf_list() {
-        assert_eq!(parse_command("ls"), Command::BufList);
-        assert_eq!(parse_command("buffers"), Command::BufList);
+        assert_eq!(parse_command("ls"), Command::BufList { all: false });
+        assert_eq!(parse_command("buffers"), Command::BufList { all: false });
+        assert_eq!(parse_command("ls!"), Command::BufList { all: true });
     }
 
     // ── Window commands ──────────────────────────────────────────────────
@@ -1239,6 +1940,366 @@ mod tests {
     fn parse_win_only() {
         assert_eq!(parse_command("only"), Command::WinOnly);
         assert_eq!(parse_command("on"), Command::WinOnly);
+        assert_eq!(parse_command("only!"), Command::WinOnlyForce);
+        assert_eq!(parse_command("on!"), Command::WinOnlyForce);
+    }
+
+    #[test]
+    fn parse_colorscheme() {
+        assert_eq!(parse_command("colo fibonacci"), Command::Colorscheme("fibonacci".to_string()));
+        assert_eq!(parse_command("colorscheme"), Command::Colorscheme(String::new()));
+        assert_eq!(parse_command("hi Normal guibg=#1e1e2e"), Command::Highlight("Normal guibg=#1e1e2e".to_string()));
+    }
+
+    #[test]
+    fn parse_filter() {
+        let filter = |range, cmd: &str| Command::Filter { range, cmd: cmd.to_string() };
+        assert_eq!(parse_command("%!sort -u"), filter(CmdRange::All, "sort -u"));
+        assert_eq!(parse_command("2,3! fmt -w 79"), filter(CmdRange::Lines(1, 2), "fmt -w 79"));
+        assert_eq!(parse_command("'<,'>!tac"), filter(CmdRange::Visual, "tac"));
+        assert!(matches!(parse_command("%!"), Command::Unknown(e) if e.starts_with("E471")));
+    }
+
+    #[test]
+    fn parse_let_and_echo() {
+        let let_ = |name: &str, expr: &str| Command::Let { name: name.to_string(), expr: expr.to_string() };
+        assert_eq!(parse_command("let b:foo = \"bar\""), let_("b:foo", "\"bar\""));
+        assert_eq!(parse_command("let x=1+2"), let_("g:x", "1+2"));
+        assert_eq!(parse_command("let g:x = y == 1"), let_("g:x", "y == 1"));
+        assert!(matches!(parse_command("let w:x = 1"), Command::Unknown(e) if e.starts_with("E461")));
+        assert!(matches!(parse_command("let x"), Command::Unknown(e) if e.starts_with("E15")));
+        assert_eq!(parse_command("echo b:foo"), Command::Echo("b:foo".to_string()));
+        assert_eq!(parse_command("ec 1"), Command::Echo("1".to_string()));
+    }
+
+    #[test]
+    fn parse_tab_commands() {
+        assert_eq!(parse_command("tabn"), Command::TabNext(None));
+        assert_eq!(parse_command("tabnext 3"), Command::TabNext(Some(3)));
+        assert_eq!(parse_command("tabp"), Command::TabPrevious(1));
+        assert_eq!(parse_command("tabN 2"), Command::TabPrevious(2));
+        assert_eq!(parse_command("tabc"), Command::TabClose);
+        assert_eq!(parse_command("tabdo set nu"), Command::Tabdo("set nu".to_string()));
+        assert!(matches!(parse_command("tabd"), Command::Unknown(m) if m.starts_with("E471")));
+        assert!(matches!(parse_command("tabn x"), Command::Unknown(_)));
+    }
+
+    #[test]
+    fn parse_checktime() {
+        assert_eq!(parse_command("checktime"), Command::Checktime);
+        assert_eq!(parse_command("checkt"), Command::Checktime);
+    }
+
+    #[test]
+    fn parse_word_count() {
+        assert_eq!(parse_command("wc"), Command::WordCount);
+    }
+
+    #[test]
+    fn parse_help() {
+        assert_eq!(parse_command("help"), Command::Help(String::new()));
+        assert_eq!(parse_command("h dd"), Command::Help("dd".to_string()));
+    }
+
+    #[test]
+    fn parse_digraphs() {
+        assert_eq!(parse_command("digraphs"), Command::Digraphs(vec![]));
+        assert_eq!(parse_command("dig"), Command::Digraphs(vec![]));
+        assert_eq!(
+            parse_command("digraph qq 10004"),
+            Command::Digraphs(vec![(['q', 'q'], '✔')])
+        );
+        assert_eq!(
+            parse_command("dig qq"),
+            Command::Unknown("E39: Number expected".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_spellgood() {
+        assert_eq!(
+            parse_command("spellgood nvim"),
+            Command::SpellGood("nvim".to_string())
+        );
+        assert_eq!(parse_command("spe grok"), Command::SpellGood("grok".to_string()));
+        assert_eq!(
+            parse_command("spellgood"),
+            Command::Unknown("E471: Argument required".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_exporttheme() {
+        assert_eq!(
+            parse_command("exporttheme alacritty ~/colors.toml"),
+            Command::ExportTheme {
+                format: ExportFormat::Alacritty,
+                path: PathBuf::from("~/colors.toml"),
+            }
+        );
+        assert_eq!(
+            parse_command("exporttheme wezterm  n.lua"),
+            Command::ExportTheme { format: ExportFormat::WezTerm, path: PathBuf::from("n.lua") }
+        );
+        assert_eq!(
+            parse_command("exporttheme alacritty"),
+            Command::Unknown("E471: Argument required".to_string())
+        );
+        assert_eq!(
+            parse_command("exporttheme kitty k.conf"),
+            Command::Unknown("E475: Invalid argument: kitty".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_float() {
+        assert_eq!(
+            parse_command("float 40 10 2 5"),
+            Command::Float { width: 40, height: 10, row: 2, col: 5, border: BorderStyle::Single }
+        );
+        assert_eq!(
+            parse_command("float 20 5 0 0 rounded"),
+            Command::Float { width: 20, height: 5, row: 0, col: 0, border: BorderStyle::Rounded }
+        );
+        assert_eq!(
+            parse_command("float 20 5"),
+            Command::Unknown("E471: Argument required".to_string())
+        );
+        assert_eq!(
+            parse_command("float 20 x 0 0"),
+            Command::Unknown("E475: Invalid argument: x".to_string())
+        );
+        assert_eq!(
+            parse_command("float 20 5 0 0 shadow"),
+            Command::Unknown("E475: Invalid argument: shadow".to_string())
+        );
+        assert!(matches!(parse_command("float 0 5 0 0"), Command::Unknown(_)));
+        assert_eq!(parse_command("fclose"), Command::FloatClose { all: false });
+        assert_eq!(parse_command("fclose!"), Command::FloatClose { all: true });
+    }
+
+    #[test]
+    fn parse_git_diff() {
+        assert_eq!(parse_command("GitDiff"), Command::GitDiff);
+        assert_eq!(parse_command("GitDiff!"), Command::GitDiff);
+    }
+
+    #[test]
+    fn parse_vimgrep() {
+        assert_eq!(
+            parse_command("vimgrep /fn \\/ x/gj src/*.rs %"),
+            Command::Vimgrep {
+                pattern: "fn / x".to_string(),
+                global: true,
+                no_jump: true,
+                files: vec!["src/*.rs".to_string(), "%".to_string()],
+                max: None,
+            }
+        );
+        assert_eq!(
+            parse_command("3vim TODO **/*.md"),
+            Command::Vimgrep {
+                pattern: "TODO".to_string(),
+                global: false,
+                no_jump: false,
+                files: vec!["**/*.md".to_string()],
+                max: Some(3),
+            }
+        );
+        assert!(matches!(parse_command("vimgrep #a\\d# f"), Command::Vimgrep { pattern, .. } if pattern == "a\\d"));
+        assert!(matches!(parse_command("vimgrep /x/"), Command::Unknown(m) if m.starts_with("E683")));
+        assert!(matches!(parse_command("vimgrep /x f"), Command::Unknown(m) if m.starts_with("E682")));
+        assert!(matches!(parse_command("vimgrep /x/q f"), Command::Unknown(m) if m.starts_with("E488")));
+    }
+
+    #[test]
+    fn parse_grep() {
+        assert_eq!(
+            parse_command("grep foo src/*.rs"),
+            Command::Grep {
+                pattern: "foo".to_string(),
+                files: "src/*.rs".to_string(),
+                force: false,
+                add: false,
+            }
+        );
+        assert_eq!(
+            parse_command("grepadd! 'a b' x y"),
+            Command::Grep {
+                pattern: "'a b'".to_string(),
+                files: "x y".to_string(),
+                force: true,
+                add: true,
+            }
+        );
+        assert!(matches!(parse_command("gr! x"), Command::Grep { force: true, add: false, .. }));
+        assert!(matches!(parse_command("grep"), Command::Unknown(m) if m.starts_with("E471")));
+    }
+
+    #[test]
+    fn parse_quickfix_navigation() {
+        assert_eq!(parse_command("cn"), Command::QfNext);
+        assert_eq!(parse_command("cnext"), Command::QfNext);
+        assert_eq!(parse_command("cp"), Command::QfPrev);
+        assert_eq!(parse_command("cN"), Command::QfPrev);
+        assert_eq!(parse_command("cfirst"), Command::QfFirst);
+        assert_eq!(parse_command("cr"), Command::QfFirst);
+        assert_eq!(parse_command("clast"), Command::QfLast);
+        assert_eq!(parse_command("cl"), Command::QfList);
+        assert_eq!(parse_command("cc"), Command::QfGo(None));
+        assert_eq!(parse_command("cc 4"), Command::QfGo(Some(4)));
+        assert!(matches!(parse_command("cc 0"), Command::Unknown(_)));
+        assert_eq!(parse_command("cdo s/a/b/g"), Command::Cdo("s/a/b/g".to_string()));
+        assert_eq!(parse_command("cfdo %s/a/b/"), Command::Cfdo("%s/a/b/".to_string()));
+    }
+
+    #[test]
+    fn parse_tag() {
+        assert_eq!(parse_command("tag main"), Command::Tag("main".to_string()));
+        assert_eq!(parse_command("ta Editor"), Command::Tag("Editor".to_string()));
+        assert!(matches!(parse_command("tag"), Command::Unknown(m) if m.starts_with("E471")));
+        assert_eq!(parse_command("tags"), Command::Tags);
+    }
+
+    #[test]
+    fn parse_map() {
+        let all = MapMode::All;
+        assert_eq!(
+            parse_command("map <Space>ff :e foo<CR>"),
+            Command::Map {
+                mode: all,
+                lhs: "<Space>ff".to_string(),
+                rhs: Some(":e foo<CR>".to_string()),
+                noremap: false,
+                expr: false,
+                buffer: false,
+            }
+        );
+        assert_eq!(
+            parse_command("no jk <Esc>"),
+            Command::Map { mode: all, lhs: "jk".to_string(), rhs: Some("<Esc>".to_string()), noremap: true, expr: false, buffer: false }
+        );
+        assert_eq!(
+            parse_command("map"),
+            Command::Map { mode: all, lhs: String::new(), rhs: None, noremap: false, expr: false, buffer: false }
+        );
+        assert_eq!(
+            parse_command("map j"),
+            Command::Map { mode: all, lhs: "j".to_string(), rhs: None, noremap: false, expr: false, buffer: false }
+        );
+        assert_eq!(
+            parse_command(r#"imap <expr> <CR> pumvisible() ? "\<C-y>" : "\<CR>""#),
+            Command::Map {
+                mode: MapMode::Insert,
+                lhs: "<CR>".to_string(),
+                rhs: Some(r#"pumvisible() ? "\<C-y>" : "\<CR>""#.to_string()),
+                noremap: false,
+                expr: true,
+                buffer: false,
+            }
+        );
+        assert_eq!(
+            parse_command("nnoremap <buffer> <expr> K 'gd'"),
+            Command::Map {
+                mode: MapMode::Normal,
+                lhs: "K".to_string(),
+                rhs: Some("'gd'".to_string()),
+                noremap: true,
+                expr: true,
+                buffer: true,
+            }
+        );
+        assert_eq!(parse_command("unm jk"), Command::Unmap { mode: all, lhs: "jk".to_string(), buffer: false });
+        assert_eq!(parse_command("unm <buffer> jk"), Command::Unmap { mode: all, lhs: "jk".to_string(), buffer: true });
+        assert!(matches!(parse_command("unmap"), Command::Unknown(m) if m.starts_with("E474")));
+    }
+
+    #[test]
+    fn parse_abbreviations() {
+        let abbrev = |mode, lhs: &str, rhs: Option<&str>| Command::Abbreviate {
+            mode,
+            lhs: lhs.to_string(),
+            rhs: rhs.map(str::to_string),
+        };
+        assert_eq!(parse_command("iabbrev teh the"), abbrev(AbbrevMode::Insert, "teh", Some("the")));
+        assert_eq!(parse_command("ia teh  the end"), abbrev(AbbrevMode::Insert, "teh", Some("the end")));
+        assert_eq!(parse_command("ab"), abbrev(AbbrevMode::All, "", None));
+        assert_eq!(parse_command("cabbrev W w"), abbrev(AbbrevMode::Command, "W", Some("w")));
+        assert_eq!(
+            parse_command("iuna teh"),
+            Command::Unabbreviate { mode: AbbrevMode::Insert, lhs: "teh".to_string() }
+        );
+        assert_eq!(
+            parse_command("unabbreviate teh"),
+            Command::Unabbreviate { mode: AbbrevMode::All, lhs: "teh".to_string() }
+        );
+        assert_eq!(
+            parse_command("iunabbreviate teh"),
+            Command::Unabbreviate { mode: AbbrevMode::Insert, lhs: "teh".to_string() }
+        );
+        assert!(matches!(parse_command("una"), Command::Unknown(m) if m.starts_with("E474")));
+        assert_eq!(parse_command("abc"), Command::AbbrevClear(AbbrevMode::All));
+        assert_eq!(parse_command("iabclear"), Command::AbbrevClear(AbbrevMode::Insert));
+    }
+
+    #[test]
+    fn parse_mode_maps() {
+        let map = |cmd: &str| match parse_command(&format!("{cmd} a b")) {
+            Command::Map { mode, noremap, .. } => Some((mode, noremap)),
+            _ => None,
+        };
+        assert_eq!(map("nmap"), Some((MapMode::Normal, false)));
+        assert_eq!(map("nn"), Some((MapMode::Normal, true)));
+        assert_eq!(map("im"), Some((MapMode::Insert, false)));
+        assert_eq!(map("ino"), Some((MapMode::Insert, true)));
+        assert_eq!(map("vnoremap"), Some((MapMode::Visual, true)));
+        assert_eq!(map("xmap"), Some((MapMode::VisualChar, false)));
+        assert_eq!(map("onoremap"), Some((MapMode::OperatorPending, true)));
+        assert_eq!(map("cno"), Some((MapMode::Command, true)));
+        // Shorter forms are other commands.
+        assert_eq!(parse_command("on"), Command::WinOnly);
+        assert_eq!(parse_command("cn"), Command::QfNext);
+        assert_eq!(parse_command("x"), Command::ExitSave);
+
+        let unmap = |mode, lhs: &str| Command::Unmap { mode, lhs: lhs.to_string(), buffer: false };
+        assert_eq!(parse_command("iu jk"), unmap(MapMode::Insert, "jk"));
+        assert_eq!(parse_command("nun Q"), unmap(MapMode::Normal, "Q"));
+        assert_eq!(parse_command("mapclear"), Command::MapClear { mode: MapMode::All, buffer: false });
+        assert_eq!(parse_command("vmapc"), Command::MapClear { mode: MapMode::Visual, buffer: false });
+        assert_eq!(parse_command("nmapc <buffer>"), Command::MapClear { mode: MapMode::Normal, buffer: true });
+        assert!(matches!(parse_command("mapclear x"), Command::Unknown(m) if m.starts_with("E488")));
+    }
+
+    #[test]
+    fn parse_shada() {
+        assert_eq!(parse_command("wshada"), Command::WriteShada(None));
+        assert_eq!(parse_command("wsh /tmp/s.json"), Command::WriteShada(Some("/tmp/s.json".to_string())));
+        assert_eq!(parse_command("rshada"), Command::ReadShada { path: None, force: false });
+        assert_eq!(
+            parse_command("rsh! other.json"),
+            Command::ReadShada { path: Some("other.json".to_string()), force: true }
+        );
+    }
+
+    #[test]
+    fn parse_messages() {
+        assert_eq!(parse_command("messages"), Command::Messages { clear: false });
+        assert_eq!(parse_command("mes"), Command::Messages { clear: false });
+        assert_eq!(parse_command("msgs clear"), Command::Messages { clear: true });
+        assert!(matches!(parse_command("mes x"), Command::Unknown(m) if m.starts_with("E488")));
+        assert_eq!(parse_command("verbose set ts?"), Command::Verbose("set ts?".to_string()));
+        assert!(matches!(parse_command("verb"), Command::Unknown(m) if m.starts_with("E471")));
+        assert!(matches!(parse_command("cdo"), Command::Unknown(m) if m.starts_with("E471")));
+    }
+
+    #[test]
+    fn parse_terminal() {
+        assert_eq!(parse_command("terminal"), Command::Terminal(String::new()));
+        assert_eq!(parse_command("term"), Command::Terminal(String::new()));
+        assert_eq!(
+            parse_command("ter ls -la  /tmp"),
+            Command::Terminal("ls -la  /tmp".to_string())
+        );
     }
 
     // ── :set command ────────────────────────────────────────────────────
@@ -0,0 +1,178 @@
+//! Conceal — hiding markup so the text reads as it would when rendered.
+//!
+//! A [`ConcealRule`] is a pattern whose matches are partly hidden: the
+//! first capture group is the text to keep, everything else in the match
+//! is concealed. `\*\*([^*]+)\*\*` shows `**bold**` as `bold`.
+//!
+//! `conceallevel` (per window) picks what is drawn in place of each
+//! concealed run:
+//!
+//! | Level | Shown                                   |
+//! |-------|-----------------------------------------|
+//! | 0     | the text as it is — no concealing       |
+//! | 1     | the rule's replacement character        |
+//! | 2     | nothing                                 |
+//!
+//! The cursor line is always shown as it is, so the markup being edited
+//! stays visible. Only the display changes: cursor columns, motions and
+//! edits work on the real text.
+//!
+//! The rules come per filetype from [`for_path`]; Markdown has a built-in
+//! set for bold, italic and links.
+
+use std::path::Path;
+use std::sync::LazyLock;
+
+use n_term::cell::Attr;
+use n_term::color::CellColor;
+use n_theme::HighlightGroup;
+use regex::Regex;
+
+/// A pattern whose matches are concealed, except for the first capture
+/// group.
+#[derive(Debug, Clone)]
+pub struct ConcealRule {
+    /// What to conceal. Group 1, if present, stays visible.
+    pub pattern: Regex,
+    /// Drawn for each concealed run at `conceallevel=1`.
+    pub replacement: char,
+    /// Colors of the replacement character. Default colors fall back to
+    /// the normal text colors.
+    pub highlight: HighlightGroup,
+}
+
+/// One concealed run of a line: the chars `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConcealSpan {
+    /// First concealed char column.
+    pub start: usize,
+    /// Char column just past the run.
+    pub end: usize,
+    /// Drawn instead of the run at `conceallevel=1`.
+    pub replacement: char,
+    /// Colors of the replacement character.
+    pub highlight: HighlightGroup,
+}
+
+/// The conceal rules of one filetype.
+#[derive(Debug, Clone)]
+pub struct ConcealEngine {
+    rules: Vec<ConcealRule>,
+}
+
+impl ConcealEngine {
+    /// An engine applying `rules`. Where matches of different rules
+    /// overlap, the earlier rule wins.
+    #[must_use]
+    pub const fn new(rules: Vec<ConcealRule>) -> Self {
+        Self { rules }
+    }
+
+    /// The built-in Markdown rules: `**bold**`, `__bold__`, `*italic*`,
+    /// `_italic_` and `[text](url)` links, each showing just the text.
+    ///
+    /// # Panics
+    ///
+    /// Never: the patterns are fixed and valid.
+    #[must_use]
+    pub fn markdown() -> Self {
+        let markup = HighlightGroup::fg_attrs(CellColor::Default, Attr::DIM);
+        let rule = |pattern: &str| ConcealRule {
+            pattern: Regex::new(pattern).expect("built-in conceal pattern"),
+            replacement: ' ',
+            highlight: markup,
+        };
+        Self::new(vec![
+            rule(r"\[([^\]]+)\]\([^)\s]*\)"),
+            rule(r"\*\*([^*]+)\*\*"),
+            rule(r"\b__([^_]+)__\b"),
+            rule(r"\*([^*\s][^*]*)\*"),
+            rule(r"\b_([^_\s][^_]*)_\b"),
+        ])
+    }
+
+    /// The concealed runs of `line`, in column order.
+    #[must_use]
+    pub fn spans(&self, line: &str) -> Vec<ConcealSpan> {
+        // Byte ranges of the matches taken so far, to skip overlaps.
+        let mut taken: Vec<(usize, usize)> = Vec::new();
+        let mut spans = Vec::new();
+        let col = |byte: usize| line[..byte].chars().count();
+        for rule in &self.rules {
+            for caps in rule.pattern.captures_iter(line) {
+                let Some(m) = caps.get(0) else { continue };
+                if taken.iter().any(|&(s, e)| m.start() < e && s < m.end()) {
+                    continue;
+                }
+                taken.push((m.start(), m.end()));
+                let runs = caps.get(1).map_or_else(
+                    || vec![(m.start(), m.end())],
+                    |kept| vec![(m.start(), kept.start()), (kept.end(), m.end())],
+                );
+                for (start, end) in runs.into_iter().filter(|(s, e)| s < e) {
+                    spans.push(ConcealSpan {
+                        start: col(start),
+                        end: col(end),
+                        replacement: rule.replacement,
+                        highlight: rule.highlight,
+                    });
+                }
+            }
+        }
+        spans.sort_by_key(|s| s.start);
+        spans
+    }
+}
+
+/// Built-in Markdown rules, compiled once.
+static MARKDOWN: LazyLock<ConcealEngine> = LazyLock::new(ConcealEngine::markdown);
+
+/// The conceal rules for the file at `path`, by extension.
+#[must_use]
+pub fn for_path(path: &Path) -> Option<&'static ConcealEngine> {
+    match path.extension()?.to_str()? {
+        "md" | "markdown" => Some(&MARKDOWN),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `line` with every concealed run replaced by `|`.
+    fn shown(line: &str) -> String {
+        let spans = ConcealEngine::markdown().spans(line);
+        let mut out = String::new();
+        for (col, ch) in line.chars().enumerate() {
+            match spans.iter().find(|s| s.start <= col && col < s.end) {
+                Some(s) if s.start == col => out.push('|'),
+                Some(_) => {}
+                None => out.push(ch),
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn markdown_bold_italic_and_links() {
+        assert_eq!(shown("a **bold** b"), "a |bold| b");
+        assert_eq!(shown("an *it* and _it_"), "an |it| and |it|");
+        assert_eq!(shown("see [the docs](https://x.y/z) now"), "see |the docs| now");
+        assert_eq!(shown("__b__ snake_case_name"), "|b| snake_case_name");
+        assert_eq!(shown("2 * 3 * 4"), "2 * 3 * 4");
+    }
+
+    #[test]
+    fn spans_are_in_char_columns() {
+        let spans = ConcealEngine::markdown().spans("é **b**");
+        let cols: Vec<(usize, usize)> = spans.iter().map(|s| (s.start, s.end)).collect();
+        assert_eq!(cols, vec![(2, 4), (5, 7)]);
+    }
+
+    #[test]
+    fn rules_by_extension() {
+        assert!(for_path(Path::new("README.md")).is_some());
+        assert!(for_path(Path::new("main.rs")).is_none());
+    }
+}
@@ -21,6 +21,10 @@
 //! moves through a short line and then reaches a long line again, it snaps
 //! back to the remembered column. Horizontal movement resets the sticky column.
 //!
+//! `$` sets a sticky end of line instead: until the next horizontal move,
+//! vertical movement lands on the last character of each line, however
+//! long.
+//!
 //! # Selection
 //!
 //! The cursor has an optional `anchor` position. When set, the text between
@@ -30,6 +34,7 @@
 
 use crate::buffer::Buffer;
 use crate::position::{Position, Range};
+use crate::text_object;
 use crate::word;
 
 /// A cursor in a text buffer.
@@ -47,6 +52,10 @@ pub struct Cursor {
     /// Horizontal movement resets it to the current column.
     sticky_col: usize,
 
+    /// Vertical movement goes to the end of each line (after `$`).
+    /// `sticky_col` is `usize::MAX` while it's set.
+    sticky_eol: bool,
+
     /// Selection anchor. When `Some`, the region between `anchor` and `pos`
     /// is selected. The anchor is the "other end" — it stays put while the
     /// cursor moves.
@@ -60,6 +69,7 @@ impl Cursor {
         Self {
             pos: Position::ZERO,
             sticky_col: 0,
+            sticky_eol: false,
             anchor: None,
         }
     }
@@ -70,6 +80,7 @@ impl Cursor {
         Self {
             pos,
             sticky_col: pos.col,
+            sticky_eol: false,
             anchor: None,
         }
     }
@@ -104,6 +115,13 @@ impl Cursor {
         self.sticky_col
     }
 
+    /// Whether vertical movement goes to the end of each line (after `$`).
+    #[inline]
+    #[must_use]
+    pub const fn sticky_eol(&self) -> bool {
+        self.sticky_eol
+    }
+
     /// The selection anchor, if a selection is active.
     #[inline]
     #[must_use]
@@ -152,7 +170,7 @@ impl Cursor {
     /// Does **not** affect the selection anchor.
     pub fn set_position(&mut self, pos: Position, buf: &Buffer, past_end: bool) {
         self.pos = clamp(pos, buf, past_end);
-        self.sticky_col = self.pos.col;
+        self.reset_sticky();
     }
 
     /// Move to a specific line, keeping the current column (or clamping).
@@ -160,33 +178,47 @@ impl Cursor {
     pub fn goto_line(&mut self, line: usize, buf: &Buffer, past_end: bool) {
         let target = Position::new(line, self.pos.col);
         self.pos = clamp(target, buf, past_end);
-        self.sticky_col = self.pos.col;
+        self.reset_sticky();
     }
 
     // -- Horizontal movement ------------------------------------------------
 
-    /// Move left by `count` characters. Stops at column 0 (no line wrapping).
-    /// Resets sticky column.
+    /// Move left by `count` characters — grapheme clusters, so an emoji
+    /// sequence or a letter with combining marks is one step. Stops at
+    /// column 0 (no line wrapping). Resets sticky column.
     pub fn move_left(&mut self, count: usize, buf: &Buffer, past_end: bool) {
         let max_col = max_col_for_line(buf, self.pos.line, past_end);
         let col = self.pos.col.min(max_col);
-        self.pos.col = col.saturating_sub(count);
-        self.sticky_col = self.pos.col;
-    }
-
-    /// Move right by `count` characters. Stops at the column limit for the
+        let cols = buf.grapheme_boundaries(self.pos.line);
+        // Index of the cluster the cursor is in (or the end boundary).
+        let idx = match cols.binary_search(&col) {
+            Ok(i) => i,
+            Err(i) => i.saturating_sub(1),
+        };
+        self.pos.col = cols.get(idx.saturating_sub(count)).copied().unwrap_or(0);
+        self.reset_sticky();
+    }
+
+    /// Move right by `count` characters — grapheme clusters, as for
+    /// [`move_left`](Self::move_left). Stops at the column limit for the
     /// current line (no line wrapping). Resets sticky column.
     pub fn move_right(&mut self, count: usize, buf: &Buffer, past_end: bool) {
         let max_col = max_col_for_line(buf, self.pos.line, past_end);
-        self.pos.col = (self.pos.col + count).min(max_col);
-        self.sticky_col = self.pos.col;
+        let cols = buf.grapheme_boundaries(self.pos.line);
+        let idx = match cols.binary_search(&self.pos.col) {
+            Ok(i) => i,
+            Err(i) => i.saturating_sub(1),
+        };
+        let target = cols.get(idx + count).or_else(|| cols.last()).copied().unwrap_or(0);
+        self.pos.col = target.min(max_col);
+        self.reset_sticky();
     }
 
     /// Move to the first column of the current line. Resets sticky column.
     /// This is `0` in Vim.
     pub const fn move_to_line_start(&mut self) {
         self.pos.col = 0;
-        self.sticky_col = 0;
+        self.reset_sticky();
     }
 
     /// Move to the first non-whitespace character of the current line.
@@ -202,14 +234,16 @@ impl Cursor {
         } else {
             self.pos.col = 0;
         }
-        self.sticky_col = self.pos.col;
+        self.reset_sticky();
     }
 
     /// Move to the last character (or past-last in insert mode) of the
-    /// current line. This is `$` in Vim. Resets sticky column.
+    /// current line. This is `$` in Vim. Sets the sticky end of line, so
+    /// `j` / `k` keep to the end of each line.
     pub fn move_to_line_end(&mut self, buf: &Buffer, past_end: bool) {
         self.pos.col = max_col_for_line(buf, self.pos.line, past_end);
-        self.sticky_col = self.pos.col;
+        self.sticky_col = usize::MAX;
+        self.sticky_eol = true;
     }
 
     // -- Vertical movement --------------------------------------------------
@@ -219,7 +253,7 @@ impl Cursor {
     pub fn move_up(&mut self, count: usize, buf: &Buffer, past_end: bool) {
         self.pos.line = self.pos.line.saturating_sub(count);
         let max_col = max_col_for_line(buf, self.pos.line, past_end);
-        self.pos.col = self.sticky_col.min(max_col);
+        self.pos.col = buf.grapheme_start_col(Position::new(self.pos.line, self.sticky_target(max_col)));
     }
 
     /// Move down by `count` lines. Uses the sticky column to maintain
@@ -228,7 +262,7 @@ impl Cursor {
         let last_line = buf.line_count().saturating_sub(1);
         self.pos.line = (self.pos.line + count).min(last_line);
         let max_col = max_col_for_line(buf, self.pos.line, past_end);
-        self.pos.col = self.sticky_col.min(max_col);
+        self.pos.col = buf.grapheme_start_col(Position::new(self.pos.line, self.sticky_target(max_col)));
     }
 
     /// Move to the first line of the buffer. This is `gg` in Vim.
@@ -236,7 +270,7 @@ impl Cursor {
     pub fn move_to_first_line(&mut self, buf: &Buffer, past_end: bool) {
         self.pos.line = 0;
         let max_col = max_col_for_line(buf, 0, past_end);
-        self.pos.col = self.sticky_col.min(max_col);
+        self.pos.col = buf.grapheme_start_col(Position::new(self.pos.line, self.sticky_target(max_col)));
     }
 
     /// Move to the last line of the buffer. This is `G` in Vim.
@@ -245,7 +279,7 @@ impl Cursor {
         let last_line = buf.line_count().saturating_sub(1);
         self.pos.line = last_line;
         let max_col = max_col_for_line(buf, last_line, past_end);
-        self.pos.col = self.sticky_col.min(max_col);
+        self.pos.col = buf.grapheme_start_col(Position::new(self.pos.line, self.sticky_target(max_col)));
     }
 
     // -- Word motions -------------------------------------------------------
@@ -258,7 +292,7 @@ impl Cursor {
         }
         let max_col = max_col_for_line(buf, self.pos.line, past_end);
         self.pos.col = self.pos.col.min(max_col);
-        self.sticky_col = self.pos.col;
+        self.reset_sticky();
     }
 
     /// Move backward to the start of the previous word. This is `b` in Vim.
@@ -269,7 +303,7 @@ impl Cursor {
         }
         let max_col = max_col_for_line(buf, self.pos.line, past_end);
         self.pos.col = self.pos.col.min(max_col);
-        self.sticky_col = self.pos.col;
+        self.reset_sticky();
     }
 
     /// Move forward to the end of the current or next word. This is `e` in Vim.
@@ -280,7 +314,7 @@ impl Cursor {
         }
         let max_col = max_col_for_line(buf, self.pos.line, past_end);
         self.pos.col = self.pos.col.min(max_col);
-        self.sticky_col = self.pos.col;
+        self.reset_sticky();
     }
 
     /// Move forward to the start of the next WORD. This is `W` in Vim.
@@ -291,7 +325,7 @@ impl Cursor {
         }
         let max_col = max_col_for_line(buf, self.pos.line, past_end);
         self.pos.col = self.pos.col.min(max_col);
-        self.sticky_col = self.pos.col;
+        self.reset_sticky();
     }
 
     /// Move backward to the start of the previous WORD. This is `B` in Vim.
@@ -302,7 +336,7 @@ impl Cursor {
         }
         let max_col = max_col_for_line(buf, self.pos.line, past_end);
         self.pos.col = self.pos.col.min(max_col);
-        self.sticky_col = self.pos.col;
+        self.reset_sticky();
     }
 
     /// Move forward to the end of the current or next WORD. This is `E` in Vim.
@@ -313,7 +347,7 @@ impl Cursor {
         }
         let max_col = max_col_for_line(buf, self.pos.line, past_end);
         self.pos.col = self.pos.col.min(max_col);
-        self.sticky_col = self.pos.col;
+        self.reset_sticky();
     }
 
     // -- Character find motions ---------------------------------------------
@@ -331,7 +365,7 @@ impl Cursor {
         if let Some(col) = find_on_line_forward(buf, self.pos.line, self.pos.col, ch, count) {
             let max = max_col_for_line(buf, self.pos.line, past_end);
             self.pos.col = col.min(max);
-            self.sticky_col = self.pos.col;
+            self.reset_sticky();
             true
         } else {
             false
@@ -355,7 +389,7 @@ impl Cursor {
             if target > self.pos.col {
                 let max = max_col_for_line(buf, self.pos.line, past_end);
                 self.pos.col = target.min(max);
-                self.sticky_col = self.pos.col;
+                self.reset_sticky();
                 true
             } else {
                 // Found char is immediately after cursor — nowhere to go.
@@ -378,7 +412,7 @@ impl Cursor {
     ) -> bool {
         if let Some(col) = find_on_line_backward(buf, self.pos.line, self.pos.col, ch, count) {
             self.pos.col = col;
-            self.sticky_col = self.pos.col;
+            self.reset_sticky();
             true
         } else {
             false
@@ -400,7 +434,7 @@ impl Cursor {
             let target = col + 1;
             if target < self.pos.col {
                 self.pos.col = target;
-                self.sticky_col = self.pos.col;
+                self.reset_sticky();
                 true
             } else {
                 // Found char is immediately before cursor — nowhere to go.
@@ -445,7 +479,7 @@ impl Cursor {
         }
 
         self.pos.col = 0;
-        self.sticky_col = 0;
+        self.reset_sticky();
     }
 
     /// Move backward to the previous paragraph boundary. This is `{` in Vim.
@@ -485,7 +519,59 @@ impl Cursor {
         }
 
         self.pos.col = 0;
-        self.sticky_col = 0;
+        self.reset_sticky();
+    }
+
+    // -- Sentence motions ---------------------------------------------------
+
+    /// Move forward to the start of the next sentence. This is `)` in Vim.
+    ///
+    /// A sentence ends at `.`, `!` or `?` followed by white space (closing
+    /// brackets and quotes may come between); empty lines and paragraph
+    /// starts also start a sentence. Past the last sentence, moves to the
+    /// end of the buffer. Resets sticky column.
+    pub fn sentence_forward(&mut self, count: usize, buf: &Buffer, past_end: bool) {
+        let rope = buf.rope();
+        let Some(mut idx) = buf.pos_to_char_idx(self.pos) else {
+            return;
+        };
+        for _ in 0..count {
+            let Some(next) = text_object::next_sentence_start(rope, idx) else {
+                idx = rope.len_chars();
+                break;
+            };
+            idx = next;
+        }
+        let pos = buf.char_idx_to_pos(idx).unwrap_or(self.pos);
+        self.set_position(pos, buf, past_end);
+    }
+
+    /// Move backward to the start of the sentence, or of the previous one
+    /// when already at a start. This is `(` in Vim. Stops at the start of
+    /// the buffer. Resets sticky column.
+    pub fn sentence_backward(&mut self, count: usize, buf: &Buffer, past_end: bool) {
+        let rope = buf.rope();
+        let Some(mut idx) = buf.pos_to_char_idx(self.pos) else {
+            return;
+        };
+        for _ in 0..count {
+            idx = text_object::prev_sentence_start(rope, idx).unwrap_or(0);
+        }
+        let pos = buf.char_idx_to_pos(idx).unwrap_or(self.pos);
+        self.set_position(pos, buf, past_end);
+    }
+
+    /// Make the current column the sticky column, clearing the sticky end
+    /// of line.
+    const fn reset_sticky(&mut self) {
+        self.sticky_col = self.pos.col;
+        self.sticky_eol = false;
+    }
+
+    /// The column vertical movement lands on in a line whose last column
+    /// is `max_col`.
+    const fn sticky_target(&self, max_col: usize) -> usize {
+        if self.sticky_eol || self.sticky_col > max_col { max_col } else { self.sticky_col }
     }
 
     // -- Clamping -----------------------------------------------------------
@@ -514,8 +600,8 @@ impl Default for Cursor {
 
 /// Maximum valid column for a given line and cursor mode.
 ///
-/// - `past_end = false`: cursor must sit ON a character → `content_len - 1`
-///   (or 0 for empty lines).
+/// - `past_end = false`: cursor must sit ON a character → the start of the
+///   last grapheme cluster (or 0 for empty lines).
 /// - `past_end = true`: cursor can sit after last char → `content_len`.
 fn max_col_for_line(buf: &Buffer, line: usize, past_end: bool) -> usize {
     let content_len = buf.line_content_len(line).unwrap_or(0);
@@ -523,7 +609,7 @@ fn max_col_for_line(buf: &Buffer, line: usize, past_end: bool) -> usize {
         content_len
     } else {
         // Normal mode: cursor on a character. Empty line → 0.
-        content_len.saturating_sub(1)
+        buf.grapheme_start_col(Position::new(line, content_len.saturating_sub(1)))
     }
 }
 
@@ -579,7 +665,7 @@ fn clamp(pos: Position, buf: &Buffer, past_end: bool) -> Position {
     }
     let line = pos.line.min(buf.line_count().saturating_sub(1));
     let max_col = max_col_for_line(buf, line, past_end);
-    let col = pos.col.min(max_col);
+    let col = buf.grapheme_start_col(Position::new(line, pos.col.min(max_col)));
     Position::new(line, col)
 }
 
@@ -814,6 +900,31 @@ mod tests {
         assert_eq!(c.col(), 3);
     }
 
+    #[test]
+    fn horizontal_motion_steps_over_grapheme_clusters() {
+        let buf = Buffer::from_text("a\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F466}b\ne\u{301}e\u{301}\n");
+        let mut c = Cursor::at(Position::new(0, 0));
+        c.move_right(1, &buf, false);
+        assert_eq!(c.col(), 1);
+        c.move_right(1, &buf, false);
+        assert_eq!(c.col(), 6);
+        c.move_right(1, &buf, false);
+        assert_eq!(c.col(), 6);
+        c.move_right(1, &buf, true);
+        assert_eq!(c.col(), 7);
+        c.move_left(2, &buf, true);
+        assert_eq!(c.col(), 1);
+
+        // `$` lands on the start of the last cluster, and `j` from inside
+        // a cluster's columns snaps back to its start.
+        c.move_down(1, &buf, false);
+        c.move_to_line_end(&buf, false);
+        assert_eq!(c.col(), 2);
+        let mut c = Cursor::at(Position::new(0, 3));
+        c.move_down(1, &buf, false);
+        assert_eq!(c.col(), 2);
+    }
+
     // -- Line start/end -----------------------------------------------------
 
     #[test]
@@ -1004,17 +1115,40 @@ mod tests {
     }
 
     #[test]
-    fn line_end_resets_sticky() {
+    fn line_end_sets_sticky_eol() {
         let buf = sample_buffer();
         let mut c = Cursor::new();
 
         c.move_to_line_end(&buf, false);
-        assert_eq!(c.sticky_col(), 4); // end of "hello"
+        assert!(c.sticky_eol());
+        assert_eq!(c.sticky_col(), usize::MAX);
 
         c.move_down(1, &buf, false);
         assert_eq!(c.col(), 4); // end of "world"
     }
 
+    #[test]
+    fn sticky_eol_follows_line_ends_until_horizontal_move() {
+        let buf = Buffer::from_text("ab\nlonger line\nx\nabcdef");
+        let mut c = Cursor::new();
+        c.move_to_line_end(&buf, false);
+        c.move_down(1, &buf, false);
+        assert_eq!(c.position(), Position::new(1, 10));
+        c.move_down(1, &buf, false);
+        assert_eq!(c.position(), Position::new(2, 0));
+        c.move_down(1, &buf, false);
+        assert_eq!(c.position(), Position::new(3, 5));
+        c.move_up(2, &buf, false);
+        assert_eq!(c.position(), Position::new(1, 10));
+
+        c.move_left(1, &buf, false);
+        assert!(!c.sticky_eol());
+        c.move_down(2, &buf, false);
+        assert_eq!(c.position(), Position::new(3, 5));
+        c.move_up(3, &buf, false);
+        assert_eq!(c.position(), Position::new(0, 1));
+    }
+
     #[test]
     fn line_start_resets_sticky() {
         let buf = sample_buffer();
@@ -1591,6 +1725,24 @@ mod tests {
         assert_eq!(c.position(), Position::new(3, 0));
     }
 
+    #[test]
+    fn sentence_forward_and_backward() {
+        let buf = Buffer::from_text("One two. Three?\n\nFour!");
+        let mut c = Cursor::at(Position::new(0, 2));
+
+        c.sentence_forward(1, &buf, false);
+        assert_eq!(c.position(), Position::new(0, 9));
+        c.sentence_forward(2, &buf, false);
+        assert_eq!(c.position(), Position::new(2, 0));
+        c.sentence_forward(1, &buf, false);
+        assert_eq!(c.position(), Position::new(2, 4)); // clamped to the last char
+
+        c.sentence_backward(1, &buf, false);
+        assert_eq!(c.position(), Position::new(2, 0));
+        c.sentence_backward(3, &buf, false);
+        assert_eq!(c.position(), Position::new(0, 0));
+    }
+
     #[test]
     fn paragraph_forward_to_end_of_buffer() {
         let buf = Buffer::from_text("aaa\nbbb\nccc");
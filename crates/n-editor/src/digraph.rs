@@ -0,0 +1,368 @@
+//! Digraphs — two-character mnemonics for entering Unicode (`Ctrl+K`).
+//!
+//! In insert and command-line mode, `Ctrl+K {char1}{char2}` inserts the
+//! character the pair stands for: `a:` → `ä`, `Eu` → `€`, `->` → `→`. The
+//! built-in table follows RFC 1345, the same mnemonics Vim uses:
+//!
+//! | Suffix | Meaning      | Example        |
+//! |--------|--------------|----------------|
+//! | `!`    | grave        | `e!` → `è`     |
+//! | `'`    | acute        | `e'` → `é`     |
+//! | `>`    | circumflex   | `e>` → `ê`     |
+//! | `?`    | tilde        | `n?` → `ñ`     |
+//! | `:`    | diaeresis    | `u:` → `ü`     |
+//! | `,`    | cedilla      | `c,` → `ç`     |
+//! | `<`    | caron        | `s<` → `š`     |
+//! | `*`    | Greek        | `l*` → `λ`     |
+//! | `=`    | Cyrillic     | `d=` → `д`     |
+//!
+//! If a pair isn't found, the reversed pair is tried (`:a` works like `a:`).
+//! `:digraph {char1}{char2} {number}` adds custom entries and `:digraphs`
+//! lists the table.
+
+use std::collections::HashMap;
+
+/// RFC 1345 digraphs: Latin-1, Latin Extended-A, Greek, Cyrillic,
+/// punctuation, sub/superscripts, currency, arrows, math, box drawing, and
+/// assorted symbols.
+const RFC1345: &[(&str, char)] = &[
+    // ASCII characters that are awkward on some keyboards.
+    ("SP", ' '), ("Nb", '#'), ("DO", '$'), ("At", '@'), ("<(", '['),
+    ("//", '\\'), (")>", ']'), ("'>", '^'), ("'!", '`'), ("(!", '{'),
+    ("!!", '|'), ("!)", '}'), ("'?", '~'),
+    // Latin-1 supplement.
+    ("NS", '\u{a0}'), ("!I", '¡'), ("Ct", '¢'), ("Pd", '£'), ("Cu", '¤'),
+    ("Ye", '¥'), ("BB", '¦'), ("SE", '§'), ("':", '¨'), ("Co", '©'),
+    ("-a", 'ª'), ("<<", '«'), ("NO", '¬'), ("--", '\u{ad}'), ("Rg", '®'),
+    ("'m", '¯'), ("DG", '°'), ("+-", '±'), ("2S", '²'), ("3S", '³'),
+    ("''", '´'), ("My", 'µ'), ("PI", '¶'), (".M", '·'), ("',", '¸'),
+    ("1S", '¹'), ("-o", 'º'), (">>", '»'), ("14", '¼'), ("12", '½'),
+    ("34", '¾'), ("?I", '¿'), ("A!", 'À'), ("A'", 'Á'), ("A>", 'Â'),
+    ("A?", 'Ã'), ("A:", 'Ä'), ("AA", 'Å'), ("AE", 'Æ'), ("C,", 'Ç'),
+    ("E!", 'È'), ("E'", 'É'), ("E>", 'Ê'), ("E:", 'Ë'), ("I!", 'Ì'),
+    ("I'", 'Í'), ("I>", 'Î'), ("I:", 'Ï'), ("D-", 'Ð'), ("N?", 'Ñ'),
+    ("O!", 'Ò'), ("O'", 'Ó'), ("O>", 'Ô'), ("O?", 'Õ'), ("O:", 'Ö'),
+    ("*X", '×'), ("O/", 'Ø'), ("U!", 'Ù'), ("U'", 'Ú'), ("U>", 'Û'),
+    ("U:", 'Ü'), ("Y'", 'Ý'), ("TH", 'Þ'), ("ss", 'ß'), ("a!", 'à'),
+    ("a'", 'á'), ("a>", 'â'), ("a?", 'ã'), ("a:", 'ä'), ("aa", 'å'),
+    ("ae", 'æ'), ("c,", 'ç'), ("e!", 'è'), ("e'", 'é'), ("e>", 'ê'),
+    ("e:", 'ë'), ("i!", 'ì'), ("i'", 'í'), ("i>", 'î'), ("i:", 'ï'),
+    ("d-", 'ð'), ("n?", 'ñ'), ("o!", 'ò'), ("o'", 'ó'), ("o>", 'ô'),
+    ("o?", 'õ'), ("o:", 'ö'), ("-:", '÷'), ("o/", 'ø'), ("u!", 'ù'),
+    ("u'", 'ú'), ("u>", 'û'), ("u:", 'ü'), ("y'", 'ý'), ("th", 'þ'),
+    ("y:", 'ÿ'),
+    // Latin Extended-A.
+    ("A-", 'Ā'), ("a-", 'ā'), ("A(", 'Ă'), ("a(", 'ă'), ("A;", 'Ą'),
+    ("a;", 'ą'), ("C'", 'Ć'), ("c'", 'ć'), ("C>", 'Ĉ'), ("c>", 'ĉ'),
+    ("C.", 'Ċ'), ("c.", 'ċ'), ("C<", 'Č'), ("c<", 'č'), ("D<", 'Ď'),
+    ("d<", 'ď'), ("D/", 'Đ'), ("d/", 'đ'), ("E-", 'Ē'), ("e-", 'ē'),
+    ("E(", 'Ĕ'), ("e(", 'ĕ'), ("E.", 'Ė'), ("e.", 'ė'), ("E;", 'Ę'),
+    ("e;", 'ę'), ("E<", 'Ě'), ("e<", 'ě'), ("G>", 'Ĝ'), ("g>", 'ĝ'),
+    ("G(", 'Ğ'), ("g(", 'ğ'), ("G.", 'Ġ'), ("g.", 'ġ'), ("G,", 'Ģ'),
+    ("g,", 'ģ'), ("H>", 'Ĥ'), ("h>", 'ĥ'), ("H/", 'Ħ'), ("h/", 'ħ'),
+    ("I?", 'Ĩ'), ("i?", 'ĩ'), ("I-", 'Ī'), ("i-", 'ī'), ("I(", 'Ĭ'),
+    ("i(", 'ĭ'), ("I;", 'Į'), ("i;", 'į'), ("I.", 'İ'), ("i.", 'ı'),
+    ("IJ", 'Ĳ'), ("ij", 'ĳ'), ("J>", 'Ĵ'), ("j>", 'ĵ'), ("K,", 'Ķ'),
+    ("k,", 'ķ'), ("kk", 'ĸ'), ("L'", 'Ĺ'), ("l'", 'ĺ'), ("L,", 'Ļ'),
+    ("l,", 'ļ'), ("L<", 'Ľ'), ("l<", 'ľ'), ("L.", 'Ŀ'), ("l.", 'ŀ'),
+    ("L/", 'Ł'), ("l/", 'ł'), ("N'", 'Ń'), ("n'", 'ń'), ("N,", 'Ņ'),
+    ("n,", 'ņ'), ("N<", 'Ň'), ("n<", 'ň'), ("'n", 'ŉ'), ("NG", 'Ŋ'),
+    ("ng", 'ŋ'), ("O-", 'Ō'), ("o-", 'ō'), ("O(", 'Ŏ'), ("o(", 'ŏ'),
+    ("O\"", 'Ő'), ("o\"", 'ő'), ("OE", 'Œ'), ("oe", 'œ'), ("R'", 'Ŕ'),
+    ("r'", 'ŕ'), ("R,", 'Ŗ'), ("r,", 'ŗ'), ("R<", 'Ř'), ("r<", 'ř'),
+    ("S'", 'Ś'), ("s'", 'ś'), ("S>", 'Ŝ'), ("s>", 'ŝ'), ("S,", 'Ş'),
+    ("s,", 'ş'), ("S<", 'Š'), ("s<", 'š'), ("T,", 'Ţ'), ("t,", 'ţ'),
+    ("T<", 'Ť'), ("t<", 'ť'), ("T/", 'Ŧ'), ("t/", 'ŧ'), ("U?", 'Ũ'),
+    ("u?", 'ũ'), ("U-", 'Ū'), ("u-", 'ū'), ("U(", 'Ŭ'), ("u(", 'ŭ'),
+    ("U0", 'Ů'), ("u0", 'ů'), ("U\"", 'Ű'), ("u\"", 'ű'), ("U;", 'Ų'),
+    ("u;", 'ų'), ("W>", 'Ŵ'), ("w>", 'ŵ'), ("Y>", 'Ŷ'), ("y>", 'ŷ'),
+    ("Y:", 'Ÿ'), ("Z'", 'Ź'), ("z'", 'ź'), ("Z.", 'Ż'), ("z.", 'ż'),
+    ("Z<", 'Ž'), ("z<", 'ž'),
+    // Greek.
+    ("A%", 'Ά'), ("E%", 'Έ'), ("Y%", 'Ή'), ("I%", 'Ί'), ("O%", 'Ό'),
+    ("U%", 'Ύ'), ("W%", 'Ώ'), ("A*", 'Α'), ("B*", 'Β'), ("G*", 'Γ'),
+    ("D*", 'Δ'), ("E*", 'Ε'), ("Z*", 'Ζ'), ("Y*", 'Η'), ("H*", 'Θ'),
+    ("I*", 'Ι'), ("K*", 'Κ'), ("L*", 'Λ'), ("M*", 'Μ'), ("N*", 'Ν'),
+    ("C*", 'Ξ'), ("O*", 'Ο'), ("P*", 'Π'), ("R*", 'Ρ'), ("S*", 'Σ'),
+    ("T*", 'Τ'), ("U*", 'Υ'), ("F*", 'Φ'), ("X*", 'Χ'), ("Q*", 'Ψ'),
+    ("W*", 'Ω'), ("J*", 'Ϊ'), ("V*", 'Ϋ'), ("a%", 'ά'), ("e%", 'έ'),
+    ("y%", 'ή'), ("i%", 'ί'), ("a*", 'α'), ("b*", 'β'), ("g*", 'γ'),
+    ("d*", 'δ'), ("e*", 'ε'), ("z*", 'ζ'), ("y*", 'η'), ("h*", 'θ'),
+    ("i*", 'ι'), ("k*", 'κ'), ("l*", 'λ'), ("m*", 'μ'), ("n*", 'ν'),
+    ("c*", 'ξ'), ("o*", 'ο'), ("p*", 'π'), ("r*", 'ρ'), ("*s", 'ς'),
+    ("s*", 'σ'), ("t*", 'τ'), ("u*", 'υ'), ("f*", 'φ'), ("x*", 'χ'),
+    ("q*", 'ψ'), ("w*", 'ω'), ("j*", 'ϊ'), ("v*", 'ϋ'), ("o%", 'ό'),
+    ("u%", 'ύ'), ("w%", 'ώ'),
+    // Cyrillic.
+    ("IO", 'Ё'), ("A=", 'А'), ("B=", 'Б'), ("V=", 'В'), ("G=", 'Г'),
+    ("D=", 'Д'), ("E=", 'Е'), ("Z%", 'Ж'), ("Z=", 'З'), ("I=", 'И'),
+    ("J=", 'Й'), ("K=", 'К'), ("L=", 'Л'), ("M=", 'М'), ("N=", 'Н'),
+    ("O=", 'О'), ("P=", 'П'), ("R=", 'Р'), ("S=", 'С'), ("T=", 'Т'),
+    ("U=", 'У'), ("F=", 'Ф'), ("H=", 'Х'), ("C=", 'Ц'), ("C%", 'Ч'),
+    ("S%", 'Ш'), ("Sc", 'Щ'), ("=\"", 'Ъ'), ("Y=", 'Ы'), ("%\"", 'Ь'),
+    ("JE", 'Э'), ("JU", 'Ю'), ("JA", 'Я'), ("a=", 'а'), ("b=", 'б'),
+    ("v=", 'в'), ("g=", 'г'), ("d=", 'д'), ("e=", 'е'), ("z%", 'ж'),
+    ("z=", 'з'), ("i=", 'и'), ("j=", 'й'), ("k=", 'к'), ("l=", 'л'),
+    ("m=", 'м'), ("n=", 'н'), ("o=", 'о'), ("p=", 'п'), ("r=", 'р'),
+    ("s=", 'с'), ("t=", 'т'), ("u=", 'у'), ("f=", 'ф'), ("h=", 'х'),
+    ("c=", 'ц'), ("c%", 'ч'), ("s%", 'ш'), ("sc", 'щ'), ("='", 'ъ'),
+    ("y=", 'ы'), ("%'", 'ь'), ("je", 'э'), ("ju", 'ю'), ("ja", 'я'),
+    ("io", 'ё'),
+    // General punctuation.
+    ("-1", '‐'), ("-N", '–'), ("-M", '—'), ("-3", '―'), ("!2", '‖'),
+    ("=2", '‗'), ("'6", '‘'), ("'9", '’'), (".9", '‚'), ("9'", '‛'),
+    ("\"6", '“'), ("\"9", '”'), (":9", '„'), ("9\"", '‟'), ("/-", '†'),
+    ("/=", '‡'), ("..", '‥'), (",.", '…'), ("%0", '‰'), ("1'", '′'),
+    ("2'", '″'), ("3'", '‴'), ("<1", '‹'), (">1", '›'), (":X", '※'),
+    ("'-", '‾'), ("/f", '⁄'),
+    // Superscripts and subscripts.
+    ("0S", '⁰'), ("4S", '⁴'), ("5S", '⁵'), ("6S", '⁶'), ("7S", '⁷'),
+    ("8S", '⁸'), ("9S", '⁹'), ("+S", '⁺'), ("-S", '⁻'), ("=S", '⁼'),
+    ("(S", '⁽'), (")S", '⁾'), ("nS", 'ⁿ'), ("0s", '₀'), ("1s", '₁'),
+    ("2s", '₂'), ("3s", '₃'), ("4s", '₄'), ("5s", '₅'), ("6s", '₆'),
+    ("7s", '₇'), ("8s", '₈'), ("9s", '₉'), ("+s", '₊'), ("-s", '₋'),
+    ("=s", '₌'), ("(s", '₍'), (")s", '₎'),
+    // Currency.
+    ("Li", '₤'), ("Pt", '₧'), ("W=", '₩'), ("Eu", '€'), ("=e", '€'),
+    ("=R", '₽'), ("=P", '₽'),
+    // Letterlike symbols and number forms.
+    ("oC", '℃'), ("co", '℅'), ("oF", '℉'), ("N0", '№'), ("PO", '℗'),
+    ("Rx", '℞'), ("SM", '℠'), ("TM", '™'), ("Om", 'Ω'), ("AO", 'Å'),
+    ("13", '⅓'), ("23", '⅔'), ("15", '⅕'), ("25", '⅖'), ("35", '⅗'),
+    ("45", '⅘'), ("16", '⅙'), ("56", '⅚'), ("18", '⅛'), ("38", '⅜'),
+    ("58", '⅝'), ("78", '⅞'),
+    // Arrows.
+    ("<-", '←'), ("-!", '↑'), ("->", '→'), ("-v", '↓'), ("<>", '↔'),
+    ("UD", '↕'), ("<=", '⇐'), ("=>", '⇒'), ("==", '⇔'),
+    // Mathematical operators.
+    ("FA", '∀'), ("dP", '∂'), ("TE", '∃'), ("/0", '∅'), ("DE", '∆'),
+    ("NB", '∇'), ("(-", '∈'), ("-)", '∋'), ("*P", '∏'), ("+Z", '∑'),
+    ("-2", '−'), ("-+", '∓'), ("*-", '∗'), ("Ob", '∘'), ("Sb", '∙'),
+    ("RT", '√'), ("0(", '∝'), ("00", '∞'), ("-L", '∟'), ("-V", '∠'),
+    ("PP", '∥'), ("AN", '∧'), ("OR", '∨'), ("(U", '∩'), (")U", '∪'),
+    ("In", '∫'), ("DI", '∬'), ("Io", '∮'), (".:", '∴'), (":.", '∵'),
+    (":R", '∶'), ("::", '∷'), ("?1", '∼'), ("CG", '∾'), ("?-", '≃'),
+    ("?=", '≅'), ("?2", '≈'), ("=?", '≌'), ("HI", '≓'), ("!=", '≠'),
+    ("=3", '≡'), ("=<", '≤'), (">=", '≥'), ("<*", '≪'), ("*>", '≫'),
+    ("!<", '≮'), ("!>", '≯'), ("(C", '⊂'), (")C", '⊃'), ("(_", '⊆'),
+    (")_", '⊇'), ("0.", '⊙'), ("02", '⊚'), ("-T", '⊥'), (".P", '⋅'),
+    (":3", '⋮'), (".3", '⋯'),
+    // Miscellaneous technical.
+    ("Eh", '⌂'), ("<7", '⌈'), (">7", '⌉'), ("7<", '⌊'), ("7>", '⌋'),
+    ("NI", '⌐'), ("(A", '⌒'), ("TR", '⌕'), ("Iu", '⌠'), ("Il", '⌡'),
+    ("</", '〈'), ("/>", '〉'),
+    // Box drawing.
+    ("hh", '─'), ("HH", '━'), ("vv", '│'), ("VV", '┃'), ("3-", '┄'),
+    ("3_", '┅'), ("3!", '┆'), ("3/", '┇'), ("4-", '┈'), ("4_", '┉'),
+    ("4!", '┊'), ("4/", '┋'), ("dr", '┌'), ("dR", '┍'), ("Dr", '┎'),
+    ("DR", '┏'), ("dl", '┐'), ("dL", '┑'), ("Dl", '┒'), ("LD", '┓'),
+    ("ur", '└'), ("uR", '┕'), ("Ur", '┖'), ("UR", '┗'), ("ul", '┘'),
+    ("uL", '┙'), ("Ul", '┚'), ("UL", '┛'), ("vr", '├'), ("VR", '┣'),
+    ("vl", '┤'), ("VL", '┫'), ("dh", '┬'), ("DH", '┳'), ("uh", '┴'),
+    ("UH", '┻'), ("vh", '┼'), ("VH", '╋'),
+    // Block elements and geometric shapes.
+    ("TB", '▀'), ("LB", '▄'), ("FB", '█'), ("lB", '▌'), ("RB", '▐'),
+    (".S", '░'), (":S", '▒'), ("?S", '▓'), ("fS", '■'), ("OS", '□'),
+    ("RO", '▢'), ("Rr", '▣'), ("RF", '▤'), ("RY", '▥'), ("RH", '▦'),
+    ("RZ", '▧'), ("RK", '▨'), ("RX", '▩'), ("sB", '▪'), ("SR", '▬'),
+    ("Or", '▭'), ("UT", '▲'), ("uT", '△'), ("PR", '▶'), ("Tr", '▷'),
+    ("Dt", '▼'), ("dT", '▽'), ("PL", '◀'), ("Tl", '◁'), ("Db", '◆'),
+    ("Dw", '◇'), ("LZ", '◊'), ("0m", '○'), ("0o", '◎'), ("0M", '●'),
+    ("0L", '◐'), ("0R", '◑'), ("Sn", '◘'), ("Ic", '◙'), ("Fd", '◢'),
+    ("Bd", '◣'),
+    // Miscellaneous symbols and dingbats.
+    ("*2", '★'), ("*1", '☆'), ("<H", '☜'), (">H", '☞'), ("0u", '☺'),
+    ("0U", '☻'), ("SU", '☼'), ("Fm", '♀'), ("Ml", '♂'), ("cS", '♠'),
+    ("cH", '♡'), ("cD", '♢'), ("cC", '♣'), ("Md", '♩'), ("M8", '♪'),
+    ("M2", '♫'), ("Mb", '♭'), ("Mx", '♮'), ("MX", '♯'), ("OK", '✓'),
+    ("XX", '✗'), ("-X", '✠'),
+];
+
+// ── Digraph table ────────────────────────────────────────────────────────
+
+/// The digraph table: RFC 1345 defaults plus user additions.
+#[derive(Debug, Clone)]
+pub struct Digraphs {
+    table: HashMap<[char; 2], char>,
+}
+
+impl Default for Digraphs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digraphs {
+    /// A table holding the built-in RFC 1345 digraphs.
+    #[must_use]
+    pub fn new() -> Self {
+        let table = RFC1345
+            .iter()
+            .filter_map(|&(key, ch)| {
+                let mut k = key.chars();
+                Some(([k.next()?, k.next()?], ch))
+            })
+            .collect();
+        Self { table }
+    }
+
+    /// Look up `{a}{b}`, falling back to `{b}{a}`.
+    #[must_use]
+    pub fn lookup(&self, a: char, b: char) -> Option<char> {
+        self.table
+            .get(&[a, b])
+            .or_else(|| self.table.get(&[b, a]))
+            .copied()
+    }
+
+    /// Add or replace a digraph (`:digraph`).
+    pub fn add(&mut self, key: [char; 2], ch: char) {
+        self.table.insert(key, ch);
+    }
+
+    /// Number of digraphs in the table.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Whether the table is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// The `:digraphs` listing: `{key} {char} {decimal}` entries ordered by
+    /// code point, packed into lines no wider than `width` columns.
+    #[must_use]
+    pub fn listing(&self, width: usize) -> String {
+        let mut entries: Vec<(&[char; 2], &char)> = self.table.iter().collect();
+        entries.sort_by_key(|&(key, &ch)| (ch, *key));
+
+        let cells: Vec<String> = entries
+            .into_iter()
+            .map(|(key, &ch)| {
+                // Show invisible characters by name rather than as blanks.
+                let shown = match ch {
+                    ' ' => "SP".to_string(),
+                    '\u{a0}' => "NS".to_string(),
+                    '\u{ad}' => "--".to_string(),
+                    c => c.to_string(),
+                };
+                format!("{}{} {shown:<2} {:>5}", key[0], key[1], ch as u32)
+            })
+            .collect();
+
+        // Every cell is 11 columns; separate them with two spaces.
+        let per_line = ((width + 2) / 13).max(1);
+        cells
+            .chunks(per_line)
+            .map(|row| row.join("  "))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Parse the arguments of `:digraph {char1}{char2} {number} ...` into
+/// `(key, char)` pairs. The number is decimal, or hex with a `0x` prefix.
+///
+/// # Errors
+///
+/// Returns a Vim-style error message for a malformed argument list.
+pub fn parse_definitions(args: &str) -> Result<Vec<([char; 2], char)>, String> {
+    let mut out = Vec::new();
+    let mut parts = args.split_whitespace();
+    while let Some(key) = parts.next() {
+        let chars: Vec<char> = key.chars().collect();
+        if chars.len() != 2 {
+            return Err(format!("E1214: Digraph must be just two characters: {key}"));
+        }
+        if chars.contains(&'\x1b') {
+            return Err("E104: Escape not allowed in digraph".to_string());
+        }
+        let Some(num) = parts.next() else {
+            return Err("E39: Number expected".to_string());
+        };
+        let code = num
+            .strip_prefix("0x")
+            .or_else(|| num.strip_prefix("0X"))
+            .map_or_else(|| num.parse(), |hex| u32::from_str_radix(hex, 16))
+            .map_err(|_| "E39: Number expected".to_string())?;
+        let ch = char::from_u32(code).ok_or_else(|| "E39: Number expected".to_string())?;
+        out.push(([chars[0], chars[1]], ch));
+    }
+    Ok(out)
+}
+
+// ── Tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_duplicate_keys() {
+        assert_eq!(Digraphs::new().len(), RFC1345.len());
+        assert!(RFC1345.iter().all(|(k, _)| k.chars().count() == 2));
+    }
+
+    #[test]
+    fn lookup_common() {
+        let d = Digraphs::new();
+        assert_eq!(d.lookup('a', ':'), Some('ä'));
+        assert_eq!(d.lookup('e', '\''), Some('é'));
+        assert_eq!(d.lookup('E', 'u'), Some('€'));
+        assert_eq!(d.lookup('-', '>'), Some('→'));
+        assert_eq!(d.lookup('l', '*'), Some('λ'));
+        assert_eq!(d.lookup('d', '='), Some('д'));
+        assert_eq!(d.lookup('s', 's'), Some('ß'));
+        assert_eq!(d.lookup('1', '2'), Some('½'));
+    }
+
+    #[test]
+    fn lookup_reversed() {
+        let d = Digraphs::new();
+        assert_eq!(d.lookup(':', 'a'), Some('ä'));
+        // An exact match wins over the reversed one.
+        assert_eq!(d.lookup('>', '>'), Some('»'));
+    }
+
+    #[test]
+    fn lookup_unknown() {
+        assert_eq!(Digraphs::new().lookup('q', 'q'), None);
+    }
+
+    #[test]
+    fn add_custom() {
+        let mut d = Digraphs::new();
+        d.add(['q', 'q'], '✔');
+        assert_eq!(d.lookup('q', 'q'), Some('✔'));
+        d.add(['a', ':'], 'x');
+        assert_eq!(d.lookup('a', ':'), Some('x'));
+    }
+
+    #[test]
+    fn listing_packs_entries() {
+        let d = Digraphs::new();
+        let list = d.listing(80);
+        assert!(list.lines().all(|l| l.chars().count() <= 80));
+        assert!(list.contains("a: ä    228"));
+        assert!(list.starts_with("SP SP    32"));
+        assert_eq!(list.lines().count(), d.len().div_ceil(6));
+    }
+
+    #[test]
+    fn parse_definitions_decimal_and_hex() {
+        assert_eq!(
+            parse_definitions("qq 10004 ok 0x2713").unwrap(),
+            vec![(['q', 'q'], '✔'), (['o', 'k'], '✓')]
+        );
+    }
+
+    #[test]
+    fn parse_definitions_errors() {
+        assert_eq!(parse_definitions("qq").unwrap_err(), "E39: Number expected");
+        assert_eq!(parse_definitions("qq abc").unwrap_err(), "E39: Number expected");
+        assert!(parse_definitions("qqq 65").unwrap_err().starts_with("E1214"));
+    }
+}
@@ -0,0 +1,467 @@
+//! A small subset of Vim's expression language.
+//!
+//! Enough for `:s/pat/\=expr/` and `<expr>` mappings: numbers, strings,
+//! arithmetic, comparisons and a few functions. Values are numbers or strings and convert between each other
+//! the way Vim's do: arithmetic reads a string's leading digits (`"12ab"`
+//! is 12, `"ab"` is 0) and `.` joins the text of both sides.
+//!
+//! | Syntax                   | Meaning                                  |
+//! |--------------------------|------------------------------------------|
+//! | `42`, `'it''s'`, `"a\n"` | Number, literal string, escaped string   |
+//! | `+ - * / %`              | Arithmetic on numbers                    |
+//! | `.` / `..`               | String concatenation                     |
+//! | `( )`                    | Grouping                                 |
+//! | `== != < <= > >=`        | Comparison (1 or 0)                      |
+//! | `a ? b : c`              | `b` if `a` is non-zero, else `c`         |
+//! | `"\<C-y>"`               | A key, in key notation (`<C-y>`)         |
+//! | `submatch(n)`            | Capture group `n` of the current match   |
+//! | `toupper(s)`, `tolower(s)` | Change case                            |
+//! | `len(s)`, `strlen(s)`    | Length in chars                          |
+//! | `repeat(s, n)`           | `s` repeated `n` times                   |
+//! | `str2nr(s)`, `string(x)` | Convert between number and string        |
+//! | `pumvisible()`           | 1 while the completion menu is shown     |
+//! | `mode()`                 | The current mode: `n`, `i`, `v`, `c`, …  |
+//! | `g:name`, `b:name`       | A global or buffer-local variable        |
+//!
+//! Comparison is numeric when either side is a number, and by text when
+//! both are strings. A variable name without a prefix is global: `x` is
+//! `g:x`.
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// The result of evaluating an expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// A 64-bit integer.
+    Number(i64),
+    /// A string.
+    String(String),
+}
+
+impl Value {
+    /// The value as a number: a string's leading (optionally signed)
+    /// digits, or 0 when there are none.
+    #[must_use]
+    pub fn to_number(&self) -> i64 {
+        match self {
+            Self::Number(n) => *n,
+            Self::String(s) => {
+                let s = s.trim_start();
+                let (sign, digits) = s.strip_prefix('-').map_or((1, s), |rest| (-1, rest));
+                let end = digits.bytes().position(|b| !b.is_ascii_digit()).unwrap_or(digits.len());
+                sign * digits[..end].parse::<i64>().unwrap_or(0)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::String(s) => f.write_str(s),
+        }
+    }
+}
+
+/// What an expression can see.
+#[derive(Debug, Clone, Default)]
+pub struct Scope {
+    /// Capture groups of the current `:s` match, for `submatch()`. Index 0
+    /// is the whole match; groups that didn't take part are empty.
+    pub submatches: Vec<String>,
+    /// Whether the insert completion menu is shown, for `pumvisible()`.
+    pub pumvisible: bool,
+    /// The current mode's `mode()` code.
+    pub mode: String,
+    /// Variables by full name, prefix included: `g:x`, `b:x`.
+    pub variables: HashMap<String, Value>,
+}
+
+/// The full name of variable `name`: `g:` is added when it has no scope
+/// prefix. `None` when `name` isn't a valid variable name.
+#[must_use]
+pub fn variable_name(name: &str) -> Option<String> {
+    let (prefix, bare) = match name.split_once(':') {
+        Some((prefix @ ("g" | "b"), bare)) => (prefix, bare),
+        Some(_) => return None,
+        None => ("g", name),
+    };
+    let mut chars = bare.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then(|| format!("{prefix}:{bare}"))
+}
+
+/// Evaluate `expr` in `scope`.
+///
+/// # Errors
+///
+/// Returns a Vim-style message for syntax errors, unknown functions and
+/// division by zero.
+pub fn eval(expr: &str, scope: &Scope) -> Result<Value, String> {
+    let mut parser = Parser { src: expr, pos: 0, scope };
+    let value = parser.ternary()?;
+    parser.skip_ws();
+    if parser.pos < expr.len() {
+        return Err(format!("E488: Trailing characters: {}", &expr[parser.pos..]));
+    }
+    Ok(value)
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+/// Recursive-descent evaluator — parses and evaluates in one pass.
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+    scope: &'a Scope,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn skip_ws(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.src.len() - trimmed.len();
+    }
+
+    /// Consume `token` (after whitespace) if it comes next.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_ws();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn invalid(&self) -> String {
+        format!("E15: Invalid expression: \"{}\"", self.src)
+    }
+
+    /// `cond ? a : b` — the lowest precedence, right-associative.
+    fn ternary(&mut self) -> Result<Value, String> {
+        let cond = self.comparison()?;
+        if !self.eat("?") {
+            return Ok(cond);
+        }
+        let yes = self.ternary()?;
+        if !self.eat(":") {
+            return Err(self.invalid());
+        }
+        let no = self.ternary()?;
+        Ok(if cond.to_number() == 0 { no } else { yes })
+    }
+
+    /// `==`, `!=`, `<`, `<=`, `>` and `>=`.
+    fn comparison(&mut self) -> Result<Value, String> {
+        let value = self.expr()?;
+        let Some(op) = ["==", "!=", "<=", ">=", "<", ">"].into_iter().find(|op| self.eat(op)) else {
+            return Ok(value);
+        };
+        let rhs = self.expr()?;
+        let ord = match (&value, &rhs) {
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            _ => value.to_number().cmp(&rhs.to_number()),
+        };
+        let result = match op {
+            "==" => ord.is_eq(),
+            "!=" => ord.is_ne(),
+            "<=" => ord.is_le(),
+            ">=" => ord.is_ge(),
+            "<" => ord.is_lt(),
+            _ => ord.is_gt(),
+        };
+        Ok(Value::Number(i64::from(result)))
+    }
+
+    /// `+`, `-` and `.` / `..` — all one precedence level, as in Vim.
+    fn expr(&mut self) -> Result<Value, String> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat("..") || self.eat(".") {
+                let rhs = self.term()?;
+                value = Value::String(format!("{value}{rhs}"));
+            } else if self.eat("+") {
+                let rhs = self.term()?;
+                value = Value::Number(value.to_number().wrapping_add(rhs.to_number()));
+            } else if self.eat("-") {
+                let rhs = self.term()?;
+                value = Value::Number(value.to_number().wrapping_sub(rhs.to_number()));
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// `*`, `/` and `%`.
+    fn term(&mut self) -> Result<Value, String> {
+        let mut value = self.unary()?;
+        loop {
+            let op = if self.eat("*") {
+                '*'
+            } else if self.eat("/") {
+                '/'
+            } else if self.eat("%") {
+                '%'
+            } else {
+                return Ok(value);
+            };
+            let (a, b) = (value.to_number(), self.unary()?.to_number());
+            value = Value::Number(match op {
+                '*' => a.wrapping_mul(b),
+                _ if b == 0 => return Err("E15: Division by zero".to_string()),
+                '/' => a.wrapping_div(b),
+                _ => a.wrapping_rem(b),
+            });
+        }
+    }
+
+    fn unary(&mut self) -> Result<Value, String> {
+        if self.eat("-") {
+            Ok(Value::Number(self.unary()?.to_number().wrapping_neg()))
+        } else if self.eat("+") {
+            Ok(Value::Number(self.unary()?.to_number()))
+        } else if self.eat("!") {
+            Ok(Value::Number(i64::from(self.unary()?.to_number() == 0)))
+        } else {
+            self.primary()
+        }
+    }
+
+    fn primary(&mut self) -> Result<Value, String> {
+        self.skip_ws();
+        let rest = self.rest();
+        let Some(first) = rest.chars().next() else {
+            return Err(self.invalid());
+        };
+        if first.is_ascii_digit() {
+            let end = rest.bytes().position(|b| !b.is_ascii_digit()).unwrap_or(rest.len());
+            let n = rest[..end].parse().map_err(|_| self.invalid())?;
+            self.pos += end;
+            return Ok(Value::Number(n));
+        }
+        if first == '\'' {
+            return self.literal_string();
+        }
+        if first == '"' {
+            return self.escaped_string();
+        }
+        if first == '(' {
+            self.pos += 1;
+            let value = self.ternary()?;
+            if !self.eat(")") {
+                return Err(self.invalid());
+            }
+            return Ok(value);
+        }
+        if first.is_ascii_alphabetic() || first == '_' {
+            let end = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            let mut name = &rest[..end];
+            self.pos += end;
+            if matches!(name, "g" | "b") && self.rest().starts_with(':') {
+                let bare = &self.rest()[1..];
+                let len = bare.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(bare.len());
+                name = &rest[..end + 1 + len];
+                self.pos += 1 + len;
+            }
+            if !self.eat("(") {
+                return variable_name(name)
+                    .and_then(|full| self.scope.variables.get(&full).cloned())
+                    .ok_or_else(|| format!("E121: Undefined variable: {name}"));
+            }
+            let mut args = Vec::new();
+            if !self.eat(")") {
+                loop {
+                    args.push(self.ternary()?);
+                    if self.eat(")") {
+                        break;
+                    }
+                    if !self.eat(",") {
+                        return Err(self.invalid());
+                    }
+                }
+            }
+            return self.call(name, &args);
+        }
+        Err(self.invalid())
+    }
+
+    /// `'…'` — no escapes; `''` is a single quote.
+    fn literal_string(&mut self) -> Result<Value, String> {
+        let mut out = String::new();
+        let mut chars = self.rest()[1..].char_indices().peekable();
+        while let Some((i, ch)) = chars.next() {
+            if ch == '\'' {
+                if chars.peek().is_some_and(|&(_, c)| c == '\'') {
+                    chars.next();
+                    out.push('\'');
+                    continue;
+                }
+                self.pos += i + 2;
+                return Ok(Value::String(out));
+            }
+            out.push(ch);
+        }
+        Err(format!("E115: Missing quote: {}", self.rest()))
+    }
+
+    /// `"…"` — backslash escapes `\n`, `\t`, `\\` and `\"`, and `\<…>` for
+    /// a key, which is kept in key notation.
+    fn escaped_string(&mut self) -> Result<Value, String> {
+        let mut out = String::new();
+        let mut chars = self.rest()[1..].char_indices();
+        while let Some((i, ch)) = chars.next() {
+            match ch {
+                '"' => {
+                    self.pos += i + 2;
+                    return Ok(Value::String(out));
+                }
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => out.push('\n'),
+                    Some((_, 't')) => out.push('\t'),
+                    Some((_, '<')) => out.push('<'),
+                    Some((_, c)) => out.push(c),
+                    None => break,
+                },
+                c => out.push(c),
+            }
+        }
+        Err(format!("E114: Missing quote: {}", self.rest()))
+    }
+
+    fn call(&self, name: &str, args: &[Value]) -> Result<Value, String> {
+        let arity = match name {
+            "repeat" => 2,
+            "submatch" | "toupper" | "tolower" | "len" | "strlen" | "str2nr" | "string" => 1,
+            "pumvisible" | "mode" => 0,
+            _ => return Err(format!("E117: Unknown function: {name}")),
+        };
+        if args.len() != arity {
+            return Err(format!("E118: Wrong number of arguments for function: {name}"));
+        }
+        match name {
+            "pumvisible" => return Ok(Value::Number(i64::from(self.scope.pumvisible))),
+            "mode" => return Ok(Value::String(self.scope.mode.clone())),
+            _ => {}
+        }
+        let text = args[0].to_string();
+        Ok(match name {
+            "submatch" => {
+                let n = usize::try_from(args[0].to_number()).map_err(|_| self.invalid())?;
+                Value::String(self.scope.submatches.get(n).cloned().unwrap_or_default())
+            }
+            "toupper" => Value::String(text.to_uppercase()),
+            "tolower" => Value::String(text.to_lowercase()),
+            "len" | "strlen" => {
+                Value::Number(i64::try_from(text.chars().count()).unwrap_or(i64::MAX))
+            }
+            "repeat" => {
+                let times = usize::try_from(args[1].to_number()).unwrap_or(0);
+                Value::String(text.repeat(times))
+            }
+            "str2nr" => Value::Number(args[0].to_number()),
+            _ => Value::String(text),
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ev(expr: &str) -> Result<Value, String> {
+        eval(expr, &Scope::default())
+    }
+
+    #[test]
+    fn arithmetic_precedence() {
+        assert_eq!(ev("1 + 2 * 3"), Ok(Value::Number(7)));
+        assert_eq!(ev("(1 + 2) * 3"), Ok(Value::Number(9)));
+        assert_eq!(ev("7 / 2 - -1"), Ok(Value::Number(4)));
+        assert_eq!(ev("7 % 4"), Ok(Value::Number(3)));
+        assert!(ev("1 / 0").is_err());
+    }
+
+    #[test]
+    fn strings_and_concatenation() {
+        assert_eq!(ev("'it''s' . \"\\tx\""), Ok(Value::String("it's\tx".into())));
+        assert_eq!(ev("1 .. 2"), Ok(Value::String("12".into())));
+        assert!(ev("'open").is_err());
+    }
+
+    #[test]
+    fn string_to_number_coercion() {
+        assert_eq!(ev("'12ab' + 1"), Ok(Value::Number(13)));
+        assert_eq!(ev("'ab' * 3"), Ok(Value::Number(0)));
+        assert_eq!(ev("'-4' * 2"), Ok(Value::Number(-8)));
+    }
+
+    #[test]
+    fn submatch_and_functions() {
+        let scope = Scope { submatches: vec!["ab12".into(), "ab".into(), "12".into()], ..Scope::default() };
+        assert_eq!(eval("submatch(2) * 2", &scope), Ok(Value::Number(24)));
+        assert_eq!(eval("toupper(submatch(1))", &scope), Ok(Value::String("AB".into())));
+        assert_eq!(eval("submatch(0)", &scope), Ok(Value::String("ab12".into())));
+        assert_eq!(eval("submatch(9)", &scope), Ok(Value::String(String::new())));
+        assert_eq!(eval("repeat('-', len(submatch(0)))", &scope), Ok(Value::String("----".into())));
+    }
+
+    #[test]
+    fn comparisons_and_ternary() {
+        assert_eq!(ev("1 + 1 == 2"), Ok(Value::Number(1)));
+        assert_eq!(ev("'abc' < 'abd'"), Ok(Value::Number(1)));
+        assert_eq!(ev("'10' > 9"), Ok(Value::Number(1)));
+        assert_eq!(ev("3 != 3 ? 'a' : 0 ? 'b' : 'c'"), Ok(Value::String("c".into())));
+        assert_eq!(ev("(1 ? 2 : 3) * 2"), Ok(Value::Number(4)));
+        assert!(ev("1 ? 2").is_err());
+    }
+
+    #[test]
+    fn editor_state_and_keys() {
+        let scope = Scope { pumvisible: true, mode: "i".into(), ..Scope::default() };
+        let pick = r#"pumvisible() ? "\<C-y>" : "\<CR>""#;
+        assert_eq!(eval(pick, &scope), Ok(Value::String("<C-y>".into())));
+        assert_eq!(ev(pick), Ok(Value::String("<CR>".into())));
+        assert_eq!(eval("mode()", &scope), Ok(Value::String("i".into())));
+        assert!(ev("mode(1)").unwrap_err().starts_with("E118"));
+    }
+
+    #[test]
+    fn variables() {
+        let variables = HashMap::from([
+            ("g:n".to_string(), Value::Number(2)),
+            ("b:s".to_string(), Value::String("x".into())),
+        ]);
+        let scope = Scope { variables, ..Scope::default() };
+        assert_eq!(eval("n * 3 + g:n", &scope), Ok(Value::Number(8)));
+        assert_eq!(eval("b:s . b:s", &scope), Ok(Value::String("xx".into())));
+        assert!(eval("b:n", &scope).unwrap_err().starts_with("E121"));
+        assert_eq!(variable_name("x1"), Some("g:x1".into()));
+        assert_eq!(variable_name("b:_y"), Some("b:_y".into()));
+        assert_eq!(variable_name("w:x"), None);
+        assert_eq!(variable_name("1x"), None);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(ev("nosuch(1)").unwrap_err().starts_with("E117"));
+        assert!(ev("toupper()").unwrap_err().starts_with("E118"));
+        assert!(ev("x").unwrap_err().starts_with("E121"));
+        assert!(ev("1 2").unwrap_err().starts_with("E488"));
+        assert!(ev("").is_err());
+    }
+}
@@ -0,0 +1,265 @@
+//! Folds — ranges of lines a window can collapse into a single row.
+//!
+//! With `:set foldmethod=indent` the folds follow the indentation: every
+//! run of lines indented at least `level * shiftwidth` columns is a fold
+//! of that level, nested inside the fold one level up. The line that
+//! introduces a block (`def f():`) stays outside it, so a closed fold
+//! reads as the header followed by one `+--` row for the body.
+//!
+//! ```text
+//! def f():            level 0
+//!     x = 1           level 1  ┐ fold 1
+//!     if x:           level 1  │
+//!         return x    level 2  │ ┐ fold 2
+//!         pass        level 2  ┘ ┘
+//! ```
+//!
+//! A blank line takes the level of the line before it, so a blank line
+//! inside a block doesn't split its fold. A fold needs at least two lines:
+//! a closed one-line fold would hide nothing.
+//!
+//! Folds belong to the window, not the buffer — two windows on the same
+//! buffer open and close them independently. [`FoldList`] remembers the
+//! buffer's `changedtick` it was computed at, so the editor recomputes it
+//! only after an edit.
+
+use crate::buffer::Buffer;
+
+/// One fold: the lines `start..=end` (0-indexed).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fold {
+    /// First line of the fold.
+    pub start: usize,
+    /// Last line of the fold (inclusive).
+    pub end: usize,
+    /// Nesting depth: 1 for an outermost fold.
+    pub level: usize,
+    /// Whether the fold is drawn as a single row.
+    pub closed: bool,
+}
+
+impl Fold {
+    /// Whether the fold covers `line`.
+    #[inline]
+    #[must_use]
+    pub const fn contains(&self, line: usize) -> bool {
+        self.start <= line && line <= self.end
+    }
+}
+
+/// The folds of one window, ordered by start line and then by level, so
+/// an enclosing fold comes before the folds nested in it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FoldList {
+    folds: Vec<Fold>,
+    changedtick: u64,
+}
+
+impl FoldList {
+    /// No folds, never computed.
+    pub const EMPTY: Self = Self { folds: Vec::new(), changedtick: 0 };
+
+    /// Collect folds computed at buffer `changedtick`.
+    #[must_use]
+    pub fn new(mut folds: Vec<Fold>, changedtick: u64) -> Self {
+        folds.sort_by_key(|f| (f.start, f.level));
+        Self { folds, changedtick }
+    }
+
+    /// True when there are no folds.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.folds.is_empty()
+    }
+
+    /// Number of folds.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.folds.len()
+    }
+
+    /// All folds in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Fold> {
+        self.folds.iter()
+    }
+
+    /// The buffer `changedtick` these folds were computed at. 0 for a list
+    /// that was never computed.
+    #[inline]
+    #[must_use]
+    pub const fn changedtick(&self) -> u64 {
+        self.changedtick
+    }
+
+    /// The lines hidden behind the closed fold covering `line`, as
+    /// `(start, end)`. When closed folds nest, the outermost one wins.
+    #[must_use]
+    pub fn closed_at(&self, line: usize) -> Option<(usize, usize)> {
+        self.folds
+            .iter()
+            .take_while(|f| f.start <= line)
+            .find(|f| f.closed && f.contains(line))
+            .map(|f| (f.start, f.end))
+    }
+
+    /// Open every fold (`zR`).
+    pub fn open_all(&mut self) {
+        for fold in &mut self.folds {
+            fold.closed = false;
+        }
+    }
+
+    /// Close every fold (`zM`).
+    pub fn close_all(&mut self) {
+        for fold in &mut self.folds {
+            fold.closed = true;
+        }
+    }
+
+    /// Open the closed fold shown at `line` (`zo`). Folds nested in it
+    /// keep their state. Returns false when `line` isn't in a closed fold.
+    pub fn open_at(&mut self, line: usize) -> bool {
+        let fold = self.folds.iter_mut().find(|f| f.closed && f.contains(line));
+        fold.map(|f| f.closed = false).is_some()
+    }
+
+    /// Close the innermost open fold covering `line` (`zc`). Inside a
+    /// closed fold, this closes the fold around it. Returns false when
+    /// there is no open fold to close.
+    pub fn close_at(&mut self, line: usize) -> bool {
+        let outer_closed = self.closed_at(line).map(|(start, _)| start);
+        let fold = self
+            .folds
+            .iter_mut()
+            .rev()
+            .filter(|f| f.contains(line) && !f.closed)
+            .find(|f| outer_closed.is_none_or(|start| f.start <= start));
+        fold.map(|f| f.closed = true).is_some()
+    }
+
+    /// Carry the open/closed state over from `old`, the folds before the
+    /// last recompute. A fold keeps its state when a fold with the same
+    /// start line and level existed; new folds keep the state they have.
+    pub fn keep_state(&mut self, old: &Self) {
+        for fold in &mut self.folds {
+            if let Some(prev) = old.folds.iter().find(|f| f.start == fold.start && f.level == fold.level) {
+                fold.closed = prev.closed;
+            }
+        }
+    }
+}
+
+/// Indent level of `line`: its leading whitespace in columns divided by
+/// `shiftwidth`, with a tab counting as `shiftwidth` columns.
+fn indent_level(line: &str, shiftwidth: usize) -> usize {
+    let cols: usize = line
+        .chars()
+        .map_while(|c| match c {
+            ' ' => Some(1),
+            '\t' => Some(shiftwidth),
+            _ => None,
+        })
+        .sum();
+    cols / shiftwidth
+}
+
+/// Compute the indent folds of `buffer` (`foldmethod=indent`), all closed.
+///
+/// A `shiftwidth` of 0 is treated as 1.
+#[must_use]
+pub fn compute_indent_folds(buffer: &Buffer, shiftwidth: usize) -> FoldList {
+    let shiftwidth = shiftwidth.max(1);
+    let mut folds = Vec::new();
+    // Start lines of the folds still open at the current line, outermost
+    // first: `open[i]` starts the fold of level `i + 1`.
+    let mut open: Vec<usize> = Vec::new();
+    let mut level = 0;
+    let line_count = buffer.line_count();
+    for idx in 0..line_count {
+        let text = buffer.line(idx).map(String::from).unwrap_or_default();
+        if !text.trim().is_empty() {
+            level = indent_level(&text, shiftwidth);
+        }
+        while open.len() > level {
+            let start = open.pop().unwrap_or(idx);
+            push_fold(&mut folds, start, idx - 1, open.len() + 1);
+        }
+        while open.len() < level {
+            open.push(idx);
+        }
+    }
+    while let Some(start) = open.pop() {
+        push_fold(&mut folds, start, line_count - 1, open.len() + 1);
+    }
+    FoldList::new(folds, buffer.changedtick())
+}
+
+/// Add the closed fold `start..=end` unless it is a single line.
+fn push_fold(folds: &mut Vec<Fold>, start: usize, end: usize, level: usize) {
+    if end > start {
+        folds.push(Fold { start, end, level, closed: true });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(folds: &FoldList) -> Vec<(usize, usize, usize)> {
+        folds.iter().map(|f| (f.start, f.end, f.level)).collect()
+    }
+
+    #[test]
+    fn python_blocks_fold_by_indent() {
+        let buf = Buffer::from_text(
+            "def f(x):\n    y = x\n    if y:\n        y += 1\n        y *= 2\n\n    return y\n\nprint(f(1))",
+        );
+        let folds = compute_indent_folds(&buf, 4);
+        // The blank line 5 inherits level 2, line 7 inherits level 1.
+        assert_eq!(ranges(&folds), vec![(1, 7, 1), (3, 5, 2)]);
+        assert!(folds.iter().all(|f| f.closed));
+        assert_eq!(folds.changedtick(), buf.changedtick());
+    }
+
+    #[test]
+    fn single_line_blocks_have_no_fold() {
+        let buf = Buffer::from_text("if a:\n    b\nc\n\tc1\n\tc2");
+        let folds = compute_indent_folds(&buf, 4);
+        assert_eq!(ranges(&folds), vec![(3, 4, 1)]);
+    }
+
+    #[test]
+    fn closed_at_prefers_the_outermost_fold() {
+        let buf = Buffer::from_text("a\n  b\n    c\n    d\n  e");
+        let mut folds = compute_indent_folds(&buf, 2);
+        assert_eq!(folds.closed_at(3), Some((1, 4)));
+        assert_eq!(folds.closed_at(0), None);
+        assert!(folds.open_at(3));
+        assert_eq!(folds.closed_at(3), Some((2, 3)));
+        assert_eq!(folds.closed_at(4), None);
+        folds.open_all();
+        assert_eq!(folds.closed_at(3), None);
+        assert!(folds.close_at(3));
+        assert_eq!(folds.closed_at(3), Some((2, 3)));
+        assert!(folds.close_at(3));
+        assert_eq!(folds.closed_at(3), Some((1, 4)));
+        assert!(!folds.close_at(3));
+        folds.open_all();
+        folds.close_all();
+        assert_eq!(folds.closed_at(2), Some((1, 4)));
+    }
+
+    #[test]
+    fn keep_state_matches_start_and_level() {
+        let buf = Buffer::from_text("a\n  b\n  c\nd\n  e\n  f");
+        let mut old = compute_indent_folds(&buf, 2);
+        old.open_all();
+        let buf = Buffer::from_text("a\n  b\n  c\nd\n  e\n  f\ng\n  h\n  i");
+        let mut new = compute_indent_folds(&buf, 2);
+        new.keep_state(&old);
+        let closed: Vec<bool> = new.iter().map(|f| f.closed).collect();
+        assert_eq!(closed, vec![false, false, true]);
+    }
+}
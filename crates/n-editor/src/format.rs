@@ -0,0 +1,140 @@
+//! Text formatting — the built-in `gq` used while `formatprg` is empty.
+//!
+//! [`reflow`] joins the words of each paragraph and breaks them again so
+//! no line is longer than the text width, where it can: a word longer
+//! than the width gets a line of its own.
+//!
+//! A line's prefix is its leading whitespace followed by an optional
+//! comment leader (`//`, `#` or `--`) and the blank after it. Every line
+//! of a paragraph gets the prefix of the paragraph's first line, so a
+//! block of `// ...` comments stays a block of comments:
+//!
+//! ```text
+//!     // one two three     →     // one two
+//!     // four                    // three four
+//! ```
+//!
+//! Paragraphs end at blank lines (a line with nothing but its prefix) and
+//! wherever the prefix changes, so code next to a comment is never pulled
+//! into it.
+
+/// Comment leaders recognised after the indentation, longest first.
+const COMMENT_LEADERS: &[&str] = &["//", "--", "#"];
+
+/// Width used when `textwidth` is 0, as in Vim.
+pub const DEFAULT_WIDTH: usize = 79;
+
+/// The prefix of `line`: indentation, then a comment leader and the
+/// blanks after it, if any.
+#[must_use]
+pub fn prefix(line: &str) -> &str {
+    let indent = line.len() - line.trim_start().len();
+    let rest = &line[indent..];
+    let Some(leader) = COMMENT_LEADERS.iter().find(|l| rest.starts_with(*l)) else {
+        return &line[..indent];
+    };
+    let after = &rest[leader.len()..];
+    let blanks = after.len() - after.trim_start().len();
+    &line[..indent + leader.len() + blanks]
+}
+
+/// The prefix with its trailing blanks removed, for comparing lines
+/// (`//` and `// ` lead the same comment).
+fn leader(line: &str) -> &str {
+    prefix(line).trim_end()
+}
+
+/// Reflow `lines` to `width` columns (see the module docs). A width of 0
+/// means [`DEFAULT_WIDTH`]. Blank lines are kept, without trailing blanks.
+#[must_use]
+pub fn reflow(lines: &[&str], width: usize) -> Vec<String> {
+    let width = if width == 0 { DEFAULT_WIDTH } else { width };
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let first = lines[i];
+        let body = &first[prefix(first).len()..];
+        if body.trim().is_empty() {
+            out.push(first.trim_end().to_string());
+            i += 1;
+            continue;
+        }
+        let lead = leader(first);
+        let mut words: Vec<&str> = Vec::new();
+        while i < lines.len() {
+            let line = lines[i];
+            let body = &line[prefix(line).len()..];
+            if body.trim().is_empty() || leader(line) != lead {
+                break;
+            }
+            words.extend(body.split_whitespace());
+            i += 1;
+        }
+        fill(&mut out, prefix(first), &words, width);
+    }
+    out
+}
+
+/// Push `words` as lines starting with `prefix`, each at most `width`
+/// columns unless a single word doesn't fit.
+fn fill(out: &mut Vec<String>, prefix: &str, words: &[&str], width: usize) {
+    let prefix_width = prefix.chars().count();
+    let mut line = String::from(prefix);
+    let mut line_width = prefix_width;
+    for word in words {
+        let word_width = word.chars().count();
+        if line_width > prefix_width && line_width + 1 + word_width > width {
+            out.push(std::mem::replace(&mut line, String::from(prefix)));
+            line_width = prefix_width;
+        }
+        if line_width > prefix_width {
+            line.push(' ');
+            line_width += 1;
+        }
+        line.push_str(word);
+        line_width += word_width;
+    }
+    out.push(line);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefixes() {
+        assert_eq!(prefix("    let x = 1;"), "    ");
+        assert_eq!(prefix("  // note"), "  // ");
+        assert_eq!(prefix("# heading"), "# ");
+        assert_eq!(prefix("-- sql"), "-- ");
+        assert_eq!(prefix("//"), "//");
+        assert_eq!(prefix("plain"), "");
+    }
+
+    #[test]
+    fn reflow_breaks_and_joins() {
+        let lines = ["one two three four five", "six", "", "seven"];
+        assert_eq!(reflow(&lines, 10), vec!["one two", "three four", "five six", "", "seven"]);
+    }
+
+    #[test]
+    fn reflow_keeps_indent_and_comment_leader() {
+        let lines = ["    // alpha beta", "    // gamma delta epsilon", "    //", "    // zeta"];
+        assert_eq!(
+            reflow(&lines, 20),
+            vec!["    // alpha beta", "    // gamma delta", "    // epsilon", "    //", "    // zeta"],
+        );
+    }
+
+    #[test]
+    fn reflow_stops_where_the_prefix_changes() {
+        let lines = ["# a b", "code here"];
+        assert_eq!(reflow(&lines, 40), vec!["# a b", "code here"]);
+    }
+
+    #[test]
+    fn long_words_get_their_own_line() {
+        assert_eq!(reflow(&["a verylongword b"], 5), vec!["a", "verylongword", "b"]);
+        assert_eq!(reflow(&["x"; 3], 0), vec!["x x x"]);
+    }
+}
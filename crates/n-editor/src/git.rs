@@ -0,0 +1,150 @@
+//! Git change markers — which lines differ from the last commit.
+//!
+//! [`diff_file`] runs `git diff --unified=0 HEAD -- file` next to the file
+//! and [`parse_diff`] turns the hunk headers into a map from buffer line to
+//! [`DiffKind`]. The editor shows the map as signs in the sign column.
+//!
+//! Only the `@@ -a,b +c,d @@` headers matter — with no context lines, each
+//! hunk says exactly which new lines replace which old ones:
+//!
+//! | Old count | New count | Meaning                                        |
+//! |-----------|-----------|------------------------------------------------|
+//! | 0         | d         | `d` lines added at `c`                         |
+//! | b         | 0         | `b` lines deleted after line `c`               |
+//! | b         | d         | `min(b, d)` changed, any extra new lines added |
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// How a line differs from the last commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    /// A new line.
+    Added,
+    /// A line whose text changed.
+    Modified,
+    /// Lines were removed just below this one (or above the first line).
+    Deleted,
+}
+
+/// Diff the file at `path` against `HEAD`, as a map from 0-indexed line to
+/// change. `None` when `git` isn't installed, the file isn't in a
+/// repository with commits, or the diff fails for any other reason.
+#[must_use]
+pub fn diff_file(path: &Path) -> Option<HashMap<usize, DiffKind>> {
+    let name = path.file_name()?;
+    let dir = path.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or_else(|| Path::new("."));
+    let output = Command::new("git")
+        .args(["diff", "--no-color", "--no-ext-diff", "--unified=0", "HEAD", "--"])
+        .arg(name)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(parse_diff(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `git diff --unified=0` output into a map from 0-indexed line (in
+/// the new file) to change.
+#[must_use]
+pub fn parse_diff(diff: &str) -> HashMap<usize, DiffKind> {
+    let mut changes = HashMap::new();
+    for (old_count, new_start, new_count) in diff.lines().filter_map(parse_hunk_header) {
+        if new_count == 0 {
+            // Deleted below `new_start` (1-indexed); 0 means above line 1.
+            changes.insert(new_start.saturating_sub(1), DiffKind::Deleted);
+            continue;
+        }
+        let first = new_start.saturating_sub(1);
+        let modified = old_count.min(new_count);
+        for line in first..first + new_count {
+            let kind = if line < first + modified { DiffKind::Modified } else { DiffKind::Added };
+            changes.insert(line, kind);
+        }
+    }
+    changes
+}
+
+/// `(old_count, new_start, new_count)` from a hunk header line such as
+/// `@@ -3,2 +3 @@ fn main()`. A missing count means 1.
+fn parse_hunk_header(line: &str) -> Option<(usize, usize, usize)> {
+    let mut parts = line.strip_prefix("@@ ")?.split(' ');
+    let old = parts.next()?.strip_prefix('-')?;
+    let new = parts.next()?.strip_prefix('+')?;
+    let count = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (_, old_count) = count(old)?;
+    let (new_start, new_count) = count(new)?;
+    Some((old_count, new_start, new_count))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hunk_headers() {
+        assert_eq!(parse_hunk_header("@@ -3,2 +3 @@ fn main()"), Some((2, 3, 1)));
+        assert_eq!(parse_hunk_header("@@ -0,0 +1,4 @@"), Some((0, 1, 4)));
+        assert_eq!(parse_hunk_header("+@@ not a header"), None);
+        assert_eq!(parse_hunk_header("diff --git a/x b/x"), None);
+    }
+
+    #[test]
+    fn added_modified_deleted() {
+        let diff = "\
+diff --git a/f.txt b/f.txt
+index 1111111..2222222 100644
+--- a/f.txt
++++ b/f.txt
+@@ -0,0 +1,2 @@
++new first
++new second
+@@ -5 +7 @@
+-old
++changed
+@@ -9,2 +10,0 @@
+-gone
+-gone too
+@@ -20,1 +20,3 @@
+-was
++is
++plus
++more
+";
+        let changes = parse_diff(diff);
+        assert_eq!(changes.get(&0), Some(&DiffKind::Added));
+        assert_eq!(changes.get(&1), Some(&DiffKind::Added));
+        assert_eq!(changes.get(&6), Some(&DiffKind::Modified));
+        assert_eq!(changes.get(&9), Some(&DiffKind::Deleted));
+        assert_eq!(changes.get(&19), Some(&DiffKind::Modified));
+        assert_eq!(changes.get(&20), Some(&DiffKind::Added));
+        assert_eq!(changes.get(&21), Some(&DiffKind::Added));
+        assert_eq!(changes.len(), 7);
+    }
+
+    #[test]
+    fn deleted_at_top() {
+        let changes = parse_diff("@@ -1,2 +0,0 @@\n-a\n-b\n");
+        assert_eq!(changes.get(&0), Some(&DiffKind::Deleted));
+    }
+
+    #[test]
+    fn not_a_repository() {
+        let dir = std::env::temp_dir();
+        assert_eq!(diff_file(&dir.join("n-nvim-no-such-file.txt")), None);
+    }
+}
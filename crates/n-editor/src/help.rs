@@ -0,0 +1,354 @@
+//! Built-in help — the text shown by `:help {topic}`.
+//!
+//! Each topic is a page of plain text compiled into the binary. A page
+//! marks the places it can be jumped to with tags between stars (`*w*`)
+//! and refers to other pages with links between bars (`|motions|`).
+//!
+//! [`find`] resolves what the user typed: a topic name opens that page at
+//! the top, any other tag opens the page that defines it, on the tag's
+//! line. An empty query opens the table of contents. [`link_at`] finds
+//! the link under the cursor, for `K` in a help buffer.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Name prefix of help buffers: the `motions` page is `help://motions`.
+pub const BUFFER_PREFIX: &str = "help://";
+
+/// Topic opened by `:help` without an argument.
+pub const CONTENTS: &str = "contents";
+
+/// The help pages in table-of-contents order, so tag lookups that could
+/// match on more than one page are deterministic.
+const PAGES: &[(&str, &str)] = &[
+    (CONTENTS, r#"*contents*  n-nvim help
+
+Move the cursor onto a name between bars, like |motions|, and press K to
+follow the link. Ctrl+^ goes back to the previous page, :q closes the help
+window.
+
+|motions|      Moving around: h j k l, w b e, 0 ^ $, gg G
+|operators|    Changing text: d c y > < r ~
+|insert|       Entering insert mode: i a I A o O
+|search|       Searching: / ? n N * #
+|registers|    Naming a register for yank, delete and put: "a
+|marks|        Setting and jumping to marks: m ` '
+|macros|       Recording and replaying keys: q @
+|windows|      Splitting and moving between windows: Ctrl+W
+
+Any tag can be looked up directly: :help w, :help dd, :help CTRL-W_s.
+"#),
+    ("motions", r"*motions*  Cursor motions                                           *motion*
+
+A motion moves the cursor. Most take a count: 3j moves down three lines.
+After an |operator| a motion selects the text to work on: dw, c$, y2j.
+
+Left-right motions                                      *left-right-motions*
+
+h  or  Left         Count characters left.                             *h*
+l  or  Right        Count characters right.                            *l*
+0                   To the first character of the line.                *0*
+^                   To the first non-blank character of the line.      *^*
+$                   To the end of the line; with a count, to the end
+                    of the line count - 1 lines down.                  *$*
+f{char}             To the next {char} on the line.                    *f*
+F{char}             To the previous {char} on the line.                *F*
+t{char}             Till before the next {char}.                       *t*
+T{char}             Till after the previous {char}.                    *T*
+;                   Repeat the last f, F, t or T.                      *;*
+,                   Repeat the last f, F, t or T the other way.        *,*
+
+Up-down motions                                            *up-down-motions*
+
+k  or  Up           Count lines up.                                    *k*
+j  or  Down         Count lines down.                                  *j*
+gg                  To the first line, or line count.                  *gg*
+G                   To the last line, or line count.                   *G*
+
+Word motions                                                  *word-motions*
+
+w                   Count words forward.                               *w*
+b                   Count words backward.                              *b*
+e                   Forward to the end of a word.                      *e*
+W  B  E             The same for WORDs: runs of non-blank characters.  *W*
+
+A word is a run of letters, digits and underscores, or a run of other
+non-blank characters.
+
+Other motions                                                *other-motions*
+
+%                   To the bracket matching the one under the cursor.  *%*
+{  }                Count paragraphs backward / forward.               *{*
+(  )                Count sentences backward / forward. A sentence ends
+                    at a ., ! or ? followed by white space.            *(*
+"),
+    ("operators", r#"*operators*  Operators and changes                                   *operator*
+
+An operator is followed by a |motion| or text object, and works on the
+text the cursor moves over. Doubling it works on whole lines: dd, cc, yy,
+>>. A count before either multiplies: 2dw, d3w, 3dd.
+
+d{motion}           Delete.                                            *d*
+dd                  Delete count lines.                                *dd*
+c{motion}           Delete, then start |insert| mode.                  *c*
+cc                  Change count lines.                                *cc*
+y{motion}           Yank (copy) into a |register|.                     *y*
+yy                  Yank count lines.                                  *yy*
+>{motion}           Shift lines one 'shiftwidth' right.                *>*
+<{motion}           Shift lines one 'shiftwidth' left.                 *<*
+gq{motion}          Format lines to 'textwidth', keeping indent and
+                    comment leaders; gqq formats count lines.          *gq*
+gu{motion}          Make lowercase; guu works on count lines.          *gu*
+gU{motion}          Make uppercase; gUU works on count lines.          *gU*
+g~{motion}          Switch case; g~~ works on count lines.             *g~*
+ys{motion}{char}    Surround the text with the pair {char} names; yss
+                    surrounds count lines.                             *ys*
+cs{old}{new}        Change the surrounding pair {old} to {new}.        *cs*
+ds{char}            Delete the surrounding pair {char}.                *ds*
+
+A pair is named by its closing bracket, b, B, r or a for (), {}, [] and
+<>; an opening bracket adds blanks inside it. A quote or other mark
+stands for itself, and <tag> for a tag: ysiw<em> gives <em>word</em>.
+
+Text objects                                                  *text-objects*
+
+After an operator or in visual mode, i picks the inside of an object and
+a the object with its white space or delimiters: daw, ci", vi(.
+
+iw  aw  iW  aW      Word / WORD.                                       *iw*
+is  as              Sentence.                                          *is*
+ii  ai              Lines at the cursor's indent or deeper; ai adds
+                    the line above. Works on whole lines.              *ii*
+i"  a"  i'  a'      Quoted string.                                     *i"*
+i(  a(  i{  a{      Block in brackets; also [ and <. A count selects
+                    the pair that many levels out, as in 2di(.         *i(*
+
+Changes without a motion                                           *changes*
+
+x                   Delete count characters under the cursor.          *x*
+D  C                Delete / change to the end of the line.            *D*
+r{char}             Replace count characters with {char}.              *r*
+~                   Switch case of count characters.                   *~*
+J                   Join count lines.                                  *J*
+p  P                Put text after / before the cursor.                *p*
+u                   Undo count changes.                                *u*
+Ctrl+R              Redo count changes.                            *CTRL-R*
+.                   Repeat the last change.                            *.*
+"#),
+    ("insert", r"*insert*  Entering insert mode                                     *modes*
+
+Insert mode types text into the buffer. Esc returns to normal mode. A
+count before i, a, I, A, o or O types the text that many times: 3ix<Esc>
+inserts xxx, 2o opens two lines.
+
+i                   Insert before the cursor.                          *i*
+a                   Append after the cursor.                           *a*
+I                   Insert before the first non-blank of the line.     *I*
+A                   Append at the end of the line.                     *A*
+o                   Open a new line below and insert.                  *o*
+O                   Open a new line above and insert.                  *O*
+R                   Replace mode: typed characters overwrite the text,
+                    Backspace puts back what was there.                *R*
+
+In insert mode:
+Ctrl+R {reg}        Insert the contents of |register| {reg}.    *i_CTRL-R*
+Ctrl+N  Ctrl+P      Complete the word before the cursor from words in
+                    the open buffers: next / previous match. Ctrl+Y
+                    or Enter accepts, Ctrl+E puts back the typed text.
+                                                                *i_CTRL-N*
+Ctrl+O {command}    Run one normal mode command, then go on
+                    inserting.                                  *i_CTRL-O*
+Ctrl+W              Delete the word before the cursor.          *i_CTRL-W*
+Ctrl+U              Delete the text typed before the cursor on this
+                    line, or all of the line before it.         *i_CTRL-U*
+
+v  V  Ctrl+V        Start charwise, linewise or blockwise visual mode.
+                    An |operator| then works on the selection.         *v*
+gv                  Select the last visual selection again.            *gv*
+u  U  ~             In visual mode: make the selection lowercase,
+                    uppercase, or switch its case.               *v_u*
+"),
+    ("search", r"*search*  Searching                                               *pattern*
+
+/{pattern}<CR>      Search forward for {pattern}.                      */*
+?{pattern}<CR>      Search backward for {pattern}.                     *?*
+n                   Repeat the last search.                            *n*
+N                   Repeat the last search the other way.              *N*
+*                   Search forward for the word under the cursor.      *star*
+#                   Search backward for the word under the cursor.     *#*
+
+Searches wrap around the end of the buffer when 'wrapscan' is set.
+'ignorecase' and 'smartcase' control case sensitivity, 'hlsearch'
+highlights every match. A search is also a |motion|: d/foo<CR>.
+"),
+    ("registers", r#"*registers*  Registers                                            *register*
+
+"{reg}              Use register {reg} for the next delete, yank or put:
+                    "ayy yanks a line into register a, "ap puts it.  *quote*
+
+""                  The unnamed register: every delete and yank.
+"a - "z             Named registers; "A - "Z append to them.
+"-                  The last delete of less than a line.
+"_                  The black hole register: stores nothing.
+
+:registers          List the registers and their contents.
+"#),
+    ("marks", r"*marks*  Marks                                                        *mark*
+
+m{a-z}              Set mark {a-z} at the cursor.                      *m*
+`{a-z}              Jump to the mark's line and column.                *`*
+'{a-z}              Jump to the first non-blank of the mark's line.    *'*
+
+Jumps to marks are also |motions|: d'a deletes to the line of mark a.
+
+:marks              List the marks.
+"),
+    ("macros", r#"*macros*  Recording and replaying keys                           *recording*
+
+q{a-z}              Record typed keys into register {a-z}.             *q*
+q                   While recording: stop.
+@{a-z}              Replay the keys in register {a-z}, count times.    *@*
+@@                  Replay the last replayed register.                 *@@*
+
+The keys are kept in the |register|, so "ap shows a recorded macro.
+"#),
+    ("windows", r"*windows*  Windows                                                  *window*
+
+:sp  or  Ctrl+W s   Split the window in two, one above the other. *CTRL-W_s*
+:vsp or  Ctrl+W v   Split the window side by side.                *CTRL-W_v*
+Ctrl+W w  Ctrl+W W  Go to the next / previous window.             *CTRL-W_w*
+Ctrl+W h/j/k/l      Go to the window left / below / above / right. *CTRL-W_h*
+Ctrl+W H/J/K/L      Move the window to the far left / bottom / top
+                    / right.                                      *CTRL-W_H*
+Ctrl+W r  Ctrl+W R  Rotate the windows down / up.                 *CTRL-W_r*
+Ctrl+W T            Move the window to a new tab page.            *CTRL-W_T*
+Ctrl+W c  or  :close  Close the window.                           *CTRL-W_c*
+Ctrl+W o  or  :only   Close every other window.                   *CTRL-W_o*
+Ctrl+W +  Ctrl+W -  Make the window count lines taller / shorter. *CTRL-W_+*
+Ctrl+W =            Make all windows the same size.               *CTRL-W_=*
+"),
+];
+
+/// Page text by topic name.
+static TOPICS: LazyLock<HashMap<&'static str, &'static str>> =
+    LazyLock::new(|| PAGES.iter().copied().collect());
+
+/// The text of the page named `topic`.
+#[must_use]
+pub fn topic_text(topic: &str) -> Option<&'static str> {
+    TOPICS.get(topic).copied()
+}
+
+/// Resolve a `:help` argument to a page and the 0-indexed line to show.
+///
+/// An empty query is the table of contents. A page name opens the page at
+/// its top; any other tag opens the page defining `*query*`, on that line.
+#[must_use]
+pub fn find(query: &str) -> Option<(&'static str, usize)> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Some((CONTENTS, 0));
+    }
+    if let Some((&topic, _)) = TOPICS.get_key_value(query) {
+        return Some((topic, 0));
+    }
+    let tag = format!("*{query}*");
+    PAGES.iter().find_map(|&(topic, text)| {
+        text.lines()
+            .position(|line| line.contains(&tag))
+            .map(|line| (topic, line))
+    })
+}
+
+/// The link (`|name|`) at char column `col` of `line`, bars excluded.
+#[must_use]
+pub fn link_at(line: &str, col: usize) -> Option<&str> {
+    let bars: Vec<(usize, usize)> = line
+        .char_indices()
+        .enumerate()
+        .filter(|&(_, (_, c))| c == '|')
+        .map(|(col, (byte, _))| (col, byte))
+        .collect();
+    // Pair the bars left to right; a pair with blanks between them is
+    // plain text, so its closing bar may still open a link.
+    let mut i = 0;
+    while i + 1 < bars.len() {
+        let ((open_col, open), (close_col, close)) = (bars[i], bars[i + 1]);
+        let name = &line[open + 1..close];
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            i += 1;
+            continue;
+        }
+        if (open_col..=close_col).contains(&col) {
+            return Some(name);
+        }
+        i += 2;
+    }
+    None
+}
+
+/// The buffer name for the page `topic`.
+#[must_use]
+pub fn buffer_name(topic: &str) -> PathBuf {
+    PathBuf::from(format!("{BUFFER_PREFIX}{topic}"))
+}
+
+/// Whether `path` names a help buffer.
+#[must_use]
+pub fn is_help_path(path: &Path) -> bool {
+    path.to_str().is_some_and(|p| p.starts_with(BUFFER_PREFIX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_page_starts_with_its_own_tag() {
+        for &(topic, text) in PAGES {
+            assert!(text.starts_with(&format!("*{topic}*")), "{topic}");
+        }
+    }
+
+    #[test]
+    fn every_link_resolves() {
+        for &(topic, text) in PAGES {
+            for line in text.lines() {
+                for col in 0..line.chars().count() {
+                    if let Some(name) = link_at(line, col) {
+                        assert!(find(name).is_some(), "{topic}: |{name}|");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn find_topics_and_tags() {
+        assert_eq!(find(""), Some((CONTENTS, 0)));
+        assert_eq!(find("motions"), Some(("motions", 0)));
+        let (topic, line) = find("dd").unwrap();
+        assert_eq!(topic, "operators");
+        assert!(topic_text(topic).unwrap().lines().nth(line).unwrap().contains("*dd*"));
+        assert_eq!(find("CTRL-W_s").map(|(t, _)| t), Some("windows"));
+        assert_eq!(find("nosuchtopic"), None);
+    }
+
+    #[test]
+    fn link_under_cursor() {
+        let line = "see |motions| and |search|.";
+        assert_eq!(link_at(line, 4), Some("motions"));
+        assert_eq!(link_at(line, 8), Some("motions"));
+        assert_eq!(link_at(line, 12), Some("motions"));
+        assert_eq!(link_at(line, 20), Some("search"));
+        assert_eq!(link_at(line, 0), None);
+        assert_eq!(link_at(line, 15), None);
+    }
+
+    #[test]
+    fn help_buffer_names() {
+        assert!(is_help_path(&buffer_name("marks")));
+        assert!(!is_help_path(Path::new("marks.txt")));
+    }
+}
@@ -257,6 +257,8 @@ fn build_capture_colors(query: &Query, theme: &Theme) -> Vec<CellColor> {
         .map(|name| {
             if is_terminal {
                 terminal_color(name)
+            } else if theme.color256 {
+                generated_color(name, &theme.syntax).to_ansi256()
             } else {
                 generated_color(name, &theme.syntax)
             }
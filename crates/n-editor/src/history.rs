@@ -19,6 +19,9 @@
 //!
 //! Empty transactions (no edits between begin and commit) are silently
 //! discarded — they don't clutter the undo stack.
+//!
+//! The undo stack can be capped with [`History::set_limit`] (`undolevels`):
+//! once full, committing a transaction drops the oldest one.
 
 use crate::buffer::Buffer;
 use crate::position::{Position, Range};
@@ -102,6 +105,11 @@ pub struct History {
     undo_stack: Vec<Transaction>,
     redo_stack: Vec<Transaction>,
     pending: Option<Transaction>,
+    /// When false, `begin` never opens a transaction, so every `record_*`
+    /// call is a no-op. Used for large file mode.
+    enabled: bool,
+    /// Most transactions kept on the undo stack; `None` is unlimited.
+    limit: Option<usize>,
 }
 
 impl History {
@@ -112,15 +120,54 @@ impl History {
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
             pending: None,
+            enabled: true,
+            limit: None,
         }
     }
 
+    /// Create a history that records nothing.
+    ///
+    /// Large files use this so edits don't accumulate an undo log whose
+    /// size scales with the file. Undo and redo always report nothing to do.
+    #[must_use]
+    pub const fn disabled() -> Self {
+        Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            pending: None,
+            enabled: false,
+            limit: None,
+        }
+    }
+
+    /// True if this history records edits (see [`disabled`](Self::disabled)).
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// The most transactions kept on the undo stack (`None` = unlimited).
+    #[must_use]
+    pub const fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Cap the undo stack at `limit` transactions, dropping the oldest ones
+    /// already beyond it. `Some(0)` keeps no undo history at all.
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+        self.trim();
+    }
+
     /// Start a new transaction. `cursor` is the cursor position before any
     /// edits in this transaction.
     ///
     /// If a previous transaction was still pending (begin without commit),
     /// it is auto-committed first.
     pub fn begin(&mut self, cursor: Position) {
+        if !self.enabled || self.limit == Some(0) {
+            return;
+        }
         if self.pending.is_some() {
             self.commit(cursor);
         }
@@ -174,7 +221,7 @@ impl History {
             let change_pos = txn.cursor_before;
             txn.cursor_after = cursor;
             self.redo_stack.clear();
-            self.undo_stack.push(txn);
+            self.push_undo(txn);
             Some(change_pos)
         } else {
             None
@@ -188,7 +235,7 @@ impl History {
         if let Some(txn) = self.pending.take() {
             if !txn.edits.is_empty() {
                 self.redo_stack.clear();
-                self.undo_stack.push(txn);
+                self.push_undo(txn);
             }
         }
 
@@ -236,6 +283,20 @@ impl History {
     pub fn redo_count(&self) -> usize {
         self.redo_stack.len()
     }
+
+    /// Push a committed transaction, dropping the oldest past the limit.
+    fn push_undo(&mut self, txn: Transaction) {
+        self.undo_stack.push(txn);
+        self.trim();
+    }
+
+    /// Drop the oldest transactions until the undo stack fits the limit.
+    fn trim(&mut self) {
+        if let Some(limit) = self.limit {
+            let excess = self.undo_stack.len().saturating_sub(limit);
+            self.undo_stack.drain(..excess);
+        }
+    }
 }
 
 impl Default for History {
@@ -682,6 +743,77 @@ mod tests {
         assert!(!h.can_redo());
     }
 
+    // -- Disabled history ---------------------------------------------------
+
+    #[test]
+    fn disabled_records_nothing() {
+        let mut buf = Buffer::from_text("");
+        let mut h = History::disabled();
+        assert!(!h.is_enabled());
+
+        h.begin(Position::ZERO);
+        buf.insert(Position::ZERO, "hello");
+        h.record_insert(Position::ZERO, "hello");
+        assert_eq!(h.commit(Position::new(0, 5)), None);
+
+        assert!(!h.can_undo());
+        assert_eq!(h.undo(&mut buf), None);
+        assert_eq!(buf.contents(), "hello");
+    }
+
+    // -- Limit --------------------------------------------------------------
+
+    /// Commit a one-character insert at the start of `buf`.
+    fn type_char(h: &mut History, buf: &mut Buffer, ch: char) {
+        h.begin(Position::ZERO);
+        buf.insert_char(Position::ZERO, ch);
+        h.record_insert(Position::ZERO, &ch.to_string());
+        h.commit(Position::ZERO);
+    }
+
+    #[test]
+    fn limit_drops_oldest_transactions() {
+        let mut buf = Buffer::from_text("");
+        let mut h = History::new();
+        h.set_limit(Some(3));
+
+        for ch in ['a', 'b', 'c', 'd', 'e'] {
+            type_char(&mut h, &mut buf, ch);
+        }
+        assert_eq!(h.undo_count(), 3);
+
+        for _ in 0..3 {
+            assert!(h.undo(&mut buf).is_some());
+        }
+        assert!(h.undo(&mut buf).is_none());
+        assert_eq!(buf.contents(), "ba");
+    }
+
+    #[test]
+    fn lowering_limit_trims_existing_history() {
+        let mut buf = Buffer::from_text("");
+        let mut h = History::new();
+        for ch in ['a', 'b', 'c'] {
+            type_char(&mut h, &mut buf, ch);
+        }
+
+        h.set_limit(Some(1));
+        assert_eq!(h.undo_count(), 1);
+        assert_eq!(h.limit(), Some(1));
+    }
+
+    #[test]
+    fn zero_limit_records_nothing() {
+        let mut buf = Buffer::from_text("");
+        let mut h = History::new();
+        h.set_limit(Some(0));
+
+        type_char(&mut h, &mut buf, 'a');
+        assert!(!h.can_undo());
+        assert!(h.undo(&mut buf).is_none());
+        assert_eq!(buf.contents(), "a");
+    }
+
     // -- Auto-commit on begin -----------------------------------------------
 
     #[test]
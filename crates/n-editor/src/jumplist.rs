@@ -19,6 +19,18 @@ const CHANGELIST_MAX: usize = 100;
 // JumpList
 // ---------------------------------------------------------------------------
 
+/// The outcome of moving through the jump list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JumpResult {
+    /// Moved to this position.
+    Jumped(Position),
+    /// Already at the oldest entry (or the list is empty) — `Ctrl+O` has
+    /// nowhere to go.
+    AtStart,
+    /// Already at the newest entry — `Ctrl+I` has nowhere to go.
+    AtEnd,
+}
+
 /// Position history for jump navigation (`Ctrl+O` / `Ctrl+I`).
 ///
 /// Jump motions push the cursor's pre-jump position onto the list. The list
@@ -83,9 +95,9 @@ impl JumpList {
     ///
     /// `current_pos` is the cursor's current position, saved on the first
     /// backward navigation so `forward()` can return to it.
-    pub fn back(&mut self, current_pos: Position) -> Option<Position> {
+    pub fn back(&mut self, current_pos: Position) -> JumpResult {
         if self.entries.is_empty() {
-            return None;
+            return JumpResult::AtStart;
         }
 
         // First backward nav from live: save the current position.
@@ -101,20 +113,26 @@ impl JumpList {
         }
 
         if self.current == 0 {
-            return None;
+            return JumpResult::AtStart;
         }
 
         self.current -= 1;
-        Some(self.entries[self.current])
+        JumpResult::Jumped(self.entries[self.current])
     }
 
     /// Go forward in the jump list (`Ctrl+I`).
-    pub fn forward(&mut self) -> Option<Position> {
+    pub fn forward(&mut self) -> JumpResult {
         if self.current + 1 >= self.entries.len() {
-            return None;
+            return JumpResult::AtEnd;
         }
         self.current += 1;
-        Some(self.entries[self.current])
+        JumpResult::Jumped(self.entries[self.current])
+    }
+
+    /// The entries, oldest first.
+    #[must_use]
+    pub fn entries(&self) -> &[Position] {
+        &self.entries
     }
 
     /// Number of entries in the list.
@@ -259,7 +277,7 @@ mod tests {
 
         // Currently at live position (line 15). Back should go to line 10.
         let pos = jl.back(Position::new(15, 0));
-        assert_eq!(pos, Some(Position::new(10, 0)));
+        assert_eq!(pos, JumpResult::Jumped(Position::new(10, 0)));
     }
 
     #[test]
@@ -273,29 +291,29 @@ mod tests {
         // Live position was saved — forward should return to it.
         let _ = jl.back(Position::new(10, 0)); // go to line 0
         let pos = jl.forward();
-        assert_eq!(pos, Some(Position::new(5, 0)));
+        assert_eq!(pos, JumpResult::Jumped(Position::new(5, 0)));
         let pos = jl.forward();
-        assert_eq!(pos, Some(Position::new(10, 0)));
+        assert_eq!(pos, JumpResult::Jumped(Position::new(10, 0)));
     }
 
     #[test]
-    fn jumplist_back_at_start_returns_none() {
+    fn jumplist_back_at_start_returns_at_start() {
         let mut jl = JumpList::new();
-        assert_eq!(jl.back(Position::new(0, 0)), None);
+        assert_eq!(jl.back(Position::new(0, 0)), JumpResult::AtStart);
 
         jl.push(Position::new(0, 0));
         // Back to line 0, then can't go further.
         let _ = jl.back(Position::new(5, 0));
-        assert_eq!(jl.back(Position::new(5, 0)), None);
+        assert_eq!(jl.back(Position::new(5, 0)), JumpResult::AtStart);
     }
 
     #[test]
-    fn jumplist_forward_at_end_returns_none() {
+    fn jumplist_forward_at_end_returns_at_end() {
         let mut jl = JumpList::new();
-        assert_eq!(jl.forward(), None);
+        assert_eq!(jl.forward(), JumpResult::AtEnd);
 
         jl.push(Position::new(0, 0));
-        assert_eq!(jl.forward(), None);
+        assert_eq!(jl.forward(), JumpResult::AtEnd);
     }
 
     #[test]
@@ -307,21 +325,21 @@ mod tests {
 
         // Live at line 30. Back three times.
         let p1 = jl.back(Position::new(30, 0));
-        assert_eq!(p1, Some(Position::new(20, 0)));
+        assert_eq!(p1, JumpResult::Jumped(Position::new(20, 0)));
         let p2 = jl.back(Position::new(30, 0));
-        assert_eq!(p2, Some(Position::new(10, 0)));
+        assert_eq!(p2, JumpResult::Jumped(Position::new(10, 0)));
         let p3 = jl.back(Position::new(30, 0));
-        assert_eq!(p3, Some(Position::new(0, 0)));
+        assert_eq!(p3, JumpResult::Jumped(Position::new(0, 0)));
 
         // Forward three times.
         let f1 = jl.forward();
-        assert_eq!(f1, Some(Position::new(10, 0)));
+        assert_eq!(f1, JumpResult::Jumped(Position::new(10, 0)));
         let f2 = jl.forward();
-        assert_eq!(f2, Some(Position::new(20, 0)));
+        assert_eq!(f2, JumpResult::Jumped(Position::new(20, 0)));
         let f3 = jl.forward();
-        assert_eq!(f3, Some(Position::new(30, 0)));
+        assert_eq!(f3, JumpResult::Jumped(Position::new(30, 0)));
         // Can't go further forward.
-        assert_eq!(jl.forward(), None);
+        assert_eq!(jl.forward(), JumpResult::AtEnd);
     }
 
     #[test]
@@ -337,7 +355,7 @@ mod tests {
 
         // New push from the middle — truncates future (20, 30).
         jl.push(Position::new(50, 0));
-        assert_eq!(jl.forward(), None); // future was truncated
+        assert_eq!(jl.forward(), JumpResult::AtEnd); // future was truncated
     }
 
     #[test]
@@ -358,18 +376,18 @@ mod tests {
         // Live position is on line 5 (same as last entry).
         // back() should NOT add a duplicate.
         let pos = jl.back(Position::new(5, 3));
-        assert_eq!(pos, Some(Position::new(0, 0)));
+        assert_eq!(pos, JumpResult::Jumped(Position::new(0, 0)));
         // Forward returns to line 5 (the original entry, not a duplicate).
         let pos = jl.forward();
-        assert_eq!(pos, Some(Position::new(5, 0)));
-        assert_eq!(jl.forward(), None);
+        assert_eq!(pos, JumpResult::Jumped(Position::new(5, 0)));
+        assert_eq!(jl.forward(), JumpResult::AtEnd);
     }
 
     #[test]
     fn jumplist_empty_back_forward() {
         let mut jl = JumpList::new();
-        assert_eq!(jl.back(Position::ZERO), None);
-        assert_eq!(jl.forward(), None);
+        assert_eq!(jl.back(Position::ZERO), JumpResult::AtStart);
+        assert_eq!(jl.forward(), JumpResult::AtEnd);
     }
 
     #[test]
@@ -379,12 +397,12 @@ mod tests {
 
         // Back from line 20 → should go to line 10.
         let pos = jl.back(Position::new(20, 0));
-        assert_eq!(pos, Some(Position::new(10, 5)));
+        assert_eq!(pos, JumpResult::Jumped(Position::new(10, 5)));
         // Can't go further back.
-        assert_eq!(jl.back(Position::new(20, 0)), None);
+        assert_eq!(jl.back(Position::new(20, 0)), JumpResult::AtStart);
         // Forward returns to line 20 (saved live).
         let pos = jl.forward();
-        assert_eq!(pos, Some(Position::new(20, 0)));
+        assert_eq!(pos, JumpResult::Jumped(Position::new(20, 0)));
     }
 
     #[test]
@@ -401,7 +419,7 @@ mod tests {
         // Now push a new entry — should work normally.
         jl.push(Position::new(15, 0));
         let pos = jl.back(Position::new(20, 0));
-        assert_eq!(pos, Some(Position::new(15, 0)));
+        assert_eq!(pos, JumpResult::Jumped(Position::new(15, 0)));
     }
 
     // ── ChangeList ───────────────────────────────────────────────────────
@@ -0,0 +1,515 @@
+//! Key mappings — `:map`, `:noremap`, `:unmap` and their per-mode forms.
+//!
+//! A [`Keymap`] replaces a sequence of typed keys (the left-hand side) with
+//! another (the right-hand side). Both are written in Vim's key notation
+//! and parsed by [`parse_keys`]:
+//!
+//! | Notation                         | Key                        |
+//! |----------------------------------|----------------------------|
+//! | `<Space>`, `<Tab>`, `<CR>`       | Space, Tab, Enter          |
+//! | `<Esc>`, `<BS>`, `<Del>`         | Escape, Backspace, Delete  |
+//! | `<Up>` … `<Right>`, `<Home>`     | Cursor keys                |
+//! | `<PageUp>`, `<PageDown>`, `<End>`| Paging keys                |
+//! | `<F1>` … `<F35>`                 | Function keys              |
+//! | `<C-x>`, `<A-x>` / `<M-x>`, `<S-x>` | With Ctrl, Alt, Shift   |
+//! | `<lt>`, `<Bar>`, `<Bslash>`      | `<`, `\|`, `\`             |
+//! | `<Nop>`                          | Nothing (an empty mapping) |
+//!
+//! Anything else, including an unknown `<…>`, is the literal characters.
+//!
+//! Each mapping belongs to a [`MapMode`], set by the command that made it:
+//!
+//! | Command                  | Mode                                     |
+//! |--------------------------|------------------------------------------|
+//! | `:map`, `:noremap`       | Normal, visual and operator-pending      |
+//! | `:nmap`, `:nnoremap`     | Normal                                   |
+//! | `:imap`, `:inoremap`     | Insert                                   |
+//! | `:vmap`, `:vnoremap`     | Visual (character, line and block)       |
+//! | `:xmap`, `:xnoremap`     | Character and line visual (not block)    |
+//! | `:omap`, `:onoremap`     | Operator-pending (after `d`, `c`, `y`…)  |
+//! | `:cmap`, `:cnoremap`     | Command line                             |
+//!
+//! `:unmap` and `:mapclear` take the same prefixes (`:nunmap`,
+//! `:imapclear`).
+//!
+//! With `<expr>` before the left-hand side (`:imap <expr> <CR>
+//! pumvisible() ? "\<C-y>" : "\<CR>"`), the right-hand side is an
+//! expression ([`crate::expr`]), evaluated each time the mapping is used;
+//! its result is read as key notation.
+//!
+//! With `<buffer>` (`:nmap <buffer> K gd`), the mapping belongs to the
+//! current buffer and applies only while that buffer is active. Each
+//! buffer keeps its own table of these, looked up before the global one;
+//! `:unmap <buffer>` and `:mapclear <buffer>` act on it alone. Filetype
+//! plugins use them for language-specific keys.
+//!
+//! When the keys typed so far are the start of a longer mapping, the
+//! editor waits for more: `timeoutlen` milliseconds after the last key,
+//! the longest mapping the keys complete (if any) is used. So with `j` and
+//! `jk` both mapped, `jk` typed quickly runs `jk`'s mapping and a lone `j`
+//! runs `j`'s once the timeout passes.
+
+use n_term::input::{KeyCode, KeyEvent, KeyEventKind, Modifiers};
+
+/// The modes a mapping applies in, or (for the concrete modes) the mode
+/// the editor is in when keys are typed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapMode {
+    /// Normal mode, with no command pending.
+    Normal,
+    /// Insert mode.
+    Insert,
+    /// Any visual mode (`:vmap`).
+    Visual,
+    /// Characterwise visual mode. As a mapping's mode (`:xmap`), linewise
+    /// visual mode too, but not block.
+    VisualChar,
+    /// Linewise visual mode.
+    VisualLine,
+    /// Blockwise visual mode.
+    VisualBlock,
+    /// After an operator, waiting for its motion.
+    OperatorPending,
+    /// Typing a `:` command line.
+    Command,
+    /// Normal, visual and operator-pending mode (plain `:map`).
+    All,
+}
+
+impl MapMode {
+    /// The modes the editor can be in.
+    const CURRENT: [Self; 7] = [
+        Self::Normal,
+        Self::Insert,
+        Self::VisualChar,
+        Self::VisualLine,
+        Self::VisualBlock,
+        Self::OperatorPending,
+        Self::Command,
+    ];
+
+    /// Whether a mapping for `self` applies when the editor is in
+    /// `current`.
+    #[must_use]
+    pub fn applies_in(self, current: Self) -> bool {
+        match self {
+            Self::All => !matches!(current, Self::Insert | Self::Command),
+            Self::Visual => matches!(current, Self::VisualChar | Self::VisualLine | Self::VisualBlock),
+            Self::VisualChar => matches!(current, Self::VisualChar | Self::VisualLine),
+            _ => self == current,
+        }
+    }
+
+    /// Whether `self` applies in every mode `other` does.
+    #[must_use]
+    pub fn contains(self, other: Self) -> bool {
+        Self::CURRENT.iter().all(|&m| !other.applies_in(m) || self.applies_in(m))
+    }
+
+    /// Whether `self` and `other` apply in some mode in common.
+    #[must_use]
+    pub fn overlaps(self, other: Self) -> bool {
+        Self::CURRENT.iter().any(|&m| self.applies_in(m) && other.applies_in(m))
+    }
+
+    /// The mode's letter in a `:map` listing, as in the command names.
+    #[must_use]
+    pub const fn letter(self) -> char {
+        match self {
+            Self::All => ' ',
+            Self::Normal => 'n',
+            Self::Insert => 'i',
+            Self::Visual | Self::VisualBlock => 'v',
+            Self::VisualChar | Self::VisualLine => 'x',
+            Self::OperatorPending => 'o',
+            Self::Command => 'c',
+        }
+    }
+}
+
+/// One mapping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keymap {
+    /// The modes it applies in.
+    pub mode: MapMode,
+    /// The keys that trigger the mapping.
+    pub lhs: Vec<KeyEvent>,
+    /// The keys they're replaced with.
+    pub rhs: Vec<KeyEvent>,
+    /// The right-hand side runs as typed, without further mapping
+    /// (`:noremap`).
+    pub noremap: bool,
+    /// The right-hand side is an expression that gives the keys
+    /// (`<expr>`); `rhs` is empty and `rhs_text` holds the expression.
+    pub expr: bool,
+    /// The left-hand side as written, for listing.
+    pub lhs_text: String,
+    /// The right-hand side as written, for listing.
+    pub rhs_text: String,
+    /// The buffer the mapping belongs to (`<buffer>`), or `None` for a
+    /// global mapping.
+    pub buf_id: Option<usize>,
+}
+
+impl Keymap {
+    /// A mapping in `mode` from the notation of `lhs` to that of `rhs`.
+    #[must_use]
+    pub fn new(mode: MapMode, lhs: &str, rhs: &str, noremap: bool) -> Self {
+        Self {
+            mode,
+            lhs: parse_keys(lhs),
+            rhs: parse_keys(rhs),
+            noremap,
+            expr: false,
+            lhs_text: lhs.to_string(),
+            rhs_text: rhs.to_string(),
+            buf_id: None,
+        }
+    }
+
+    /// An `<expr>` mapping in `mode` from the notation of `lhs` to the keys
+    /// that `expr` evaluates to.
+    #[must_use]
+    pub fn expr(mode: MapMode, lhs: &str, expr: &str, noremap: bool) -> Self {
+        Self { rhs: Vec::new(), expr: true, rhs_text: expr.to_string(), ..Self::new(mode, lhs, "", noremap) }
+    }
+
+    /// The same mapping, belonging to buffer `buf_id`.
+    #[must_use]
+    pub fn for_buffer(self, buf_id: usize) -> Self {
+        Self { buf_id: Some(buf_id), ..self }
+    }
+
+    /// Whether the mapping belongs to one buffer (`<buffer>`).
+    #[must_use]
+    pub const fn is_buffer_local(&self) -> bool {
+        self.buf_id.is_some()
+    }
+}
+
+/// How a sequence of typed keys relates to the mappings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lookup<'a> {
+    /// No mapping starts with these keys.
+    None,
+    /// A longer mapping starts with these keys — wait for more.
+    Partial,
+    /// The keys are exactly this mapping's left-hand side, and no longer
+    /// mapping starts with them.
+    Exact(&'a Keymap),
+}
+
+/// A set of mappings, at most one per left-hand side and mode.
+#[derive(Debug, Default)]
+pub struct KeymapTable {
+    maps: Vec<Keymap>,
+}
+
+impl KeymapTable {
+    /// An empty table.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { maps: Vec::new() }
+    }
+
+    /// Add a mapping, replacing any with the same left-hand side and mode.
+    /// A mapping with an empty left-hand side is ignored.
+    pub fn map(&mut self, keymap: Keymap) {
+        if keymap.lhs.is_empty() {
+            return;
+        }
+        let existing = self.maps.iter_mut().find(|m| m.mode == keymap.mode && same_keys(&m.lhs, &keymap.lhs));
+        if let Some(existing) = existing {
+            *existing = keymap;
+        } else {
+            self.maps.push(keymap);
+        }
+    }
+
+    /// Remove the mappings for `lhs` (in key notation) whose modes are
+    /// within `mode`. Returns `false` if there were none.
+    pub fn unmap(&mut self, mode: MapMode, lhs: &str) -> bool {
+        let keys = parse_keys(lhs);
+        let before = self.maps.len();
+        self.maps.retain(|m| !(mode.contains(m.mode) && same_keys(&m.lhs, &keys)));
+        self.maps.len() != before
+    }
+
+    /// Remove every mapping whose modes are within `mode`.
+    pub fn clear(&mut self, mode: MapMode) {
+        self.maps.retain(|m| !mode.contains(m.mode));
+    }
+
+    /// Whether there are no mappings.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.maps.is_empty()
+    }
+
+    /// The mappings, in the order they were first added.
+    pub fn iter(&self) -> impl Iterator<Item = &Keymap> {
+        self.maps.iter()
+    }
+
+    /// How `keys` (typed so far in mode `current`) relate to the mappings
+    /// for that mode.
+    #[must_use]
+    pub fn lookup(&self, keys: &[KeyEvent], current: MapMode) -> Lookup<'_> {
+        let mut exact = None;
+        for map in self.maps.iter().filter(|m| m.mode.applies_in(current)) {
+            if map.lhs.len() > keys.len() && same_keys(&map.lhs[..keys.len()], keys) {
+                return Lookup::Partial;
+            }
+            if same_keys(&map.lhs, keys) {
+                exact = Some(map);
+            }
+        }
+        exact.map_or(Lookup::None, Lookup::Exact)
+    }
+
+    /// The mapping for mode `current` with the longest left-hand side that
+    /// `keys` starts with.
+    #[must_use]
+    pub fn longest_match(&self, keys: &[KeyEvent], current: MapMode) -> Option<&Keymap> {
+        self.maps
+            .iter()
+            .filter(|m| m.mode.applies_in(current))
+            .filter(|m| m.lhs.len() <= keys.len() && same_keys(&m.lhs, &keys[..m.lhs.len()]))
+            .max_by_key(|m| m.lhs.len())
+    }
+
+    /// The `:map` listing of mappings that apply in any of `mode`'s modes
+    /// and whose left-hand side starts with `prefix` (all of them for an
+    /// empty prefix). Each line starts with the mapping's mode letter;
+    /// `*` marks `:noremap` and `@` a buffer-local mapping.
+    #[must_use]
+    pub fn listing(&self, mode: MapMode, prefix: &str) -> Vec<String> {
+        let prefix = parse_keys(prefix);
+        self.maps
+            .iter()
+            .filter(|m| m.mode.overlaps(mode))
+            .filter(|m| m.lhs.len() >= prefix.len() && same_keys(&m.lhs[..prefix.len()], &prefix))
+            .map(|m| {
+                let star = if m.noremap { '*' } else { ' ' };
+                let local = if m.is_buffer_local() { "@" } else { "" };
+                format!("{}  {:<12} {star}{local}{}", m.mode.letter(), m.lhs_text, m.rhs_text)
+            })
+            .collect()
+    }
+}
+
+/// Whether two key sequences are the same keys. Shift is ignored on
+/// characters (it's already in the character), and so is press / repeat.
+fn same_keys(a: &[KeyEvent], b: &[KeyEvent]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(x, y)| normalize(x) == normalize(y))
+}
+
+fn normalize(key: &KeyEvent) -> (KeyCode, Modifiers) {
+    match key.code {
+        KeyCode::Char(_) => (key.code, key.modifiers - Modifiers::SHIFT),
+        code => (code, key.modifiers),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Key notation
+// ---------------------------------------------------------------------------
+
+/// Parse key notation (`<C-w>j`, `<Space>ff`, `:w<CR>`) into key events.
+#[must_use]
+pub fn parse_keys(notation: &str) -> Vec<KeyEvent> {
+    let chars: Vec<char> = notation.chars().collect();
+    let mut keys = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '<' {
+            if let Some(len) = chars[i + 1..].iter().position(|&c| c == '>') {
+                let name: String = chars[i + 1..i + 1 + len].iter().collect();
+                if let Some(key) = parse_special(&name) {
+                    keys.extend(key);
+                    i += len + 2;
+                    continue;
+                }
+            }
+        }
+        keys.push(key(KeyCode::Char(chars[i]), Modifiers::empty()));
+        i += 1;
+    }
+    keys
+}
+
+const fn key(code: KeyCode, modifiers: Modifiers) -> KeyEvent {
+    KeyEvent { code, modifiers, kind: KeyEventKind::Press }
+}
+
+/// The keys for the `<name>` notation (without the brackets): one key, or
+/// none for `<Nop>`. An unknown name is `None`.
+fn parse_special(name: &str) -> Option<Vec<KeyEvent>> {
+    let mut modifiers = Modifiers::empty();
+    let mut rest = name;
+    while rest.len() > 2 && rest.as_bytes()[1] == b'-' {
+        modifiers |= match rest.as_bytes()[0].to_ascii_uppercase() {
+            b'C' => Modifiers::CTRL,
+            b'A' | b'M' => Modifiers::ALT,
+            b'S' => Modifiers::SHIFT,
+            _ => return None,
+        };
+        rest = &rest[2..];
+    }
+
+    let mut chars = rest.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        if modifiers.is_empty() {
+            return None;
+        }
+        // Ctrl letters are reported in lower case (`<C-W>` is `<C-w>`), and
+        // shifted ones are just the capital (`<S-x>` is `X`).
+        let ch = if modifiers.contains(Modifiers::CTRL) {
+            ch.to_ascii_lowercase()
+        } else if modifiers.contains(Modifiers::SHIFT) {
+            ch.to_ascii_uppercase()
+        } else {
+            ch
+        };
+        return Some(vec![key(KeyCode::Char(ch), modifiers - Modifiers::SHIFT)]);
+    }
+
+    let code = match rest.to_ascii_lowercase().as_str() {
+        "nop" if modifiers.is_empty() => return Some(Vec::new()),
+        "space" => KeyCode::Char(' '),
+        "lt" => KeyCode::Char('<'),
+        "bar" => KeyCode::Char('|'),
+        "bslash" => KeyCode::Char('\\'),
+        "cr" | "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "esc" => KeyCode::Escape,
+        "bs" => KeyCode::Backspace,
+        "del" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        f => match f.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+            Some(n @ 1..=35) => KeyCode::F(n),
+            _ => return None,
+        },
+    };
+    Some(vec![key(code, modifiers)])
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ch(c: char) -> KeyEvent {
+        key(KeyCode::Char(c), Modifiers::empty())
+    }
+
+    #[test]
+    fn parses_notation() {
+        assert_eq!(parse_keys("jk"), vec![ch('j'), ch('k')]);
+        assert_eq!(parse_keys("<Space>f"), vec![ch(' '), ch('f')]);
+        assert_eq!(parse_keys(":w<CR>"), vec![ch(':'), ch('w'), key(KeyCode::Enter, Modifiers::empty())]);
+        assert_eq!(parse_keys("<C-W>"), vec![key(KeyCode::Char('w'), Modifiers::CTRL)]);
+        assert_eq!(parse_keys("<C-S-Tab>"), vec![key(KeyCode::Tab, Modifiers::CTRL | Modifiers::SHIFT)]);
+        assert_eq!(parse_keys("<M-x>"), vec![key(KeyCode::Char('x'), Modifiers::ALT)]);
+        assert_eq!(parse_keys("<S-x>"), vec![ch('X')]);
+        assert_eq!(parse_keys("<F5>"), vec![key(KeyCode::F(5), Modifiers::empty())]);
+        assert_eq!(parse_keys("<lt>"), vec![ch('<')]);
+        assert!(parse_keys("<Nop>").is_empty());
+        // Unknown names and stray brackets are literal.
+        assert_eq!(parse_keys("<x>").len(), 3);
+        assert_eq!(parse_keys("a<b"), vec![ch('a'), ch('<'), ch('b')]);
+    }
+
+    #[test]
+    fn lookup_waits_for_longer_mappings() {
+        let n = MapMode::Normal;
+        let mut t = KeymapTable::new();
+        t.map(Keymap::new(MapMode::All, "j", "gj", false));
+        t.map(Keymap::new(n, "jk", "<Esc>", true));
+        t.map(Keymap::new(n, "<Space>ff", ":e<CR>", true));
+
+        assert_eq!(t.lookup(&parse_keys("j"), n), Lookup::Partial);
+        assert!(matches!(t.lookup(&parse_keys("jk"), n), Lookup::Exact(m) if m.rhs_text == "<Esc>"));
+        assert_eq!(t.lookup(&parse_keys("x"), n), Lookup::None);
+        assert_eq!(t.lookup(&parse_keys(" f"), n), Lookup::Partial);
+        assert_eq!(t.lookup(&parse_keys("jx"), n), Lookup::None);
+
+        assert_eq!(t.longest_match(&parse_keys("jx"), n).map(|m| m.rhs_text.as_str()), Some("gj"));
+        assert_eq!(t.longest_match(&parse_keys("jkx"), n).map(|m| m.rhs_text.as_str()), Some("<Esc>"));
+        assert!(t.longest_match(&parse_keys(" fx"), n).is_none());
+    }
+
+    #[test]
+    fn shift_is_ignored_on_characters() {
+        let mut t = KeymapTable::new();
+        t.map(Keymap::new(MapMode::Normal, "Q", "gq", false));
+        let typed = key(KeyCode::Char('Q'), Modifiers::SHIFT);
+        assert!(matches!(t.lookup(&[typed], MapMode::Normal), Lookup::Exact(_)));
+    }
+
+    #[test]
+    fn remap_replaces_and_unmap_removes() {
+        let mut t = KeymapTable::new();
+        t.map(Keymap::new(MapMode::All, "<C-s>", ":w<CR>", false));
+        t.map(Keymap::new(MapMode::All, "<C-S>", ":wa<CR>", true));
+        assert_eq!(t.iter().count(), 1);
+        assert_eq!(t.listing(MapMode::All, ""), vec!["   <C-S>        *:wa<CR>"]);
+        assert!(t.listing(MapMode::All, "x").is_empty());
+        assert!(t.unmap(MapMode::All, "<C-s>"));
+        assert!(!t.unmap(MapMode::All, "<C-s>"));
+        assert!(t.is_empty());
+    }
+
+    #[test]
+    fn mappings_apply_in_their_modes() {
+        use MapMode::{All, Command, Insert, Normal, OperatorPending, Visual, VisualBlock, VisualChar, VisualLine};
+        let mut t = KeymapTable::new();
+        t.map(Keymap::new(Normal, "Q", "n", false));
+        t.map(Keymap::new(Insert, "Q", "i", false));
+        t.map(Keymap::new(VisualChar, "Q", "x", false));
+        t.map(Keymap::new(Visual, "W", "v", false));
+        t.map(Keymap::new(All, "E", "all", false));
+        let rhs = |t: &KeymapTable, keys: &str, mode| t.longest_match(&parse_keys(keys), mode).map(|m| m.rhs_text.clone());
+
+        assert_eq!(rhs(&t, "Q", Normal).as_deref(), Some("n"));
+        assert_eq!(rhs(&t, "Q", Insert).as_deref(), Some("i"));
+        assert_eq!(rhs(&t, "Q", VisualLine).as_deref(), Some("x"));
+        assert_eq!(rhs(&t, "Q", VisualBlock), None);
+        assert_eq!(rhs(&t, "W", VisualBlock).as_deref(), Some("v"));
+        assert_eq!(rhs(&t, "Q", OperatorPending), None);
+        assert_eq!(rhs(&t, "E", OperatorPending).as_deref(), Some("all"));
+        assert_eq!(rhs(&t, "E", Insert), None);
+        assert_eq!(rhs(&t, "E", Command), None);
+
+        assert_eq!(t.listing(Normal, "").len(), 2);
+        assert_eq!(t.listing(Visual, ""), vec!["x  Q             x", "v  W             v", "   E             all"]);
+
+        // `:unmap` reaches the modes `:map` covers; `:nunmap` just normal.
+        assert!(!t.unmap(Normal, "E"));
+        assert!(t.unmap(All, "Q"));
+        assert_eq!(rhs(&t, "Q", Insert).as_deref(), Some("i"));
+        t.clear(Insert);
+        assert_eq!(t.iter().count(), 2);
+    }
+
+    #[test]
+    fn buffer_local_mappings_are_marked() {
+        let mut t = KeymapTable::new();
+        t.map(Keymap::new(MapMode::Normal, "K", "gd", true).for_buffer(3));
+        let map = t.iter().next().unwrap();
+        assert!(map.is_buffer_local());
+        assert_eq!(map.buf_id, Some(3));
+        assert_eq!(t.listing(MapMode::Normal, ""), vec!["n  K            *@gd"]);
+    }
+}
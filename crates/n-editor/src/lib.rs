@@ -8,27 +8,57 @@
 //! - **[`cursor`]** — Cursor with movement, sticky column, and selection
 //! - **[`word`]** — Word/WORD boundary detection for `w`/`b`/`e`/`W`/`B`/`E` motions
 //! - **[`text_object`]** — Text objects (`iw`, `a"`, `i(`, etc.) for composable editing
+//! - **[`fold`]** — Folds (`:set foldmethod=indent`, `zo`/`zc`/`zR`/`zM`) drawn as one row
+//! - **[`surround`]** — Delimiter pairs for `ys`/`cs`/`ds`: finding, adding, changing
+//! - **[`format`]** — Built-in `gq`: reflow paragraphs to `textwidth`, keeping comment leaders
 //! - **[`search`]** — Incremental search (`/`, `?`, `n`, `N`) with match highlighting
 //! - **[`view`]** — View layer that bridges buffers to n-term's framebuffer
+//! - **[`digraph`]** — Digraph table (`Ctrl+K a:` → `ä`) following RFC 1345
 //! - **[`history`]** — Undo/redo: transaction-based, cursor position restore
 //! - **[`command`]** — Command-line parsing (`:w`, `:q`, `:e`, `:s`, `:bn`, `:bd`, `:ls`, `:set`)
 //! - **[`options`]** — `:set` option system: parsing, names, abbreviations
 //! - **[`register`]** — Register file: unnamed + 26 named registers (a-z) with append
+//! - **[`abbrev`]** — Abbreviations (`:abbreviate`, `:iabbrev`) expanded as words are typed
+//! - **[`keymap`]** — Key mappings (`:map`, `:noremap`) and key notation (`<C-w>`, `<Space>`)
 //! - **[`jumplist`]** — Jump list (`Ctrl+O`/`Ctrl+I`) and change list (`g;`/`g,`)
+//! - **[`spell`]** — Spell checking (`:set spell`, `[s`/`]s`) against bundled word lists
 //! - **[`split`]** — Split tree layout for window panes (`:sp`, `:vsp`, `Ctrl+W`)
+//! - **[`git`]** — Lines changed since the last commit, for the sign column
+//! - **[`help`]** — Built-in help pages for `:help`, with tags and links
+//! - **[`conceal`]** — Concealed markup (`:set conceallevel`), with built-in Markdown rules
+//! - **[`expr`]** — Vim expressions for `:s/pat/\=expr/`: arithmetic, strings, `submatch()`
+//! - **[`quickfix`]** — Quickfix list (`:vimgrep`, `:cnext`, `:cprev`) and file globbing
+//! - **[`message`]** — Message history for `:messages`
+//! - **[`shada`]** — Shared data (jumps, marks, registers, history) for `:wshada` / `:rshada`
+//! - **[`tags`]** — ctags `tags` files for `Ctrl+]` and `:tag`
 
+pub mod abbrev;
 pub mod buffer;
 pub mod command;
+pub mod conceal;
 pub mod cursor;
+pub mod digraph;
+pub mod expr;
+pub mod fold;
+pub mod format;
+pub mod git;
+pub mod help;
 pub mod highlight;
 pub mod history;
 pub mod jumplist;
+pub mod keymap;
+pub mod message;
 pub mod mode;
 pub mod options;
 pub mod position;
+pub mod quickfix;
 pub mod register;
 pub mod search;
+pub mod shada;
+pub mod spell;
 pub mod split;
+pub mod surround;
+pub mod tags;
 pub mod text_object;
 pub mod view;
 pub mod word;
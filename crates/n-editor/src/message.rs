@@ -0,0 +1,126 @@
+//! Message history — what the message line has shown, for `:messages`.
+//!
+//! Every message and error the editor shows is also pushed onto a
+//! [`MessageHistory`], so one that was overwritten before it could be read
+//! can be looked up again. The history keeps the most recent
+//! [`MESSAGE_HISTORY_MAX`] entries; `:messages clear` empties it.
+//!
+//! `:messages` lists them numbered, errors flagged with `E`:
+//!
+//! ```text
+//!    1 "notes.txt" 12L, 340B written
+//! E  2 E492: Not an editor command: wq1
+//! ```
+
+use std::collections::VecDeque;
+
+/// Number of messages kept.
+pub const MESSAGE_HISTORY_MAX: usize = 50;
+
+/// The most recent messages, oldest first, each flagged as error or not.
+#[derive(Debug, Default)]
+pub struct MessageHistory {
+    entries: VecDeque<(String, bool)>,
+}
+
+impl MessageHistory {
+    /// An empty history.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { entries: VecDeque::new() }
+    }
+
+    /// Record a message, dropping the oldest once the history is full.
+    /// Empty messages aren't recorded.
+    pub fn push(&mut self, msg: &str, is_error: bool) {
+        if msg.is_empty() {
+            return;
+        }
+        if self.entries.len() == MESSAGE_HISTORY_MAX {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((msg.to_string(), is_error));
+    }
+
+    /// Forget every message.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// The messages, oldest first, with whether each was an error.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, bool)> {
+        self.entries.iter().map(|(msg, is_error)| (msg.as_str(), *is_error))
+    }
+
+    /// Number of messages.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there are no messages.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The history as display lines for `:messages`, oldest first. Each
+    /// message starts with `E` for an error (a space otherwise) and its
+    /// 1-based number; a message spanning several lines gives one line
+    /// each, the rest indented to match.
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (i, (msg, is_error)) in self.entries.iter().enumerate() {
+            let flag = if *is_error { 'E' } else { ' ' };
+            for (j, line) in msg.lines().enumerate() {
+                if j == 0 {
+                    lines.push(format!("{flag}{:>3} {line}", i + 1));
+                } else {
+                    lines.push(format!("     {line}"));
+                }
+            }
+        }
+        lines
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_order_and_kind() {
+        let mut h = MessageHistory::new();
+        h.push("written", false);
+        h.push("", false);
+        h.push("E37: No write since last change", true);
+        let all: Vec<_> = h.iter().collect();
+        assert_eq!(all, vec![("written", false), ("E37: No write since last change", true)]);
+        h.clear();
+        assert!(h.is_empty());
+    }
+
+    #[test]
+    fn drops_oldest_past_limit() {
+        let mut h = MessageHistory::new();
+        for i in 0..MESSAGE_HISTORY_MAX + 5 {
+            h.push(&format!("msg {i}"), false);
+        }
+        assert_eq!(h.len(), MESSAGE_HISTORY_MAX);
+        assert_eq!(h.iter().next(), Some(("msg 5", false)));
+        assert_eq!(h.iter().last().map(|(m, _)| m.to_string()), Some(format!("msg {}", MESSAGE_HISTORY_MAX + 4)));
+    }
+
+    #[test]
+    fn lines_are_numbered_and_flagged() {
+        let mut h = MessageHistory::new();
+        h.push("one\ntwo", false);
+        h.push("three", true);
+        assert_eq!(h.lines(), vec!["   1 one", "     two", "E  2 three"]);
+    }
+}
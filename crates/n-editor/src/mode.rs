@@ -10,6 +10,8 @@
 //! | Visual    | Block        | `0..content_len-1`   | Selecting text       |
 //! | Replace   | Underline    | `0..content_len-1`   | Overwriting text     |
 //! | Command   | Bar          | (in command line)    | `:` commands         |
+//! | Confirm   | Block        | (on the match)       | `:s///c` prompts     |
+//! | Terminal  | Block        | (the program's)      | `:terminal` input    |
 
 use std::fmt;
 
@@ -63,6 +65,12 @@ pub enum Mode {
     /// Command-line mode (`:`, `/`, `?`). The cursor moves to the command
     /// line at the bottom of the screen.
     Command,
+    /// Waiting for `y`/`n`/`a`/`q`/`l` at a `:s///c` match. The match is
+    /// highlighted and the prompt sits on the command line.
+    SubstituteConfirm,
+    /// Keys go straight to the program running in a `:terminal` buffer,
+    /// until `Ctrl-\ Ctrl-N`.
+    Terminal,
 }
 
 impl Mode {
@@ -79,6 +87,8 @@ impl Mode {
             },
             Self::Replace => "REPLACE",
             Self::Command => "COMMAND",
+            Self::SubstituteConfirm => "CONFIRM",
+            Self::Terminal => "TERMINAL",
         }
     }
 
@@ -90,7 +100,9 @@ impl Mode {
     #[must_use]
     pub const fn cursor_shape(self) -> CursorShape {
         match self {
-            Self::Normal | Self::Visual(_) => CursorShape::SteadyBlock,
+            Self::Normal | Self::Visual(_) | Self::SubstituteConfirm | Self::Terminal => {
+                CursorShape::SteadyBlock
+            }
             Self::Insert | Self::Command => CursorShape::SteadyBar,
             Self::Replace => CursorShape::SteadyUnderline,
         }
@@ -165,6 +177,8 @@ mod tests {
         );
         assert_eq!(Mode::Replace.display_name(), "REPLACE");
         assert_eq!(Mode::Command.display_name(), "COMMAND");
+        assert_eq!(Mode::SubstituteConfirm.display_name(), "CONFIRM");
+        assert_eq!(Mode::Terminal.display_name(), "TERMINAL");
     }
 
     #[test]
@@ -243,6 +257,8 @@ mod tests {
     fn is_input_false_for_command_modes() {
         assert!(!Mode::Normal.is_input());
         assert!(!Mode::Visual(VisualKind::Char).is_input());
+        // Terminal keys go to the program, not into the buffer.
+        assert!(!Mode::Terminal.is_input());
     }
 
     // -- is_visual ----------------------------------------------------------
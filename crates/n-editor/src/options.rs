@@ -13,6 +13,7 @@
 //! | `:set option!`   | Toggle boolean                |
 //! | `:set option?`   | Query current value           |
 //! | `:set option=N`  | Assign numeric value          |
+//! | `:set option=s`  | Assign string value           |
 //! | `:set`           | Show changed options          |
 //! | `:set all`       | Show all options              |
 //!
@@ -27,13 +28,240 @@
 //! | `scrolloff`      | `so`   | integer | 0       |
 //! | `tabstop`        | `ts`   | integer | 4       |
 //! | `shiftwidth`     | `sw`   | integer | 4       |
+//! | `softtabstop`    | `sts`  | integer | 0       |
+//! | `textwidth`      | `tw`   | integer | 0       |
+//! | `timeout`        | `to`   | bool    | true    |
+//! | `timeoutlen`     | `tm`   | integer | 1000    |
+//! | `ttimeout`       |        | bool    | true    |
+//! | `ttimeoutlen`    | `ttm`  | integer | 50      |
+//! | `undolevels`     | `ul`   | integer | 1000    |
+//! | `undobreak`      | `ub`   | bool    | false   |
+//! | `largefile`      |        | integer | 10 MB   |
 //! | `expandtab`      | `et`   | bool    | true    |
+//! | `detectindent`   | `di`   | bool    | true    |
+//! | `smartindent`    | `si`   | bool    | false   |
 //! | `ignorecase`     | `ic`   | bool    | false   |
 //! | `smartcase`      | `scs`  | bool    | false   |
 //! | `hlsearch`       | `hls`  | bool    | true    |
 //! | `incsearch`      | `is`   | bool    | true    |
+//! | `inccommand`     | `icm`  | string  | (empty) |
 //! | `wrapscan`       | `ws`   | bool    | true    |
 //! | `cursorline`     | `cul`  | bool    | false   |
+//! | `colorcolumn`    | `cc`   | string  | (empty) |
+//! | `ruler`          | `ru`   | bool    | true    |
+//! | `rulerformat`    | `ruf`  | string  | char    |
+//! | `foldmethod`     | `fdm`  | string  | manual  |
+//! | `conceallevel`   | `cole` | integer | 0       |
+//! | `fileformat`     | `ff`   | string  | unix    |
+//! | `buftype`        | `bt`   | string  | (empty) |
+//! | `buflisted`      | `bl`   | bool    | true    |
+//! | `modifiable`     | `ma`   | bool    | true    |
+//! | `readonly`       | `ro`   | bool    | false   |
+//! | `backup`         | `bk`   | bool    | false   |
+//! | `backupcopy`     | `bkc`  | string  | no      |
+//! | `backupext`      | `bex`  | string  | ~       |
+//! | `backupdir`      | `bdir` | string  | .       |
+//! | `autoread`       | `ar`   | bool    | true    |
+//! | `spell`          |        | bool    | false   |
+//! | `spelllang`      | `spl`  | string  | `en_us` |
+//! | `spellfile`      | `spf`  | string  | (config)|
+//! | `keywordprg`     | `kp`   | string  | man     |
+//! | `equalprg`       | `ep`   | string  | (empty) |
+//! | `formatprg`      | `fp`   | string  | (empty) |
+//! | `grepprg`        | `gp`   | string  | (below) |
+//! | `grepformat`     | `gfm`  | string  | (below) |
+//! | `tags`           | `tag`  | string  | `./tags,tags` |
+//! | `sectionpattern` |        | string  | (below) |
+//! | `lspservers`     | `lsp`  | string  | (empty) |
+//!
+//! A backslash before a space keeps it in the value: `:set kp=man\ -s`.
+//!
+//! `equalprg` and `formatprg` are filters for `=` and `gq`: the lines go to
+//! the program's stdin and its stdout replaces them. For Rust,
+//! `:set ep=rustfmt\ --edition\ 2021` is a good choice.
+//! With `formatprg` empty, `gq` reflows the lines itself to `textwidth`
+//! (79 columns while it is 0); see [`crate::format`].
+//!
+//! `grepprg` is the program `:grep` runs, with `$*` replaced by the
+//! arguments, and `grepformat` says how to read its output (see
+//! [`crate::quickfix`]). The defaults suit `grep`; the format reads
+//! `rg --vimgrep` and `ag --vimgrep` output as well, so switching is just
+//! `:set gp=rg\ --vimgrep`.
+//!
+//! `tags` lists the tags files `Ctrl+]` and `:tag` read (see
+//! [`crate::tags`]).
+//!
+//! `timeoutlen` is how many milliseconds the editor waits, after a key
+//! that starts a longer mapping, for the rest of it (see
+//! [`crate::keymap`]); `notimeout` waits forever. `ttimeoutlen` is the
+//! same for the terminal's escape sequences: how long a lone `Esc` waits
+//! before it counts as the Escape key.
+//!
+//! `undolevels` is how many changes each buffer can undo; beyond that the
+//! oldest are forgotten. `0` or a negative value keeps no undo history.
+//! With `undobreak`, each line typed in insert mode is its own undo step:
+//! Enter ends one and starts the next, so `u` takes back a line at a time.
+//!
+//! `largefile` is the size in bytes above which a file opens in large file
+//! mode, without undo or syntax highlighting ([`LARGE_FILE_THRESHOLD`] by
+//! default). It applies to files opened after it is set.
+//!
+//! New lines (`o`, `O`, Enter in insert mode) always copy the indent of
+//! the line they were opened from. `smartindent` adds a level after a
+//! line ending in `{`, `(` or `:`, for C-like code and Python.
+//!
+//! `buftype` is per buffer: `nofile` makes a scratch buffer that is never
+//! written or reported as modified, `nowrite` one that keeps its name but
+//! isn't written (see [`crate::buffer::BufType`]). `buflisted` is per
+//! buffer too: `nobuflisted` leaves the buffer out of `:ls` (`:ls!` still
+//! shows it). `modifiable` and `readonly` are per buffer as well:
+//! `nomodifiable` refuses every change to the text, while `readonly` lets
+//! the text change but makes `:w` need a `!`.
+//!
+//! `tabstop`, `shiftwidth` and `expandtab` are per buffer with a global
+//! default: `:set` changes both the current buffer and the default that
+//! buffers without their own value follow, `:setlocal` only the current
+//! buffer. For every other option `:setlocal` is the same as `:set`.
+//!
+//! `ruler` shows the cursor's `{line}:{col}` at the right of the status
+//! line. `rulerformat` picks what the column counts ([`RulerFormat`]):
+//! `byte`, `char`, or `virtual` (screen columns, with a tab reaching the
+//! next tab stop).
+//!
+//! `foldmethod` picks how folds are made ([`FoldMethod`]): `manual`
+//! makes none, `indent` folds every block of lines indented deeper than
+//! the line before it (see [`crate::fold`]). Indent folds start closed and
+//! follow the text as it changes.
+//!
+//! `conceallevel` hides markup off the cursor line (see
+//! [`crate::conceal`]): at 1 each concealed run is drawn as one
+//! replacement character, at 2 it isn't drawn at all. 0 shows the text
+//! as it is.
+//!
+//! `detectindent` indents each file the way it is already indented: when
+//! a file is read, its first lines are sampled for tabs or spaces and the
+//! indent width (see [`crate::buffer::Buffer::detect_indent`]). `>>`, `<<`,
+//! `Tab` and indent folds then follow that instead of `shiftwidth` and
+//! `expandtab`; setting either of those for the buffer overrides it again.
+//! `:set detectindent` re-detects the current buffer.
+//!
+//! With `expandtab`, `Tab` in insert mode inserts spaces up to the next
+//! multiple of `softtabstop` columns (of `tabstop` while `softtabstop` is
+//! 0), and `Backspace` over such spaces deletes back to the previous
+//! multiple of `softtabstop` — one space at a time while it's 0.
+//!
+//! `colorcolumn` is a comma-separated list of screen columns to highlight,
+//! e.g. `:set cc=80,100,120`. An entry starting with `+` or `-` is
+//! relative to `textwidth` — `:set tw=79 cc=+1` marks column 80 — and is
+//! left out while `textwidth` is 0. See [`parse_colorcolumn`].
+//!
+//! `inccommand=nosplit` previews a `:s` in the buffer while it is typed.
+//!
+//! `lspservers` is a comma-separated list of `language:command` pairs
+//! naming the language server to start for each LSP language ID, e.g.
+//! `:set lspservers=rust:rust-analyzer,python:pylsp`.
+
+/// Byte size above which a file is opened in large file mode (10 MB).
+///
+/// Large files skip undo history and syntax highlighting — both cost time
+/// and memory proportional to the file, which makes opening them sluggish.
+pub const LARGE_FILE_THRESHOLD: u64 = 10 * 1024 * 1024;
+
+/// Default `sectionpattern`: the lines `[[` / `]]` stop at.
+///
+/// Vim's rule is a `{` in the first column, which suits C written in K&R
+/// style. Most Rust and Go code opens the brace at the end of the line, so
+/// top-level `fn` / `struct` / `impl` (and friends) count as well.
+pub const DEFAULT_SECTION_PATTERN: &str =
+    r"^(\{|(pub(\(\S+\))?\s+)?((async|const|unsafe)\s+)*(fn|struct|enum|union|trait|impl|mod|type|func)\b)";
+
+/// Default `grepprg`. `/dev/null` makes grep print the file name even when
+/// given just one file.
+pub const DEFAULT_GREPPRG: &str = "grep -n $* /dev/null";
+
+/// Default `grepformat`: `file:line:col:text` (ripgrep, ag) or
+/// `file:line:text` (grep).
+pub const DEFAULT_GREPFORMAT: &str = "%f:%l:%c:%m,%f:%l:%m";
+
+/// Default `tags`: `tags` next to the current file, then in the working
+/// directory (each also searched for in parent directories).
+pub const DEFAULT_TAGS: &str = "./tags,tags";
+
+/// Default `timeoutlen`, in milliseconds.
+pub const DEFAULT_TIMEOUTLEN: u64 = 1000;
+
+/// Default `ttimeoutlen`, in milliseconds.
+pub const DEFAULT_TTIMEOUTLEN: u64 = 50;
+
+/// Default `undolevels`.
+pub const DEFAULT_UNDOLEVELS: i64 = 1000;
+
+/// What the ruler's column counts (`rulerformat`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RulerFormat {
+    /// Bytes from the start of the line (`col()`).
+    Byte,
+    /// Characters from the start of the line.
+    #[default]
+    Char,
+    /// Screen columns (`virtcol()`): a tab counts up to the next tab stop
+    /// and a wide character as two.
+    Virtual,
+}
+
+impl RulerFormat {
+    /// The format for an option value, or `None` if it isn't one.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "byte" => Some(Self::Byte),
+            "char" => Some(Self::Char),
+            "virtual" => Some(Self::Virtual),
+            _ => None,
+        }
+    }
+
+    /// The option value for the format.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Byte => "byte",
+            Self::Char => "char",
+            Self::Virtual => "virtual",
+        }
+    }
+}
+
+/// How folds are made (`foldmethod`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FoldMethod {
+    /// No automatic folds.
+    #[default]
+    Manual,
+    /// One fold per indented block, nested by indent level.
+    Indent,
+}
+
+impl FoldMethod {
+    /// The method for an option value, or `None` if it isn't one.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "manual" => Some(Self::Manual),
+            "indent" => Some(Self::Indent),
+            _ => None,
+        }
+    }
+
+    /// The option value for the method.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Manual => "manual",
+            Self::Indent => "indent",
+        }
+    }
+}
 
 /// A parsed `:set` directive.
 ///
@@ -86,6 +314,28 @@ pub fn is_bool_option(name: &str) -> bool {
             | "ws"
             | "cursorline"
             | "cul"
+            | "ruler"
+            | "ru"
+            | "backup"
+            | "bk"
+            | "autoread"
+            | "ar"
+            | "spell"
+            | "timeout"
+            | "to"
+            | "ttimeout"
+            | "buflisted"
+            | "bl"
+            | "modifiable"
+            | "ma"
+            | "readonly"
+            | "ro"
+            | "undobreak"
+            | "detectindent"
+            | "di"
+            | "smartindent"
+            | "si"
+            | "ub"
     )
 }
 
@@ -94,14 +344,77 @@ pub fn is_bool_option(name: &str) -> bool {
 pub fn is_numeric_option(name: &str) -> bool {
     matches!(
         name,
-        "scrolloff" | "so" | "tabstop" | "ts" | "shiftwidth" | "sw"
+        "scrolloff"
+            | "so"
+            | "tabstop"
+            | "ts"
+            | "shiftwidth"
+            | "sw"
+            | "softtabstop"
+            | "sts"
+            | "textwidth"
+            | "tw"
+            | "timeoutlen"
+            | "tm"
+            | "ttimeoutlen"
+            | "ttm"
+            | "undolevels"
+            | "ul"
+            | "largefile"
+            | "conceallevel"
+            | "cole"
+    )
+}
+
+/// Returns `true` if `name` is a known string option (full name or abbreviation).
+#[must_use]
+pub fn is_string_option(name: &str) -> bool {
+    matches!(
+        name,
+        "fileformat"
+            | "ff"
+            | "buftype"
+            | "bt"
+            | "backupcopy"
+            | "bkc"
+            | "backupext"
+            | "bex"
+            | "backupdir"
+            | "bdir"
+            | "spelllang"
+            | "spl"
+            | "spellfile"
+            | "spf"
+            | "inccommand"
+            | "icm"
+            | "rulerformat"
+            | "ruf"
+            | "foldmethod"
+            | "fdm"
+            | "keywordprg"
+            | "kp"
+            | "equalprg"
+            | "ep"
+            | "formatprg"
+            | "fp"
+            | "grepprg"
+            | "gp"
+            | "grepformat"
+            | "gfm"
+            | "tags"
+            | "tag"
+            | "sectionpattern"
+            | "lspservers"
+            | "lsp"
+            | "colorcolumn"
+            | "cc"
     )
 }
 
-/// Returns `true` if `name` is any known option (boolean or numeric).
+/// Returns `true` if `name` is any known option (boolean, numeric, or string).
 #[must_use]
 pub fn is_known_option(name: &str) -> bool {
-    is_bool_option(name) || is_numeric_option(name)
+    is_bool_option(name) || is_numeric_option(name) || is_string_option(name)
 }
 
 /// Parse the full `:set` arguments string into directives.
@@ -114,7 +427,91 @@ pub fn parse_set(args: &str) -> Vec<SetDirective> {
     if trimmed.is_empty() {
         return vec![SetDirective::ShowChanged];
     }
-    trimmed.split_whitespace().map(parse_set_arg).collect()
+    split_set_args(trimmed).iter().map(|arg| parse_set_arg(arg)).collect()
+}
+
+/// Split `:set` arguments on whitespace, except where a backslash escapes
+/// it (`\ `). The backslash is dropped; other backslashes are kept.
+fn split_set_args(args: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut cur = String::new();
+    let mut chars = args.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if chars.peek().is_some_and(|c| c.is_whitespace()) => {
+                cur.extend(chars.next());
+            }
+            c if c.is_whitespace() => {
+                if !cur.is_empty() {
+                    out.push(std::mem::take(&mut cur));
+                }
+            }
+            c => cur.push(c),
+        }
+    }
+    if !cur.is_empty() {
+        out.push(cur);
+    }
+    out
+}
+
+/// The language server command configured for LSP language ID `language`
+/// in an `lspservers` value.
+#[must_use]
+pub fn lsp_server_for<'a>(servers: &'a str, language: &str) -> Option<&'a str> {
+    servers
+        .split(',')
+        .filter_map(|entry| entry.split_once(':'))
+        .find(|(lang, _)| lang.trim() == language)
+        .map(|(_, cmd)| cmd.trim())
+        .filter(|cmd| !cmd.is_empty())
+}
+
+/// Whether `value` is a well-formed `lspservers` value: empty, or
+/// `language:command` pairs separated by commas.
+#[must_use]
+pub fn is_valid_lsp_servers(value: &str) -> bool {
+    value.is_empty()
+        || value.split(',').all(|entry| {
+            entry
+                .split_once(':')
+                .is_some_and(|(lang, cmd)| !lang.trim().is_empty() && !cmd.trim().is_empty())
+        })
+}
+
+/// Whether `value` is a well-formed `colorcolumn` value: empty, or
+/// comma-separated column numbers, each optionally prefixed with `+` or
+/// `-`.
+#[must_use]
+pub fn is_valid_colorcolumn(value: &str) -> bool {
+    value.is_empty()
+        || value.split(',').all(|entry| {
+            let digits = entry.strip_prefix(['+', '-']).unwrap_or(entry);
+            !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+        })
+}
+
+/// The 1-based screen columns a `colorcolumn` value marks, in the order
+/// given and without repeats.
+///
+/// `+N` and `-N` are `textwidth + N` and `textwidth - N`; with a
+/// `textwidth` of 0 they mark nothing. Entries that aren't numbers, or
+/// that come to column 0 or before, are skipped.
+#[must_use]
+pub fn parse_colorcolumn(s: &str, textwidth: usize) -> Vec<usize> {
+    let mut columns = Vec::new();
+    for entry in s.split(',') {
+        let relative = |n: &str| n.parse::<usize>().ok().filter(|_| textwidth > 0);
+        let col = match entry.as_bytes().first() {
+            Some(b'+') => relative(&entry[1..]).map(|n| textwidth + n),
+            Some(b'-') => relative(&entry[1..]).and_then(|n| textwidth.checked_sub(n)),
+            _ => entry.parse().ok(),
+        };
+        if let Some(col) = col.filter(|&c| c > 0 && !columns.contains(&c)) {
+            columns.push(col);
+        }
+    }
+    columns
 }
 
 /// Parse a single `:set` argument into a directive.
@@ -150,8 +547,8 @@ pub fn parse_set_arg(arg: &str) -> SetDirective {
         }
     }
 
-    // Bare numeric option name = query its value (Vim behavior).
-    if is_numeric_option(arg) {
+    // Bare numeric or string option name = query its value (Vim behavior).
+    if is_numeric_option(arg) || is_string_option(arg) {
         return SetDirective::Query(arg.to_string());
     }
 
@@ -266,6 +663,19 @@ mod tests {
         assert_eq!(parse_set_arg("sw"), SetDirective::Query("sw".into()));
     }
 
+    #[test]
+    fn parse_string_bare_is_query() {
+        assert_eq!(
+            parse_set_arg("fileformat"),
+            SetDirective::Query("fileformat".into())
+        );
+        assert_eq!(parse_set_arg("ff"), SetDirective::Query("ff".into()));
+        assert_eq!(
+            parse_set_arg("ff=dos"),
+            SetDirective::Assign("ff".into(), "dos".into())
+        );
+    }
+
     #[test]
     fn parse_number_not_confused_with_no_prefix() {
         // "number" starts with "no" but "mber" isn't a known option.
@@ -305,6 +715,23 @@ mod tests {
         assert_eq!(result[2], SetDirective::Off("hlsearch".into()));
     }
 
+    #[test]
+    fn parse_escaped_space() {
+        let result = parse_set(r"kp=man\ -s nu");
+        assert_eq!(
+            result,
+            vec![
+                SetDirective::Assign("kp".into(), "man -s".into()),
+                SetDirective::On("nu".into()),
+            ]
+        );
+        // Other backslashes are kept.
+        assert_eq!(
+            parse_set(r"para=\.P"),
+            vec![SetDirective::Assign("para".into(), r"\.P".into())]
+        );
+    }
+
     // ── Abbreviations ────────────────────────────────────────────────────
 
     #[test]
@@ -327,6 +754,176 @@ mod tests {
         assert!(is_numeric_option("sw"));
     }
 
+    #[test]
+    fn abbreviations_string() {
+        assert!(is_string_option("fileformat"));
+        assert!(is_string_option("ff"));
+        assert!(is_string_option("bkc"));
+        assert!(is_string_option("bex"));
+        assert!(is_string_option("bdir"));
+        assert!(is_bool_option("bk"));
+        assert!(is_bool_option("ar"));
+        assert!(is_known_option("ff"));
+        assert!(!is_bool_option("ff"));
+    }
+
+    #[test]
+    fn spell_options() {
+        assert!(is_bool_option("spell"));
+        assert!(is_string_option("spl"));
+        assert!(is_string_option("spellfile"));
+        assert!(is_string_option("spf"));
+    }
+
+    #[test]
+    fn keywordprg_option() {
+        assert!(is_string_option("keywordprg"));
+        assert!(is_string_option("kp"));
+        assert!(!is_bool_option("kp"));
+    }
+
+    #[test]
+    fn filter_program_options() {
+        for name in ["equalprg", "ep", "formatprg", "fp"] {
+            assert!(is_string_option(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn grep_options() {
+        for name in ["grepprg", "gp", "grepformat", "gfm"] {
+            assert!(is_string_option(name), "{name}");
+        }
+        assert!(DEFAULT_GREPPRG.contains("$*"));
+    }
+
+    #[test]
+    fn timeout_options() {
+        for name in ["timeout", "to", "ttimeout"] {
+            assert!(is_bool_option(name), "{name}");
+        }
+        for name in ["timeoutlen", "tm", "ttimeoutlen", "ttm"] {
+            assert!(is_numeric_option(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn modifiable_and_readonly_options() {
+        for name in ["modifiable", "ma", "readonly", "ro"] {
+            assert!(is_bool_option(name), "{name}");
+        }
+        assert_eq!(parse_set_arg("noma"), SetDirective::Off("ma".into()));
+    }
+
+    #[test]
+    fn undolevels_option() {
+        assert!(is_numeric_option("undolevels"));
+        assert!(is_numeric_option("ul"));
+        assert!(is_numeric_option("largefile"));
+        assert_eq!(parse_set_arg("ul=-1"), SetDirective::Assign("ul".into(), "-1".into()));
+        assert!(is_bool_option("undobreak"));
+        assert!(is_bool_option("ub"));
+        assert!(is_bool_option("smartindent"));
+        assert!(is_bool_option("si"));
+    }
+
+    #[test]
+    fn detectindent_option() {
+        assert!(is_bool_option("detectindent"));
+        assert!(is_bool_option("di"));
+    }
+
+    #[test]
+    fn conceallevel_option() {
+        assert!(is_numeric_option("conceallevel"));
+        assert!(is_numeric_option("cole"));
+    }
+
+    #[test]
+    fn tags_option() {
+        assert!(is_string_option("tags"));
+        assert!(is_string_option("tag"));
+    }
+
+    #[test]
+    fn inccommand_option() {
+        assert!(is_string_option("inccommand"));
+        assert!(is_string_option("icm"));
+    }
+
+    #[test]
+    fn buftype_option() {
+        assert!(is_string_option("buftype"));
+        assert!(is_string_option("bt"));
+        assert!(is_bool_option("buflisted"));
+        assert!(is_bool_option("bl"));
+    }
+
+    #[test]
+    fn ruler_options() {
+        assert!(is_bool_option("ruler"));
+        assert!(is_bool_option("ru"));
+        assert!(is_string_option("rulerformat"));
+        assert!(is_string_option("ruf"));
+        for format in [RulerFormat::Byte, RulerFormat::Char, RulerFormat::Virtual] {
+            assert_eq!(RulerFormat::from_name(format.name()), Some(format));
+        }
+        assert_eq!(RulerFormat::from_name("screen"), None);
+    }
+
+    #[test]
+    fn foldmethod_option() {
+        assert!(is_string_option("foldmethod"));
+        assert!(is_string_option("fdm"));
+        for method in [FoldMethod::Manual, FoldMethod::Indent] {
+            assert_eq!(FoldMethod::from_name(method.name()), Some(method));
+        }
+        assert_eq!(FoldMethod::from_name("syntax"), None);
+    }
+
+    #[test]
+    fn section_pattern_option() {
+        assert!(is_string_option("sectionpattern"));
+        assert!(is_known_option("sectionpattern"));
+    }
+
+    #[test]
+    fn lsp_servers_option() {
+        assert!(is_string_option("lspservers"));
+        assert!(is_string_option("lsp"));
+        let servers = "rust:rust-analyzer,typescript:typescript-language-server --stdio";
+        assert_eq!(lsp_server_for(servers, "rust"), Some("rust-analyzer"));
+        assert_eq!(
+            lsp_server_for(servers, "typescript"),
+            Some("typescript-language-server --stdio")
+        );
+        assert_eq!(lsp_server_for(servers, "go"), None);
+        assert_eq!(lsp_server_for("", "rust"), None);
+        assert!(is_valid_lsp_servers(""));
+        assert!(is_valid_lsp_servers(servers));
+        assert!(!is_valid_lsp_servers("rust"));
+        assert!(!is_valid_lsp_servers("rust:ra,"));
+    }
+
+    #[test]
+    fn colorcolumn_option() {
+        assert!(is_string_option("colorcolumn"));
+        assert!(is_string_option("cc"));
+        assert!(is_numeric_option("tw"));
+        assert_eq!(parse_colorcolumn("80,+10,-5", 79), vec![80, 89, 74]);
+        assert_eq!(parse_colorcolumn("80,100,120", 0), vec![80, 100, 120]);
+        // Relative entries need a textwidth, and a column past the left
+        // edge or repeated is dropped.
+        assert_eq!(parse_colorcolumn("+1,40", 0), vec![40]);
+        assert_eq!(parse_colorcolumn("-20,0,+0,10", 10), vec![10]);
+        assert!(parse_colorcolumn("", 79).is_empty());
+        assert!(is_valid_colorcolumn(""));
+        assert!(is_valid_colorcolumn("80,+1,-2"));
+        assert!(!is_valid_colorcolumn("80,"));
+        assert!(!is_valid_colorcolumn("+"));
+        assert!(!is_valid_colorcolumn("eighty"));
+    }
+
     #[test]
     fn unknown_is_not_option() {
         assert!(!is_known_option("foobar"));
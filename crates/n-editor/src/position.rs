@@ -10,6 +10,8 @@
 
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
+
 // ---------------------------------------------------------------------------
 // Position
 // ---------------------------------------------------------------------------
@@ -19,12 +21,14 @@ use std::fmt;
 /// `col` is the char offset from the start of the line, **not** a byte offset.
 /// For the line `"café\n"`, column 3 is `'é'` and column 4 is past the last
 /// visible character (the cursor-after-last-char position used in insert mode).
+/// A grapheme cluster of several chars (`"e\u{301}"`, an emoji ZWJ sequence)
+/// spans several columns; cursor motion only lands on the first of them.
 ///
 /// # Ordering
 ///
 /// Positions are ordered lexicographically: line first, then column. This means
 /// `Position { line: 0, col: 5 }` < `Position { line: 1, col: 0 }`.
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Position {
     pub line: usize,
     pub col: usize,
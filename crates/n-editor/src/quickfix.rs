@@ -0,0 +1,487 @@
+//! The quickfix list — locations collected by `:vimgrep` and `:grep` to
+//! step through.
+//!
+//! A [`QuickfixList`] is a list of [`QuickfixEntry`] locations (file, line,
+//! column and the line's text) plus the index of the selected one. The
+//! editor moves through it with:
+//!
+//! | Command               | Action                                  |
+//! |-----------------------|-----------------------------------------|
+//! | `:cn` / `:cnext`      | Next entry                              |
+//! | `:cp` / `:cprevious`  | Previous entry (also `:cN`)             |
+//! | `:cc [n]`             | Entry `n` (1-based), or the current one |
+//! | `:cfirst` / `:clast`  | First / last entry (`:cr` = `:cfirst`)  |
+//! | `:cl` / `:clist`      | List all entries                        |
+//!
+//! [`expand_glob`] turns the file arguments of `:vimgrep` into paths, and
+//! [`parse_with_format`] reads the output of `:grep`'s external program
+//! using `grepformat`:
+//!
+//! | Item | Matches                                   |
+//! |------|-------------------------------------------|
+//! | `%f` | File name (as short as the rest allows)   |
+//! | `%l` | Line number                               |
+//! | `%c` | Column number                             |
+//! | `%m` | Message — the rest of the line            |
+//! | `%%` | A literal `%`                             |
+//!
+//! Anything else matches itself. A comma separates alternative formats,
+//! tried in order (`\,` is a literal comma).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// One location in the quickfix list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickfixEntry {
+    /// The file the location is in.
+    pub file: PathBuf,
+    /// 0-indexed line.
+    pub line: usize,
+    /// 0-indexed column, in chars.
+    pub col: usize,
+    /// The text shown for the entry (for `:vimgrep`, the matching line).
+    pub text: String,
+}
+
+/// The quickfix list and its selected entry.
+#[derive(Debug, Default)]
+pub struct QuickfixList {
+    entries: Vec<QuickfixEntry>,
+    /// Index of the selected entry (0 when the list is empty).
+    current: usize,
+}
+
+impl QuickfixList {
+    /// An empty list.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { entries: Vec::new(), current: 0 }
+    }
+
+    /// Replace the list with `entries` and select the first.
+    pub fn set(&mut self, entries: Vec<QuickfixEntry>) {
+        self.entries = entries;
+        self.current = 0;
+    }
+
+    /// Append `entries`, keeping the selection.
+    pub fn extend(&mut self, entries: Vec<QuickfixEntry>) {
+        self.entries.extend(entries);
+    }
+
+    /// All entries, in order.
+    #[must_use]
+    pub fn entries(&self) -> &[QuickfixEntry] {
+        &self.entries
+    }
+
+    /// Number of entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// True if there are no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Index of the selected entry.
+    #[must_use]
+    pub const fn index(&self) -> usize {
+        self.current
+    }
+
+    /// The selected entry.
+    #[must_use]
+    pub fn current(&self) -> Option<&QuickfixEntry> {
+        self.entries.get(self.current)
+    }
+
+    /// Select entry `index` (0-based), clamped to the list. Returns the
+    /// selected entry, or `None` if the list is empty.
+    pub fn select(&mut self, index: usize) -> Option<&QuickfixEntry> {
+        self.current = index.min(self.entries.len().saturating_sub(1));
+        self.current()
+    }
+
+    /// Move `count` entries forward. Returns `None` (and stays put) when
+    /// already at the last entry; stops at the last entry otherwise.
+    pub fn next(&mut self, count: usize) -> Option<&QuickfixEntry> {
+        if self.current + 1 >= self.entries.len() {
+            return None;
+        }
+        self.select(self.current.saturating_add(count))
+    }
+
+    /// Move `count` entries back. Returns `None` (and stays put) when
+    /// already at the first entry; stops at the first entry otherwise.
+    pub fn prev(&mut self, count: usize) -> Option<&QuickfixEntry> {
+        if self.current == 0 || self.entries.is_empty() {
+            return None;
+        }
+        self.select(self.current.saturating_sub(count))
+    }
+
+    /// The `:clist` listing: one ` n file:line col c: text` row per entry,
+    /// with the selected entry marked by `>`.
+    #[must_use]
+    pub fn listing(&self) -> String {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let mark = if i == self.current { '>' } else { ' ' };
+                format!(
+                    "{mark}{:>2} {}:{} col {}: {}",
+                    i + 1,
+                    e.file.display(),
+                    e.line + 1,
+                    e.col + 1,
+                    e.text.trim()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Error formats
+// ---------------------------------------------------------------------------
+
+/// One piece of a format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FormatItem {
+    File,
+    Line,
+    Col,
+    Message,
+    Literal(char),
+}
+
+/// Parse one line of program output with `formats` (comma-separated, see
+/// the module docs). Returns `None` if no format matches or the match has
+/// no file name or line number.
+#[must_use]
+pub fn parse_with_format(formats: &str, line: &str) -> Option<QuickfixEntry> {
+    let text: Vec<char> = line.chars().collect();
+    split_formats(formats).into_iter().find_map(|items| {
+        let mut found = Found::default();
+        if !match_items(&items, &text, &mut found) {
+            return None;
+        }
+        Some(QuickfixEntry {
+            file: PathBuf::from(found.file?),
+            line: found.line?.checked_sub(1)?,
+            col: found.col.map_or(0, |c| c.saturating_sub(1)),
+            text: found.message.unwrap_or_default(),
+        })
+    })
+}
+
+/// What a format captured.
+#[derive(Debug, Default)]
+struct Found {
+    file: Option<String>,
+    line: Option<usize>,
+    col: Option<usize>,
+    message: Option<String>,
+}
+
+/// Split `formats` at unescaped commas and parse each into items.
+fn split_formats(formats: &str) -> Vec<Vec<FormatItem>> {
+    let mut all = vec![Vec::new()];
+    let mut chars = formats.chars();
+    while let Some(ch) = chars.next() {
+        let item = match ch {
+            ',' => {
+                all.push(Vec::new());
+                continue;
+            }
+            '\\' => FormatItem::Literal(chars.next().unwrap_or('\\')),
+            '%' => match chars.next() {
+                Some('f') => FormatItem::File,
+                Some('l') => FormatItem::Line,
+                Some('c') => FormatItem::Col,
+                Some('m') => FormatItem::Message,
+                Some(other) => FormatItem::Literal(other),
+                None => FormatItem::Literal('%'),
+            },
+            other => FormatItem::Literal(other),
+        };
+        all.last_mut().expect("starts non-empty").push(item);
+    }
+    all.retain(|items| !items.is_empty());
+    all
+}
+
+/// Match `items` against all of `text`, filling in `found`.
+fn match_items(items: &[FormatItem], text: &[char], found: &mut Found) -> bool {
+    let Some((&item, rest)) = items.split_first() else {
+        return text.is_empty();
+    };
+    match item {
+        FormatItem::Literal(c) => text.first() == Some(&c) && match_items(rest, &text[1..], found),
+        FormatItem::Line | FormatItem::Col => {
+            let digits = text.iter().take_while(|c| c.is_ascii_digit()).count();
+            let Ok(n) = text[..digits].iter().collect::<String>().parse::<usize>() else {
+                return false;
+            };
+            if item == FormatItem::Line {
+                found.line = Some(n);
+            } else {
+                found.col = Some(n);
+            }
+            match_items(rest, &text[digits..], found)
+        }
+        FormatItem::Message => {
+            found.message = Some(text.iter().collect());
+            match_items(rest, &[], found)
+        }
+        FormatItem::File => (1..=text.len()).any(|end| {
+            found.file = Some(text[..end].iter().collect());
+            match_items(rest, &text[end..], found)
+        }),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Glob expansion
+// ---------------------------------------------------------------------------
+
+/// Expand a file pattern into the files it names, sorted.
+///
+/// `*` matches any run of characters within a path component, `?` any one
+/// character, `[abc]` / `[a-z]` one from a set, and a `**` component any
+/// number of directories (including none). Wildcards don't match a leading
+/// `.` unless the pattern component starts with one. A pattern without
+/// wildcards is returned as is, whether or not the file exists.
+#[must_use]
+pub fn expand_glob(pattern: &str) -> Vec<PathBuf> {
+    if !pattern.contains(['*', '?', '[']) {
+        return vec![PathBuf::from(pattern)];
+    }
+    let (root, rest) = pattern
+        .strip_prefix('/')
+        .map_or((PathBuf::new(), pattern), |rest| (PathBuf::from("/"), rest));
+    let components: Vec<&str> = rest.split('/').filter(|c| !c.is_empty() && *c != ".").collect();
+    let mut found = Vec::new();
+    expand_from(&root, &components, &mut found);
+    found.sort();
+    found.dedup();
+    found
+}
+
+/// Match `components` against the tree under `dir`, collecting files.
+fn expand_from(dir: &Path, components: &[&str], found: &mut Vec<PathBuf>) {
+    let Some((&first, rest)) = components.split_first() else {
+        if dir.is_file() {
+            found.push(dir.to_path_buf());
+        }
+        return;
+    };
+    if first == "**" {
+        // Zero directories, then one more level down with `**` still in
+        // play.
+        expand_from(dir, rest, found);
+        for child in read_dir_sorted(dir) {
+            if child.is_dir() && !is_hidden(&child) {
+                expand_from(&child, components, found);
+            }
+        }
+        return;
+    }
+    if !first.contains(['*', '?', '[']) {
+        expand_from(&dir.join(first), rest, found);
+        return;
+    }
+    for child in read_dir_sorted(dir) {
+        let Some(name) = child.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if (first.starts_with('.') || !name.starts_with('.')) && glob_match(first, name) {
+            expand_from(&child, rest, found);
+        }
+    }
+}
+
+/// Entries of `dir` (the working directory when empty), sorted. Paths keep
+/// the form of `dir`, so `src/*.rs` yields `src/main.rs`, not
+/// `./src/main.rs`.
+fn read_dir_sorted(dir: &Path) -> Vec<PathBuf> {
+    let listed = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+    let mut children: Vec<PathBuf> = fs::read_dir(listed)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| dir.join(entry.file_name()))
+        .collect();
+    children.sort();
+    children
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with('.'))
+}
+
+/// Match one path component against a pattern with `*`, `?` and `[…]`.
+#[must_use]
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pat: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = name.chars().collect();
+    // Backtracking matcher: remember the last `*` and retry it one char
+    // further on mismatch.
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        let step = match pat.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+                continue;
+            }
+            Some('?') => Some(1),
+            Some('[') => class_match(&pat[p..], text[t]),
+            Some(&c) if c == text[t] => Some(1),
+            _ => None,
+        };
+        if let Some(len) = step {
+            p += len;
+            t += 1;
+        } else if let Some((sp, st)) = star {
+            p = sp + 1;
+            t = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    pat[p..].iter().all(|&c| c == '*')
+}
+
+/// Match `ch` against the class at the start of `pat` (`[abc]`, `[a-z]`,
+/// `[!x]`). Returns the class's length in pattern chars on a match. An
+/// unclosed `[` matches itself.
+fn class_match(pat: &[char], ch: char) -> Option<usize> {
+    let Some(close) = pat.iter().skip(2).position(|&c| c == ']').map(|i| i + 2) else {
+        return (ch == '[').then_some(1);
+    };
+    let (negate, body) = match pat[1] {
+        '!' | '^' => (true, &pat[2..close]),
+        _ => (false, &pat[1..close]),
+    };
+    let mut hit = false;
+    let mut i = 0;
+    while i < body.len() {
+        if i + 2 < body.len() && body[i + 1] == '-' {
+            hit |= (body[i]..=body[i + 2]).contains(&ch);
+            i += 3;
+        } else {
+            hit |= body[i] == ch;
+            i += 1;
+        }
+    }
+    (hit != negate).then_some(close + 1)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(line: usize) -> QuickfixEntry {
+        QuickfixEntry { file: PathBuf::from("a.rs"), line, col: 0, text: format!("line {line}") }
+    }
+
+    #[test]
+    fn navigation_stops_at_ends() {
+        let mut qf = QuickfixList::new();
+        assert!(qf.next(1).is_none());
+        assert!(qf.current().is_none());
+        qf.set((0..4).map(entry).collect());
+        assert_eq!(qf.index(), 0);
+        assert!(qf.prev(1).is_none());
+        assert_eq!(qf.next(2).map(|e| e.line), Some(2));
+        assert_eq!(qf.next(5).map(|e| e.line), Some(3));
+        assert!(qf.next(1).is_none());
+        assert_eq!(qf.prev(1).map(|e| e.line), Some(2));
+        assert_eq!(qf.select(99).map(|e| e.line), Some(3));
+    }
+
+    #[test]
+    fn listing_marks_current() {
+        let mut qf = QuickfixList::new();
+        qf.set(vec![entry(0), entry(9)]);
+        qf.next(1);
+        assert_eq!(qf.listing(), "  1 a.rs:1 col 1: line 0\n> 2 a.rs:10 col 1: line 9");
+    }
+
+    #[test]
+    fn extend_keeps_selection() {
+        let mut qf = QuickfixList::new();
+        qf.set(vec![entry(0), entry(1)]);
+        qf.next(1);
+        qf.extend(vec![entry(2)]);
+        assert_eq!(qf.len(), 3);
+        assert_eq!(qf.index(), 1);
+    }
+
+    #[test]
+    fn formats_parse_grep_output() {
+        let both = "%f:%l:%c:%m,%f:%l:%m";
+        let rg = parse_with_format(both, "src/main.rs:12:5:    let x = 1;").unwrap();
+        assert_eq!(rg.file, PathBuf::from("src/main.rs"));
+        assert_eq!((rg.line, rg.col), (11, 4));
+        assert_eq!(rg.text, "    let x = 1;");
+        // `grep -n` has no column; the second format takes it.
+        let grep = parse_with_format(both, "a.txt:3:time: 10:30").unwrap();
+        assert_eq!((grep.line, grep.col), (2, 0));
+        assert_eq!(grep.text, "time: 10:30");
+        assert!(parse_with_format(both, "Binary file x matches").is_none());
+        assert!(parse_with_format("%f:%l:%m", "x:0:line zero").is_none());
+        let odd = parse_with_format("%f(%l\\,%c) %%%m", "f.c(3,4) %warn").unwrap();
+        assert_eq!((odd.line, odd.col, odd.text.as_str()), (2, 3, "warn"));
+    }
+
+    #[test]
+    fn glob_patterns() {
+        assert!(glob_match("*.rs", "main.rs"));
+        assert!(!glob_match("*.rs", "main.rs.bak"));
+        assert!(glob_match("a?c*", "abc"));
+        assert!(glob_match("*a*b", "xxaxxb"));
+        assert!(glob_match("[a-c]x", "bx"));
+        assert!(!glob_match("[!a-c]x", "bx"));
+        assert!(glob_match("[x", "[x"));
+        assert!(!glob_match("?", ""));
+    }
+
+    #[test]
+    fn expand_walks_directories() {
+        let root = std::env::temp_dir().join(format!("n-nvim-glob-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/deep")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        for file in ["a.rs", "b.txt", "src/c.rs", "src/deep/d.rs", ".git/e.rs", ".hidden.rs"] {
+            fs::write(root.join(file), "").unwrap();
+        }
+        let base = root.display();
+        let names = |pattern: String| -> Vec<String> {
+            expand_glob(&pattern)
+                .iter()
+                .map(|p| p.strip_prefix(&root).unwrap().display().to_string())
+                .collect()
+        };
+        assert_eq!(names(format!("{base}/*.rs")), ["a.rs"]);
+        assert_eq!(names(format!("{base}/**/*.rs")), ["a.rs", "src/c.rs", "src/deep/d.rs"]);
+        assert_eq!(names(format!("{base}/src/*/*.rs")), ["src/deep/d.rs"]);
+        assert_eq!(names(format!("{base}/.*.rs")), [".hidden.rs"]);
+        assert_eq!(expand_glob("no/wildcards.txt"), [PathBuf::from("no/wildcards.txt")]);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -16,9 +16,16 @@
 //! - **Named (`"a`–`"z`)**: 26 user-selectable registers. Lowercase
 //!   overwrites, uppercase (`"A`–`"Z`) appends to the corresponding
 //!   lowercase register.
+//! - **Small delete (`"-`)**: The last delete of less than a line, when no
+//!   register was named. Linewise deletes and yanks leave it alone, so a
+//!   deleted word survives a following `dd`.
+//! - **Black hole (`"_`)**: Writing to it stores nothing — not even in the
+//!   unnamed register.
+
+use serde::{Deserialize, Serialize};
 
 /// How the register content was captured — determines paste behavior.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RegisterKind {
     /// Character-wise (from `v` visual or single-char delete).
     /// Paste inserts inline at cursor position.
@@ -137,6 +144,9 @@ pub struct RegisterFile {
     /// On macOS both point to the same system pasteboard. On X11, `*` is
     /// PRIMARY and `+` is CLIPBOARD — we unify them for simplicity.
     clipboard: Register,
+
+    /// Small delete register `"-` — the last charwise delete within a line.
+    small_delete: Register,
 }
 
 impl RegisterFile {
@@ -147,6 +157,7 @@ impl RegisterFile {
             unnamed: Register::new(),
             named: std::array::from_fn(|_| Register::new()),
             clipboard: Register::new(),
+            small_delete: Register::new(),
         }
     }
 
@@ -162,10 +173,12 @@ impl RegisterFile {
     /// - `name == Some('a'..='z')` → overwrite named, copy to unnamed
     /// - `name == Some('A'..='Z')` → append to named, copy result to unnamed
     /// - `name == Some('+')` or `Some('*')` → clipboard + unnamed
+    /// - `name == Some('_')` → nothing (black hole)
     ///
     /// Any other name falls back to unnamed-only.
     pub fn yank(&mut self, name: Option<char>, text: String, kind: RegisterKind) {
         match name {
+            Some('_') => {}
             Some('+' | '*') => {
                 self.clipboard.yank(text.clone(), kind);
                 self.unnamed.yank(text, kind);
@@ -190,6 +203,29 @@ impl RegisterFile {
         }
     }
 
+    /// Store deleted text. Like [`yank`], and a charwise delete that stays
+    /// within one line also goes to the small delete register (`"-`) when
+    /// no register was named.
+    ///
+    /// [`yank`]: RegisterFile::yank
+    pub fn delete(&mut self, name: Option<char>, text: String, kind: RegisterKind) {
+        if name.is_none() && kind == RegisterKind::Char && !text.contains('\n') {
+            self.small_delete.yank(text.clone(), kind);
+        }
+        self.yank(name, text, kind);
+    }
+
+    /// Store text in exactly one register — `None` for unnamed, or
+    /// `a`–`z` — without copying it to the unnamed register. For restoring
+    /// saved registers; other names are ignored.
+    pub fn set(&mut self, name: Option<char>, text: String, kind: RegisterKind) {
+        match name {
+            None => self.unnamed.yank(text, kind),
+            Some(ch @ 'a'..='z') => self.named[(ch as u8 - b'a') as usize].yank(text, kind),
+            Some(_) => {}
+        }
+    }
+
     /// Push text into the clipboard register from an external source.
     ///
     /// Call this before paste operations to synchronize the clipboard
@@ -204,12 +240,14 @@ impl RegisterFile {
     /// - `Some('a'..='z')` → named register
     /// - `Some('A'..='Z')` → same as lowercase (reads are case-insensitive)
     /// - `Some('+')` or `Some('*')` → clipboard register
+    /// - `Some('-')` → small delete register
     ///
     /// Any other name falls back to unnamed.
     #[must_use]
     pub const fn get(&self, name: Option<char>) -> &Register {
         match name {
             Some('+' | '*') => &self.clipboard,
+            Some('-') => &self.small_delete,
             Some(ch) if ch.is_ascii_lowercase() => {
                 &self.named[(ch as u8 - b'a') as usize]
             }
@@ -629,4 +667,40 @@ mod tests {
         // Clipboard unchanged by named register write.
         assert_eq!(rf.get(Some('+')).content(), "clip");
     }
+
+    #[test]
+    fn set_leaves_unnamed_alone() {
+        let mut rf = RegisterFile::new();
+        rf.yank(None, "unnamed".into(), RegisterKind::Char);
+        rf.set(Some('q'), "saved".into(), RegisterKind::Line);
+        assert_eq!(rf.get(Some('q')).content(), "saved");
+        assert_eq!(rf.get(Some('q')).kind(), RegisterKind::Line);
+        assert_eq!(rf.get(None).content(), "unnamed");
+        rf.set(Some('+'), "ignored".into(), RegisterKind::Char);
+        assert!(rf.get(Some('+')).is_empty());
+    }
+
+    // ── Small delete (-) and black hole (_) ─────────────────────────────
+
+    #[test]
+    fn small_delete_keeps_charwise_deletes_within_a_line() {
+        let mut rf = RegisterFile::new();
+        rf.delete(None, "w".into(), RegisterKind::Char);
+        rf.delete(None, "line\n".into(), RegisterKind::Line);
+        assert_eq!(rf.get(Some('-')).content(), "w");
+        assert_eq!(rf.get(None).content(), "line\n");
+        rf.delete(None, "two\nlines".into(), RegisterKind::Char);
+        rf.delete(Some('a'), "named".into(), RegisterKind::Char);
+        rf.yank(None, "yanked".into(), RegisterKind::Char);
+        assert_eq!(rf.get(Some('-')).content(), "w");
+    }
+
+    #[test]
+    fn black_hole_stores_nothing() {
+        let mut rf = RegisterFile::new();
+        rf.yank(None, "kept".into(), RegisterKind::Char);
+        rf.delete(Some('_'), "gone".into(), RegisterKind::Char);
+        assert_eq!(rf.get(None).content(), "kept");
+        assert!(rf.get(Some('-')).is_empty());
+    }
 }
@@ -2,7 +2,27 @@
 //!
 //! Provides forward (`/`) and backward (`?`) search over a text buffer.
 //! Searches are literal string matches — simple, fast, and sufficient for
-//! most editing. Regex support can be layered on later.
+//! most editing — unless the pattern starts with one of Vim's magic
+//! switches, which make it a regular expression:
+//!
+//! | Switch | Mode          | Special without a backslash          |
+//! |--------|---------------|--------------------------------------|
+//! | `\v`   | very magic    | everything but `_`, letters, digits  |
+//! | `\m`   | magic         | `^ $ . * [ ~`                        |
+//! | `\M`   | no magic      | `^ $`                                |
+//! | `\V`   | very no magic | nothing (`\\` and `\n` still are)    |
+//!
+//! In magic mode `\(…\)`, `\|`, `\+`, `\?`, `\=` and `\{n,m}` are the
+//! group, alternation and counts, and `\<` / `\>` the word boundaries; in
+//! very magic mode they're written without the backslash. [`normalize_pattern`]
+//! turns such a pattern into the `regex` crate's syntax. `:s` patterns go
+//! through it too, and there a pattern without a switch is already taken
+//! as `regex` crate syntax — close to very magic.
+//!
+//! `\c` anywhere in a pattern makes the search ignore case, and `\C`
+//! makes it match case; neither is part of the text searched for. The
+//! editor adds `\c` itself for `ignorecase` / `smartcase` (see
+//! [`ignores_case`]).
 //!
 //! # Search flow
 //!
@@ -11,11 +31,30 @@
 //! 3. Enter confirms the pattern (stores it for `n`/`N` repeat)
 //! 4. Escape cancels and restores the cursor to its original position
 //!
+//! # Search offsets
+//!
+//! Text after a closing `/` (or `?`) is an offset saying where the cursor
+//! lands relative to the match (see [`SearchOffset`]):
+//!
+//! | Offset         | Cursor                                         |
+//! |----------------|------------------------------------------------|
+//! | `/foo/+2`      | two lines below the match, in column 0         |
+//! | `/foo/-`       | one line above the match                       |
+//! | `/foo/e`       | on the last character of the match             |
+//! | `/foo/e-1`     | one character before the end of the match      |
+//! | `/foo/b+1`     | one character after the start (`s` is the same)|
+//!
+//! `n` and `N` land with the same offset.
+//!
 //! # Match highlighting
 //!
 //! [`find_all`] returns all matches in a line range, used by the view layer
 //! to paint match highlights on visible lines.
 
+use std::ops::Range;
+
+use regex::{Regex, RegexBuilder};
+
 use crate::buffer::Buffer;
 use crate::position::Position;
 use crate::word::{classify, CharClass};
@@ -55,6 +94,98 @@ pub struct Match {
     pub len: usize,
 }
 
+// ---------------------------------------------------------------------------
+// Offset
+// ---------------------------------------------------------------------------
+
+/// What a [`SearchOffset`] counts from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OffsetKind {
+    /// `[+-]n` — lines below (above) the match, landing in column 0.
+    LineOffset,
+    /// `e[+-n]` — characters after (before) the last character of the match.
+    End,
+    /// `b[+-n]` / `s[+-n]` — characters after (before) the start of the match.
+    Begin,
+}
+
+/// Where the cursor lands relative to a match: `/foo/e+1` is
+/// `SearchOffset { kind: End, n: 1 }`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SearchOffset {
+    /// What the offset counts from.
+    pub kind: OffsetKind,
+    /// How far from it: lines or characters, negative for backward.
+    pub n: i64,
+}
+
+impl SearchOffset {
+    /// Parse the text after the closing delimiter: `+2`, `-`, `3`, `e`,
+    /// `e-1`, `b+1`, `s`. Returns `None` when it's not an offset.
+    #[must_use]
+    pub fn parse(text: &str) -> Option<Self> {
+        let (kind, count) = match text.chars().next()? {
+            'e' => (OffsetKind::End, &text[1..]),
+            'b' | 's' => (OffsetKind::Begin, &text[1..]),
+            _ => (OffsetKind::LineOffset, text),
+        };
+        let n = match count {
+            "" => 0,
+            "+" => 1,
+            "-" => -1,
+            _ => count.strip_prefix('+').unwrap_or(count).parse().ok()?,
+        };
+        // A bare count (`/foo/3`) is a line offset; `e3` needs its sign.
+        if kind != OffsetKind::LineOffset && !count.is_empty() && !count.starts_with(['+', '-']) {
+            return None;
+        }
+        Some(Self { kind, n })
+    }
+
+    /// Where the cursor goes for match `m`, kept within the buffer.
+    #[must_use]
+    pub fn apply(self, m: Match, buf: &Buffer) -> Position {
+        // Move `n` from `base` (a line or char index), clamped to `0..=last`.
+        let step = |base: usize, last: usize| {
+            let moved = i64::try_from(base).unwrap_or(i64::MAX).saturating_add(self.n);
+            usize::try_from(moved).unwrap_or(0).min(last)
+        };
+        if self.kind == OffsetKind::LineOffset {
+            let line = step(m.start.line, buf.line_count().saturating_sub(1));
+            return Position::new(line, 0);
+        }
+        buf.pos_to_char_idx(m.start).map_or(m.start, |start| {
+            let base = match self.kind {
+                OffsetKind::End => start + m.len.saturating_sub(1),
+                _ => start,
+            };
+            let idx = step(base, buf.len_chars().saturating_sub(1));
+            buf.char_idx_to_pos(idx).unwrap_or(m.start)
+        })
+    }
+}
+
+/// Split search input at its last unescaped `delim` into the pattern and
+/// the offset after it: `foo/e` → (`foo`, `e`). Input without a closing
+/// delimiter is all pattern.
+#[must_use]
+pub fn split_offset(input: &str, delim: char) -> (&str, Option<SearchOffset>) {
+    let mut split = None;
+    let mut escaped = false;
+    for (i, c) in input.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == delim {
+            split = Some(i);
+        }
+    }
+    split.map_or((input, None), |i| {
+        (&input[..i], SearchOffset::parse(&input[i + delim.len_utf8()..]))
+    })
+}
+
 // ---------------------------------------------------------------------------
 // SearchState
 // ---------------------------------------------------------------------------
@@ -154,6 +285,13 @@ impl SearchState {
         self.input.is_empty()
     }
 
+    /// The typed pattern and offset: `foo/e` is `foo` with an
+    /// [`OffsetKind::End`] offset (`?` separates them searching backward).
+    #[must_use]
+    pub fn pattern_and_offset(&self) -> (&str, Option<SearchOffset>) {
+        split_offset(&self.input, self.prefix())
+    }
+
     /// The prefix character for display (`/` for forward, `?` for backward).
     #[must_use]
     pub const fn prefix(&self) -> char {
@@ -186,11 +324,10 @@ impl SearchState {
 /// buffer, continues from the beginning.
 #[must_use]
 pub fn find_forward(buf: &Buffer, pattern: &str, from: Position) -> Option<Match> {
-    if pattern.is_empty() || buf.is_empty() {
+    if buf.is_empty() {
         return None;
     }
-
-    let pat_chars = pattern.chars().count();
+    let needle = Needle::new(pattern)?;
     let line_count = buf.line_count();
 
     // Search from current line through all lines (wrapping around).
@@ -198,7 +335,7 @@ pub fn find_forward(buf: &Buffer, pattern: &str, from: Position) -> Option<Match
         let line_idx = (from.line + offset) % line_count;
         let start_col = if offset == 0 { from.col } else { 0 };
 
-        if let Some(m) = search_line_forward(buf, pattern, pat_chars, line_idx, start_col) {
+        if let Some(m) = search_line_forward(buf, &needle, line_idx, start_col) {
             return Some(m);
         }
     }
@@ -206,7 +343,7 @@ pub fn find_forward(buf: &Buffer, pattern: &str, from: Position) -> Option<Match
     // Wrap: the loop above searched the starting line from `from.col`.
     // Check the starting line from col 0 for matches before `from.col`.
     if from.col > 0 {
-        return search_line_forward(buf, pattern, pat_chars, from.line, 0);
+        return search_line_forward(buf, &needle, from.line, 0);
     }
 
     None
@@ -222,11 +359,10 @@ pub fn find_forward(buf: &Buffer, pattern: &str, from: Position) -> Option<Match
 /// the buffer, continues from the end.
 #[must_use]
 pub fn find_backward(buf: &Buffer, pattern: &str, from: Position) -> Option<Match> {
-    if pattern.is_empty() || buf.is_empty() {
+    if buf.is_empty() {
         return None;
     }
-
-    let pat_chars = pattern.chars().count();
+    let needle = Needle::new(pattern)?;
     let line_count = buf.line_count();
 
     for offset in 0..line_count {
@@ -235,35 +371,108 @@ pub fn find_backward(buf: &Buffer, pattern: &str, from: Position) -> Option<Matc
         // On other lines, search the entire line.
         let before_col = if offset == 0 { from.col } else { usize::MAX };
 
-        if let Some(m) = search_line_backward(buf, pattern, pat_chars, line_idx, before_col) {
+        if let Some(m) = search_line_backward(buf, &needle, line_idx, before_col) {
             return Some(m);
         }
     }
 
     // Wrap: the loop above searched the starting line up to `from.col`.
     // Check the starting line fully for matches after `from.col`.
-    search_line_backward(buf, pattern, pat_chars, from.line, usize::MAX)
+    search_line_backward(buf, &needle, from.line, usize::MAX)
+}
+
+/// Forward search confined to `lines`, without wrapping.
+fn find_forward_in(
+    buf: &Buffer,
+    pattern: &str,
+    from: Position,
+    lines: Range<usize>,
+) -> Option<Match> {
+    if buf.is_empty() {
+        return None;
+    }
+    let needle = Needle::new(pattern)?;
+    let end = lines.end.min(buf.line_count());
+    let (first, first_col) = if from.line < lines.start {
+        (lines.start, 0)
+    } else {
+        (from.line, from.col)
+    };
+
+    (first..end).find_map(|line_idx| {
+        let start_col = if line_idx == first { first_col } else { 0 };
+        search_line_forward(buf, &needle, line_idx, start_col)
+    })
+}
+
+/// Backward search confined to `lines`, without wrapping.
+fn find_backward_in(
+    buf: &Buffer,
+    pattern: &str,
+    from: Position,
+    lines: Range<usize>,
+) -> Option<Match> {
+    if buf.is_empty() {
+        return None;
+    }
+    let needle = Needle::new(pattern)?;
+    let end = lines.end.min(buf.line_count());
+    if end <= lines.start {
+        return None;
+    }
+    let (last, last_col) = if from.line >= end {
+        (end - 1, usize::MAX)
+    } else {
+        (from.line, from.col)
+    };
+    if last < lines.start {
+        return None;
+    }
+
+    (lines.start..=last).rev().find_map(|line_idx| {
+        let before_col = if line_idx == last { last_col } else { usize::MAX };
+        search_line_backward(buf, &needle, line_idx, before_col)
+    })
 }
 
 /// Find the next match in the given direction. Convenience wrapper over
 /// [`find_forward`] and [`find_backward`].
+///
+/// When `lines` is `Some`, the search is confined to that half-open line
+/// range and does **not** wrap — it stops after the last line of the range.
+/// This keeps viewport-sized work (match highlighting) proportional to the
+/// viewport instead of the whole buffer.
+///
+/// Otherwise the whole buffer is searched, wrapping around the end (or
+/// the start, searching backward) when `wrapscan` is set, and stopping
+/// there when it isn't (`:set nowrapscan`).
 #[must_use]
 pub fn find(
     buf: &Buffer,
     pattern: &str,
     from: Position,
     direction: SearchDirection,
+    lines: Option<Range<usize>>,
+    wrapscan: bool,
 ) -> Option<Match> {
-    match direction {
-        SearchDirection::Forward => find_forward(buf, pattern, from),
-        SearchDirection::Backward => find_backward(buf, pattern, from),
+    let lines = lines.or_else(|| (!wrapscan).then(|| 0..buf.line_count()));
+    match (direction, lines) {
+        (SearchDirection::Forward, None) => find_forward(buf, pattern, from),
+        (SearchDirection::Backward, None) => find_backward(buf, pattern, from),
+        (SearchDirection::Forward, Some(lines)) => {
+            find_forward_in(buf, pattern, from, lines)
+        }
+        (SearchDirection::Backward, Some(lines)) => {
+            find_backward_in(buf, pattern, from, lines)
+        }
     }
 }
 
 /// Find all matches of `pattern` in the line range `[start_line, end_line)`.
 ///
 /// Used by the view layer to highlight all visible matches. Returns matches
-/// in document order.
+/// in document order. Lines outside the range are never read, so the cost is
+/// bounded by the range size, not the buffer size.
 #[must_use]
 pub fn find_all(
     buf: &Buffer,
@@ -271,34 +480,20 @@ pub fn find_all(
     start_line: usize,
     end_line: usize,
 ) -> Vec<Match> {
-    if pattern.is_empty() {
-        return Vec::new();
-    }
-
-    let pat_chars = pattern.chars().count();
     let mut matches = Vec::new();
+    let mut from = Position::new(start_line, 0);
 
-    for line_idx in start_line..end_line.min(buf.line_count()) {
-        let Some(line) = buf.line(line_idx) else {
-            continue;
-        };
-        let line_str = line_content_string(line);
-
-        let mut start_byte = 0;
-        while start_byte < line_str.len() {
-            if let Some(byte_idx) = line_str[start_byte..].find(pattern) {
-                let abs_byte = start_byte + byte_idx;
-                let char_col = byte_to_char(&line_str, abs_byte);
-                matches.push(Match {
-                    start: Position::new(line_idx, char_col),
-                    len: pat_chars,
-                });
-                // Advance past this match (non-overlapping).
-                start_byte = abs_byte + pattern.len().max(1);
-            } else {
-                break;
-            }
-        }
+    while let Some(m) = find(
+        buf,
+        pattern,
+        from,
+        SearchDirection::Forward,
+        Some(start_line..end_line),
+        false,
+    ) {
+        matches.push(m);
+        // Advance past this match (non-overlapping).
+        from = Position::new(m.start.line, m.start.col + m.len.max(1));
     }
 
     matches
@@ -355,21 +550,268 @@ pub fn word_under_cursor(buf: &Buffer, pos: Position) -> Option<String> {
     buf.slice(range).map(|s| s.to_string())
 }
 
+// ---------------------------------------------------------------------------
+// Magic
+// ---------------------------------------------------------------------------
+
+/// How a pattern's characters are read: Vim's four magic levels, and the
+/// `regex` crate's own syntax that a `:s` pattern starts in.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MagicLevel {
+    Regex,
+    VeryMagic,
+    Magic,
+    NoMagic,
+    VeryNoMagic,
+}
+
+impl MagicLevel {
+    /// Whether `ch` is special without a backslash (and so literal with
+    /// one).
+    fn plain_special(self, ch: char) -> bool {
+        match self {
+            Self::Regex | Self::VeryNoMagic => false,
+            Self::VeryMagic => ch.is_ascii_punctuation() && ch != '_',
+            Self::Magic => "^$.*[~".contains(ch),
+            Self::NoMagic => "^$".contains(ch),
+        }
+    }
+}
+
+/// Whether `pattern` starts with a magic switch (`\v`, `\m`, `\M`, `\V`).
+fn is_magic(pattern: &str) -> bool {
+    ["\\v", "\\m", "\\M", "\\V"].iter().any(|switch| pattern.starts_with(switch))
+}
+
+/// Translate a Vim pattern into `regex` crate syntax.
+///
+/// The pattern is read as `regex` syntax until a `\v`, `\m`, `\M` or `\V`
+/// switches the magic level (see the module docs); switches can appear
+/// anywhere and last until the next one. `\<` / `\>` become `\b`, `\=`
+/// becomes `?`, `\{-n,m}` a lazy count and `\%(` a non-capturing group.
+/// Letter escapes such as `\d`, `\w` and `\n` are passed on unchanged.
+#[must_use]
+pub fn normalize_pattern(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut magic = MagicLevel::Regex;
+    let mut chars = raw.chars().peekable();
+    while let Some(ch) = chars.next() {
+        let (ch, escaped) = if ch == '\\' {
+            match chars.next() {
+                Some('v') => {
+                    magic = MagicLevel::VeryMagic;
+                    continue;
+                }
+                Some('m') => {
+                    magic = MagicLevel::Magic;
+                    continue;
+                }
+                Some('M') => {
+                    magic = MagicLevel::NoMagic;
+                    continue;
+                }
+                Some('V') => {
+                    magic = MagicLevel::VeryNoMagic;
+                    continue;
+                }
+                Some(next) => (next, true),
+                None => {
+                    out.push_str("\\\\");
+                    break;
+                }
+            }
+        } else {
+            (ch, false)
+        };
+
+        if magic == MagicLevel::Regex {
+            if escaped {
+                out.push('\\');
+            }
+            out.push(ch);
+            continue;
+        }
+        if escaped && ch.is_alphanumeric() {
+            out.push('\\');
+            out.push(ch);
+            continue;
+        }
+        let special = ch != '\\' && ch != '/' && (magic.plain_special(ch) != escaped);
+        if !special || !ch.is_ascii_punctuation() {
+            push_literal(&mut out, ch);
+            continue;
+        }
+        match ch {
+            '(' | ')' | '|' | '+' | '?' | '.' | '*' | '^' | '$' => out.push(ch),
+            '=' => out.push('?'),
+            '<' | '>' => out.push_str("\\b"),
+            '{' => push_count(&mut out, &mut chars),
+            '[' => push_class(&mut out, &mut chars),
+            '%' if chars.peek() == Some(&'(') => {
+                chars.next();
+                out.push_str("(?:");
+            }
+            _ => push_literal(&mut out, ch),
+        }
+    }
+    out
+}
+
+/// Push `ch` so that it matches itself.
+fn push_literal(out: &mut String, ch: char) {
+    if regex::escape(ch.encode_utf8(&mut [0; 4])).len() > ch.len_utf8() {
+        out.push('\\');
+    }
+    out.push(ch);
+}
+
+/// Push a Vim count (`{n,m}` after its `{`): `{-…}` is lazy, an empty
+/// count is `*`, and a missing minimum is 0.
+fn push_count(out: &mut String, chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+    let mut body = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'}') => {}
+            '}' => break,
+            c => body.push(c),
+        }
+    }
+    let (lazy, body) = body.strip_prefix('-').map_or((false, body.as_str()), |b| (true, b));
+    if body.is_empty() {
+        out.push('*');
+    } else if body.starts_with(',') {
+        out.push_str("{0");
+        out.push_str(body);
+        out.push('}');
+    } else {
+        out.push('{');
+        out.push_str(body);
+        out.push('}');
+    }
+    if lazy {
+        out.push('?');
+    }
+}
+
+/// Push a bracket expression (after its `[`) up to and including the `]`.
+/// A `]` right after the `[` (or `[^`) is part of the set; `[`, `&` and
+/// `~`, which the `regex` crate gives meaning inside a set, are escaped.
+fn push_class(out: &mut String, chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+    out.push('[');
+    if chars.peek() == Some(&'^') {
+        out.push('^');
+        chars.next();
+    }
+    if chars.peek() == Some(&']') {
+        out.push_str("\\]");
+        chars.next();
+    }
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                out.push('\\');
+                out.extend(chars.next());
+            }
+            ']' => {
+                out.push(']');
+                return;
+            }
+            '[' | '&' | '~' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Internal helpers
 // ---------------------------------------------------------------------------
 
+/// Whether a search for `pattern` should ignore case: with `ignorecase`,
+/// unless `smartcase` is on too and the pattern has a capital letter.
+///
+/// A letter after a backslash (`\S`, `\C`) is an escape, not a capital.
+#[must_use]
+pub fn ignores_case(pattern: &str, ignorecase: bool, smartcase: bool) -> bool {
+    if !ignorecase {
+        return false;
+    }
+    let mut escaped = false;
+    let has_capital = pattern.chars().any(|c| {
+        let capital = !escaped && c.is_uppercase();
+        escaped = !escaped && c == '\\';
+        capital
+    });
+    !(smartcase && has_capital)
+}
+
+/// A pattern ready to search for: its text without `\c` / `\C`, and
+/// whether case matters. A pattern with a magic switch is compiled to
+/// `regex`.
+struct Needle {
+    text: String,
+    chars: Vec<char>,
+    ignore_case: bool,
+    regex: Option<Regex>,
+}
+
+impl Needle {
+    /// `None` for a pattern with nothing to search for (or a regular
+    /// expression that doesn't compile).
+    fn new(pattern: &str) -> Option<Self> {
+        let ignore_case = pattern.contains("\\c");
+        let text = pattern.replace("\\c", "").replace("\\C", "");
+        if text.is_empty() {
+            return None;
+        }
+        let regex = if is_magic(&text) {
+            Some(RegexBuilder::new(&normalize_pattern(&text)).case_insensitive(ignore_case).build().ok()?)
+        } else {
+            None
+        };
+        Some(Self { chars: text.chars().collect(), text, ignore_case, regex })
+    }
+
+    /// Whether the needle matches `line` starting at char `col`, ignoring
+    /// case (the case-sensitive search compares bytes instead).
+    fn matches_at(&self, line: &[char], col: usize) -> bool {
+        line.get(col..col + self.chars.len()).is_some_and(|window| {
+            window.iter().zip(&self.chars).all(|(&a, &b)| a == b || a.to_lowercase().eq(b.to_lowercase()))
+        })
+    }
+
+    fn matched(&self, line_idx: usize, col: usize) -> Match {
+        Match { start: Position::new(line_idx, col), len: self.chars.len() }
+    }
+}
+
+/// A regex match in `content` as a [`Match`] on line `line_idx`.
+fn regex_match(content: &str, m: &regex::Match<'_>, line_idx: usize) -> Match {
+    Match { start: Position::new(line_idx, byte_to_char(content, m.start())), len: m.as_str().chars().count() }
+}
+
 /// Search forward within a single line starting at `from_col`.
-fn search_line_forward(
-    buf: &Buffer,
-    pattern: &str,
-    pat_chars: usize,
-    line_idx: usize,
-    from_col: usize,
-) -> Option<Match> {
+fn search_line_forward(buf: &Buffer, needle: &Needle, line_idx: usize, from_col: usize) -> Option<Match> {
     let line = buf.line(line_idx)?;
     let content = line_content_string(line);
 
+    if let Some(re) = &needle.regex {
+        let start_byte = char_to_byte(&content, from_col);
+        if start_byte > content.len() || (start_byte == content.len() && from_col > 0) {
+            return None;
+        }
+        return re.find_at(&content, start_byte).map(|m| regex_match(&content, &m, line_idx));
+    }
+
+    if needle.ignore_case {
+        let chars: Vec<char> = content.chars().collect();
+        let last = chars.len().checked_sub(needle.chars.len())?;
+        return (from_col..=last).find(|&col| needle.matches_at(&chars, col)).map(|col| needle.matched(line_idx, col));
+    }
+
+    let pattern = needle.text.as_str();
     let start_byte = char_to_byte(&content, from_col);
     if start_byte >= content.len() {
         return None;
@@ -378,24 +820,30 @@ fn search_line_forward(
     let byte_idx = content[start_byte..].find(pattern)?;
     let abs_byte = start_byte + byte_idx;
     let char_col = byte_to_char(&content, abs_byte);
-    Some(Match {
-        start: Position::new(line_idx, char_col),
-        len: pat_chars,
-    })
+    Some(needle.matched(line_idx, char_col))
 }
 
 /// Search backward within a single line, finding the last match at or before
 /// `before_col`. Pass `usize::MAX` to search the entire line.
-fn search_line_backward(
-    buf: &Buffer,
-    pattern: &str,
-    pat_chars: usize,
-    line_idx: usize,
-    before_col: usize,
-) -> Option<Match> {
+fn search_line_backward(buf: &Buffer, needle: &Needle, line_idx: usize, before_col: usize) -> Option<Match> {
     let line = buf.line(line_idx)?;
     let content = line_content_string(line);
 
+    if let Some(re) = &needle.regex {
+        return re
+            .find_iter(&content)
+            .map(|m| regex_match(&content, &m, line_idx))
+            .take_while(|m| m.start.col <= before_col)
+            .last();
+    }
+
+    if needle.ignore_case {
+        let chars: Vec<char> = content.chars().collect();
+        let last = chars.len().checked_sub(needle.chars.len())?.min(before_col);
+        return (0..=last).rev().find(|&col| needle.matches_at(&chars, col)).map(|col| needle.matched(line_idx, col));
+    }
+
+    let pattern = needle.text.as_str();
     // Compute the byte limit: we want matches that START at or before before_col.
     let end_byte = if before_col == usize::MAX {
         content.len()
@@ -418,10 +866,7 @@ fn search_line_backward(
         return None;
     }
 
-    Some(Match {
-        start: Position::new(line_idx, char_col),
-        len: pat_chars,
-    })
+    Some(needle.matched(line_idx, char_col))
 }
 
 /// Extract line content as a string, excluding trailing newline characters.
@@ -459,6 +904,49 @@ mod tests {
         assert_eq!(SearchDirection::Backward.opposite(), SearchDirection::Forward);
     }
 
+    // -- Offset -------------------------------------------------------------
+
+    #[test]
+    fn parse_offsets() {
+        let off = |kind, n| Some(SearchOffset { kind, n });
+        assert_eq!(SearchOffset::parse("+2"), off(OffsetKind::LineOffset, 2));
+        assert_eq!(SearchOffset::parse("-"), off(OffsetKind::LineOffset, -1));
+        assert_eq!(SearchOffset::parse("3"), off(OffsetKind::LineOffset, 3));
+        assert_eq!(SearchOffset::parse("e"), off(OffsetKind::End, 0));
+        assert_eq!(SearchOffset::parse("e-1"), off(OffsetKind::End, -1));
+        assert_eq!(SearchOffset::parse("b+1"), off(OffsetKind::Begin, 1));
+        assert_eq!(SearchOffset::parse("s"), off(OffsetKind::Begin, 0));
+        assert_eq!(SearchOffset::parse(""), None);
+        assert_eq!(SearchOffset::parse("e2"), None);
+        assert_eq!(SearchOffset::parse("x"), None);
+    }
+
+    #[test]
+    fn split_offset_at_last_unescaped_delimiter() {
+        assert_eq!(split_offset("foo", '/'), ("foo", None));
+        assert_eq!(split_offset("foo/", '/'), ("foo", None));
+        assert_eq!(
+            split_offset("a\\/b/e", '/'),
+            ("a\\/b", Some(SearchOffset { kind: OffsetKind::End, n: 0 }))
+        );
+        assert_eq!(
+            split_offset("foo?-1", '?'),
+            ("foo", Some(SearchOffset { kind: OffsetKind::LineOffset, n: -1 }))
+        );
+    }
+
+    #[test]
+    fn apply_offsets() {
+        let buf = Buffer::from_text("one\nfoobar\nthree");
+        let m = Match { start: Position::new(1, 3), len: 3 };
+        let apply = |kind, n| SearchOffset { kind, n }.apply(m, &buf);
+        assert_eq!(apply(OffsetKind::LineOffset, 1), Position::new(2, 0));
+        assert_eq!(apply(OffsetKind::LineOffset, -5), Position::new(0, 0));
+        assert_eq!(apply(OffsetKind::End, 0), Position::new(1, 5));
+        assert_eq!(apply(OffsetKind::End, 2), Position::new(2, 0));
+        assert_eq!(apply(OffsetKind::Begin, -1), Position::new(1, 2));
+    }
+
     // -- SearchState -------------------------------------------------------
 
     #[test]
@@ -653,17 +1141,100 @@ mod tests {
     #[test]
     fn find_dispatches_forward() {
         let buf = Buffer::from_text("hello world");
-        let m = find(&buf, "world", Position::ZERO, SearchDirection::Forward).unwrap();
+        let m = find(&buf, "world", Position::ZERO, SearchDirection::Forward, None, true).unwrap();
         assert_eq!(m.start, Position::new(0, 6));
     }
 
     #[test]
     fn find_dispatches_backward() {
         let buf = Buffer::from_text("hello world");
-        let m = find(&buf, "hello", Position::new(0, 10), SearchDirection::Backward).unwrap();
+        let m = find(&buf, "hello", Position::new(0, 10), SearchDirection::Backward, None, true).unwrap();
+        assert_eq!(m.start, Position::ZERO);
+    }
+
+    #[test]
+    fn find_without_wrapscan_stops_at_the_ends() {
+        let buf = Buffer::from_text("abc\nx\nabc\nx");
+        let fwd = |from| find(&buf, "abc", from, SearchDirection::Forward, None, false);
+        assert_eq!(fwd(Position::new(1, 0)).map(|m| m.start), Some(Position::new(2, 0)));
+        assert_eq!(fwd(Position::new(2, 1)), None);
+        let back = |from| find(&buf, "abc", from, SearchDirection::Backward, None, false);
+        assert_eq!(back(Position::new(1, 0)).map(|m| m.start), Some(Position::ZERO));
+        assert_eq!(back(Position::ZERO).map(|m| m.start), Some(Position::ZERO));
+        // With wrapscan the same searches go round.
+        let m = find(&buf, "abc", Position::new(2, 1), SearchDirection::Forward, None, true).unwrap();
         assert_eq!(m.start, Position::ZERO);
     }
 
+    #[test]
+    fn find_bounded_forward_stops_at_range_end() {
+        let buf = Buffer::from_text("x\nx\nabc\nx\nabc");
+        // The only matches inside lines 0..2 — none; must not wrap or run past.
+        assert_eq!(find(&buf, "abc", Position::ZERO, SearchDirection::Forward, Some(0..2), true), None);
+        let m = find(&buf, "abc", Position::ZERO, SearchDirection::Forward, Some(0..3), true).unwrap();
+        assert_eq!(m.start, Position::new(2, 0));
+    }
+
+    #[test]
+    fn find_bounded_forward_starts_at_range_start() {
+        let buf = Buffer::from_text("abc\nx\nabc");
+        let m = find(&buf, "abc", Position::ZERO, SearchDirection::Forward, Some(1..3), true).unwrap();
+        assert_eq!(m.start, Position::new(2, 0));
+    }
+
+    #[test]
+    fn find_bounded_forward_does_not_wrap() {
+        let buf = Buffer::from_text("abc\nx\nx");
+        assert_eq!(
+            find(&buf, "abc", Position::new(1, 0), SearchDirection::Forward, Some(0..3), true),
+            None
+        );
+    }
+
+    #[test]
+    fn find_bounded_backward_stays_in_range() {
+        let buf = Buffer::from_text("abc\nx\nabc\nx\nabc");
+        let m = find(&buf, "abc", Position::new(4, 2), SearchDirection::Backward, Some(1..4), true).unwrap();
+        assert_eq!(m.start, Position::new(2, 0));
+        assert_eq!(
+            find(&buf, "abc", Position::new(1, 0), SearchDirection::Backward, Some(1..4), true),
+            None
+        );
+    }
+
+    #[test]
+    fn find_bounded_range_past_buffer_end() {
+        let buf = Buffer::from_text("x\nabc");
+        let m = find(&buf, "abc", Position::ZERO, SearchDirection::Forward, Some(0..100), true).unwrap();
+        assert_eq!(m.start, Position::new(1, 0));
+    }
+
+    // -- case ----------------------------------------------------------------
+
+    #[test]
+    fn backslash_c_ignores_case() {
+        let buf = Buffer::from_text("say Foo\nfoo FOO");
+        let m = find(&buf, "\\cfoo", Position::ZERO, SearchDirection::Forward, None, true).unwrap();
+        assert_eq!((m.start, m.len), (Position::new(0, 4), 3));
+        let m = find(&buf, "foo\\c", Position::new(1, 2), SearchDirection::Backward, None, true).unwrap();
+        assert_eq!(m.start, Position::new(1, 0));
+        let m = find(&buf, "FOO\\C", Position::ZERO, SearchDirection::Forward, None, true).unwrap();
+        assert_eq!(m.start, Position::new(1, 4));
+        assert_eq!(find_all(&buf, "\\cfoo", 0, 2).len(), 3);
+        assert_eq!(find(&buf, "\\c", Position::ZERO, SearchDirection::Forward, None, true), None);
+    }
+
+    #[test]
+    fn ignorecase_and_smartcase() {
+        assert!(!ignores_case("foo", false, true));
+        assert!(ignores_case("foo", true, false));
+        assert!(ignores_case("Foo", true, false));
+        assert!(ignores_case("foo", true, true));
+        assert!(!ignores_case("Foo", true, true));
+        // Escapes aren't capitals.
+        assert!(ignores_case(r"\Sfoo", true, true));
+    }
+
     // -- find_all ----------------------------------------------------------
 
     #[test]
@@ -890,4 +1461,68 @@ mod tests {
         let m = find_backward(&buf, "world", Position::ZERO).unwrap();
         assert_eq!(m.start, Position::new(0, 6));
     }
+
+    // ── Magic ────────────────────────────────────────────────────────
+
+    #[test]
+    fn normalize_very_magic() {
+        assert_eq!(normalize_pattern(r"\v\d+"), r"\d+");
+        assert_eq!(normalize_pattern(r"\v(foo|bar)"), "(foo|bar)");
+        assert_eq!(normalize_pattern(r"\v<the>"), r"\bthe\b");
+        assert_eq!(normalize_pattern(r"\vcolou=r x{2,}"), r"colou?r x{2,}");
+        assert_eq!(normalize_pattern(r"\va\.b\(\)"), r"a\.b\(\)");
+        assert_eq!(normalize_pattern(r"\v%(a|b)c"), "(?:a|b)c");
+    }
+
+    #[test]
+    fn normalize_magic() {
+        assert_eq!(normalize_pattern(r"\m\(foo\|bar\)\+"), "(foo|bar)+");
+        // Without the backslash they're literal, unlike in the regex crate.
+        assert_eq!(normalize_pattern(r"\ma+(b)|c?"), r"a\+\(b\)\|c\?");
+        assert_eq!(normalize_pattern(r"\m\<the\>"), r"\bthe\b");
+        assert_eq!(normalize_pattern(r"\m^a.*b$"), "^a.*b$");
+        assert_eq!(normalize_pattern(r"\mx\{2,3}y\{,4\}"), "x{2,3}y{0,4}");
+        assert_eq!(normalize_pattern(r"\mx\{-1,}z\{-}"), "x{1,}?z*?");
+        assert_eq!(normalize_pattern(r"\m[]a[&]\.x"), r"[\]a\[\&]\.x");
+    }
+
+    #[test]
+    fn normalize_no_magic() {
+        assert_eq!(normalize_pattern(r"\Ma.*[x]"), r"a\.\*\[x\]");
+        assert_eq!(normalize_pattern(r"\Ma\.\*\[xy]"), "a.*[xy]");
+        assert_eq!(normalize_pattern(r"\M^a$"), "^a$");
+    }
+
+    #[test]
+    fn normalize_very_no_magic() {
+        assert_eq!(normalize_pattern(r"\Va.b*c(d)"), r"a\.b\*c\(d\)");
+        assert_eq!(normalize_pattern(r"\V$x^"), r"\$x\^");
+        assert_eq!(normalize_pattern(r"\Va\nb\\c"), r"a\nb\\c");
+    }
+
+    #[test]
+    fn normalize_without_switch_is_regex_syntax() {
+        assert_eq!(normalize_pattern(r"(\w+) (\w+)"), r"(\w+) (\w+)");
+        assert_eq!(normalize_pattern(r"[0-9]+\."), r"[0-9]+\.");
+        // A switch can come part way through.
+        assert_eq!(normalize_pattern(r"a+\Va+"), r"a+a\+");
+    }
+
+    #[test]
+    fn magic_switch_makes_search_a_regex() {
+        let buf = Buffer::from_text("id: x42y\nfoo.bar foo-bar");
+        let m = find_forward(&buf, r"\v\d+", Position::ZERO).unwrap();
+        assert_eq!((m.start, m.len), (Position::new(0, 5), 2));
+        // Without a switch the search is literal.
+        assert!(find_forward(&buf, r"\d+", Position::ZERO).is_none());
+        let m = find_forward(&buf, r"\mfoo.bar", Position::new(1, 1)).unwrap();
+        assert_eq!(m.start, Position::new(1, 8));
+        let m = find_forward(&buf, r"\Vfoo.bar", Position::new(1, 1)).unwrap();
+        assert_eq!(m.start, Position::new(1, 0));
+        let m = find_backward(&buf, r"\v(ID|bar)\c", Position::new(1, 20)).unwrap();
+        assert_eq!((m.start, m.len), (Position::new(1, 12), 3));
+        let all = find_all(&buf, r"\v<foo>", 0, 2);
+        assert_eq!(all.iter().map(|m| m.start.col).collect::<Vec<_>>(), [0, 8]);
+        assert!(find_forward(&buf, r"\v(unclosed", Position::ZERO).is_none());
+    }
 }
@@ -0,0 +1,239 @@
+//! Shared data — editor state kept between sessions (`:wshada`, `:rshada`).
+//!
+//! A [`ShaDa`] snapshot holds the jump list, file marks, registers, and the
+//! search and command-line history. It is stored as versioned TOML: a
+//! `version` key says which layout the file uses, and every other field
+//! is optional, so a file missing some of them (or written by an older
+//! version) still reads. A file from a newer version is refused rather
+//! than half-understood.
+//!
+//! | Command          | Action                                          |
+//! |------------------|-------------------------------------------------|
+//! | `:wsh[ada]`      | Write the data file now                         |
+//! | `:wshada {file}` | Write to `file` instead                         |
+//! | `:rsh[ada]`      | Read the data file, merging with current state  |
+//! | `:rshada!`       | Read the data file, replacing current state     |
+//!
+//! The default file is [`default_path`].
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::position::Position;
+use crate::register::RegisterKind;
+
+/// The layout written by this version. Bumped when a field changes
+/// meaning; adding a field doesn't need a bump.
+pub const SHADA_VERSION: u32 = 1;
+
+/// Entries kept in each history list.
+pub const HISTORY_MAX: usize = 100;
+
+/// A mark (`ma`) in a file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileMark {
+    /// The mark name, `a`–`z`.
+    pub name: char,
+    /// The file the mark is in.
+    pub file: PathBuf,
+    /// Where in the file.
+    pub pos: Position,
+}
+
+/// The contents of one register.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedRegister {
+    /// The register name: `"` for the unnamed register, or `a`–`z`.
+    pub name: char,
+    /// Char-, line- or block-wise.
+    pub kind: RegisterKind,
+    /// The text.
+    pub content: String,
+}
+
+/// Everything kept between sessions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShaDa {
+    /// The layout version ([`SHADA_VERSION`] when written by this version).
+    pub version: u32,
+    /// Jump list positions, oldest first.
+    pub jump_list: Vec<Position>,
+    /// Marks in files that had a name.
+    pub marks: Vec<FileMark>,
+    /// Non-empty registers.
+    pub registers: Vec<SavedRegister>,
+    /// Search patterns, oldest first. The editor only remembers its last
+    /// search, so it writes at most one and reads back the newest.
+    pub search_history: Vec<String>,
+    /// Command lines, oldest first.
+    pub cmd_history: Vec<String>,
+}
+
+impl Default for ShaDa {
+    fn default() -> Self {
+        Self {
+            version: SHADA_VERSION,
+            jump_list: Vec::new(),
+            marks: Vec::new(),
+            registers: Vec::new(),
+            search_history: Vec::new(),
+            cmd_history: Vec::new(),
+        }
+    }
+}
+
+impl ShaDa {
+    /// Parse the contents of a data file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if the text isn't a data file
+    /// or was written by a newer version.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let data: Self = toml::from_str(text).map_err(|e| e.message().to_string())?;
+        if data.version > SHADA_VERSION {
+            return Err(format!("written by a newer version (format {})", data.version));
+        }
+        Ok(data)
+    }
+
+    /// The data as file contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a mark's file name isn't valid UTF-8, which TOML
+    /// can't store.
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string_pretty(self)
+    }
+
+    /// Read a data file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't a data file this
+    /// version understands.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Write the data to `path`, creating its directory if needed. The file
+    /// is written beside the old one and renamed over it, so a failed
+    /// write never leaves a truncated file.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from creating the directory or writing the file,
+    /// or from [`to_toml`](Self::to_toml) — the old file is left alone then.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let text = self.to_toml().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        fs::write(&tmp, text)?;
+        fs::rename(&tmp, path)
+    }
+}
+
+/// The default data file: `$XDG_STATE_HOME/n-nvim/shada/main.toml`,
+/// falling back to `~/.local/state`.
+#[must_use]
+pub fn default_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_STATE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local").join("state")))?;
+    Some(base.join("n-nvim").join("shada").join("main.toml"))
+}
+
+/// Merge history lists: the entries of `older` that aren't in `newer`,
+/// followed by `newer`, keeping the newest [`HISTORY_MAX`].
+#[must_use]
+pub fn merge_history(older: &[String], newer: &[String]) -> Vec<String> {
+    let mut merged: Vec<String> = older.iter().filter(|e| !newer.contains(e)).cloned().collect();
+    merged.extend(newer.iter().cloned());
+    let excess = merged.len().saturating_sub(HISTORY_MAX);
+    merged.drain(..excess);
+    merged
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ShaDa {
+        ShaDa {
+            jump_list: vec![Position::new(3, 1), Position::new(10, 0)],
+            marks: vec![FileMark { name: 'a', file: PathBuf::from("/tmp/x.rs"), pos: Position::new(2, 4) }],
+            registers: vec![SavedRegister { name: 'q', kind: RegisterKind::Line, content: "dd\n".to_string() }],
+            search_history: vec!["foo".to_string()],
+            cmd_history: vec!["w".to_string(), "set ts=2".to_string()],
+            ..ShaDa::default()
+        }
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let data = sample();
+        let text = data.to_toml().unwrap();
+        assert!(text.starts_with("version = 1\n"), "{text}");
+        assert_eq!(ShaDa::parse(&text), Ok(data));
+    }
+
+    #[test]
+    fn missing_fields_default_and_newer_versions_are_refused() {
+        let data = ShaDa::parse("version = 1\ncmd_history = [\"q\"]\n").unwrap();
+        assert_eq!(data.cmd_history, vec!["q"]);
+        assert!(data.jump_list.is_empty());
+        assert!(ShaDa::parse("version = 99").unwrap_err().contains("newer"));
+        assert!(ShaDa::parse("not toml").is_err());
+    }
+
+    #[test]
+    fn write_and_read_file() {
+        let dir = std::env::temp_dir().join(format!("n-nvim-shada-{}", std::process::id()));
+        let path = dir.join("nested").join("main.toml");
+        sample().write(&path).unwrap();
+        assert_eq!(ShaDa::read(&path).unwrap(), sample());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unwritable_data_keeps_the_old_file() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = std::env::temp_dir().join(format!("n-nvim-shada-bad-{}", std::process::id()));
+        let path = dir.join("main.toml");
+        sample().write(&path).unwrap();
+        let mut data = sample();
+        data.marks[0].file = PathBuf::from(std::ffi::OsStr::from_bytes(b"/tmp/\xff.rs"));
+        assert!(data.to_toml().is_err());
+        assert_eq!(data.write(&path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(ShaDa::read(&path).unwrap(), sample());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn history_merge_keeps_newest() {
+        let older = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let newer = vec!["b".to_string(), "d".to_string()];
+        assert_eq!(merge_history(&older, &newer), vec!["a", "c", "b", "d"]);
+        let long: Vec<String> = (0..HISTORY_MAX).map(|i| i.to_string()).collect();
+        let merged = merge_history(&long, &newer);
+        assert_eq!(merged.len(), HISTORY_MAX);
+        assert_eq!(merged[HISTORY_MAX - 2..], ["b", "d"]);
+    }
+}
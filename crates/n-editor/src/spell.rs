@@ -0,0 +1,506 @@
+//! Spell checking — dictionary lookup for `:set spell`.
+//!
+//! A [`SpellChecker`] wraps a static word list bundled into the binary plus a
+//! user dictionary of words added with `:spellgood`. The bundled lists live in
+//! `dict/` as one lowercase word per line:
+//!
+//! | `spelllang` | Word lists                       |
+//! |-------------|----------------------------------|
+//! | `en`        | `en.txt` + `en_us.txt` + `en_gb.txt` |
+//! | `en_us`     | `en.txt` + `en_us.txt`           |
+//! | `en_gb`     | `en.txt` + `en_gb.txt`           |
+//!
+//! The lists only hold base forms. Common inflections (`-s`, `-es`, `-ed`,
+//! `-ing`, `-er`, `-est`, `-ly`, `-ness`, `-ment`, possessive `'s`, …) and a
+//! handful of prefixes (`un-`, `re-`, `pre-`, …) are stripped before lookup,
+//! so "highlighted" and "unwrapping" are accepted through "highlight" and
+//! "wrap".
+//!
+//! [`SpellChecker::check_line`] tokenizes a line into alphabetic words and
+//! returns the byte ranges of the ones it doesn't recognize. Tokens that look
+//! like code rather than prose are skipped: `camelCase` and `ALLCAPS` words,
+//! words glued to digits or underscores (`utf8`, `snake_case`), single
+//! letters, and whole URLs / e-mail addresses.
+//!
+//! Suggestions (`z=`) are not implemented.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::buffer::Buffer;
+use crate::position::Position;
+use crate::search::SearchDirection;
+
+/// Base English word list, shared by every `en` variant.
+const WORDS_EN: &str = include_str!("../dict/en.txt");
+
+/// American spellings (`color`, `center`, `analyze`, …).
+const WORDS_EN_US: &str = include_str!("../dict/en_us.txt");
+
+/// British spellings (`colour`, `centre`, `analyse`, …).
+const WORDS_EN_GB: &str = include_str!("../dict/en_gb.txt");
+
+/// Values accepted by `:set spelllang`.
+pub const LANGUAGES: &[&str] = &["en", "en_us", "en_gb"];
+
+/// Suffixes stripped before lookup, longest first. Each entry is the suffix
+/// and the replacement that restores the base form (`"ies"` → `"y"`).
+const SUFFIXES: &[(&str, &str)] = &[
+    ("iness", "y"),
+    ("iest", "y"),
+    ("ness", ""),
+    ("ment", ""),
+    ("less", ""),
+    ("able", ""),
+    ("able", "e"),
+    ("ally", ""),
+    ("ies", "y"),
+    ("ied", "y"),
+    ("ier", "y"),
+    ("ily", "y"),
+    ("ing", ""),
+    ("ing", "e"),
+    ("est", ""),
+    ("est", "e"),
+    ("ful", ""),
+    ("'s", ""),
+    ("es", ""),
+    ("ed", ""),
+    ("ed", "e"),
+    ("er", ""),
+    ("er", "e"),
+    ("ly", ""),
+    ("s", ""),
+];
+
+/// Prefixes stripped before lookup.
+const PREFIXES: &[&str] = &["non", "pre", "dis", "un", "re"];
+
+// ---------------------------------------------------------------------------
+// SpellChecker
+// ---------------------------------------------------------------------------
+
+/// Dictionary-backed spell checker for one `spelllang`.
+#[derive(Debug, Clone)]
+pub struct SpellChecker {
+    lang: String,
+    words: HashSet<&'static str>,
+    user_words: HashSet<String>,
+}
+
+impl SpellChecker {
+    /// Build a checker for `lang` (one of [`LANGUAGES`]). Returns `None` for
+    /// an unknown language.
+    #[must_use]
+    pub fn new(lang: &str) -> Option<Self> {
+        let lists: &[&'static str] = match lang {
+            "en" => &[WORDS_EN, WORDS_EN_US, WORDS_EN_GB],
+            "en_us" => &[WORDS_EN, WORDS_EN_US],
+            "en_gb" => &[WORDS_EN, WORDS_EN_GB],
+            _ => return None,
+        };
+        let words = lists
+            .iter()
+            .flat_map(|list| list.lines())
+            .map(str::trim)
+            .filter(|w| !w.is_empty() && !w.starts_with('#'))
+            .collect();
+        Some(Self {
+            lang: lang.to_string(),
+            words,
+            user_words: HashSet::new(),
+        })
+    }
+
+    /// The `spelllang` this checker was built for.
+    #[must_use]
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    /// Add a word to the in-memory user dictionary.
+    pub fn add_word(&mut self, word: &str) {
+        self.user_words.insert(normalize(word));
+    }
+
+    /// Load a user dictionary file (one word per line, `#` comments).
+    /// A missing file is not an error. Returns the number of words read.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error other than `NotFound`.
+    pub fn load_word_file(&mut self, path: &Path) -> io::Result<usize> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+        let mut count = 0;
+        for word in text.lines().map(str::trim) {
+            if !word.is_empty() && !word.starts_with('#') {
+                self.add_word(word);
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Whether `word` is spelled correctly.
+    #[must_use]
+    pub fn is_known(&self, word: &str) -> bool {
+        let word = normalize(word);
+        if self.lookup(&word) {
+            return true;
+        }
+        let unprefixed = PREFIXES
+            .iter()
+            .filter_map(|p| word.strip_prefix(p))
+            .filter(|rest| rest.len() >= 3);
+        std::iter::once(word.as_str())
+            .chain(unprefixed)
+            .any(|w| self.lookup(w) || self.lookup_stemmed(w))
+    }
+
+    /// Byte ranges of the misspelled words in `line`.
+    #[must_use]
+    pub fn check_line(&self, line: &str) -> Vec<Range<usize>> {
+        words(line)
+            .filter(|r| !self.is_known(&line[r.clone()]))
+            .collect()
+    }
+
+    /// Find the next (or previous) misspelled word from `from`, wrapping
+    /// around the end of the buffer. Returns the start of the word.
+    #[must_use]
+    pub fn find_misspelled(
+        &self,
+        buf: &Buffer,
+        from: Position,
+        direction: SearchDirection,
+    ) -> Option<Position> {
+        let n = buf.line_count();
+        if n == 0 {
+            return None;
+        }
+        let from_line = from.line.min(n - 1);
+        for step in 0..=n {
+            let line_idx = match direction {
+                SearchDirection::Forward => (from_line + step) % n,
+                SearchDirection::Backward => (from_line + n - step % n) % n,
+            };
+            let text = buf.line(line_idx).map(|l| l.to_string()).unwrap_or_default();
+            let text = text.trim_end_matches(['\n', '\r']);
+            let mut cols = self
+                .check_line(text)
+                .into_iter()
+                .map(|r| text[..r.start].chars().count());
+            // The starting line is visited twice: first for words past the
+            // cursor, then (after wrapping) for the words before it.
+            let hit = match (direction, step) {
+                (SearchDirection::Forward, 0) => cols.find(|&c| c > from.col),
+                (SearchDirection::Forward, s) if s == n => cols.find(|&c| c <= from.col),
+                (SearchDirection::Forward, _) => cols.next(),
+                (SearchDirection::Backward, 0) => cols.rfind(|&c| c < from.col),
+                (SearchDirection::Backward, s) if s == n => cols.rfind(|&c| c >= from.col),
+                (SearchDirection::Backward, _) => cols.next_back(),
+            };
+            if let Some(col) = hit {
+                return Some(Position::new(line_idx, col));
+            }
+        }
+        None
+    }
+
+    fn lookup(&self, word: &str) -> bool {
+        self.words.contains(word) || self.user_words.contains(word)
+    }
+
+    fn lookup_stemmed(&self, word: &str) -> bool {
+        SUFFIXES.iter().any(|&(suffix, repl)| {
+            let Some(stem) = word.strip_suffix(suffix) else {
+                return false;
+            };
+            if stem.len() < 2 {
+                return false;
+            }
+            let base = format!("{stem}{repl}");
+            if self.lookup(&base) {
+                return true;
+            }
+            // "stopped" → "stop", "running" → "run".
+            let b = stem.as_bytes();
+            repl.is_empty()
+                && b.len() >= 3
+                && b[b.len() - 1] == b[b.len() - 2]
+                && !b"aeiou".contains(&b[b.len() - 1])
+                && self.lookup(&stem[..stem.len() - 1])
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// User dictionary file
+// ---------------------------------------------------------------------------
+
+/// Default user dictionary (`spellfile`) for `lang`:
+/// `$XDG_CONFIG_HOME/n-nvim/spell/<region-less lang>.utf-8.add`, falling back
+/// to `~/.config`. Like Vim, all regions of a language share one file.
+#[must_use]
+pub fn default_spellfile(lang: &str) -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+    let region_less = lang.split('_').next().unwrap_or(lang);
+    Some(
+        base.join("n-nvim")
+            .join("spell")
+            .join(format!("{region_less}.utf-8.add")),
+    )
+}
+
+/// Append `word` to the user dictionary at `path`, creating the file and
+/// its parent directories as needed.
+///
+/// # Errors
+///
+/// Returns any I/O error from creating or writing the file.
+pub fn append_word_file(path: &Path, word: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{word}")
+}
+
+// ---------------------------------------------------------------------------
+// Tokenizer
+// ---------------------------------------------------------------------------
+
+/// Lowercase and fold typographic apostrophes to ASCII.
+fn normalize(word: &str) -> String {
+    word.to_lowercase().replace('\u{2019}', "'")
+}
+
+const fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '\u{2019}'
+}
+
+/// Byte ranges of the checkable words in `line`.
+fn words(line: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut out = Vec::new();
+    let mut offset = 0;
+    for chunk in line.split_inclusive(char::is_whitespace) {
+        let start = offset;
+        offset += chunk.len();
+        if chunk.contains("://") || chunk.contains('@') {
+            continue;
+        }
+        words_in_chunk(chunk, start, &mut out);
+    }
+    out.into_iter()
+}
+
+fn words_in_chunk(chunk: &str, base: usize, out: &mut Vec<Range<usize>>) {
+    let chars: Vec<(usize, char)> = chunk.char_indices().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        if !chars[i].1.is_alphabetic() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        // Letters, plus apostrophes between two letters ("don't").
+        while i < chars.len()
+            && (chars[i].1.is_alphabetic()
+                || (is_apostrophe(chars[i].1)
+                    && i + 1 < chars.len()
+                    && chars[i + 1].1.is_alphabetic()
+                    && i > start))
+        {
+            i += 1;
+        }
+        let end = i;
+        let glued = |c: char| c.is_ascii_digit() || c == '_';
+        if start > 0 && glued(chars[start - 1].1) || end < chars.len() && glued(chars[end].1) {
+            continue;
+        }
+        let word: Vec<char> = chars[start..end].iter().map(|&(_, c)| c).collect();
+        if word.len() < 2 || word[1..].iter().any(|c| c.is_uppercase()) {
+            continue;
+        }
+        let byte_end = chars.get(end).map_or(chunk.len(), |&(b, _)| b);
+        out.push(base + chars[start].0..base + byte_end);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn en() -> SpellChecker {
+        SpellChecker::new("en_us").unwrap()
+    }
+
+    fn bad(line: &str) -> Vec<&str> {
+        en().check_line(line).into_iter().map(|r| &line[r]).collect()
+    }
+
+    // ── Dictionary ──────────────────────────────────────────
+
+    #[test]
+    fn unknown_language() {
+        assert!(SpellChecker::new("xx").is_none());
+        for lang in LANGUAGES {
+            assert!(SpellChecker::new(lang).is_some(), "{lang}");
+        }
+    }
+
+    #[test]
+    fn known_words() {
+        let s = en();
+        assert!(s.is_known("the"));
+        assert!(s.is_known("The"));
+        assert!(s.is_known("don't"));
+        assert!(s.is_known("don\u{2019}t"));
+        assert!(!s.is_known("teh"));
+    }
+
+    #[test]
+    fn inflections() {
+        let s = en();
+        for w in ["words", "boxes", "tried", "used", "stopped", "running", "making",
+            "highlighted", "faster", "happily", "quickly", "kindness", "user's"]
+        {
+            assert!(s.is_known(w), "{w}");
+        }
+    }
+
+    #[test]
+    fn prefixes() {
+        let s = en();
+        assert!(s.is_known("unwrap"));
+        assert!(s.is_known("rewrite"));
+        assert!(s.is_known("unhappy"));
+        assert!(!s.is_known("unteh"));
+    }
+
+    #[test]
+    fn region_variants() {
+        let us = SpellChecker::new("en_us").unwrap();
+        let gb = SpellChecker::new("en_gb").unwrap();
+        let any = SpellChecker::new("en").unwrap();
+        assert!(us.is_known("color") && !us.is_known("colour"));
+        assert!(gb.is_known("colour") && !gb.is_known("color"));
+        assert!(any.is_known("color") && any.is_known("colour"));
+        assert_eq!(gb.lang(), "en_gb");
+    }
+
+    #[test]
+    fn user_words() {
+        let mut s = en();
+        assert!(!s.is_known("nvim"));
+        s.add_word("Nvim");
+        assert!(s.is_known("nvim"));
+        assert!(s.is_known("nvims"));
+    }
+
+    #[test]
+    fn word_file_roundtrip() {
+        let dir = std::env::temp_dir().join("n-nvim-test").join("spell");
+        let path = dir.join(format!("roundtrip-{}.add", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut s = en();
+        assert_eq!(s.load_word_file(&path).unwrap(), 0);
+        append_word_file(&path, "frobnicate").unwrap();
+        append_word_file(&path, "grok").unwrap();
+        assert_eq!(s.load_word_file(&path).unwrap(), 2);
+        assert!(s.is_known("frobnicate"));
+        assert!(s.is_known("grok"));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn default_spellfile_drops_region() {
+        if let Some(p) = default_spellfile("en_gb") {
+            assert!(p.ends_with("n-nvim/spell/en.utf-8.add"));
+        }
+    }
+
+    // ── check_line ──────────────────────────────────────────
+
+    #[test]
+    fn check_line_byte_ranges() {
+        let s = en();
+        let line = "the qiuck brown fox";
+        assert_eq!(s.check_line(line), vec![4..9]);
+        assert!(s.check_line("").is_empty());
+    }
+
+    #[test]
+    fn check_line_multibyte() {
+        let line = "— teh café";
+        let ranges = en().check_line(line);
+        assert_eq!(&line[ranges[0].clone()], "teh");
+        assert_eq!(&line[ranges[1].clone()], "café");
+    }
+
+    #[test]
+    fn check_line_skips_code_like_tokens() {
+        assert!(bad("fooBar HTTP utf8 snake_case x").is_empty());
+        assert!(bad("see https://exmple.com or mail me@exmple.com").is_empty());
+    }
+
+    #[test]
+    fn check_line_punctuation() {
+        assert_eq!(bad("(teh), \"wrod\"."), vec!["teh", "wrod"]);
+        assert_eq!(bad("'quoted' it's"), Vec::<&str>::new());
+    }
+
+    // ── find_misspelled ─────────────────────────────────────
+
+    #[test]
+    fn find_forward_and_wrap() {
+        let s = en();
+        let buf = Buffer::from_text("teh one\nthe two\nwrod three\n");
+        let fwd = SearchDirection::Forward;
+        assert_eq!(s.find_misspelled(&buf, Position::new(0, 0), fwd), Some(Position::new(2, 0)));
+        assert_eq!(s.find_misspelled(&buf, Position::new(2, 0), fwd), Some(Position::new(0, 0)));
+    }
+
+    #[test]
+    fn find_backward_and_wrap() {
+        let s = en();
+        let buf = Buffer::from_text("teh one\nthe two\nwrod three\n");
+        let back = SearchDirection::Backward;
+        assert_eq!(s.find_misspelled(&buf, Position::new(2, 0), back), Some(Position::new(0, 0)));
+        assert_eq!(s.find_misspelled(&buf, Position::new(0, 0), back), Some(Position::new(2, 0)));
+    }
+
+    #[test]
+    fn find_same_line() {
+        let s = en();
+        let buf = Buffer::from_text("teh and wrod\n");
+        let fwd = SearchDirection::Forward;
+        assert_eq!(s.find_misspelled(&buf, Position::new(0, 0), fwd), Some(Position::new(0, 8)));
+        assert_eq!(s.find_misspelled(&buf, Position::new(0, 8), fwd), Some(Position::new(0, 0)));
+        let back = SearchDirection::Backward;
+        assert_eq!(s.find_misspelled(&buf, Position::new(0, 8), back), Some(Position::new(0, 0)));
+    }
+
+    #[test]
+    fn find_none_when_clean() {
+        let buf = Buffer::from_text("all good here\n");
+        assert_eq!(en().find_misspelled(&buf, Position::new(0, 0), SearchDirection::Forward), None);
+    }
+}
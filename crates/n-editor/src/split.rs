@@ -21,10 +21,23 @@
 //! because each window's status line (rendered by [`View`]) acts as a
 //! natural visual boundary.
 //!
+//! # Sizes
+//!
+//! A new horizontal split divides its rows in half. Each horizontal split
+//! keeps the share of its rows given to the top side, so `Ctrl+W +` /
+//! `Ctrl+W -` ([`Split::resize_height`]) can move rows between the two
+//! sides and `Ctrl+W =` ([`Split::equalize`]) can even them out again.
+//! Vertical splits always divide their columns in half.
+//!
 //! # Window IDs
 //!
 //! Each window has a unique `WinId` (monotonically increasing). The split
 //! tree stores only IDs; actual window state lives in the editor.
+//!
+//! # Floating windows
+//!
+//! Floats sit outside the tree: the editor draws them over the layout at
+//! a fixed [`Rect`], optionally framed by a [`BorderStyle`].
 
 /// Unique window identifier. Monotonically increasing, never reused.
 pub type WinId = usize;
@@ -38,6 +51,72 @@ pub struct Rect {
     pub h: u16,
 }
 
+impl Rect {
+    /// Clip this rectangle to `bounds`. The result may be empty.
+    #[must_use]
+    pub fn clamp_to(self, bounds: Self) -> Self {
+        let x = self.x.clamp(bounds.x, bounds.x + bounds.w);
+        let y = self.y.clamp(bounds.y, bounds.y + bounds.h);
+        let right = (self.x.saturating_add(self.w)).min(bounds.x + bounds.w);
+        let bottom = (self.y.saturating_add(self.h)).min(bounds.y + bounds.h);
+        Self { x, y, w: right.saturating_sub(x), h: bottom.saturating_sub(y) }
+    }
+}
+
+/// Border drawn around a floating window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderStyle {
+    /// No border — the content fills the whole rectangle.
+    None,
+    /// `┌─┐` light box-drawing lines.
+    Single,
+    /// `╔═╗` double lines.
+    Double,
+    /// `╭─╮` light lines with rounded corners.
+    Rounded,
+}
+
+impl BorderStyle {
+    /// Parse a border style from its name (`none`, `single`, `double`,
+    /// `rounded`).
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(Self::None),
+            "single" => Some(Self::Single),
+            "double" => Some(Self::Double),
+            "rounded" => Some(Self::Rounded),
+            _ => None,
+        }
+    }
+
+    /// Box-drawing characters as `[top-left, top-right, bottom-left,
+    /// bottom-right, horizontal, vertical]`, or `None` for no border.
+    #[must_use]
+    pub const fn chars(self) -> Option<[char; 6]> {
+        match self {
+            Self::None => None,
+            Self::Single => Some(['┌', '┐', '└', '┘', '─', '│']),
+            Self::Double => Some(['╔', '╗', '╚', '╝', '═', '║']),
+            Self::Rounded => Some(['╭', '╮', '╰', '╯', '─', '│']),
+        }
+    }
+
+    /// The area inside the border of a window occupying `rect`.
+    #[must_use]
+    pub const fn inner(self, rect: Rect) -> Rect {
+        if matches!(self, Self::None) {
+            return rect;
+        }
+        Rect {
+            x: rect.x + 1,
+            y: rect.y + 1,
+            w: rect.w.saturating_sub(2),
+            h: rect.h.saturating_sub(2),
+        }
+    }
+}
+
 /// Navigation direction for `Ctrl+W h/j/k/l`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
@@ -51,7 +130,7 @@ pub enum Direction {
 ///
 /// Leaves hold window IDs. Internal nodes split the space either
 /// horizontally (top/bottom) or vertically (left/right).
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Split {
     /// A single window occupying the entire area.
     Leaf(WinId),
@@ -61,6 +140,9 @@ pub enum Split {
     Horizontal {
         first: Box<Self>,
         second: Box<Self>,
+        /// Share of the height given to `first` (0.5 for an even split).
+        /// Changed by `Ctrl+W +` / `Ctrl+W -`, reset by `Ctrl+W =`.
+        ratio: f32,
     },
 
     /// Vertical split: first is on the left, second is on the right.
@@ -84,6 +166,7 @@ impl Split {
         Self::Horizontal {
             first: Box::new(top),
             second: Box::new(bottom),
+            ratio: 0.5,
         }
     }
 
@@ -109,7 +192,7 @@ impl Split {
     fn collect_leaves(&self, out: &mut Vec<WinId>) {
         match self {
             Self::Leaf(id) => out.push(*id),
-            Self::Horizontal { first, second } | Self::Vertical { first, second } => {
+            Self::Horizontal { first, second, .. } | Self::Vertical { first, second } => {
                 first.collect_leaves(out);
                 second.collect_leaves(out);
             }
@@ -121,7 +204,7 @@ impl Split {
     pub fn window_count(&self) -> usize {
         match self {
             Self::Leaf(_) => 1,
-            Self::Horizontal { first, second } | Self::Vertical { first, second } => {
+            Self::Horizontal { first, second, .. } | Self::Vertical { first, second } => {
                 first.window_count() + second.window_count()
             }
         }
@@ -132,12 +215,23 @@ impl Split {
     pub fn contains(&self, id: WinId) -> bool {
         match self {
             Self::Leaf(w) => *w == id,
-            Self::Horizontal { first, second } | Self::Vertical { first, second } => {
+            Self::Horizontal { first, second, .. } | Self::Vertical { first, second } => {
                 first.contains(id) || second.contains(id)
             }
         }
     }
 
+    /// The fewest rows the subtree fits in: one per window stacked top
+    /// to bottom.
+    #[must_use]
+    pub fn min_height(&self) -> u16 {
+        match self {
+            Self::Leaf(_) => 1,
+            Self::Horizontal { first, second, .. } => first.min_height() + second.min_height(),
+            Self::Vertical { first, second } => first.min_height().max(second.min_height()),
+        }
+    }
+
     // -- Layout ----------------------------------------------------------------
 
     /// Compute screen rectangles for all leaf windows.
@@ -161,19 +255,11 @@ impl Split {
             Self::Leaf(id) => {
                 out.push((*id, area));
             }
-            Self::Horizontal { first, second } => {
-                // Split vertically in half (top/bottom).
-                let top_h = area.h / 2;
-                let bottom_h = area.h - top_h;
-
-                first.layout_into(
-                    Rect { x: area.x, y: area.y, w: area.w, h: top_h },
-                    out,
-                );
-                second.layout_into(
-                    Rect { x: area.x, y: area.y + top_h, w: area.w, h: bottom_h },
-                    out,
-                );
+            Self::Horizontal { first, second, ratio } => {
+                // Split vertically by `ratio` (top/bottom).
+                let (top, bottom) = split_rows(area, *ratio);
+                first.layout_into(top, out);
+                second.layout_into(bottom, out);
             }
             Self::Vertical { first, second } => {
                 // Split horizontally (left/right) with 1-col separator.
@@ -212,18 +298,10 @@ impl Split {
     fn separators_into(&self, area: Rect, out: &mut Vec<(u16, u16, u16)>) {
         match self {
             Self::Leaf(_) => {}
-            Self::Horizontal { first, second } => {
-                let top_h = area.h / 2;
-                let bottom_h = area.h - top_h;
-
-                first.separators_into(
-                    Rect { x: area.x, y: area.y, w: area.w, h: top_h },
-                    out,
-                );
-                second.separators_into(
-                    Rect { x: area.x, y: area.y + top_h, w: area.w, h: bottom_h },
-                    out,
-                );
+            Self::Horizontal { first, second, ratio } => {
+                let (top, bottom) = split_rows(area, *ratio);
+                first.separators_into(top, out);
+                second.separators_into(bottom, out);
             }
             Self::Vertical { first, second } => {
                 if area.w < 3 {
@@ -260,11 +338,12 @@ impl Split {
                 *self = Self::Horizontal {
                     first: Box::new(Self::Leaf(target)),
                     second: Box::new(Self::Leaf(new_id)),
+                    ratio: 0.5,
                 };
                 true
             }
             Self::Leaf(_) => false,
-            Self::Horizontal { first, second } | Self::Vertical { first, second } => {
+            Self::Horizontal { first, second, .. } | Self::Vertical { first, second } => {
                 first.split_horizontal(target, new_id)
                     || second.split_horizontal(target, new_id)
             }
@@ -285,7 +364,7 @@ impl Split {
                 true
             }
             Self::Leaf(_) => false,
-            Self::Horizontal { first, second } | Self::Vertical { first, second } => {
+            Self::Horizontal { first, second, .. } | Self::Vertical { first, second } => {
                 first.split_vertical(target, new_id)
                     || second.split_vertical(target, new_id)
             }
@@ -310,7 +389,7 @@ impl Split {
     fn remove_inner(&mut self, target: WinId) -> bool {
         match self {
             Self::Leaf(_) => false,
-            Self::Horizontal { first, second } | Self::Vertical { first, second } => {
+            Self::Horizontal { first, second, .. } | Self::Vertical { first, second } => {
                 // Check if target is a direct child.
                 if matches!(first.as_ref(), Self::Leaf(id) if *id == target) {
                     // Replace self with the sibling.
@@ -327,6 +406,116 @@ impl Split {
         }
     }
 
+    /// Move the window `target` to an edge of the screen (`Ctrl+W H/J/K/L`):
+    /// it's taken out of its split and put beside the rest of the tree, so
+    /// it spans the full height (left / right) or width (top / bottom).
+    ///
+    /// Returns `false` if the target isn't in the tree or is the only
+    /// window.
+    pub fn move_to_edge(&mut self, target: WinId, dir: Direction) -> bool {
+        if !self.contains(target) || !self.remove(target) {
+            return false;
+        }
+        let rest = std::mem::replace(self, Self::Leaf(target));
+        let moved = Self::Leaf(target);
+        *self = match dir {
+            Direction::Left => Self::vertical(moved, rest),
+            Direction::Right => Self::vertical(rest, moved),
+            Direction::Up => Self::horizontal(moved, rest),
+            Direction::Down => Self::horizontal(rest, moved),
+        };
+        true
+    }
+
+    /// Rotate the windows in the row or column `target` is in
+    /// (`Ctrl+W r` / `Ctrl+W R`). Forward moves each window one slot down
+    /// or right and the last to the front: `[A][B][C]` becomes
+    /// `[C][A][B]`. The slots keep their sizes; only which window is in
+    /// each one changes.
+    ///
+    /// Returns `false` if the target isn't in the tree, or if another
+    /// window in its row or column is split the other way.
+    pub fn rotate(&mut self, target: WinId, forward: bool) -> bool {
+        let Some(group) = self.group_mut(target) else {
+            return false;
+        };
+        if !group.is_uniform() {
+            return false;
+        }
+        let mut ids = group.leaves();
+        if forward {
+            ids.rotate_right(1);
+        } else {
+            ids.rotate_left(1);
+        }
+        group.set_leaves(&mut ids.into_iter());
+        true
+    }
+
+    /// The outermost split whose row or column `target` is directly in
+    /// (`self` for a lone leaf).
+    fn group_mut(&mut self, target: WinId) -> Option<&mut Self> {
+        if self.in_group(target, self.is_horizontal()) {
+            return Some(self);
+        }
+        match self {
+            Self::Leaf(_) => None,
+            Self::Horizontal { first, second, .. } | Self::Vertical { first, second } => {
+                if first.contains(target) {
+                    first.group_mut(target)
+                } else {
+                    second.group_mut(target)
+                }
+            }
+        }
+    }
+
+    /// Whether `target` is reached from here through splits that are all
+    /// horizontal (or all vertical).
+    fn in_group(&self, target: WinId, horizontal: bool) -> bool {
+        match self {
+            Self::Leaf(id) => *id == target,
+            Self::Horizontal { first, second, .. } | Self::Vertical { first, second } => {
+                self.is_horizontal() == horizontal
+                    && (first.in_group(target, horizontal) || second.in_group(target, horizontal))
+            }
+        }
+    }
+
+    const fn is_horizontal(&self) -> bool {
+        matches!(self, Self::Horizontal { .. })
+    }
+
+    /// Whether every split in the subtree goes the same way as this one.
+    fn is_uniform(&self) -> bool {
+        fn same_way(node: &Split, horizontal: bool) -> bool {
+            match node {
+                Split::Leaf(_) => true,
+                Split::Horizontal { first, second, .. } | Split::Vertical { first, second } => {
+                    node.is_horizontal() == horizontal
+                        && same_way(first, horizontal)
+                        && same_way(second, horizontal)
+                }
+            }
+        }
+        same_way(self, self.is_horizontal())
+    }
+
+    /// Put the window IDs from `ids` into the leaves, in order.
+    fn set_leaves(&mut self, ids: &mut impl Iterator<Item = WinId>) {
+        match self {
+            Self::Leaf(id) => {
+                if let Some(next) = ids.next() {
+                    *id = next;
+                }
+            }
+            Self::Horizontal { first, second, .. } | Self::Vertical { first, second } => {
+                first.set_leaves(ids);
+                second.set_leaves(ids);
+            }
+        }
+    }
+
     /// Replace the tree with a single leaf, removing all other windows.
     /// Returns the list of removed window IDs.
     pub fn keep_only(&mut self, keep: WinId) -> Vec<WinId> {
@@ -336,6 +525,67 @@ impl Split {
         removed
     }
 
+    /// Make the window `target` `delta` rows taller (shorter for a
+    /// negative `delta`) when the tree is laid out in `area`.
+    ///
+    /// The rows come from (or go to) the other side of the nearest
+    /// horizontal split above the window. Neither side shrinks below
+    /// [`Split::min_height`]. Returns `false` if the window isn't in a
+    /// horizontal split (or isn't in the tree).
+    pub fn resize_height(&mut self, target: WinId, delta: i32, area: Rect) -> bool {
+        self.resize_height_inner(target, delta, area) == Some(true)
+    }
+
+    /// `None` if `target` isn't in this subtree, otherwise whether a
+    /// split was resized.
+    fn resize_height_inner(&mut self, target: WinId, delta: i32, area: Rect) -> Option<bool> {
+        match self {
+            Self::Leaf(id) => (*id == target).then_some(false),
+            Self::Vertical { first, second } => first
+                .resize_height_inner(target, delta, area)
+                .or_else(|| second.resize_height_inner(target, delta, area)),
+            Self::Horizontal { first, second, ratio } => {
+                let (top, bottom) = split_rows(area, *ratio);
+                let (resized, grow_top) = match first.resize_height_inner(target, delta, top) {
+                    Some(resized) => (resized, delta),
+                    None => (second.resize_height_inner(target, delta, bottom)?, -delta),
+                };
+                if resized {
+                    return Some(true);
+                }
+                let lo = i32::from(first.min_height());
+                let hi = i32::from(area.h) - i32::from(second.min_height());
+                if lo > hi {
+                    return Some(false);
+                }
+                let new_top = (i32::from(top.h) + grow_top).clamp(lo, hi);
+                #[allow(clippy::cast_precision_loss)]
+                let new_ratio = new_top as f32 / f32::from(area.h);
+                *ratio = new_ratio;
+                Some(true)
+            }
+        }
+    }
+
+    /// Give every window an equal share of the height (`Ctrl+W =`): each
+    /// horizontal split divides its rows by the number of windows stacked
+    /// on either side.
+    pub fn equalize(&mut self) {
+        match self {
+            Self::Leaf(_) => {}
+            Self::Horizontal { first, second, ratio } => {
+                first.equalize();
+                second.equalize();
+                let top = f32::from(first.min_height());
+                *ratio = top / (top + f32::from(second.min_height()));
+            }
+            Self::Vertical { first, second } => {
+                first.equalize();
+                second.equalize();
+            }
+        }
+    }
+
     // -- Navigation ------------------------------------------------------------
 
     /// Find the next window to cycle to after `current`.
@@ -422,6 +672,17 @@ impl Split {
     }
 }
 
+/// Divide `area` into top and bottom parts, the top getting `ratio` of
+/// the rows. An exact half row goes to the bottom.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn split_rows(area: Rect, ratio: f32) -> (Rect, Rect) {
+    let top_h = (f32::from(area.h).mul_add(ratio, -0.5).ceil().max(0.0) as u16).min(area.h);
+    (
+        Rect { x: area.x, y: area.y, w: area.w, h: top_h },
+        Rect { x: area.x, y: area.y + top_h, w: area.w, h: area.h - top_h },
+    )
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -790,6 +1051,162 @@ mod tests {
         assert_eq!(rects[1], (2, Rect { x: 5, y: 13, w: 40, h: 10 }));
     }
 
+    // ── Resizing ─────────────────────────────────────────────────────────
+
+    fn height_of(s: &Split, id: WinId, area: Rect) -> u16 {
+        s.layout(area).iter().find(|(w, _)| *w == id).unwrap().1.h
+    }
+
+    #[test]
+    fn resize_grows_at_the_neighbors_expense() {
+        let mut s = Split::horizontal(Split::leaf(1), Split::leaf(2));
+        let area = Rect { x: 0, y: 0, w: 80, h: 24 };
+        assert!(s.resize_height(2, 5, area));
+        assert_eq!(height_of(&s, 1, area), 7);
+        assert_eq!(height_of(&s, 2, area), 17);
+        assert!(s.resize_height(1, -3, area));
+        assert_eq!(height_of(&s, 1, area), 4);
+    }
+
+    #[test]
+    fn resize_clamps_to_min_height() {
+        // HSplit(1, HSplit(2, 3)): 1 can't take the last row from 2 or 3.
+        let mut s = Split::horizontal(
+            Split::leaf(1),
+            Split::horizontal(Split::leaf(2), Split::leaf(3)),
+        );
+        let area = Rect { x: 0, y: 0, w: 80, h: 24 };
+        assert!(s.resize_height(1, 100, area));
+        assert_eq!(height_of(&s, 1, area), 22);
+        assert_eq!(height_of(&s, 2, area), 1);
+        assert_eq!(height_of(&s, 3, area), 1);
+        assert!(s.resize_height(1, -100, area));
+        assert_eq!(height_of(&s, 1, area), 1);
+    }
+
+    #[test]
+    fn resize_uses_nearest_horizontal_split() {
+        // HSplit(VSplit(1, 2), 3): 2 grows its whole row.
+        let mut s = Split::horizontal(
+            Split::vertical(Split::leaf(1), Split::leaf(2)),
+            Split::leaf(3),
+        );
+        let area = Rect { x: 0, y: 0, w: 80, h: 20 };
+        assert!(s.resize_height(2, 4, area));
+        assert_eq!(height_of(&s, 1, area), 14);
+        assert_eq!(height_of(&s, 2, area), 14);
+        assert_eq!(height_of(&s, 3, area), 6);
+
+        // No horizontal split above the window: nothing to resize.
+        let mut v = Split::vertical(Split::leaf(1), Split::leaf(2));
+        assert!(!v.resize_height(1, 4, area));
+        assert!(!s.resize_height(9, 4, area));
+    }
+
+    #[test]
+    fn equalize_counts_stacked_windows() {
+        // HSplit(HSplit(1, 3), 2) — what `:sp` twice from window 1 gives.
+        let mut s = Split::horizontal(
+            Split::horizontal(Split::leaf(1), Split::leaf(3)),
+            Split::leaf(2),
+        );
+        let area = Rect { x: 0, y: 0, w: 80, h: 24 };
+        assert_eq!(height_of(&s, 2, area), 12);
+        s.equalize();
+        for id in [1, 2, 3] {
+            assert_eq!(height_of(&s, id, area), 8);
+        }
+    }
+
+    // ── Moving to an edge ────────────────────────────────────────────────
+
+    #[test]
+    fn move_to_edge_spans_the_screen() {
+        // VSplit(1, HSplit(2, 3))
+        let mut s = Split::vertical(
+            Split::leaf(1),
+            Split::horizontal(Split::leaf(2), Split::leaf(3)),
+        );
+        let area = Rect { x: 0, y: 0, w: 81, h: 24 };
+        assert_eq!(s.layout(area)[2], (3, Rect { x: 41, y: 12, w: 40, h: 12 }));
+
+        // Ctrl+W J: 3 takes the full width at the bottom.
+        assert!(s.move_to_edge(3, Direction::Down));
+        assert_eq!(
+            s.layout(area),
+            vec![
+                (1, Rect { x: 0, y: 0, w: 40, h: 12 }),
+                (2, Rect { x: 41, y: 0, w: 40, h: 12 }),
+                (3, Rect { x: 0, y: 12, w: 81, h: 12 }),
+            ]
+        );
+
+        // Ctrl+W H: 2 takes the full height on the left.
+        assert!(s.move_to_edge(2, Direction::Left));
+        assert_eq!(
+            s.layout(area),
+            vec![
+                (2, Rect { x: 0, y: 0, w: 40, h: 24 }),
+                (1, Rect { x: 41, y: 0, w: 40, h: 12 }),
+                (3, Rect { x: 41, y: 12, w: 40, h: 12 }),
+            ]
+        );
+
+        // Ctrl+W K and Ctrl+W L put it on top and on the right.
+        assert!(s.move_to_edge(3, Direction::Up));
+        assert_eq!(s.layout(area)[0], (3, Rect { x: 0, y: 0, w: 81, h: 12 }));
+        assert!(s.move_to_edge(3, Direction::Right));
+        assert_eq!(s.layout(area)[2], (3, Rect { x: 41, y: 0, w: 40, h: 24 }));
+        assert_eq!(s.window_count(), 3);
+    }
+
+    #[test]
+    fn move_to_edge_needs_another_window() {
+        let mut s = Split::leaf(1);
+        assert!(!s.move_to_edge(1, Direction::Left));
+        assert_eq!(s, Split::leaf(1));
+        let mut s = Split::horizontal(Split::leaf(1), Split::leaf(2));
+        assert!(!s.move_to_edge(7, Direction::Left));
+        assert_eq!(s.window_count(), 2);
+    }
+
+    // ── Rotation ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn rotate_moves_windows_between_slots() {
+        // HSplit(1, HSplit(2, 3)) — one column [1][2][3].
+        let mut s = Split::horizontal(
+            Split::leaf(1),
+            Split::horizontal(Split::leaf(2), Split::leaf(3)),
+        );
+        let area = Rect { x: 0, y: 0, w: 80, h: 24 };
+        let slots = s.layout(area).iter().map(|(_, r)| *r).collect::<Vec<_>>();
+
+        assert!(s.rotate(2, true));
+        assert_eq!(s.leaves(), vec![3, 1, 2]);
+        let after: Vec<_> = s.layout(area).iter().map(|(_, r)| *r).collect();
+        assert_eq!(after, slots);
+
+        assert!(s.rotate(2, false));
+        assert!(s.rotate(2, false));
+        assert_eq!(s.leaves(), vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn rotate_stays_in_the_windows_row() {
+        // VSplit(HSplit(1, 2), 3): rotating 1 swaps it with 2 only.
+        let mut s = Split::vertical(
+            Split::horizontal(Split::leaf(1), Split::leaf(2)),
+            Split::leaf(3),
+        );
+        assert!(s.rotate(1, true));
+        assert_eq!(s.leaves(), vec![2, 1, 3]);
+        // 3's row holds a split column, so it can't rotate.
+        assert!(!s.rotate(3, true));
+        assert_eq!(s.leaves(), vec![2, 1, 3]);
+        assert!(!s.rotate(9, true));
+    }
+
     // ── Contains ─────────────────────────────────────────────────────────
 
     #[test]
@@ -803,4 +1220,32 @@ mod tests {
         assert!(s.contains(3));
         assert!(!s.contains(4));
     }
+
+    // ── Floats ───────────────────────────────────────────────────────────
+
+    #[test]
+    fn border_inner_rect() {
+        let r = Rect { x: 4, y: 2, w: 10, h: 5 };
+        assert_eq!(BorderStyle::None.inner(r), r);
+        assert_eq!(BorderStyle::Rounded.inner(r), Rect { x: 5, y: 3, w: 8, h: 3 });
+        let tiny = Rect { x: 0, y: 0, w: 1, h: 1 };
+        assert_eq!(BorderStyle::Single.inner(tiny).w, 0);
+    }
+
+    #[test]
+    fn border_names_and_chars() {
+        assert_eq!(BorderStyle::from_name("double"), Some(BorderStyle::Double));
+        assert_eq!(BorderStyle::from_name("shadow"), None);
+        assert!(BorderStyle::None.chars().is_none());
+        assert_eq!(BorderStyle::Rounded.chars().unwrap()[0], '╭');
+    }
+
+    #[test]
+    fn rect_clamped_to_bounds() {
+        let screen = Rect { x: 0, y: 0, w: 20, h: 10 };
+        let r = Rect { x: 15, y: 8, w: 10, h: 10 };
+        assert_eq!(r.clamp_to(screen), Rect { x: 15, y: 8, w: 5, h: 2 });
+        let off = Rect { x: 30, y: 0, w: 5, h: 5 };
+        assert_eq!(off.clamp_to(screen).w, 0);
+    }
 }
@@ -0,0 +1,146 @@
+//! Surround — the pair of delimiters around some text, for `ys`, `cs` and
+//! `ds`.
+//!
+//! A delimiter key names a pair, as in vim-surround:
+//!
+//! | Key              | Pair                                   |
+//! |------------------|----------------------------------------|
+//! | `)` `b`          | `(` … `)`                              |
+//! | `]` `r`          | `[` … `]`                              |
+//! | `}` `B`          | `{` … `}`                              |
+//! | `>` `a`          | `<` … `>`                              |
+//! | `(` `[` `{`      | the same pair with a blank inside each |
+//! | `"` `'` `` ` ``  | the quote on both sides                |
+//! | other punctuation| the character on both sides            |
+//!
+//! Adding also takes a tag (`<em>` wraps in `<em>` … `</em>`); the editor
+//! reads it and builds the pair with [`tag`].
+//!
+//! [`find`] locates the pair a key names around the cursor, using the
+//! [`text_object`](crate::text_object) finders for brackets and quotes.
+//! Other punctuation pairs up on the cursor line like quotes do.
+//! With an opening bracket key (`ds(`), the blanks just inside the pair go
+//! with it, undoing what `ys{motion}(` added.
+
+use crate::buffer::Buffer;
+use crate::position::{Position, Range};
+use crate::text_object;
+
+/// The opening and closing text `key` surrounds with, or `None` for keys
+/// that don't name a pair (letters, digits, blanks, and `<`, which starts
+/// a tag).
+#[must_use]
+pub fn pair(key: char) -> Option<(String, String)> {
+    let (open, close) = match key {
+        ')' | 'b' => ("(", ")"),
+        ']' | 'r' => ("[", "]"),
+        '}' | 'B' => ("{", "}"),
+        '>' | 'a' => ("<", ">"),
+        '(' => ("( ", " )"),
+        '[' => ("[ ", " ]"),
+        '{' => ("{ ", " }"),
+        '<' => return None,
+        c if c.is_ascii_punctuation() => {
+            return Some((c.to_string(), c.to_string()));
+        }
+        _ => return None,
+    };
+    Some((open.to_string(), close.to_string()))
+}
+
+/// The pair for the tag typed as `text` (without the `<` and `>`):
+/// `a href="x"` gives `<a href="x">` and `</a>`.
+#[must_use]
+pub fn tag(text: &str) -> (String, String) {
+    let name = text.split_whitespace().next().unwrap_or_default();
+    (format!("<{text}>"), format!("</{name}>"))
+}
+
+/// A pair found in the buffer: the ranges of its opening and closing text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Found {
+    /// The opening delimiter, with the blanks after it for `(`, `[`, `{`.
+    pub open: Range,
+    /// The closing delimiter, with the blanks before it for `(`, `[`, `{`.
+    pub close: Range,
+}
+
+/// The pair `key` names around `pos` (see the module docs). Quotes and
+/// other punctuation are looked for on the cursor line only, pairing up
+/// from the start of the line.
+#[must_use]
+pub fn find(buf: &Buffer, pos: Position, key: char) -> Option<Found> {
+    let around = match key {
+        '(' | ')' | 'b' => text_object::a_paren(buf, pos, 1)?,
+        '[' | ']' | 'r' => text_object::a_square(buf, pos, 1)?,
+        '{' | '}' | 'B' => text_object::a_curly(buf, pos, 1)?,
+        '<' | '>' | 'a' => text_object::a_angle(buf, pos, 1)?,
+        c if c.is_ascii_punctuation() => text_object::a_quote(buf, pos, c)?,
+        _ => return None,
+    };
+    let rope = buf.rope();
+    let open = buf.pos_to_char_idx(around.start)?;
+    let close = buf.pos_to_char_idx(around.end)?.checked_sub(1)?;
+    let (mut open_end, mut close_start) = (open + 1, close);
+    if matches!(key, '(' | '[' | '{') {
+        while open_end < close_start && matches!(rope.char(open_end), ' ' | '\t') {
+            open_end += 1;
+        }
+        while close_start > open_end && matches!(rope.char(close_start - 1), ' ' | '\t') {
+            close_start -= 1;
+        }
+    }
+    let to_pos = |idx: usize| buf.char_idx_to_pos(idx);
+    Some(Found {
+        open: Range::new(to_pos(open)?, to_pos(open_end)?),
+        close: Range::new(to_pos(close_start)?, to_pos(close + 1)?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(sl: usize, sc: usize, el: usize, ec: usize) -> Range {
+        Range::new(Position::new(sl, sc), Position::new(el, ec))
+    }
+
+    #[test]
+    fn pairs_by_key() {
+        assert_eq!(pair('b'), Some(("(".into(), ")".into())));
+        assert_eq!(pair('('), Some(("( ".into(), " )".into())));
+        assert_eq!(pair('"'), Some(("\"".into(), "\"".into())));
+        assert_eq!(pair('*'), Some(("*".into(), "*".into())));
+        assert_eq!(pair('<'), None);
+        assert_eq!(pair('x'), None);
+        assert_eq!(tag("a href=\"x\""), ("<a href=\"x\">".into(), "</a>".into()));
+    }
+
+    #[test]
+    fn find_brackets_and_quotes() {
+        let buf = Buffer::from_text("f(\"a b\", [ x ])");
+        let found = find(&buf, Position::new(0, 4), '"').unwrap();
+        assert_eq!((found.open, found.close), (r(0, 2, 0, 3), r(0, 6, 0, 7)));
+        let found = find(&buf, Position::new(0, 4), ')').unwrap();
+        assert_eq!((found.open, found.close), (r(0, 1, 0, 2), r(0, 14, 0, 15)));
+        // An opening bracket key takes the blanks inside the pair too.
+        let found = find(&buf, Position::new(0, 11), '[').unwrap();
+        assert_eq!((found.open, found.close), (r(0, 9, 0, 11), r(0, 12, 0, 14)));
+        let found = find(&buf, Position::new(0, 11), ']').unwrap();
+        assert_eq!((found.open, found.close), (r(0, 9, 0, 10), r(0, 13, 0, 14)));
+        assert_eq!(find(&buf, Position::new(0, 4), '{'), None);
+    }
+
+    #[test]
+    fn find_other_punctuation_on_the_line() {
+        let buf = Buffer::from_text("a *b* c *d*");
+        let found = find(&buf, Position::new(0, 3), '*').unwrap();
+        assert_eq!((found.open, found.close), (r(0, 2, 0, 3), r(0, 4, 0, 5)));
+        // On the closing one, and between pairs: the next pair.
+        let found = find(&buf, Position::new(0, 4), '*').unwrap();
+        assert_eq!(found.open, r(0, 2, 0, 3));
+        let found = find(&buf, Position::new(0, 6), '*').unwrap();
+        assert_eq!((found.open, found.close), (r(0, 8, 0, 9), r(0, 10, 0, 11)));
+        assert_eq!(find(&buf, Position::new(0, 6), '_'), None);
+    }
+}
@@ -0,0 +1,243 @@
+//! Tag files — `ctags` output for `Ctrl+]`, `Ctrl+T` and `:tag`.
+//!
+//! A [`TagDatabase`] holds the tags read from the files named by the
+//! `tags` option. Each line of a tags file (exuberant / universal ctags
+//! format) is
+//!
+//! ```text
+//! {tagname}<Tab>{filename}<Tab>{address}[;"<Tab>{extension fields}]
+//! ```
+//!
+//! The address is either a line number (`42`) or a search pattern
+//! (`/^fn main() {$/`, or `?…?`). Patterns are literal text — only `^`
+//! and `$` at the ends are anchors, and `\/` / `\\` are escapes. Lines
+//! starting with `!_TAG_` are metadata and are skipped.
+//!
+//! File names in a tags file are relative to the directory the tags file
+//! is in.
+//!
+//! The `tags` option is a comma-separated list of file names. A name
+//! starting with `./` is relative to the current file's directory, other
+//! relative names to the working directory. Each name is looked for in
+//! that directory first and then in each parent up to the root; the first
+//! file found is used.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where a tag's definition is within its file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagAddress {
+    /// A 0-indexed line number.
+    Line(usize),
+    /// A line to search for: the literal text, and whether it must match
+    /// the whole line (`^…$`) or just its start (`^…`).
+    Pattern {
+        /// The text to look for.
+        text: String,
+        /// Anchored at the start of the line (`^`).
+        start: bool,
+        /// Anchored at the end of the line (`$`).
+        end: bool,
+    },
+}
+
+/// One tag: a name and where it's defined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    /// The identifier.
+    pub name: String,
+    /// The file it's defined in, resolved against the tags file's
+    /// directory.
+    pub file: PathBuf,
+    /// Where in the file.
+    pub address: TagAddress,
+}
+
+impl Tag {
+    /// The 0-indexed line of the definition in `text` (the file's
+    /// contents). A pattern that no longer matches gives `None`.
+    #[must_use]
+    pub fn line_in(&self, text: &str) -> Option<usize> {
+        match &self.address {
+            TagAddress::Line(line) => Some(*line),
+            TagAddress::Pattern { text: pat, start, end } => text.lines().position(|line| match (start, end) {
+                (true, true) => line == pat,
+                (true, false) => line.starts_with(pat.as_str()),
+                (false, true) => line.ends_with(pat.as_str()),
+                (false, false) => line.contains(pat.as_str()),
+            }),
+        }
+    }
+}
+
+/// The tags from every tags file found.
+#[derive(Debug, Default)]
+pub struct TagDatabase {
+    tags: Vec<Tag>,
+}
+
+impl TagDatabase {
+    /// Read the tags files named by `option` (the `tags` option). `cwd` is
+    /// the working directory; `file_dir` is the current file's directory,
+    /// for `./` names (the working directory if the buffer has no file).
+    #[must_use]
+    pub fn load(option: &str, cwd: &Path, file_dir: Option<&Path>) -> Self {
+        let mut db = Self::default();
+        let mut seen: Vec<PathBuf> = Vec::new();
+        for name in option.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            let (base, rel) = name.strip_prefix("./").map_or((cwd, name), |rel| (file_dir.unwrap_or(cwd), rel));
+            let Some(path) = find_upward(base, rel) else { continue };
+            let canon = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if seen.contains(&canon) {
+                continue;
+            }
+            seen.push(canon);
+            if let Ok(text) = fs::read_to_string(&path) {
+                let dir = path.parent().unwrap_or_else(|| Path::new(""));
+                db.tags.extend(text.lines().filter_map(|line| parse_line(line, dir)));
+            }
+        }
+        db
+    }
+
+    /// A database of the tags in `text`, a tags file in `dir`.
+    #[must_use]
+    pub fn parse(text: &str, dir: &Path) -> Self {
+        Self { tags: text.lines().filter_map(|line| parse_line(line, dir)).collect() }
+    }
+
+    /// Every tag named `name`, in file order.
+    #[must_use]
+    pub fn find(&self, name: &str) -> Vec<&Tag> {
+        self.tags.iter().filter(|t| t.name == name).collect()
+    }
+
+    /// Number of tags.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    /// Whether no tags were found.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+}
+
+/// `rel` in `dir` or the nearest parent that has it.
+fn find_upward(dir: &Path, rel: &str) -> Option<PathBuf> {
+    let rel = Path::new(rel);
+    if rel.is_absolute() {
+        return rel.is_file().then(|| rel.to_path_buf());
+    }
+    dir.ancestors().map(|d| d.join(rel)).find(|p| p.is_file())
+}
+
+/// Parse one line of a tags file in `dir`.
+fn parse_line(line: &str, dir: &Path) -> Option<Tag> {
+    if line.starts_with("!_TAG_") {
+        return None;
+    }
+    let mut fields = line.splitn(3, '\t');
+    let name = fields.next().filter(|n| !n.is_empty())?;
+    let file = fields.next().filter(|f| !f.is_empty())?;
+    let address = parse_address(fields.next()?)?;
+    Some(Tag { name: name.to_string(), file: dir.join(file), address })
+}
+
+/// Parse a tag address, ignoring any `;"` extension fields after it.
+fn parse_address(field: &str) -> Option<TagAddress> {
+    let delim = field.chars().next()?;
+    if delim == '/' || delim == '?' {
+        let mut text = String::new();
+        let mut chars = field[1..].chars();
+        loop {
+            match chars.next()? {
+                '\\' => text.push(chars.next()?),
+                c if c == delim => break,
+                c => text.push(c),
+            }
+        }
+        let start = text.starts_with('^');
+        if start {
+            text.remove(0);
+        }
+        let end = text.ends_with('$');
+        if end {
+            text.pop();
+        }
+        return Some(TagAddress::Pattern { text, start, end });
+    }
+    let digits = field.split(|c: char| !c.is_ascii_digit()).next()?;
+    let line: usize = digits.parse().ok()?;
+    Some(TagAddress::Line(line.saturating_sub(1)))
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::DEFAULT_TAGS;
+
+    const TAGS: &str = "!_TAG_FILE_FORMAT\t2\t/extended format/\n\
+        main\tsrc/main.rs\t/^fn main() {$/;\"\tf\n\
+        Editor\tsrc/main.rs\t42;\"\ts\n\
+        helper\ta.c\t/^int helper(char *s, int n)$/\n\
+        helper\tb.c\t?^static int helper\\/2?\n";
+
+    #[test]
+    fn parses_lines_and_addresses() {
+        let db = TagDatabase::parse(TAGS, Path::new("/proj"));
+        assert_eq!(db.len(), 4);
+        let main = db.find("main");
+        assert_eq!(main.len(), 1);
+        assert_eq!(main[0].file, Path::new("/proj/src/main.rs"));
+        assert_eq!(
+            main[0].address,
+            TagAddress::Pattern { text: "fn main() {".to_string(), start: true, end: true }
+        );
+        assert_eq!(db.find("Editor")[0].address, TagAddress::Line(41));
+        let helpers = db.find("helper");
+        assert_eq!(helpers.len(), 2);
+        assert_eq!(
+            helpers[1].address,
+            TagAddress::Pattern { text: "static int helper/2".to_string(), start: true, end: false }
+        );
+        assert!(db.find("missing").is_empty());
+    }
+
+    #[test]
+    fn resolves_lines() {
+        let db = TagDatabase::parse(TAGS, Path::new(""));
+        let text = "use x;\n\nfn main() {\n}\n";
+        assert_eq!(db.find("main")[0].line_in(text), Some(2));
+        assert_eq!(db.find("Editor")[0].line_in(text), Some(41));
+        assert_eq!(db.find("main")[0].line_in("fn main() {} // moved"), None);
+    }
+
+    #[test]
+    fn load_searches_parent_directories() {
+        let root = std::env::temp_dir().join(format!("n-nvim-tags-{}", std::process::id()));
+        let sub = root.join("src").join("deep");
+        fs::create_dir_all(&sub).unwrap();
+        fs::write(root.join("tags"), "top\tsrc/lib.rs\t1\n").unwrap();
+        fs::write(root.join("src").join("tags"), "near\tlib.rs\t3\n").unwrap();
+
+        // `./tags` from the file's directory finds src/tags first; `tags`
+        // from the working directory finds the top-level file.
+        let db = TagDatabase::load(DEFAULT_TAGS, &root, Some(&sub));
+        assert_eq!(db.find("near")[0].file, root.join("src").join("lib.rs"));
+        assert_eq!(db.find("top")[0].file, root.join("src").join("lib.rs"));
+
+        // The same file named twice is read once.
+        let db = TagDatabase::load("tags,./tags", &root, None);
+        assert_eq!(db.len(), 1);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
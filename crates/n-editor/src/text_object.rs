@@ -13,6 +13,8 @@
 //! Each function takes a buffer and a cursor position, returning
 //! `Option<Range>` — the half-open `[start, end)` range of the text object.
 //! Returns `None` when the object cannot be found (e.g., no enclosing brackets).
+//! The bracket objects also take a count, the number of pairs to walk out:
+//! `2i(` selects inside the parentheses around the innermost ones.
 //!
 //! # Supported text objects
 //!
@@ -27,6 +29,8 @@
 //! | `i[`     | `a[`     | square-bracketed block          |
 //! | `i{`     | `a{`     | curly-braced block              |
 //! | `i<`     | `a<`     | angle-bracketed block           |
+//! | `is`     | `as`     | sentence                        |
+//! | `ii`     | `ai`     | indent block (whole lines)      |
 
 use crate::buffer::Buffer;
 use crate::position::{Position, Range};
@@ -267,8 +271,9 @@ fn inner_quote(buf: &Buffer, pos: Position, quote: char) -> Option<Range> {
     Some(Range::new(start, end))
 }
 
-/// Around quote — text including the quotes.
-fn a_quote(buf: &Buffer, pos: Position, quote: char) -> Option<Range> {
+/// Around quote — text including the quotes. Works for any character
+/// that pairs up on a line the way quotes do.
+pub(crate) fn a_quote(buf: &Buffer, pos: Position, quote: char) -> Option<Range> {
     let (open_col, close_col) = find_quote_pair(buf, pos, quote)?;
     Some(Range::new(
         Position::new(pos.line, open_col),
@@ -329,55 +334,61 @@ fn find_quote_pair(buf: &Buffer, pos: Position, quote: char) -> Option<(usize, u
 
 /// `i(` / `i)` — inner parentheses.
 #[must_use]
-pub fn inner_paren(buf: &Buffer, pos: Position) -> Option<Range> {
-    inner_bracket(buf, pos, '(', ')')
+pub fn inner_paren(buf: &Buffer, pos: Position, count: usize) -> Option<Range> {
+    inner_bracket(buf, pos, '(', ')', count)
 }
 
 /// `a(` / `a)` — around parentheses.
 #[must_use]
-pub fn a_paren(buf: &Buffer, pos: Position) -> Option<Range> {
-    a_bracket(buf, pos, '(', ')')
+pub fn a_paren(buf: &Buffer, pos: Position, count: usize) -> Option<Range> {
+    a_bracket(buf, pos, '(', ')', count)
 }
 
 /// `i[` / `i]` — inner square brackets.
 #[must_use]
-pub fn inner_square(buf: &Buffer, pos: Position) -> Option<Range> {
-    inner_bracket(buf, pos, '[', ']')
+pub fn inner_square(buf: &Buffer, pos: Position, count: usize) -> Option<Range> {
+    inner_bracket(buf, pos, '[', ']', count)
 }
 
 /// `a[` / `a]` — around square brackets.
 #[must_use]
-pub fn a_square(buf: &Buffer, pos: Position) -> Option<Range> {
-    a_bracket(buf, pos, '[', ']')
+pub fn a_square(buf: &Buffer, pos: Position, count: usize) -> Option<Range> {
+    a_bracket(buf, pos, '[', ']', count)
 }
 
 /// `i{` / `i}` — inner curly braces.
 #[must_use]
-pub fn inner_curly(buf: &Buffer, pos: Position) -> Option<Range> {
-    inner_bracket(buf, pos, '{', '}')
+pub fn inner_curly(buf: &Buffer, pos: Position, count: usize) -> Option<Range> {
+    inner_bracket(buf, pos, '{', '}', count)
 }
 
 /// `a{` / `a}` — around curly braces.
 #[must_use]
-pub fn a_curly(buf: &Buffer, pos: Position) -> Option<Range> {
-    a_bracket(buf, pos, '{', '}')
+pub fn a_curly(buf: &Buffer, pos: Position, count: usize) -> Option<Range> {
+    a_bracket(buf, pos, '{', '}', count)
 }
 
 /// `i<` / `i>` — inner angle brackets.
 #[must_use]
-pub fn inner_angle(buf: &Buffer, pos: Position) -> Option<Range> {
-    inner_bracket(buf, pos, '<', '>')
+pub fn inner_angle(buf: &Buffer, pos: Position, count: usize) -> Option<Range> {
+    inner_bracket(buf, pos, '<', '>', count)
 }
 
 /// `a<` / `a>` — around angle brackets.
 #[must_use]
-pub fn a_angle(buf: &Buffer, pos: Position) -> Option<Range> {
-    a_bracket(buf, pos, '<', '>')
+pub fn a_angle(buf: &Buffer, pos: Position, count: usize) -> Option<Range> {
+    a_bracket(buf, pos, '<', '>', count)
 }
 
 /// Inner bracket — text between matching brackets (excluding brackets).
-fn inner_bracket(buf: &Buffer, pos: Position, open: char, close: char) -> Option<Range> {
-    let (open_idx, close_idx) = find_bracket_pair(buf, pos, open, close)?;
+fn inner_bracket(
+    buf: &Buffer,
+    pos: Position,
+    open: char,
+    close: char,
+    count: usize,
+) -> Option<Range> {
+    let (open_idx, close_idx) = find_bracket_pair(buf, pos, open, close, count)?;
     let start = open_idx + 1;
     let end = close_idx;
     if start >= end {
@@ -388,15 +399,22 @@ fn inner_bracket(buf: &Buffer, pos: Position, open: char, close: char) -> Option
 }
 
 /// Around bracket — text including the brackets themselves.
-fn a_bracket(buf: &Buffer, pos: Position, open: char, close: char) -> Option<Range> {
-    let (open_idx, close_idx) = find_bracket_pair(buf, pos, open, close)?;
+fn a_bracket(
+    buf: &Buffer,
+    pos: Position,
+    open: char,
+    close: char,
+    count: usize,
+) -> Option<Range> {
+    let (open_idx, close_idx) = find_bracket_pair(buf, pos, open, close, count)?;
     Some(Range::new(
         idx_to_pos(buf, open_idx),
         idx_to_pos(buf, close_idx + 1),
     ))
 }
 
-/// Find the matching bracket pair containing the cursor.
+/// Find the bracket pair `count` levels out from the cursor: 1 (or 0) is
+/// the innermost pair containing it, 2 the pair around that one, and so on.
 ///
 /// Handles nesting and works across multiple lines. Returns the char indices
 /// of the opening and closing brackets: `(open_idx, close_idx)`.
@@ -405,6 +423,26 @@ fn find_bracket_pair(
     pos: Position,
     open: char,
     close: char,
+    count: usize,
+) -> Option<(usize, usize)> {
+    let (mut open_idx, mut close_idx) = find_innermost_pair(buf, pos, open, close)?;
+    let rope = buf.rope();
+    for _ in 1..count {
+        if open_idx == 0 {
+            return None;
+        }
+        open_idx = find_opening(rope, open_idx, open, close)?;
+        close_idx = find_closing(rope, open_idx, rope.len_chars(), open, close)?;
+    }
+    Some((open_idx, close_idx))
+}
+
+/// Find the innermost matching bracket pair containing the cursor.
+fn find_innermost_pair(
+    buf: &Buffer,
+    pos: Position,
+    open: char,
+    close: char,
 ) -> Option<(usize, usize)> {
     let rope = buf.rope();
     let total = rope.len_chars();
@@ -495,6 +533,274 @@ fn find_closing(
     None
 }
 
+// ---------------------------------------------------------------------------
+// Sentence objects
+// ---------------------------------------------------------------------------
+
+/// `is` — inner sentence.
+///
+/// Selects the sentence under the cursor without its trailing white space.
+/// On the white space between two sentences, selects that white space. On
+/// an empty line, selects the run of empty lines.
+#[must_use]
+pub fn inner_sentence(buf: &Buffer, pos: Position) -> Option<Range> {
+    let (start, end) = sentence_at(buf, pos)?;
+    Some(Range::new(idx_to_pos(buf, start), idx_to_pos(buf, end)))
+}
+
+/// `as` — a sentence.
+///
+/// Selects the sentence under the cursor plus the white space after it.
+/// When the sentence ends its paragraph, the blanks before it on the same
+/// line are taken instead. On the white space between two sentences,
+/// selects that white space and the following sentence.
+#[must_use]
+pub fn a_sentence(buf: &Buffer, pos: Position) -> Option<Range> {
+    let rope = buf.rope();
+    let total = rope.len_chars();
+    let (start, end) = sentence_at(buf, pos)?;
+    let (start, end) = if is_empty_line_start(rope, start) {
+        (start, end)
+    } else if rope.char(start).is_whitespace() {
+        // On the gap: take it and the sentence after it.
+        let next = next_sentence_start(rope, end).unwrap_or(total);
+        (start, sentence_text_end(rope, end, next))
+    } else {
+        match next_sentence_start(rope, start) {
+            Some(next) if !is_empty_line_start(rope, next) => (start, next),
+            _ => {
+                let mut lead = start;
+                while lead > 0 && matches!(rope.char(lead - 1), ' ' | '\t') {
+                    lead -= 1;
+                }
+                (lead, end)
+            }
+        }
+    };
+    Some(Range::new(idx_to_pos(buf, start), idx_to_pos(buf, end)))
+}
+
+/// The inner sentence around `pos` as char indices `[start, end)`.
+fn sentence_at(buf: &Buffer, pos: Position) -> Option<(usize, usize)> {
+    let rope = buf.rope();
+    let total = rope.len_chars();
+    let idx = buf.pos_to_char_idx(pos)?;
+    if idx >= total {
+        return None;
+    }
+    let next = next_sentence_start(rope, idx).unwrap_or(total);
+    let Some(start) = prev_sentence_start(rope, idx + 1) else {
+        // White space before the first sentence of the buffer.
+        return Some((0, next));
+    };
+    let end = sentence_text_end(rope, start, next);
+    if idx >= end {
+        // On the white space between two sentences.
+        return Some((end, next));
+    }
+    Some((start, end))
+}
+
+/// Where the text of the sentence `start..next` ends: `next` without the
+/// white space before it. For a sentence made of empty lines, the end of
+/// the run of empty lines.
+fn sentence_text_end(rope: &ropey::Rope, start: usize, next: usize) -> usize {
+    if is_empty_line_start(rope, start) {
+        let mut end = start;
+        while end < next && rope.char(end) == '\n' {
+            end += 1;
+        }
+        return end;
+    }
+    let mut end = next;
+    while end > start && rope.char(end - 1).is_whitespace() {
+        end -= 1;
+    }
+    end
+}
+
+/// Whether char `idx` is the line ending of an empty line.
+fn is_empty_line_start(rope: &ropey::Rope, idx: usize) -> bool {
+    idx < rope.len_chars() && rope.char(idx) == '\n' && (idx == 0 || rope.char(idx - 1) == '\n')
+}
+
+/// Whether `c` ends a sentence when followed by white space.
+const fn is_sentence_end(c: char) -> bool {
+    matches!(c, '.' | '!' | '?')
+}
+
+/// Whether `c` may follow a sentence end before the white space.
+const fn is_sentence_close(c: char) -> bool {
+    matches!(c, ')' | ']' | '"' | '\'')
+}
+
+/// Whether `line` has no characters besides its line ending.
+fn is_empty_line(rope: &ropey::Rope, line: usize) -> bool {
+    rope.line(line).chars().next().is_none_or(|c| c == '\n' || c == '\r')
+}
+
+/// A line at or above `line` where scanning for sentences can start: the
+/// first line of the buffer, or a line whose emptiness differs from the
+/// line above it.
+fn scan_line(rope: &ropey::Rope, mut line: usize) -> usize {
+    while line > 0 && is_empty_line(rope, line - 1) == is_empty_line(rope, line) {
+        line -= 1;
+    }
+    line
+}
+
+/// The char indices where sentences start, scanning forward from a line
+/// picked by [`scan_line`].
+///
+/// A sentence starts at the first non-blank character after a `.`, `!`
+/// or `?` that is followed (after any closing `)`, `]` and quotes) by
+/// white space, and at the first non-blank character of a paragraph. The
+/// first of a run of empty lines is a sentence of its own.
+struct SentenceStarts<'a> {
+    rope: &'a ropey::Rope,
+    idx: usize,
+    pending: bool,
+    blank_run: bool,
+}
+
+impl<'a> SentenceStarts<'a> {
+    fn from_line(rope: &'a ropey::Rope, line: usize) -> Self {
+        Self { rope, idx: rope.line_to_char(line), pending: true, blank_run: false }
+    }
+}
+
+impl Iterator for SentenceStarts<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let total = self.rope.len_chars();
+        while self.idx < total {
+            let i = self.idx;
+            let ch = self.rope.char(i);
+            self.idx += 1;
+            if is_empty_line_start(self.rope, i) {
+                self.pending = true;
+                if !std::mem::replace(&mut self.blank_run, true) {
+                    return Some(i);
+                }
+                continue;
+            }
+            self.blank_run = false;
+            if ch.is_whitespace() {
+                continue;
+            }
+            let starts = std::mem::take(&mut self.pending);
+            if is_sentence_end(ch) {
+                while self.idx < total && is_sentence_close(self.rope.char(self.idx)) {
+                    self.idx += 1;
+                }
+                self.pending = self.idx >= total || self.rope.char(self.idx).is_whitespace();
+            }
+            if starts {
+                return Some(i);
+            }
+        }
+        None
+    }
+}
+
+/// The start of the first sentence after char `idx` — the target of `)`.
+pub(crate) fn next_sentence_start(rope: &ropey::Rope, idx: usize) -> Option<usize> {
+    if idx >= rope.len_chars() {
+        return None;
+    }
+    let line = scan_line(rope, rope.char_to_line(idx));
+    SentenceStarts::from_line(rope, line).find(|&s| s > idx)
+}
+
+/// The start of the last sentence before char `idx` — the target of `(`.
+pub(crate) fn prev_sentence_start(rope: &ropey::Rope, idx: usize) -> Option<usize> {
+    let mut line = rope.char_to_line(idx.min(rope.len_chars()));
+    loop {
+        let from = scan_line(rope, line);
+        let found = SentenceStarts::from_line(rope, from).take_while(|&s| s < idx).last();
+        if found.is_some() || from == 0 {
+            return found;
+        }
+        line = from - 1;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Indent objects
+// ---------------------------------------------------------------------------
+
+/// `ii` — inner indent.
+///
+/// Selects the lines around the cursor indented at least as far as the
+/// cursor line, as whole lines. Blank lines inside the block belong to it;
+/// blank lines at its edges don't. On a blank line, the indent of the next
+/// non-blank line counts. Returns `None` in a buffer of blank lines.
+#[must_use]
+pub fn inner_indent(buf: &Buffer, pos: Position) -> Option<Range> {
+    let (first, last, _) = indent_block(buf, pos.line)?;
+    Some(line_range(buf, first, last))
+}
+
+/// `ai` — an indent.
+///
+/// Like `ii`, plus the line above the block — the `def`, `if` or key
+/// line that introduces it.
+#[must_use]
+pub fn an_indent(buf: &Buffer, pos: Position) -> Option<Range> {
+    let (first, last, header) = indent_block(buf, pos.line)?;
+    Some(line_range(buf, header.unwrap_or(first), last))
+}
+
+/// The block of lines around `line` at its indent or deeper, as `(first,
+/// last, header)` where `header` is the less indented line above it.
+fn indent_block(buf: &Buffer, line: usize) -> Option<(usize, usize, Option<usize>)> {
+    let count = buf.line_count();
+    let blank = |l: usize| buf.line(l).is_none_or(|s| s.chars().all(char::is_whitespace));
+    let base = (line..count).chain((0..line).rev()).find(|&l| !blank(l))?;
+    let level = indent_width(buf, base);
+    let inside = |l: usize| blank(l) || indent_width(buf, l) >= level;
+
+    let mut top = line.min(base);
+    while top > 0 && inside(top - 1) {
+        top -= 1;
+    }
+    let mut bottom = line.max(base);
+    while bottom + 1 < count && inside(bottom + 1) {
+        bottom += 1;
+    }
+    let header = top.checked_sub(1);
+    let first = (top..=bottom).find(|&l| !blank(l))?;
+    let last = (first..=bottom).rev().find(|&l| !blank(l))?;
+    Some((first, last, header))
+}
+
+/// Width of the leading white space of `line` in columns, with tab stops
+/// every 8 columns.
+fn indent_width(buf: &Buffer, line: usize) -> usize {
+    let Some(text) = buf.line(line) else { return 0 };
+    let mut col = 0;
+    for ch in text.chars() {
+        match ch {
+            ' ' => col += 1,
+            '\t' => col = col / 8 * 8 + 8,
+            _ => break,
+        }
+    }
+    col
+}
+
+/// The whole lines `first..=last`, including the line break after `last`
+/// when there is a line after it.
+fn line_range(buf: &Buffer, first: usize, last: usize) -> Range {
+    let end = if last + 1 < buf.line_count() {
+        Position::new(last + 1, 0)
+    } else {
+        Position::new(last, buf.line_len(last).unwrap_or(0))
+    };
+    Range::new(Position::new(first, 0), end)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -775,63 +1081,63 @@ mod tests {
     #[test]
     fn ip_simple() {
         let buf = Buffer::from_text("f(hello)");
-        assert_eq!(inner_paren(&buf, p(0, 3)), Some(r(0, 2, 0, 7)));
+        assert_eq!(inner_paren(&buf, p(0, 3), 1), Some(r(0, 2, 0, 7)));
     }
 
     #[test]
     fn ip_cursor_on_open() {
         let buf = Buffer::from_text("(hello)");
-        assert_eq!(inner_paren(&buf, p(0, 0)), Some(r(0, 1, 0, 6)));
+        assert_eq!(inner_paren(&buf, p(0, 0), 1), Some(r(0, 1, 0, 6)));
     }
 
     #[test]
     fn ip_cursor_on_close() {
         let buf = Buffer::from_text("(hello)");
-        assert_eq!(inner_paren(&buf, p(0, 6)), Some(r(0, 1, 0, 6)));
+        assert_eq!(inner_paren(&buf, p(0, 6), 1), Some(r(0, 1, 0, 6)));
     }
 
     #[test]
     fn ip_empty() {
         let buf = Buffer::from_text("f()");
-        assert_eq!(inner_paren(&buf, p(0, 1)), Some(Range::point(p(0, 2))));
+        assert_eq!(inner_paren(&buf, p(0, 1), 1), Some(Range::point(p(0, 2))));
     }
 
     #[test]
     fn ip_nested_inner() {
         let buf = Buffer::from_text("f(a(b)c)");
         // Cursor on 'b' at col 4 — selects inner pair.
-        assert_eq!(inner_paren(&buf, p(0, 4)), Some(r(0, 4, 0, 5)));
+        assert_eq!(inner_paren(&buf, p(0, 4), 1), Some(r(0, 4, 0, 5)));
     }
 
     #[test]
     fn ip_nested_outer() {
         let buf = Buffer::from_text("f(a(b)c)");
         // Cursor on 'a' at col 2 — selects outer pair.
-        assert_eq!(inner_paren(&buf, p(0, 2)), Some(r(0, 2, 0, 7)));
+        assert_eq!(inner_paren(&buf, p(0, 2), 1), Some(r(0, 2, 0, 7)));
     }
 
     #[test]
     fn ip_multiline() {
         let buf = Buffer::from_text("f(\n  hello\n)");
-        assert_eq!(inner_paren(&buf, p(1, 2)), Some(r(0, 2, 2, 0)));
+        assert_eq!(inner_paren(&buf, p(1, 2), 1), Some(r(0, 2, 2, 0)));
     }
 
     #[test]
     fn ip_no_match() {
         let buf = Buffer::from_text("hello world");
-        assert_eq!(inner_paren(&buf, p(0, 3)), None);
+        assert_eq!(inner_paren(&buf, p(0, 3), 1), None);
     }
 
     #[test]
     fn ip_unmatched_open() {
         let buf = Buffer::from_text("f(hello");
-        assert_eq!(inner_paren(&buf, p(0, 3)), None);
+        assert_eq!(inner_paren(&buf, p(0, 3), 1), None);
     }
 
     #[test]
     fn ip_unmatched_close() {
         let buf = Buffer::from_text("hello)");
-        assert_eq!(inner_paren(&buf, p(0, 3)), None);
+        assert_eq!(inner_paren(&buf, p(0, 3), 1), None);
     }
 
     // -- a_paren (a() -------------------------------------------------------
@@ -839,20 +1145,20 @@ mod tests {
     #[test]
     fn ap_simple() {
         let buf = Buffer::from_text("f(hello)");
-        assert_eq!(a_paren(&buf, p(0, 3)), Some(r(0, 1, 0, 8)));
+        assert_eq!(a_paren(&buf, p(0, 3), 1), Some(r(0, 1, 0, 8)));
     }
 
     #[test]
     fn ap_nested_outer() {
         let buf = Buffer::from_text("f(a(b)c)");
         // Cursor on 'a' — outer pair.
-        assert_eq!(a_paren(&buf, p(0, 2)), Some(r(0, 1, 0, 8)));
+        assert_eq!(a_paren(&buf, p(0, 2), 1), Some(r(0, 1, 0, 8)));
     }
 
     #[test]
     fn ap_multiline() {
         let buf = Buffer::from_text("f(\n  hello\n)");
-        assert_eq!(a_paren(&buf, p(1, 2)), Some(r(0, 1, 2, 1)));
+        assert_eq!(a_paren(&buf, p(1, 2), 1), Some(r(0, 1, 2, 1)));
     }
 
     // -- square brackets (i[/a[) -------------------------------------------
@@ -860,13 +1166,13 @@ mod tests {
     #[test]
     fn isq_brackets() {
         let buf = Buffer::from_text("arr[42]");
-        assert_eq!(inner_square(&buf, p(0, 4)), Some(r(0, 4, 0, 6)));
+        assert_eq!(inner_square(&buf, p(0, 4), 1), Some(r(0, 4, 0, 6)));
     }
 
     #[test]
     fn asq_brackets() {
         let buf = Buffer::from_text("arr[42]");
-        assert_eq!(a_square(&buf, p(0, 4)), Some(r(0, 3, 0, 7)));
+        assert_eq!(a_square(&buf, p(0, 4), 1), Some(r(0, 3, 0, 7)));
     }
 
     // -- curly braces (i{/a{) -----------------------------------------------
@@ -874,19 +1180,19 @@ mod tests {
     #[test]
     fn ic_simple() {
         let buf = Buffer::from_text("{ body }");
-        assert_eq!(inner_curly(&buf, p(0, 3)), Some(r(0, 1, 0, 7)));
+        assert_eq!(inner_curly(&buf, p(0, 3), 1), Some(r(0, 1, 0, 7)));
     }
 
     #[test]
     fn ac_simple() {
         let buf = Buffer::from_text("{ body }");
-        assert_eq!(a_curly(&buf, p(0, 3)), Some(r(0, 0, 0, 8)));
+        assert_eq!(a_curly(&buf, p(0, 3), 1), Some(r(0, 0, 0, 8)));
     }
 
     #[test]
     fn ic_multiline() {
         let buf = Buffer::from_text("fn main() {\n    body\n}");
-        assert_eq!(inner_curly(&buf, p(1, 4)), Some(r(0, 11, 2, 0)));
+        assert_eq!(inner_curly(&buf, p(1, 4), 1), Some(r(0, 11, 2, 0)));
     }
 
     // -- angle brackets (i</a<) --------------------------------------------
@@ -894,22 +1200,22 @@ mod tests {
     #[test]
     fn ia_simple() {
         let buf = Buffer::from_text("Vec<i32>");
-        assert_eq!(inner_angle(&buf, p(0, 5)), Some(r(0, 4, 0, 7)));
+        assert_eq!(inner_angle(&buf, p(0, 5), 1), Some(r(0, 4, 0, 7)));
     }
 
     #[test]
     fn aa_simple() {
         let buf = Buffer::from_text("Vec<i32>");
-        assert_eq!(a_angle(&buf, p(0, 5)), Some(r(0, 3, 0, 8)));
+        assert_eq!(a_angle(&buf, p(0, 5), 1), Some(r(0, 3, 0, 8)));
     }
 
     #[test]
     fn ia_nested() {
         let buf = Buffer::from_text("Vec<Option<i32>>");
         // Cursor on 'i' at col 11 — inner angle of inner pair.
-        assert_eq!(inner_angle(&buf, p(0, 11)), Some(r(0, 11, 0, 14)));
+        assert_eq!(inner_angle(&buf, p(0, 11), 1), Some(r(0, 11, 0, 14)));
         // Cursor on 'O' at col 4 — inner angle of outer pair.
-        assert_eq!(inner_angle(&buf, p(0, 4)), Some(r(0, 4, 0, 15)));
+        assert_eq!(inner_angle(&buf, p(0, 4), 1), Some(r(0, 4, 0, 15)));
     }
 
     // == Edge cases ==========================================================
@@ -932,11 +1238,11 @@ mod tests {
     fn ip_deeply_nested() {
         let buf = Buffer::from_text("(a(b(c)d)e)");
         // Cursor on 'c' at col 5 — innermost.
-        assert_eq!(inner_paren(&buf, p(0, 5)), Some(r(0, 5, 0, 6)));
+        assert_eq!(inner_paren(&buf, p(0, 5), 1), Some(r(0, 5, 0, 6)));
         // Cursor on 'b' at col 3 — middle.
-        assert_eq!(inner_paren(&buf, p(0, 3)), Some(r(0, 3, 0, 8)));
+        assert_eq!(inner_paren(&buf, p(0, 3), 1), Some(r(0, 3, 0, 8)));
         // Cursor on 'a' at col 1 — outermost.
-        assert_eq!(inner_paren(&buf, p(0, 1)), Some(r(0, 1, 0, 10)));
+        assert_eq!(inner_paren(&buf, p(0, 1), 1), Some(r(0, 1, 0, 10)));
     }
 
     #[test]
@@ -963,13 +1269,114 @@ mod tests {
     fn ip_cursor_on_nested_close() {
         let buf = Buffer::from_text("f(a(b)c)");
         // Cursor on ')' at col 5 — the inner close paren.
-        assert_eq!(inner_paren(&buf, p(0, 5)), Some(r(0, 4, 0, 5)));
+        assert_eq!(inner_paren(&buf, p(0, 5), 1), Some(r(0, 4, 0, 5)));
     }
 
     #[test]
     fn ip_cursor_on_outer_close() {
         let buf = Buffer::from_text("f(a(b)c)");
         // Cursor on ')' at col 7 — the outer close paren.
-        assert_eq!(inner_paren(&buf, p(0, 7)), Some(r(0, 2, 0, 7)));
+        assert_eq!(inner_paren(&buf, p(0, 7), 1), Some(r(0, 2, 0, 7)));
+    }
+
+    #[test]
+    fn bracket_count_walks_outward() {
+        let buf = Buffer::from_text("f(a, (b), g(c[0], (d)))");
+        // Cursor on 'd': the innermost pair, then the ones around it.
+        assert_eq!(inner_paren(&buf, p(0, 19), 1), Some(r(0, 19, 0, 20)));
+        assert_eq!(inner_paren(&buf, p(0, 19), 2), Some(r(0, 12, 0, 21)));
+        assert_eq!(a_paren(&buf, p(0, 19), 3), Some(r(0, 1, 0, 23)));
+        assert_eq!(a_paren(&buf, p(0, 19), 4), None);
+        // Sibling pairs on the way out are skipped.
+        assert_eq!(a_paren(&buf, p(0, 6), 2), Some(r(0, 1, 0, 23)));
+        // Other bracket kinds don't count as levels.
+        assert_eq!(inner_paren(&buf, p(0, 14), 1), Some(r(0, 12, 0, 21)));
+        assert_eq!(inner_square(&buf, p(0, 14), 2), None);
+    }
+
+    // == Sentence objects ====================================================
+
+    #[test]
+    fn is_selects_the_sentence_without_trailing_space() {
+        let buf = Buffer::from_text("Hello there. How are you? Fine.");
+        assert_eq!(inner_sentence(&buf, p(0, 2)), Some(r(0, 0, 0, 12)));
+        assert_eq!(inner_sentence(&buf, p(0, 17)), Some(r(0, 13, 0, 25)));
+        // On the gap between sentences: just the white space.
+        assert_eq!(inner_sentence(&buf, p(0, 12)), Some(r(0, 12, 0, 13)));
+    }
+
+    #[test]
+    fn as_takes_trailing_or_leading_space() {
+        let buf = Buffer::from_text("Hello there. How are you? Fine.");
+        assert_eq!(a_sentence(&buf, p(0, 2)), Some(r(0, 0, 0, 13)));
+        // Last sentence: the space before it instead.
+        assert_eq!(a_sentence(&buf, p(0, 27)), Some(r(0, 25, 0, 31)));
+        // On the gap: the space and the next sentence.
+        assert_eq!(a_sentence(&buf, p(0, 12)), Some(r(0, 12, 0, 25)));
+    }
+
+    #[test]
+    fn sentence_end_needs_white_space_after_it() {
+        let buf = Buffer::from_text("Pi is 3.14 or so. He said \"stop.\" Then left.");
+        assert_eq!(inner_sentence(&buf, p(0, 8)), Some(r(0, 0, 0, 17)));
+        // Closing quotes stay with the sentence they end.
+        assert_eq!(inner_sentence(&buf, p(0, 20)), Some(r(0, 18, 0, 33)));
+    }
+
+    #[test]
+    fn sentences_span_lines_and_stop_at_empty_lines() {
+        let buf = Buffer::from_text("One. Two\nthree.\n\n\nFour.");
+        assert_eq!(inner_sentence(&buf, p(1, 0)), Some(r(0, 5, 1, 6)));
+        assert_eq!(inner_sentence(&buf, p(2, 0)), Some(r(2, 0, 4, 0)));
+        assert_eq!(inner_sentence(&buf, p(4, 2)), Some(r(4, 0, 4, 5)));
+        // Last sentence of its paragraph: the blank before it instead.
+        assert_eq!(a_sentence(&buf, p(1, 0)), Some(r(0, 4, 1, 6)));
+    }
+
+    #[test]
+    fn sentence_starts_forward_and_backward() {
+        let buf = Buffer::from_text("A b. C d!\n\nE f? G.");
+        let rope = buf.rope();
+        assert_eq!(next_sentence_start(rope, 0), Some(5));
+        assert_eq!(next_sentence_start(rope, 5), Some(10));
+        assert_eq!(next_sentence_start(rope, 10), Some(11));
+        assert_eq!(next_sentence_start(rope, 11), Some(16));
+        assert_eq!(next_sentence_start(rope, 16), None);
+        assert_eq!(prev_sentence_start(rope, 18), Some(16));
+        assert_eq!(prev_sentence_start(rope, 16), Some(11));
+        assert_eq!(prev_sentence_start(rope, 11), Some(10));
+        assert_eq!(prev_sentence_start(rope, 10), Some(5));
+        assert_eq!(prev_sentence_start(rope, 7), Some(5));
+        assert_eq!(prev_sentence_start(rope, 0), None);
+    }
+
+    // == Indent objects ======================================================
+
+    const PY: &str = "def f(x):\n    y = x\n\n    if y:\n        y += 1\n    return y\n\nprint(f(1))";
+
+    #[test]
+    fn ii_selects_the_lines_at_the_same_indent_or_deeper() {
+        let buf = Buffer::from_text(PY);
+        assert_eq!(inner_indent(&buf, p(1, 0)), Some(r(1, 0, 6, 0)));
+        assert_eq!(inner_indent(&buf, p(4, 3)), Some(r(4, 0, 5, 0)));
+        // A blank line inside the block takes the indent below it.
+        assert_eq!(inner_indent(&buf, p(2, 0)), Some(r(1, 0, 6, 0)));
+    }
+
+    #[test]
+    fn ai_adds_the_line_above() {
+        let buf = Buffer::from_text(PY);
+        assert_eq!(an_indent(&buf, p(1, 4)), Some(r(0, 0, 6, 0)));
+        assert_eq!(an_indent(&buf, p(4, 0)), Some(r(3, 0, 5, 0)));
+        // At the top level the whole buffer is one block, with no header.
+        assert_eq!(an_indent(&buf, p(7, 0)), Some(r(0, 0, 7, 11)));
+    }
+
+    #[test]
+    fn indent_object_in_a_blank_buffer() {
+        let buf = Buffer::from_text("\n  \n");
+        assert_eq!(inner_indent(&buf, p(0, 0)), None);
+        let buf = Buffer::from_text("a:\n\tb\n        c\nd");
+        assert_eq!(inner_indent(&buf, p(1, 0)), Some(r(1, 0, 3, 0)));
     }
 }
@@ -9,6 +9,13 @@
 //! - **Wide characters** — CJK characters consume two terminal columns
 //! - **Status line** — mode indicator, filename, cursor position
 //! - **Tilde lines** — `~` markers for lines past the end of the buffer
+//! - **Virtual lines** — annotation rows (e.g. inlay hints) above or below
+//!   a buffer line that aren't part of the buffer text
+//! - **Sign column** — a one-character mark per line (e.g. git changes)
+//!   left of the line numbers, shown while any signs are set
+//! - **Folds** — a closed fold's lines drawn as a single `+--` row
+//! - **Conceal** — markup hidden or replaced by a character off the cursor
+//!   line (`conceallevel`)
 //!
 //! # Architecture
 //!
@@ -33,16 +40,21 @@
 use unicode_width::UnicodeWidthChar;
 
 use crate::buffer::Buffer;
+use crate::conceal::{self, ConcealSpan};
 use crate::cursor::Cursor;
+use crate::fold::FoldList;
 use crate::mode::{Mode, VisualKind};
+use crate::options::RulerFormat;
 use crate::position::Range;
-use crate::search;
+use crate::search::{self, Match};
+use crate::spell::SpellChecker;
+use crate::split::{BorderStyle, Rect};
 
 use n_term::buffer::FrameBuffer;
 use n_term::cell::{Attr, Cell, UnderlineStyle};
 use n_term::color::CellColor;
 
-use n_theme::Theme;
+use n_theme::{HighlightGroup, Theme};
 
 // ---------------------------------------------------------------------------
 // Helpers
@@ -207,6 +219,162 @@ fn line_selection_cols(
     }
 }
 
+// ---------------------------------------------------------------------------
+// Virtual lines
+// ---------------------------------------------------------------------------
+
+/// Which side of its buffer line a [`VirtualLine`] is drawn on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VirtualPosition {
+    /// On its own row just before the buffer line.
+    Above,
+    /// On its own row just after the buffer line.
+    Below,
+}
+
+/// A row of annotation text drawn next to a buffer line without being part
+/// of the buffer — inlay hints, diagnostics, blame.
+///
+/// Virtual lines take screen rows but no buffer lines: line numbers, cursor
+/// motions and the jump list never see them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VirtualLine {
+    /// Buffer line (0-indexed) this row annotates.
+    pub line: usize,
+    /// Whether the row goes above or below that line.
+    pub position: VirtualPosition,
+    /// The text to show. Drawn from the first text column, unscrolled.
+    pub text: String,
+    /// Colors and attributes for the text.
+    pub hl: HighlightGroup,
+}
+
+/// The virtual lines of one buffer, ordered by the line they annotate.
+///
+/// Several rows on the same side of a line are drawn in the order given.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VirtualLines {
+    lines: Vec<VirtualLine>,
+}
+
+impl VirtualLines {
+    /// Collect virtual lines, sorting them by buffer line.
+    #[must_use]
+    pub fn new(mut lines: Vec<VirtualLine>) -> Self {
+        // Stable, so rows on the same line keep their relative order.
+        lines.sort_by_key(|v| v.line);
+        Self { lines }
+    }
+
+    /// True when there are no virtual lines.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Number of virtual lines.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// All virtual lines in buffer-line order.
+    pub fn iter(&self) -> impl Iterator<Item = &VirtualLine> {
+        self.lines.iter()
+    }
+
+    /// Virtual lines attached to buffer lines in `[from, to)`.
+    fn in_range(&self, from: usize, to: usize) -> &[VirtualLine] {
+        let start = self.lines.partition_point(|v| v.line < from);
+        let end = self.lines.partition_point(|v| v.line < to);
+        &self.lines[start..end.max(start)]
+    }
+
+    /// Rows drawn above `line`, top to bottom.
+    pub fn above(&self, line: usize) -> impl Iterator<Item = &VirtualLine> {
+        self.in_range(line, line + 1).iter().filter(|v| v.position == VirtualPosition::Above)
+    }
+
+    /// Rows drawn below `line`, top to bottom.
+    pub fn below(&self, line: usize) -> impl Iterator<Item = &VirtualLine> {
+        self.in_range(line, line + 1).iter().filter(|v| v.position == VirtualPosition::Below)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Signs
+// ---------------------------------------------------------------------------
+
+/// Columns the sign column takes: the sign and a separator space.
+pub const SIGN_COLUMN_WIDTH: u16 = 2;
+
+/// A mark in the sign column next to a buffer line — git changes,
+/// diagnostics, breakpoints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sign {
+    /// Buffer line (0-indexed) the sign is drawn next to.
+    pub line: usize,
+    /// The sign itself, one column wide.
+    pub text: char,
+    /// Colors and attributes for the sign.
+    pub hl: HighlightGroup,
+}
+
+/// The signs of one buffer, ordered by line. When several signs share a
+/// line, the first one given is drawn.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Signs {
+    signs: Vec<Sign>,
+}
+
+impl Signs {
+    /// Collect signs, sorting them by buffer line.
+    #[must_use]
+    pub fn new(mut signs: Vec<Sign>) -> Self {
+        // Stable, so the first sign given for a line stays first.
+        signs.sort_by_key(|s| s.line);
+        Self { signs }
+    }
+
+    /// True when there are no signs.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.signs.is_empty()
+    }
+
+    /// Number of signs.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.signs.len()
+    }
+
+    /// All signs in line order.
+    pub fn iter(&self) -> impl Iterator<Item = &Sign> {
+        self.signs.iter()
+    }
+
+    /// The sign drawn next to `line`, if any.
+    #[must_use]
+    pub fn get(&self, line: usize) -> Option<&Sign> {
+        let idx = self.signs.partition_point(|s| s.line < line);
+        self.signs.get(idx).filter(|s| s.line == line)
+    }
+}
+
+/// One screen row of the text area, in display order.
+enum DisplayRow<'a> {
+    /// A buffer line.
+    Text(usize),
+    /// A virtual line.
+    Virtual(&'a VirtualLine),
+    /// A closed fold hiding the lines `start..=end`.
+    Fold { start: usize, end: usize },
+}
+
 // ---------------------------------------------------------------------------
 // View
 // ---------------------------------------------------------------------------
@@ -228,6 +396,7 @@ fn line_selection_cols(
 /// └───────────────────────────────┘
 /// ```
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct View {
     /// First visible buffer line (0-indexed).
     top_line: usize,
@@ -253,6 +422,29 @@ pub struct View {
 
     /// Tab stop width (display columns per tab stop).
     tab_width: u8,
+
+    /// Annotation rows interleaved with the buffer lines.
+    virtual_lines: VirtualLines,
+
+    /// Whether the bottom row of the area is a status line. Off for
+    /// floating windows, which use every row for text.
+    status_line: bool,
+
+    /// Marks drawn in the sign column.
+    signs: Signs,
+
+    /// Whether the status line shows the cursor position (`ruler`).
+    ruler: bool,
+
+    /// What the ruler's column counts (`rulerformat`).
+    ruler_format: RulerFormat,
+
+    /// Folds of this window; closed ones take a single row.
+    folds: FoldList,
+
+    /// How concealed text is drawn (`conceallevel`): 0 as it is, 1 as the
+    /// rule's replacement character, 2 not at all.
+    conceallevel: u8,
 }
 
 impl Default for View {
@@ -272,6 +464,13 @@ impl View {
             relativenumber: false,
             scrolloff: 0,
             tab_width: 4,
+            virtual_lines: VirtualLines { lines: Vec::new() },
+            status_line: true,
+            signs: Signs { signs: Vec::new() },
+            ruler: true,
+            ruler_format: RulerFormat::Char,
+            folds: FoldList::EMPTY,
+            conceallevel: 0,
         }
     }
 
@@ -319,6 +518,96 @@ impl View {
         self.tab_width
     }
 
+    /// Whether a status line is drawn at the bottom of the area.
+    #[inline]
+    #[must_use]
+    pub const fn status_line(&self) -> bool {
+        self.status_line
+    }
+
+    /// Rows taken by the status line (0 or 1).
+    #[inline]
+    #[must_use]
+    pub const fn status_rows(&self) -> u16 {
+        self.status_line as u16
+    }
+
+    /// Whether the status line shows the cursor position.
+    #[inline]
+    #[must_use]
+    pub const fn ruler(&self) -> bool {
+        self.ruler
+    }
+
+    /// What the ruler's column counts.
+    #[inline]
+    #[must_use]
+    pub const fn ruler_format(&self) -> RulerFormat {
+        self.ruler_format
+    }
+
+    /// The ruler text for `cursor` in `buf`: `{line}:{col}`, both 1-based,
+    /// with the column counted as [`Self::ruler_format`] says.
+    #[must_use]
+    pub fn ruler_text(&self, buf: &Buffer, cursor: &Cursor) -> String {
+        let col = buf.line(cursor.line()).map_or(0, |line| match self.ruler_format {
+            RulerFormat::Byte => line.chars().take(cursor.col()).map(char::len_utf8).sum(),
+            RulerFormat::Char => cursor.col(),
+            RulerFormat::Virtual => char_col_to_display_col(line.chars(), cursor.col(), self.tab_width),
+        });
+        format!("{}:{}", cursor.line() + 1, col + 1)
+    }
+
+    /// How concealed text is drawn (`conceallevel`).
+    #[inline]
+    #[must_use]
+    pub const fn conceallevel(&self) -> u8 {
+        self.conceallevel
+    }
+
+    /// Virtual lines drawn with the buffer.
+    #[inline]
+    #[must_use]
+    pub const fn virtual_lines(&self) -> &VirtualLines {
+        &self.virtual_lines
+    }
+
+    /// Signs drawn in the sign column.
+    #[inline]
+    #[must_use]
+    pub const fn signs(&self) -> &Signs {
+        &self.signs
+    }
+
+    /// Folds of this window.
+    #[inline]
+    #[must_use]
+    pub const fn folds(&self) -> &FoldList {
+        &self.folds
+    }
+
+    /// Folds of this window, to open or close them.
+    #[inline]
+    pub const fn folds_mut(&mut self) -> &mut FoldList {
+        &mut self.folds
+    }
+
+    /// Columns taken by the sign column: [`SIGN_COLUMN_WIDTH`] while any
+    /// signs are set, otherwise 0.
+    #[inline]
+    #[must_use]
+    pub fn sign_width(&self) -> u16 {
+        if self.signs.signs.is_empty() { 0 } else { SIGN_COLUMN_WIDTH }
+    }
+
+    /// Width of the whole gutter — sign column plus line numbers — for a
+    /// buffer of `line_count` lines. Text starts this many columns in.
+    #[inline]
+    #[must_use]
+    pub fn gutter_cols(&self, line_count: usize) -> u16 {
+        self.sign_width() + gutter_width(line_count, self.line_numbers || self.relativenumber)
+    }
+
     // -- Configuration ------------------------------------------------------
 
     /// Enable or disable line numbers.
@@ -336,6 +625,21 @@ impl View {
         self.scrolloff = lines;
     }
 
+    /// Show or hide the ruler in the status line.
+    pub const fn set_ruler(&mut self, show: bool) {
+        self.ruler = show;
+    }
+
+    /// Set what the ruler's column counts.
+    pub const fn set_ruler_format(&mut self, format: RulerFormat) {
+        self.ruler_format = format;
+    }
+
+    /// Show or hide the status line.
+    pub const fn set_status_line(&mut self, show: bool) {
+        self.status_line = show;
+    }
+
     /// Set the tab stop width (minimum 1).
     pub fn set_tab_width(&mut self, width: u8) {
         self.tab_width = width.max(1);
@@ -351,6 +655,116 @@ impl View {
         self.left_col = col;
     }
 
+    /// Replace the virtual lines drawn with the buffer.
+    pub fn set_virtual_lines(&mut self, lines: VirtualLines) {
+        self.virtual_lines = lines;
+    }
+
+    /// Replace the signs drawn in the sign column.
+    pub fn set_signs(&mut self, signs: Signs) {
+        self.signs = signs;
+    }
+
+    /// Set how concealed text is drawn (`conceallevel`).
+    pub const fn set_conceallevel(&mut self, level: u8) {
+        self.conceallevel = level;
+    }
+
+    /// Replace the folds of this window.
+    pub fn set_folds(&mut self, folds: FoldList) {
+        self.folds = folds;
+    }
+
+    // -- Screen mapping -----------------------------------------------------
+
+    /// Row within the text area where buffer line `line` is drawn, counting
+    /// the virtual lines above it. A line inside a closed fold is on the
+    /// fold's row. `None` if `line` is above the viewport; rows past the
+    /// bottom of the text area are returned as-is.
+    #[must_use]
+    pub fn screen_row(&self, line: usize) -> Option<usize> {
+        let offset = line.checked_sub(self.top_line)?;
+        if self.folds.is_empty() {
+            if self.virtual_lines.is_empty() {
+                return Some(offset);
+            }
+            let virt = self.virtual_lines.in_range(self.top_line, line).len();
+            return Some(offset + virt + self.virtual_lines.above(line).count());
+        }
+        let mut row = 0;
+        let mut first = self.top_line;
+        loop {
+            let last = self.last_line_of_row(first);
+            row += self.virtual_lines.above(first).count();
+            if line <= last {
+                return Some(row);
+            }
+            row += 1 + self.virtual_lines.below(last).count();
+            first = last + 1;
+        }
+    }
+
+    /// Buffer line shown on text-area row `row`. A virtual line maps to the
+    /// buffer line it annotates, a closed fold to its first line. Past the
+    /// end of the buffer, this keeps counting one line per row.
+    #[must_use]
+    pub fn line_at_row(&self, row: usize) -> usize {
+        if self.virtual_lines.is_empty() && self.folds.is_empty() {
+            return self.top_line + row;
+        }
+        let mut remaining = row;
+        let mut line = self.top_line;
+        loop {
+            let last = self.last_line_of_row(line);
+            let rows = 1 + self.virtual_lines.above(line).count() + self.virtual_lines.below(last).count();
+            if remaining < rows {
+                return line;
+            }
+            remaining -= rows;
+            line = last + 1;
+        }
+    }
+
+    /// Last buffer line drawn on the row showing `line`: the end of the
+    /// closed fold covering it, or `line` itself.
+    fn last_line_of_row(&self, line: usize) -> usize {
+        self.folds.closed_at(line).map_or(line, |(_, end)| end)
+    }
+
+    /// The number of buffer lines from `top_line` that fit in a text area
+    /// `text_height` rows tall, counting the lines hidden in closed folds.
+    /// Stops at the end of the buffer.
+    #[must_use]
+    pub fn lines_shown(&self, line_count: usize, text_height: usize) -> usize {
+        match self.display_rows(line_count, text_height).last() {
+            Some(&DisplayRow::Text(line)) => line + 1 - self.top_line,
+            Some(&DisplayRow::Fold { end, .. }) => end + 1 - self.top_line,
+            Some(DisplayRow::Virtual(virt)) => virt.line + 1 - self.top_line,
+            None => 0,
+        }
+    }
+
+    /// The rows of the text area from `top_line` down, interleaving virtual
+    /// lines with buffer lines and standing closed folds in for the lines
+    /// they hide. Stops at the end of the buffer.
+    fn display_rows(&self, line_count: usize, text_height: usize) -> Vec<DisplayRow<'_>> {
+        let mut rows = Vec::with_capacity(text_height);
+        let mut line = self.top_line;
+        while rows.len() < text_height && line < line_count {
+            let last = self.last_line_of_row(line).min(line_count - 1);
+            rows.extend(self.virtual_lines.above(line).map(DisplayRow::Virtual));
+            if last == line {
+                rows.push(DisplayRow::Text(line));
+            } else {
+                rows.push(DisplayRow::Fold { start: line, end: last });
+            }
+            rows.extend(self.virtual_lines.below(last).map(DisplayRow::Virtual));
+            line = last + 1;
+        }
+        rows.truncate(text_height);
+        rows
+    }
+
     // -- Scrolling ----------------------------------------------------------
 
     /// Adjust scroll position so the cursor is visible in the viewport.
@@ -364,10 +778,9 @@ impl View {
         area_width: u16,
         area_height: u16,
     ) {
-        let show_gutter = self.line_numbers || self.relativenumber;
-        let gw = gutter_width(buf.line_count(), show_gutter);
+        let gw = self.gutter_cols(buf.line_count());
         let text_width = area_width.saturating_sub(gw) as usize;
-        let text_height = area_height.saturating_sub(1) as usize; // -1 for status
+        let text_height = area_height.saturating_sub(self.status_rows()) as usize;
 
         if text_height == 0 || text_width == 0 {
             return;
@@ -379,12 +792,26 @@ impl View {
         // when the viewport is very small or scrolloff is very large).
         let so = self.scrolloff.min(text_height.saturating_sub(1) / 2);
 
-        // Vertical: cursor must stay at least `so` lines from top and bottom.
-        if cursor_line < self.top_line + so {
-            self.top_line = cursor_line.saturating_sub(so);
-        }
-        if cursor_line + so >= self.top_line + text_height {
-            self.top_line = cursor_line + so + 1 - text_height;
+        if self.folds.is_empty() {
+            // Vertical: cursor must stay at least `so` lines from top and bottom.
+            if cursor_line < self.top_line + so {
+                self.top_line = cursor_line.saturating_sub(so);
+            }
+            if cursor_line + so >= self.top_line + text_height {
+                self.top_line = cursor_line + so + 1 - text_height;
+            }
+            // Virtual lines take rows too: keep scrolling until the last line
+            // that must be visible fits.
+            if !self.virtual_lines.is_empty() {
+                let last = (cursor_line + so).min(buf.line_count().saturating_sub(1)).max(cursor_line);
+                while self.top_line < cursor_line
+                    && self.screen_row(last).is_some_and(|row| row >= text_height)
+                {
+                    self.top_line += 1;
+                }
+            }
+        } else {
+            self.scroll_over_folds(cursor_line, so, buf.line_count(), text_height);
         }
 
         // Horizontal: cursor display column must be within [left_col, left_col + text_width)
@@ -402,6 +829,33 @@ impl View {
         }
     }
 
+    /// Vertical scrolling with closed folds: a fold is one row however
+    /// many lines it hides, so `so` counts rows and the viewport moves a
+    /// row at a time. `top_line` never points inside a closed fold.
+    fn scroll_over_folds(&mut self, cursor_line: usize, so: usize, line_count: usize, text_height: usize) {
+        let first_of_row = |line: usize| self.folds.closed_at(line).map_or(line, |(start, _)| start);
+        let cursor_line = first_of_row(cursor_line);
+        let mut top = first_of_row(self.top_line);
+        let mut min_top = cursor_line;
+        for _ in 0..so {
+            let Some(prev) = min_top.checked_sub(1) else { break };
+            min_top = first_of_row(prev);
+        }
+        top = top.min(min_top);
+        let mut last = cursor_line;
+        for _ in 0..so {
+            let next = self.last_line_of_row(last) + 1;
+            if next >= line_count {
+                break;
+            }
+            last = next;
+        }
+        self.top_line = top;
+        while self.top_line < cursor_line && self.screen_row(last).is_some_and(|row| row >= text_height) {
+            self.top_line = self.last_line_of_row(self.top_line) + 1;
+        }
+    }
+
     // -- Rendering ----------------------------------------------------------
 
     /// Render the buffer into the framebuffer.
@@ -442,41 +896,69 @@ impl View {
 
         let line_count = buf.line_count();
         let show_gutter = self.line_numbers || self.relativenumber;
-        let gw = gutter_width(line_count, show_gutter);
+        let gw = self.gutter_cols(line_count);
+        let sw = self.sign_width();
         let text_width = area_width.saturating_sub(gw);
-        let text_height = area_height.saturating_sub(1); // status line
+        let text_height = area_height.saturating_sub(self.status_rows());
         let text_x = area_x + gw;
         let cursor_line = cursor.line();
+        let conceal = if self.conceallevel > 0 { buf.path().and_then(conceal::for_path) } else { None };
 
         let mut cursor_screen: Option<(u16, u16)> = None;
 
         // -- Text rows and gutter -------------------------------------------
 
+        let rows = self.display_rows(line_count, text_height as usize);
         for row in 0..text_height {
             let screen_y = area_y + row;
-            let buf_line = self.top_line + row as usize;
+            let (buf_line, fold_end) = match rows.get(row as usize) {
+                Some(&DisplayRow::Text(line)) => (line, None),
+                Some(&DisplayRow::Fold { start, end }) => (start, Some(end)),
+                Some(DisplayRow::Virtual(virt)) => {
+                    if gw > 0 {
+                        fill_empty(frame, area_x, screen_y, gw, theme.line_nr.bg);
+                    }
+                    render_virtual_line(frame, virt, text_x, screen_y, text_width, theme);
+                    continue;
+                }
+                None => (line_count, None),
+            };
 
-            if buf_line < line_count {
+            if let Some(end) = fold_end {
+                if sw > 0 {
+                    render_sign(frame, area_x, screen_y, self.signs.get(buf_line), theme);
+                }
+                let is_cursor_line = (buf_line..=end).contains(&cursor_line);
+                if show_gutter && gw > sw {
+                    let num = self.gutter_number(buf_line, cursor_line, is_cursor_line);
+                    render_line_number(frame, area_x + sw, screen_y, gw - sw, num, is_cursor_line, theme);
+                }
+                render_fold_line(frame, buf, buf_line, end, text_x, screen_y, text_width, theme);
+                if is_cursor_line {
+                    cursor_screen = Some((text_x, screen_y));
+                }
+            } else if buf_line < line_count {
+                if sw > 0 {
+                    render_sign(frame, area_x, screen_y, self.signs.get(buf_line), theme);
+                }
                 // Gutter: line number (absolute, relative, or hybrid)
-                if show_gutter && gw > 0 {
+                if show_gutter && gw > sw {
                     let is_cursor_line = buf_line == cursor_line;
-                    let num = if self.relativenumber {
-                        if is_cursor_line && self.line_numbers {
-                            // Hybrid mode: cursor line shows absolute number.
-                            buf_line + 1
-                        } else {
-                            buf_line.abs_diff(cursor_line)
-                        }
-                    } else {
-                        buf_line + 1
-                    };
-                    render_line_number(frame, area_x, screen_y, gw, num, is_cursor_line, theme);
+                    let num = self.gutter_number(buf_line, cursor_line, is_cursor_line);
+                    render_line_number(frame, area_x + sw, screen_y, gw - sw, num, is_cursor_line, theme);
                 }
 
                 // Text content (with optional selection + syntax highlighting)
                 let line_sel = selection.and_then(|(r, k)| line_selection_cols(r, k, buf_line));
-                let line_syntax = syntax.and_then(|s| s.get(row as usize));
-                self.render_text_line(frame, buf, buf_line, text_x, screen_y, text_width, line_sel, theme, line_syntax);
+                let line_syntax = syntax.and_then(|s| s.get(buf_line - self.top_line));
+                // The cursor line shows its markup, so it can be edited.
+                let concealed = match (conceal, buf.line(buf_line)) {
+                    (Some(engine), Some(line)) if buf_line != cursor_line => engine.spans(&line.to_string()),
+                    _ => Vec::new(),
+                };
+                self.render_text_line(
+                    frame, buf, buf_line, text_x, screen_y, text_width, line_sel, theme, line_syntax, &concealed,
+                );
 
                 // Cursor screen position
                 if buf_line == cursor_line {
@@ -501,14 +983,42 @@ impl View {
 
         // -- Status line ----------------------------------------------------
 
-        if area_height > 0 {
+        if self.status_line {
             let status_y = area_y + text_height;
-            render_status_line(frame, buf, cursor, mode, buf_info, area_x, status_y, area_width, active, theme);
+            let ruler = if self.ruler { self.ruler_text(buf, cursor) } else { String::new() };
+            render_status_line(
+                frame,
+                buf,
+                &ruler,
+                cursor.line(),
+                mode,
+                buf_info,
+                area_x,
+                status_y,
+                area_width,
+                active,
+                theme,
+            );
         }
 
         cursor_screen
     }
 
+    /// The number shown in the gutter next to `line`: absolute, or with
+    /// `relativenumber` the distance from the cursor line.
+    const fn gutter_number(&self, line: usize, cursor_line: usize, is_cursor_line: bool) -> usize {
+        if self.relativenumber {
+            if is_cursor_line && self.line_numbers {
+                // Hybrid mode: cursor line shows absolute number.
+                line + 1
+            } else {
+                line.abs_diff(cursor_line)
+            }
+        } else {
+            line + 1
+        }
+    }
+
     /// Paint one line of text content into the framebuffer.
     ///
     /// `line_sel` is the optional column range `[start, end)` to highlight
@@ -517,6 +1027,9 @@ impl View {
     ///
     /// `syntax_colors` provides per-character foreground colors from the syntax
     /// highlighter. `CellColor::Default` entries use the theme's normal fg.
+    ///
+    /// `concealed` runs are drawn as their replacement character, or left
+    /// out, as `conceallevel` says.
     #[allow(clippy::too_many_arguments)]
     fn render_text_line(
         &self,
@@ -529,6 +1042,7 @@ impl View {
         line_sel: Option<(usize, usize)>,
         theme: &Theme,
         syntax_colors: Option<&Vec<CellColor>>,
+        concealed: &[ConcealSpan],
     ) {
         let Some(line) = buf.line(line_idx) else {
             fill_empty(frame, x, y, width, theme.normal.bg);
@@ -550,6 +1064,24 @@ impl View {
                 break;
             }
 
+            if let Some(span) = concealed.iter().find(|s| s.start <= char_col && char_col < s.end) {
+                if span.start == char_col && self.conceallevel == 1 {
+                    if display_col >= left_col {
+                        if screen_col >= width {
+                            break;
+                        }
+                        let hl = &span.highlight;
+                        let fg = if hl.fg.is_default() { normal_colors.0 } else { hl.fg };
+                        let bg = if hl.bg.is_default() { normal_colors.1 } else { hl.bg };
+                        frame.set(x + screen_col, y, Cell::styled(span.replacement, fg, bg, hl.attrs, hl.underline));
+                        screen_col += 1;
+                    }
+                    display_col += 1;
+                }
+                char_col += 1;
+                continue;
+            }
+
             let selected = line_sel
                 .is_some_and(|(sel_start, sel_end)| char_col >= sel_start && char_col < sel_end);
 
@@ -641,6 +1173,18 @@ impl View {
 // Rendering helpers (stateless, no &self needed)
 // ---------------------------------------------------------------------------
 
+/// Render the sign column cells for one line: the sign (or a blank) and a
+/// separator space, on the line number background.
+fn render_sign(frame: &mut FrameBuffer, x: u16, y: u16, sign: Option<&Sign>, theme: &Theme) {
+    let bg = theme.line_nr.bg;
+    fill_empty(frame, x, y, SIGN_COLUMN_WIDTH, bg);
+    if let Some(sign) = sign {
+        let hl = &sign.hl;
+        let sign_bg = if hl.bg.is_default() { bg } else { hl.bg };
+        frame.set(x, y, Cell::styled(sign.text, hl.fg, sign_bg, hl.attrs, hl.underline));
+    }
+}
+
 /// Render a right-aligned line number in the gutter.
 ///
 /// When `is_cursor_line` is true, the number is rendered at normal brightness
@@ -715,16 +1259,153 @@ fn render_tilde_line(frame: &mut FrameBuffer, x: u16, y: u16, width: u16, theme:
     }
 }
 
+/// Render the row of a closed fold: `+--` and the number of lines it hides,
+/// then the first line's text, padded out with dashes like Vim's default
+/// `foldtext`.
+#[allow(clippy::too_many_arguments)]
+fn render_fold_line(
+    frame: &mut FrameBuffer,
+    buf: &Buffer,
+    start: usize,
+    end: usize,
+    x: u16,
+    y: u16,
+    width: u16,
+    theme: &Theme,
+) {
+    let first = buf.line(start).map(String::from).unwrap_or_default();
+    let text = format!("+--{:>3} lines: {}", end - start + 1, first.trim());
+    let hl = &theme.folded;
+    let mut chars = text.chars().map(|c| if c == '\t' { ' ' } else { c });
+    let mut col: u16 = 0;
+    while col < width {
+        let ch = chars.next().unwrap_or('-');
+        let w = ch.width().unwrap_or(0);
+        if w == 0 {
+            continue;
+        }
+        // Safe: w is 1 or 2.
+        #[allow(clippy::cast_possible_truncation)]
+        let w = w as u16;
+        if col + w > width {
+            frame.set(x + col, y, Cell::styled('-', hl.fg, hl.bg, hl.attrs, hl.underline));
+            col += 1;
+            continue;
+        }
+        frame.set(x + col, y, Cell::styled(ch, hl.fg, hl.bg, hl.attrs, hl.underline));
+        if w == 2 {
+            frame.set(x + col + 1, y, Cell::continuation(hl.fg, hl.bg, hl.attrs));
+        }
+        col += w;
+    }
+}
+
+/// Render a virtual line's text into the text area, clipped to `width`.
+///
+/// The text isn't part of the buffer, so it doesn't scroll horizontally.
+/// Columns past the text are filled with the normal background.
+fn render_virtual_line(
+    frame: &mut FrameBuffer,
+    virt: &VirtualLine,
+    x: u16,
+    y: u16,
+    width: u16,
+    theme: &Theme,
+) {
+    let hl = &virt.hl;
+    let mut col: u16 = 0;
+    for ch in virt.text.chars() {
+        let w = match ch {
+            '\t' => 1,
+            _ => match ch.width() {
+                Some(w @ 1..) => w,
+                _ => continue,
+            },
+        };
+        // Safe: w is 1 or 2.
+        #[allow(clippy::cast_possible_truncation)]
+        let w = w as u16;
+        if col + w > width {
+            break;
+        }
+        let ch = if ch == '\t' { ' ' } else { ch };
+        frame.set(x + col, y, Cell::styled(ch, hl.fg, hl.bg, hl.attrs, hl.underline));
+        if w == 2 {
+            frame.set(x + col + 1, y, Cell::continuation(hl.fg, hl.bg, hl.attrs));
+        }
+        col += w;
+    }
+    fill_empty(frame, x + col, y, width - col, theme.normal.bg);
+}
+
+/// Draw a floating window's border around the edge of `rect`.
+///
+/// Uses the `vert_split` colors, like the separators between split
+/// windows. Does nothing for [`BorderStyle::None`] or a rectangle too small
+/// to frame.
+pub fn render_border(frame: &mut FrameBuffer, rect: Rect, style: BorderStyle, theme: &Theme) {
+    let Some([tl, tr, bl, br, horiz, vert]) = style.chars() else {
+        return;
+    };
+    if rect.w < 2 || rect.h < 2 {
+        return;
+    }
+    let g = &theme.vert_split;
+    let cell = |ch| Cell::styled(ch, g.fg, g.bg, g.attrs, g.underline);
+    let right = rect.x + rect.w - 1;
+    let bottom = rect.y + rect.h - 1;
+
+    frame.set(rect.x, rect.y, cell(tl));
+    frame.set(right, rect.y, cell(tr));
+    frame.set(rect.x, bottom, cell(bl));
+    frame.set(right, bottom, cell(br));
+    for x in rect.x + 1..right {
+        frame.set(x, rect.y, cell(horiz));
+        frame.set(x, bottom, cell(horiz));
+    }
+    for y in rect.y + 1..bottom {
+        frame.set(rect.x, y, cell(vert));
+        frame.set(right, y, cell(vert));
+    }
+}
+
+/// Columns the ruler text is padded to, enough for `12345:123` without
+/// shifting.
+const RULER_WIDTH: usize = 10;
+
+/// How far through the file `line` (0-indexed) is, as the ruler shows it:
+/// `Top` on the first line, `Bot` on the last, `All` when the file has one
+/// line, and otherwise a percentage like `42%`.
+#[must_use]
+pub fn file_percentage(line: usize, line_count: usize) -> String {
+    if line_count <= 1 {
+        "All".to_string()
+    } else if line == 0 {
+        "Top".to_string()
+    } else if line + 1 >= line_count {
+        "Bot".to_string()
+    } else {
+        format!("{}%", line * 100 / line_count)
+    }
+}
+
 /// Render the status line at the bottom of the view.
 ///
 /// `buf_info` is an optional string shown after the filename, typically
 /// indicating the buffer position within a multi-buffer set (e.g., `"[2/3]"`).
 /// Pass an empty string when there is only one buffer.
+///
+/// `ruler` is the cursor position ([`View::ruler_text`]), or empty with
+/// `noruler`. It is padded to [`RULER_WIDTH`] and followed by how far
+/// through the file the cursor's `line` is ([`file_percentage`]),
+/// right-aligned in three columns, so the right portion doesn't shift as
+/// the numbers change width.
 #[allow(clippy::too_many_arguments)]
 fn render_status_line(
     frame: &mut FrameBuffer,
     buf: &Buffer,
-    cursor: &Cursor,
+    ruler: &str,
+    line: usize,
     mode: Mode,
     buf_info: &str,
     x: u16,
@@ -737,14 +1418,27 @@ fn render_status_line(
         return;
     }
 
-    // Left: " MODE | filename [+] [2/3]"
+    // Left: " MODE | filename [+] [2/3]" ("[RO]" in place of "[+]" for a
+    // buffer the editor owns, like a terminal; "[-]" when 'modifiable' is
+    // off, "[RO]" after "[+]" when 'readonly' is set).
     let mode_str = mode.display_name();
     let filename = buf
         .path()
         .and_then(|p| p.file_name())
         .and_then(|n| n.to_str())
-        .unwrap_or("[No Name]");
-    let modified = if buf.is_modified() { " [+]" } else { "" };
+        .unwrap_or_else(|| buf.buf_type().unnamed_label());
+    let modified = if buf.buf_type().is_read_only() {
+        " [RO]"
+    } else if !buf.is_modifiable() {
+        " [-]"
+    } else {
+        match (buf.is_modified(), buf.is_readonly()) {
+            (true, true) => " [+][RO]",
+            (true, false) => " [+]",
+            (false, true) => " [RO]",
+            (false, false) => "",
+        }
+    };
     let buf_label = if buf_info.is_empty() {
         String::new()
     } else {
@@ -752,8 +1446,13 @@ fn render_status_line(
     };
     let left = format!(" {mode_str} | {filename}{modified}{buf_label}");
 
-    // Right: " line:col "
-    let right = format!(" {}:{} ", cursor.line() + 1, cursor.col() + 1);
+    // Right: " line:col    pct "
+    let right = if ruler.is_empty() {
+        String::new()
+    } else {
+        let pct = file_percentage(line, buf.line_count());
+        format!(" {ruler:<RULER_WIDTH$} {pct:>3} ")
+    };
 
     // Active: mode-specific color. Inactive: always status_line_nc.
     let group = if active {
@@ -855,10 +1554,10 @@ pub fn highlight_matches(
         return;
     }
 
-    let gw = gutter_width(buf.line_count(), view.line_numbers || view.relativenumber);
+    let gw = view.gutter_cols(buf.line_count());
     let text_x = area_x + gw;
     let text_width = area_width.saturating_sub(gw);
-    let text_height = area_height.saturating_sub(1); // status line
+    let text_height = area_height.saturating_sub(view.status_rows());
 
     if text_height == 0 || text_width == 0 {
         return;
@@ -871,58 +1570,221 @@ pub fn highlight_matches(
         view.top_line + text_height as usize,
     );
 
-    for m in &matches {
-        let row = m.start.line.saturating_sub(view.top_line);
-        if row >= text_height as usize {
-            continue;
-        }
+    for m in &matches {
+        paint_match(view, frame, buf, m, text_x, area_y, text_width, text_height, &theme.search);
+    }
+}
+
+/// Highlight one match with the `inc_search` group — the match a `:s///c`
+/// prompt is asking about. Call this **after** [`View::render`].
+#[allow(clippy::too_many_arguments)]
+pub fn highlight_current_match(
+    view: &View,
+    frame: &mut FrameBuffer,
+    buf: &Buffer,
+    m: &Match,
+    area_x: u16,
+    area_y: u16,
+    area_width: u16,
+    area_height: u16,
+    theme: &Theme,
+) {
+    let gw = view.gutter_cols(buf.line_count());
+    let text_width = area_width.saturating_sub(gw);
+    let text_height = area_height.saturating_sub(view.status_rows());
+    if text_height == 0 || text_width == 0 {
+        return;
+    }
+    paint_match(view, frame, buf, m, area_x + gw, area_y, text_width, text_height, &theme.inc_search);
+}
+
+/// Paint the cells of one on-screen match with `group`.
+#[allow(clippy::too_many_arguments)]
+fn paint_match(
+    view: &View,
+    frame: &mut FrameBuffer,
+    buf: &Buffer,
+    m: &Match,
+    text_x: u16,
+    area_y: u16,
+    text_width: u16,
+    text_height: u16,
+    group: &HighlightGroup,
+) {
+    let Some(row) = view.screen_row(m.start.line) else {
+        return;
+    };
+    if row >= text_height as usize || view.folds.closed_at(m.start.line).is_some() {
+        return;
+    }
+
+    let Some(line) = buf.line(m.start.line) else {
+        return;
+    };
+
+    // Compute display column range for the match.
+    let match_start_dc = char_col_to_display_col(
+        line.chars(),
+        m.start.col,
+        view.tab_width,
+    );
+    let match_end_dc = char_col_to_display_col(
+        line.chars(),
+        m.start.col + m.len,
+        view.tab_width,
+    );
+
+    // Paint all display columns in [match_start_dc, match_end_dc).
+    for dc in match_start_dc..match_end_dc {
+        if dc < view.left_col {
+            continue;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let screen_col = (dc - view.left_col) as u16;
+        if screen_col >= text_width {
+            break;
+        }
+
+        let sx = text_x + screen_col;
+        #[allow(clippy::cast_possible_truncation)]
+        let sy = area_y + row as u16;
+
+        if let Some(cell) = frame.get(sx, sy) {
+            if cell.is_continuation() {
+                frame.set(
+                    sx,
+                    sy,
+                    Cell::continuation(group.fg, group.bg, group.attrs),
+                );
+            } else {
+                let ch = cell.character().unwrap_or(' ');
+                frame.set(
+                    sx,
+                    sy,
+                    Cell::styled(ch, group.fg, group.bg, group.attrs, group.underline),
+                );
+            }
+        }
+    }
+}
+
+/// Underline misspelled words in the visible lines (`:set spell`).
+///
+/// Call this **after** [`View::render`]. Only the lines in the viewport are
+/// checked. The `spell_bad` group is layered on top of the existing cells:
+/// its underline always applies, and its fg only when it isn't `Default`, so
+/// syntax colors show through.
+#[allow(clippy::too_many_arguments)]
+pub fn highlight_spelling(
+    view: &View,
+    frame: &mut FrameBuffer,
+    buf: &Buffer,
+    checker: &SpellChecker,
+    area_x: u16,
+    area_y: u16,
+    area_width: u16,
+    area_height: u16,
+    theme: &Theme,
+) {
+    let gw = view.gutter_cols(buf.line_count());
+    let text_x = area_x + gw;
+    let text_width = area_width.saturating_sub(gw);
+    let text_height = area_height.saturating_sub(view.status_rows());
+
+    if text_height == 0 || text_width == 0 {
+        return;
+    }
+
+    let sg = &theme.spell_bad;
+    for line_idx in view.top_line.. {
+        let Some(row) = view.screen_row(line_idx).filter(|&r| r < text_height as usize) else {
+            break;
+        };
+        if view.folds.closed_at(line_idx).is_some() {
+            continue;
+        }
+        let Some(line) = buf.line(line_idx) else {
+            break;
+        };
+        let text = line.to_string();
+        let text = text.trim_end_matches(['\n', '\r']);
+
+        for range in checker.check_line(text) {
+            let start_col = text[..range.start].chars().count();
+            let end_col = start_col + text[range].chars().count();
+            let start_dc = char_col_to_display_col(line.chars(), start_col, view.tab_width);
+            let end_dc = char_col_to_display_col(line.chars(), end_col, view.tab_width);
+
+            for dc in start_dc..end_dc {
+                if dc < view.left_col {
+                    continue;
+                }
+                #[allow(clippy::cast_possible_truncation)]
+                let screen_col = (dc - view.left_col) as u16;
+                if screen_col >= text_width {
+                    break;
+                }
+                let sx = text_x + screen_col;
+                #[allow(clippy::cast_possible_truncation)]
+                let sy = area_y + row as u16;
+                if let Some(mut cell) = frame.get(sx, sy).copied() {
+                    cell.underline = sg.underline;
+                    if !sg.fg.is_default() {
+                        cell.fg = sg.fg;
+                    }
+                    frame.set(sx, sy, cell);
+                }
+            }
+        }
+    }
+}
+
+/// Mark the `colorcolumn` columns: give the cells at each 1-based screen
+/// column in `columns` the `color_column` background, on every row showing
+/// a buffer line.
+///
+/// Call this after [`View::render`] and before [`highlight_cursorline`], so
+/// the column stays visible on the cursor line. Cells that already have a
+/// background (a visual selection, a search match) keep it.
+#[allow(clippy::too_many_arguments)]
+pub fn highlight_color_columns(
+    view: &View,
+    frame: &mut FrameBuffer,
+    buf: &Buffer,
+    columns: &[usize],
+    area_x: u16,
+    area_y: u16,
+    area_width: u16,
+    area_height: u16,
+    theme: &Theme,
+) {
+    let gw = view.gutter_cols(buf.line_count());
+    let text_x = area_x + gw;
+    let text_width = area_width.saturating_sub(gw);
+    let text_height = area_height.saturating_sub(view.status_rows());
+
+    if columns.is_empty() || text_height == 0 || text_width == 0 {
+        return;
+    }
 
-        let Some(line) = buf.line(m.start.line) else {
-            continue;
+    let bg = theme.color_column.bg;
+    for line_idx in view.top_line..buf.line_count() {
+        let Some(row) = view.screen_row(line_idx).filter(|&r| r < text_height as usize) else {
+            break;
         };
-
-        // Compute display column range for the match.
-        let match_start_dc = char_col_to_display_col(
-            line.chars(),
-            m.start.col,
-            view.tab_width,
-        );
-        let match_end_dc = char_col_to_display_col(
-            line.chars(),
-            m.start.col + m.len,
-            view.tab_width,
-        );
-
-        // Paint all display columns in [match_start_dc, match_end_dc).
-        for dc in match_start_dc..match_end_dc {
-            if dc < view.left_col {
+        #[allow(clippy::cast_possible_truncation)]
+        let sy = area_y + row as u16;
+        for &col in columns {
+            let Some(dc) = col.checked_sub(1) else { continue };
+            if dc < view.left_col || dc - view.left_col >= text_width as usize {
                 continue;
             }
             #[allow(clippy::cast_possible_truncation)]
-            let screen_col = (dc - view.left_col) as u16;
-            if screen_col >= text_width {
-                break;
-            }
-
-            let sx = text_x + screen_col;
-            #[allow(clippy::cast_possible_truncation)]
-            let sy = area_y + row as u16;
-
-            if let Some(cell) = frame.get(sx, sy) {
-                let sg = &theme.search;
-                if cell.is_continuation() {
-                    frame.set(
-                        sx,
-                        sy,
-                        Cell::continuation(sg.fg, sg.bg, sg.attrs),
-                    );
-                } else {
-                    let ch = cell.character().unwrap_or(' ');
-                    frame.set(
-                        sx,
-                        sy,
-                        Cell::styled(ch, sg.fg, sg.bg, sg.attrs, sg.underline),
-                    );
+            let sx = text_x + (dc - view.left_col) as u16;
+            if let Some(mut cell) = frame.get(sx, sy).copied() {
+                if cell.bg.is_default() {
+                    cell.bg = bg;
+                    frame.set(sx, sy, cell);
                 }
             }
         }
@@ -953,13 +1815,12 @@ pub fn highlight_cursorline(
         return;
     }
 
-    let text_height = area_height.saturating_sub(1); // exclude status line
+    let text_height = area_height.saturating_sub(view.status_rows());
 
     // The cursor line must be visible.
-    if cursor_line < view.top_line {
+    let Some(row) = view.screen_row(cursor_line) else {
         return;
-    }
-    let row = cursor_line - view.top_line;
+    };
     if row >= text_height as usize {
         return;
     }
@@ -987,43 +1848,95 @@ pub fn highlight_cursorline(
     }
 }
 
+/// Most items a completion popup shows at once.
+pub const COMPLETION_MENU_MAX_HEIGHT: usize = 10;
+
+/// The insert mode completion popup: its candidates, which one is
+/// selected, and which one is at the top of the popup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionMenu {
+    /// The candidates, in menu order.
+    pub items: Vec<String>,
+    /// Index of the selected item; past the end when none is (the typed
+    /// text is back in the buffer).
+    pub selected: usize,
+    /// Index of the first item shown.
+    pub visible_offset: usize,
+}
+
+impl CompletionMenu {
+    /// A menu of `items` with `selected` selected, scrolled to show it.
+    #[must_use]
+    pub fn new(items: Vec<String>, selected: usize) -> Self {
+        let mut menu = Self { items, selected, visible_offset: 0 };
+        menu.scroll_to_selected();
+        menu
+    }
+
+    /// Rows the popup takes: every item, up to
+    /// [`COMPLETION_MENU_MAX_HEIGHT`].
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.items.len().min(COMPLETION_MENU_MAX_HEIGHT)
+    }
+
+    /// Select `index`, scrolling as little as needed to keep it shown.
+    pub fn select(&mut self, index: usize) {
+        self.selected = index;
+        self.scroll_to_selected();
+    }
+
+    fn scroll_to_selected(&mut self) {
+        let height = self.height();
+        let max_offset = self.items.len() - height;
+        if self.selected < self.items.len() {
+            if self.selected < self.visible_offset {
+                self.visible_offset = self.selected;
+            } else if self.selected >= self.visible_offset + height {
+                self.visible_offset = self.selected + 1 - height;
+            }
+        }
+        self.visible_offset = self.visible_offset.min(max_offset);
+    }
+}
+
 /// Render a completion popup menu below the cursor.
 ///
-/// Shows a list of candidates with the current selection highlighted. The popup
-/// appears below `cursor_y` at column `cursor_x`, clamped to fit within the
-/// screen. Shows at most `MAX_POPUP_HEIGHT` items, scrolling if there are more.
-///
-/// The `selected` index highlights one candidate with inverse video. The
-/// `prefix` is shown as the last entry (cycling back to what the user typed).
+/// The popup is one column wider on each side than the longest item and
+/// [`CompletionMenu::height`] rows tall. It opens on the row below
+/// `cursor_y` (above it if there's no room) with its text starting just
+/// right of `cursor_x`, shifted left to fit the screen. The selected item
+/// uses `pmenu_sel`, the rest `pmenu`. When the items don't all fit, the
+/// right-hand column is a scrollbar whose thumb (in `pmenu_sel`) shows
+/// which part of the list is visible.
 #[allow(clippy::too_many_arguments)]
 pub fn render_completion_popup(
     frame: &mut FrameBuffer,
-    candidates: &[String],
-    selected: usize,
+    menu: &CompletionMenu,
     cursor_x: u16,
     cursor_y: u16,
     frame_width: u16,
     frame_height: u16,
     theme: &Theme,
 ) {
-    const MAX_POPUP_HEIGHT: u16 = 10;
-
-    if candidates.is_empty() || frame_width == 0 || frame_height == 0 {
+    if menu.items.is_empty() || frame_width == 0 || frame_height == 0 {
         return;
     }
 
     // Compute popup dimensions.
     #[allow(clippy::cast_possible_truncation)]
-    let item_count = candidates.len().min(MAX_POPUP_HEIGHT as usize) as u16;
-    let popup_height = item_count;
+    let popup_height = menu.height() as u16;
+    let scrollbar = menu.items.len() > menu.height();
     #[allow(clippy::cast_possible_truncation)]
-    let max_word_len = candidates
+    let max_word_len = menu
+        .items
         .iter()
         .map(|s| s.chars().count())
         .max()
         .unwrap_or(0)
         .min(frame_width as usize) as u16;
-    let popup_width = (max_word_len + 2).min(frame_width.saturating_sub(2)).max(4);
+    let popup_width = (max_word_len + 2 + u16::from(scrollbar)).min(frame_width.saturating_sub(2)).max(4);
+    let text_width = popup_width - u16::from(scrollbar);
 
     // Position: prefer below cursor, shift up if near bottom.
     let popup_y = if cursor_y + 1 + popup_height <= frame_height {
@@ -1035,23 +1948,22 @@ pub fn render_completion_popup(
     // Horizontal: align left edge with cursor, clamp to frame.
     let popup_x = cursor_x.min(frame_width.saturating_sub(popup_width));
 
-    // Scroll offset: keep selected item visible.
-    #[allow(clippy::cast_possible_truncation)]
-    let scroll: u16 = if selected >= popup_height as usize {
-        (selected as u16).saturating_sub(popup_height) + 1
-    } else {
-        0
-    };
+    // Scrollbar thumb: rows proportional to the visible share of the list,
+    // at least one.
+    let height = menu.height();
+    let total = menu.items.len();
+    let thumb_len = (height * height).div_ceil(total).max(1);
+    let thumb_top = (menu.visible_offset * height / total).min(height - thumb_len);
 
     // Render each visible item.
     for row in 0..popup_height {
-        let idx = (scroll + row) as usize;
-        if idx >= candidates.len() {
+        let idx = menu.visible_offset + row as usize;
+        if idx >= menu.items.len() {
             break;
         }
 
         let sy = popup_y + row;
-        let is_selected = idx == selected;
+        let is_selected = idx == menu.selected;
 
         let group = if is_selected { &theme.pmenu_sel } else { &theme.pmenu };
         let (fg, bg, attrs) = (group.fg, group.bg, group.attrs);
@@ -1063,8 +1975,8 @@ pub fn render_completion_popup(
 
         // Candidate text.
         let mut col: u16 = 1;
-        for ch in candidates[idx].chars() {
-            if col >= popup_width - 1 {
+        for ch in menu.items[idx].chars() {
+            if col >= text_width - 1 {
                 break;
             }
             let sx = popup_x + col;
@@ -1075,13 +1987,23 @@ pub fn render_completion_popup(
         }
 
         // Fill remaining width with background.
-        while col < popup_width {
+        while col < text_width {
             let sx = popup_x + col;
             if sx < frame_width {
                 frame.set(sx, sy, Cell::styled(' ', fg, bg, attrs, UnderlineStyle::None));
             }
             col += 1;
         }
+
+        if scrollbar {
+            let r = row as usize;
+            let bar = if r >= thumb_top && r < thumb_top + thumb_len { &theme.pmenu_sel } else { &theme.pmenu };
+            frame.set(
+                popup_x + text_width,
+                sy,
+                Cell::styled(' ', bar.fg, bar.bg, Attr::empty(), UnderlineStyle::None),
+            );
+        }
     }
 }
 
@@ -1237,6 +2159,102 @@ pub fn render_message_line(
     fill_empty(frame, x + col, y, width - col, theme.normal.bg);
 }
 
+/// Render the tab page line: one label per tab page, the `active` one in
+/// the status line colors and the rest in the inactive status line colors.
+/// Labels past `width` are cut off.
+pub fn render_tabline(
+    frame: &mut FrameBuffer,
+    labels: &[String],
+    active: usize,
+    x: u16,
+    y: u16,
+    width: u16,
+    theme: &Theme,
+) {
+    let mut col: u16 = 0;
+    for (i, label) in labels.iter().enumerate() {
+        let group = if i == active { &theme.status_line } else { &theme.status_line_nc };
+        for ch in label.chars() {
+            if col >= width {
+                return;
+            }
+            frame.set(x + col, y, Cell::styled(ch, group.fg, group.bg, group.attrs, group.underline));
+            col += 1;
+        }
+    }
+    fill_empty(frame, x + col, y, width - col, theme.status_line_nc.bg);
+}
+
+/// Render a page of command output (e.g. from `K`) over the given area.
+///
+/// `lines[top..]` fill the rows above the last one; the last row holds the
+/// pager prompt — `-- More --` while output remains below, otherwise the
+/// "Press ENTER" prompt. Tabs are expanded to 8-column stops.
+#[allow(clippy::too_many_arguments)]
+pub fn render_output(
+    frame: &mut FrameBuffer,
+    lines: &[String],
+    top: usize,
+    x: u16,
+    y: u16,
+    width: u16,
+    height: u16,
+    theme: &Theme,
+) {
+    if width == 0 || height == 0 {
+        return;
+    }
+    let text_h = height - 1;
+    let normal = &theme.normal;
+
+    for row in 0..text_h {
+        let mut col: u16 = 0;
+        if let Some(line) = lines.get(top + row as usize) {
+            for ch in line.chars() {
+                if col >= width {
+                    break;
+                }
+                if ch == '\t' {
+                    let next = (col / 8 + 1) * 8;
+                    while col < next.min(width) {
+                        frame.set(
+                            x + col,
+                            y + row,
+                            Cell::styled(' ', normal.fg, normal.bg, normal.attrs, normal.underline),
+                        );
+                        col += 1;
+                    }
+                    continue;
+                }
+                let ch = if ch.is_control() { '?' } else { ch };
+                frame.set(
+                    x + col,
+                    y + row,
+                    Cell::styled(ch, normal.fg, normal.bg, normal.attrs, normal.underline),
+                );
+                col += 1;
+            }
+        }
+        fill_empty(frame, x + col, y + row, width - col, normal.bg);
+    }
+
+    let prompt = if top + (text_h as usize) < lines.len() {
+        "-- More --"
+    } else {
+        "Press ENTER or type command to continue"
+    };
+    let q = &theme.msg;
+    let mut col: u16 = 0;
+    for ch in prompt.chars() {
+        if col >= width {
+            break;
+        }
+        frame.set(x + col, y + text_h, Cell::styled(ch, q.fg, q.bg, q.attrs, q.underline));
+        col += 1;
+    }
+    fill_empty(frame, x + col, y + text_h, width - col, normal.bg);
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -1244,6 +2262,7 @@ pub fn render_message_line(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::buffer::BufType;
     use crate::position::Position;
     use std::path::PathBuf;
 
@@ -1786,6 +2805,180 @@ mod tests {
         assert!(row2.starts_with("3 ccc"), "row2 = '{row2}'");
     }
 
+    // ── render — virtual lines ────────────────────────────────────────────
+
+    fn virt(line: usize, position: VirtualPosition, text: &str) -> VirtualLine {
+        VirtualLine {
+            line,
+            position,
+            text: text.to_string(),
+            hl: HighlightGroup::fg_only(CellColor::Rgb(1, 2, 3)),
+        }
+    }
+
+    #[test]
+    fn virtual_lines_sorted_and_split_by_side() {
+        let vl = VirtualLines::new(vec![
+            virt(3, VirtualPosition::Below, "b3"),
+            virt(1, VirtualPosition::Above, "a1"),
+            virt(1, VirtualPosition::Below, "b1"),
+            virt(1, VirtualPosition::Above, "a1'"),
+        ]);
+        assert_eq!(vl.len(), 4);
+        let texts = |it: Vec<&VirtualLine>| it.iter().map(|v| v.text.clone()).collect::<Vec<_>>();
+        assert_eq!(texts(vl.above(1).collect()), ["a1", "a1'"]);
+        assert_eq!(texts(vl.below(1).collect()), ["b1"]);
+        assert_eq!(vl.above(3).count(), 0);
+        assert_eq!(vl.below(2).count(), 0);
+    }
+
+    #[test]
+    fn render_virtual_lines_take_their_own_rows() {
+        let buf = Buffer::from_text("aaa\nbbb\nccc");
+        let cursor = Cursor::at(Position::new(1, 0));
+        let mut frame = FrameBuffer::new(20, 6);
+        let mut v = View::new();
+        v.set_virtual_lines(VirtualLines::new(vec![
+            virt(1, VirtualPosition::Above, "hint above"),
+            virt(1, VirtualPosition::Below, "hint below"),
+        ]));
+
+        let pos = v.render(&buf, &cursor, Mode::Normal, None, "", &mut frame, 0, 0, 20, 6, true, &test_theme(), None);
+
+        assert!(row_chars(&frame, 0).starts_with("1 aaa"));
+        assert!(row_chars(&frame, 1).starts_with("  hint above"));
+        assert!(row_chars(&frame, 2).starts_with("2 bbb"));
+        assert!(row_chars(&frame, 3).starts_with("  hint below"));
+        assert!(row_chars(&frame, 4).starts_with("3 ccc"));
+        // Virtual text uses its own highlight group.
+        assert_eq!(frame.get(2, 1).unwrap().fg, CellColor::Rgb(1, 2, 3));
+        // The cursor is drawn on the buffer line's row.
+        assert_eq!(pos, Some((2, 2)));
+        assert_eq!(v.screen_row(1), Some(2));
+        assert_eq!(v.line_at_row(1), 1);
+        assert_eq!(v.line_at_row(3), 1);
+        assert_eq!(v.line_at_row(4), 2);
+    }
+
+    #[test]
+    fn render_virtual_lines_clipped_to_width() {
+        let buf = Buffer::from_text("x");
+        let mut frame = FrameBuffer::new(8, 4);
+        let mut v = View::new();
+        v.set_virtual_lines(VirtualLines::new(vec![virt(0, VirtualPosition::Below, "a long hint")]));
+        v.render(&buf, &Cursor::new(), Mode::Normal, None, "", &mut frame, 0, 0, 8, 4, true, &test_theme(), None);
+        assert_eq!(row_chars(&frame, 1), "  a long");
+    }
+
+    #[test]
+    fn scroll_accounts_for_virtual_lines() {
+        let buf = Buffer::from_text("0\n1\n2\n3\n4\n5");
+        let mut v = View::new();
+        v.set_virtual_lines(VirtualLines::new(vec![
+            virt(2, VirtualPosition::Above, "x"),
+            virt(3, VirtualPosition::Above, "y"),
+        ]));
+        // 4 text rows; line 3 sits on row 5 from the top, so the view scrolls.
+        v.ensure_cursor_visible(&Cursor::at(Position::new(3, 0)), &buf, 20, 5);
+        assert_eq!(v.top_line(), 2);
+        assert_eq!(v.screen_row(3), Some(3));
+    }
+
+    // ── Folds ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn render_closed_fold_as_one_row() {
+        let buf = Buffer::from_text("def f():\n    a\n    b\nx");
+        let mut frame = FrameBuffer::new(24, 4);
+        let mut v = View::new();
+        v.set_folds(crate::fold::compute_indent_folds(&buf, 4));
+
+        let pos = v.render(&buf, &Cursor::at(Position::new(2, 4)), Mode::Normal, None, "", &mut frame, 0, 0, 24, 4, true, &test_theme(), None);
+
+        assert!(row_chars(&frame, 0).starts_with("1 def f():"));
+        assert_eq!(row_chars(&frame, 1), "2 +--  2 lines: a-------");
+        assert!(row_chars(&frame, 2).starts_with("4 x"));
+        // A cursor inside the fold is drawn at the start of its row.
+        assert_eq!(pos, Some((2, 1)));
+        assert_eq!(v.screen_row(2), Some(1));
+        assert_eq!(v.screen_row(3), Some(2));
+        assert_eq!(v.line_at_row(1), 1);
+        assert_eq!(v.line_at_row(2), 3);
+        assert_eq!(v.lines_shown(4, 3), 4);
+    }
+
+    #[test]
+    fn scroll_counts_a_closed_fold_as_one_row() {
+        let buf = Buffer::from_text("a\n b\n c\n d\n e\nf\ng\nh");
+        let mut v = View::new();
+        v.set_folds(crate::fold::compute_indent_folds(&buf, 1));
+        // 3 text rows: a, the fold, f — g needs one more.
+        v.ensure_cursor_visible(&Cursor::at(Position::new(5, 0)), &buf, 20, 4);
+        assert_eq!(v.top_line(), 0);
+        v.ensure_cursor_visible(&Cursor::at(Position::new(6, 0)), &buf, 20, 4);
+        assert_eq!(v.top_line(), 1);
+        // Scrolling back up stops at the fold's first line, not inside it.
+        v.set_top_line(3);
+        v.ensure_cursor_visible(&Cursor::at(Position::new(2, 0)), &buf, 20, 4);
+        assert_eq!(v.top_line(), 1);
+    }
+
+    // ── Sign column ───────────────────────────────────────────────────────
+
+    fn sign(line: usize, text: char) -> Sign {
+        Sign { line, text, hl: HighlightGroup::fg_only(CellColor::Rgb(4, 5, 6)) }
+    }
+
+    #[test]
+    fn signs_lookup_by_line() {
+        let signs = Signs::new(vec![sign(3, 'b'), sign(1, 'a'), sign(3, 'c')]);
+        assert_eq!(signs.len(), 3);
+        assert_eq!(signs.get(1).map(|s| s.text), Some('a'));
+        assert_eq!(signs.get(3).map(|s| s.text), Some('b'));
+        assert_eq!(signs.get(2), None);
+        assert!(Signs::default().is_empty());
+    }
+
+    #[test]
+    fn render_sign_column_left_of_numbers() {
+        let buf = Buffer::from_text("aaa\nbbb");
+        let mut frame = FrameBuffer::new(12, 3);
+        let mut v = View::new();
+        assert_eq!(v.gutter_cols(2), 2);
+        v.set_signs(Signs::new(vec![sign(1, '│')]));
+        assert_eq!(v.gutter_cols(2), 2 + SIGN_COLUMN_WIDTH);
+
+        let pos = v.render(&buf, &Cursor::at(Position::new(1, 1)), Mode::Normal, None, "", &mut frame, 0, 0, 12, 3, true, &test_theme(), None);
+
+        assert!(row_chars(&frame, 0).starts_with("  1 aaa"));
+        assert!(row_chars(&frame, 1).starts_with("│ 2 bbb"));
+        assert_eq!(frame.get(0, 1).unwrap().fg, CellColor::Rgb(4, 5, 6));
+        assert_eq!(pos, Some((5, 1)));
+    }
+
+    // ── Floating windows ──────────────────────────────────────────────────
+
+    #[test]
+    fn render_without_status_line_uses_every_row() {
+        let buf = Buffer::from_text("a\nb\nc");
+        let mut frame = FrameBuffer::new(10, 3);
+        let mut v = View::new();
+        v.set_status_line(false);
+        v.render(&buf, &Cursor::new(), Mode::Normal, None, "", &mut frame, 0, 0, 10, 3, false, &test_theme(), None);
+        assert!(row_chars(&frame, 2).starts_with("3 c"));
+    }
+
+    #[test]
+    fn render_border_rounded() {
+        let mut frame = FrameBuffer::new(6, 4);
+        let rect = Rect { x: 1, y: 0, w: 4, h: 3 };
+        render_border(&mut frame, rect, BorderStyle::Rounded, &test_theme());
+        assert_eq!(row_chars(&frame, 0), " ╭──╮ ");
+        assert_eq!(row_chars(&frame, 1), " │  │ ");
+        assert_eq!(row_chars(&frame, 2), " ╰──╯ ");
+        assert_eq!(row_chars(&frame, 3), "      ");
+    }
+
     // ── render — tilde lines ──────────────────────────────────────────────
 
     #[test]
@@ -1872,6 +3065,25 @@ mod tests {
         assert!(status.contains("[No Name]"), "status = '{status}'");
     }
 
+    #[test]
+    fn status_line_shows_buffer_type() {
+        let mut buf = Buffer::from_text("hello");
+        buf.set_buf_type(BufType::NoFile);
+        buf.insert(Position::ZERO, "x");
+        let cursor = Cursor::new();
+        let mut frame = FrameBuffer::new(50, 3);
+        let mut v = View::new();
+
+        v.render(&buf, &cursor, Mode::Normal, None, "", &mut frame, 0, 0, 50, 3, true, &test_theme(), None);
+        let status = row_chars(&frame, 2);
+        assert!(status.contains("[Scratch]") && !status.contains("[+]"), "status = '{status}'");
+
+        buf.set_buf_type(BufType::QuickFix);
+        v.render(&buf, &cursor, Mode::Normal, None, "", &mut frame, 0, 0, 50, 3, true, &test_theme(), None);
+        let status = row_chars(&frame, 2);
+        assert!(status.contains("[Quickfix List] [RO]"), "status = '{status}'");
+    }
+
     #[test]
     fn status_line_shows_modified() {
         let mut buf = Buffer::from_text("hello");
@@ -1886,6 +3098,29 @@ mod tests {
         assert!(status.contains("[+]"), "status = '{status}'");
     }
 
+    #[test]
+    fn status_line_shows_readonly_and_nomodifiable() {
+        let mut buf = Buffer::from_text("hello");
+        buf.set_readonly(true);
+        let cursor = Cursor::new();
+        let mut frame = FrameBuffer::new(50, 3);
+        let mut v = View::new();
+
+        v.render(&buf, &cursor, Mode::Normal, None, "", &mut frame, 0, 0, 50, 3, true, &test_theme(), None);
+        let status = row_chars(&frame, 2);
+        assert!(status.contains("[RO]") && !status.contains("[+]"), "status = '{status}'");
+
+        buf.insert(Position::ZERO, "x");
+        v.render(&buf, &cursor, Mode::Normal, None, "", &mut frame, 0, 0, 50, 3, true, &test_theme(), None);
+        let status = row_chars(&frame, 2);
+        assert!(status.contains("[+][RO]"), "status = '{status}'");
+
+        buf.set_modifiable(false);
+        v.render(&buf, &cursor, Mode::Normal, None, "", &mut frame, 0, 0, 50, 3, true, &test_theme(), None);
+        let status = row_chars(&frame, 2);
+        assert!(status.contains("[-]"), "status = '{status}'");
+    }
+
     #[test]
     fn status_line_shows_cursor_position() {
         let buf = Buffer::from_text("hello\nworld");
@@ -1900,6 +3135,59 @@ mod tests {
         assert!(status.contains("2:4"), "status = '{status}'");
     }
 
+    #[test]
+    fn ruler_column_formats() {
+        let buf = Buffer::from_text("\tab\u{e9}c");
+        let cursor = Cursor::at(Position::new(0, 4));
+        let mut v = View::new();
+        assert_eq!(v.ruler_text(&buf, &cursor), "1:5");
+        v.set_ruler_format(RulerFormat::Byte);
+        assert_eq!(v.ruler_text(&buf, &cursor), "1:6");
+        v.set_ruler_format(RulerFormat::Virtual);
+        assert_eq!(v.ruler_text(&buf, &cursor), "1:8");
+        v.set_tab_width(8);
+        assert_eq!(v.ruler_text(&buf, &cursor), "1:12");
+    }
+
+    #[test]
+    fn ruler_is_padded_and_can_be_hidden() {
+        let buf = Buffer::from_text("hello");
+        let cursor = Cursor::new();
+        let mut frame = FrameBuffer::new(40, 3);
+        let mut v = View::new();
+
+        v.render(&buf, &cursor, Mode::Normal, None, "", &mut frame, 0, 0, 40, 3, true, &test_theme(), None);
+        let status = row_chars(&frame, 2);
+        assert!(status.ends_with(" 1:1        All "), "status = '{status}'");
+
+        v.set_ruler(false);
+        v.render(&buf, &cursor, Mode::Normal, None, "", &mut frame, 0, 0, 40, 3, true, &test_theme(), None);
+        let status = row_chars(&frame, 2);
+        assert!(!status.contains("1:1"), "status = '{status}'");
+    }
+
+    #[test]
+    fn file_percentage_names_the_ends() {
+        assert_eq!(file_percentage(0, 1), "All");
+        assert_eq!(file_percentage(0, 0), "All");
+        assert_eq!(file_percentage(0, 200), "Top");
+        assert_eq!(file_percentage(199, 200), "Bot");
+        assert_eq!(file_percentage(84, 200), "42%");
+        assert_eq!(file_percentage(1, 200), "0%");
+    }
+
+    #[test]
+    fn ruler_percentage_is_right_aligned() {
+        let text = (0..200).map(|i| i.to_string()).collect::<Vec<_>>().join("\n");
+        let buf = Buffer::from_text(&text);
+        let mut frame = FrameBuffer::new(40, 3);
+        let mut v = View::new();
+        let cursor = Cursor::at(Position::new(10, 0));
+        v.render(&buf, &cursor, Mode::Normal, None, "", &mut frame, 0, 0, 40, 3, true, &test_theme(), None);
+        let status = row_chars(&frame, 2);
+        assert!(status.ends_with(" 11:1        5% "), "status = '{status}'");
+    }
+
     #[test]
     fn status_line_has_status_bg() {
         let buf = Buffer::from_text("hello");
@@ -2212,6 +3500,35 @@ mod tests {
         assert_eq!(cell.fg, test_theme().error_msg.fg);
     }
 
+    #[test]
+    fn tabline_marks_active_tab() {
+        let theme = test_theme();
+        let labels = vec![" 1 a.rs ".to_string(), " 2 b.rs ".to_string()];
+        let mut frame = FrameBuffer::new(20, 1);
+        render_tabline(&mut frame, &labels, 1, 0, 0, 20, &theme);
+        assert_eq!(row_chars(&frame, 0).trim_end(), " 1 a.rs  2 b.rs");
+        assert_eq!(frame.get(1, 0).unwrap().bg, theme.status_line_nc.bg);
+        assert_eq!(frame.get(9, 0).unwrap().bg, theme.status_line.bg);
+
+        let mut narrow = FrameBuffer::new(10, 1);
+        render_tabline(&mut narrow, &labels, 0, 0, 0, 10, &theme);
+        assert_eq!(row_chars(&narrow, 0), " 1 a.rs  2");
+    }
+
+    #[test]
+    fn output_pager_prompts() {
+        let lines: Vec<String> = ["one", "\ttwo", "three"].iter().map(|s| (*s).to_string()).collect();
+        let mut frame = FrameBuffer::new(20, 3);
+        render_output(&mut frame, &lines, 0, 0, 0, 20, 3, &test_theme());
+        assert!(row_chars(&frame, 0).starts_with("one "));
+        assert!(row_chars(&frame, 1).starts_with("        two"));
+        assert!(row_chars(&frame, 2).starts_with("-- More --"));
+
+        render_output(&mut frame, &lines, 1, 0, 0, 20, 3, &test_theme());
+        assert!(row_chars(&frame, 1).starts_with("three"));
+        assert!(row_chars(&frame, 2).starts_with("Press ENTER"));
+    }
+
     #[test]
     fn message_line_empty() {
         let mut frame = FrameBuffer::new(10, 1);
@@ -2588,6 +3905,78 @@ mod tests {
         assert_eq!(frame.get(gw + 3, 0).unwrap().character(), Some('l'));
     }
 
+    #[test]
+    fn highlight_only_within_viewport() {
+        // 10 lines of "abc"; a 4-row area shows 3 text lines starting at 4.
+        let text = ["abc"; 10].join("\n");
+        let buf = Buffer::from_text(&text);
+        let cursor = Cursor::at(Position::new(5, 0));
+        let mut frame = FrameBuffer::new(20, 4);
+        let mut v = View::new();
+        v.set_top_line(4);
+
+        v.render(&buf, &cursor, Mode::Normal, None, "", &mut frame, 0, 0, 20, 4, true, &test_theme(), None);
+        let before = frame.clone();
+        highlight_matches(&v, &mut frame, &buf, "abc", 0, 0, 20, 4, &test_theme());
+
+        let gw = gutter_width(10, true);
+        // Visible lines 4..7 are highlighted.
+        for row in 0..3 {
+            assert!(is_search_bg(&frame, gw, row), "row {row} not highlighted");
+        }
+        // The status row (where line 7 would be) keeps its colors — nothing
+        // below the viewport leaks into the frame.
+        for x in 0..20 {
+            assert_eq!(frame.get(x, 3), before.get(x, 3), "status col {x} changed");
+        }
+    }
+
+    #[test]
+    fn highlight_skips_matches_outside_viewport() {
+        // Matches only above and below the viewport — the visible lines
+        // contain none, so no cell may change.
+        let buf = Buffer::from_text("abc\nabc\nxyz\nxyz\nabc\nabc");
+        let cursor = Cursor::at(Position::new(2, 0));
+        let mut frame = FrameBuffer::new(20, 3);
+        let mut v = View::new();
+        v.set_top_line(2);
+
+        v.render(&buf, &cursor, Mode::Normal, None, "", &mut frame, 0, 0, 20, 3, true, &test_theme(), None);
+        let before = frame.clone();
+        highlight_matches(&v, &mut frame, &buf, "abc", 0, 0, 20, 3, &test_theme());
+
+        for y in 0..3 {
+            for x in 0..20 {
+                assert_eq!(frame.get(x, y), before.get(x, y), "cell ({x}, {y}) changed");
+            }
+        }
+    }
+
+    // ── highlight_spelling ──────────────────────────────────────────────
+
+    #[test]
+    fn spelling_underlines_only_bad_words() {
+        use n_term::cell::UnderlineStyle;
+
+        let buf = Buffer::from_text("the teh end");
+        let cursor = Cursor::at(Position::new(0, 0));
+        let mut frame = FrameBuffer::new(20, 2);
+        let mut v = View::new();
+        let theme = test_theme();
+        let checker = SpellChecker::new("en_us").unwrap();
+
+        v.render(&buf, &cursor, Mode::Normal, None, "", &mut frame, 0, 0, 20, 2, true, &theme, None);
+        highlight_spelling(&v, &mut frame, &buf, &checker, 0, 0, 20, 2, &theme);
+
+        let gw = gutter_width(1, true);
+        let underlined: Vec<u16> = (0..11)
+            .filter(|&c| frame.get(gw + c, 0).unwrap().underline == UnderlineStyle::Curly)
+            .collect();
+        assert_eq!(underlined, vec![4, 5, 6]);
+        // Character content is untouched.
+        assert_eq!(frame.get(gw + 4, 0).unwrap().character(), Some('t'));
+    }
+
     // ── render_search_line ──────────────────────────────────────────────
 
     #[test]
@@ -2954,7 +4343,7 @@ mod tests {
     fn completion_popup_renders_candidates() {
         let mut frame = FrameBuffer::new(40, 20);
         let candidates = vec!["hello".to_string(), "help".to_string(), "heap".to_string()];
-        render_completion_popup(&mut frame, &candidates, 0, 5, 3, 40, 20, &test_theme());
+        render_completion_popup(&mut frame, &CompletionMenu::new(candidates, 0), 5, 3, 40, 20, &test_theme());
 
         // Popup should be at row 4 (below cursor_y=3), starting at col 5.
         // Selected item (0 = "hello") should have BOLD.
@@ -2970,7 +4359,7 @@ mod tests {
     fn completion_popup_empty_noop() {
         let mut frame = FrameBuffer::new(40, 20);
         let empty: Vec<String> = vec![];
-        render_completion_popup(&mut frame, &empty, 0, 5, 3, 40, 20, &test_theme());
+        render_completion_popup(&mut frame, &CompletionMenu::new(empty, 0), 5, 3, 40, 20, &test_theme());
         // Should not crash or modify the frame.
         assert!(frame.get(5, 4).unwrap().ch == b' ' as u32);
     }
@@ -2980,7 +4369,7 @@ mod tests {
         // Cursor at row 18 (near bottom of 20-row frame) with 5 candidates.
         let mut frame = FrameBuffer::new(40, 20);
         let candidates: Vec<String> = (0..5).map(|i| format!("word{i}")).collect();
-        render_completion_popup(&mut frame, &candidates, 0, 5, 18, 40, 20, &test_theme());
+        render_completion_popup(&mut frame, &CompletionMenu::new(candidates, 0), 5, 18, 40, 20, &test_theme());
 
         // Popup should shift above cursor since row 19 is the last.
         // It should NOT render below row 19.
@@ -2992,7 +4381,7 @@ mod tests {
     fn completion_popup_selection_highlight() {
         let mut frame = FrameBuffer::new(40, 20);
         let candidates = vec!["aaa".to_string(), "bbb".to_string()];
-        render_completion_popup(&mut frame, &candidates, 1, 5, 3, 40, 20, &test_theme());
+        render_completion_popup(&mut frame, &CompletionMenu::new(candidates, 1), 5, 3, 40, 20, &test_theme());
 
         // Selection index 1 ("bbb") should be at row 5 and be BOLD.
         let cell = frame.get(6, 5).unwrap();
@@ -3002,4 +4391,48 @@ mod tests {
         let cell0 = frame.get(6, 4).unwrap();
         assert!(!cell0.attrs.contains(Attr::BOLD), "non-selected (idx=0) should not be BOLD");
     }
+
+    #[test]
+    fn completion_menu_scrolls_as_little_as_needed() {
+        let items: Vec<String> = (0..25).map(|i| format!("w{i}")).collect();
+        let mut menu = CompletionMenu::new(items, 0);
+        assert_eq!(menu.height(), COMPLETION_MENU_MAX_HEIGHT);
+        menu.select(9);
+        assert_eq!(menu.visible_offset, 0);
+        menu.select(12);
+        assert_eq!(menu.visible_offset, 3);
+        // Moving back up within the shown rows doesn't scroll.
+        menu.select(5);
+        assert_eq!(menu.visible_offset, 3);
+        menu.select(1);
+        assert_eq!(menu.visible_offset, 1);
+        // No selection keeps the offset.
+        menu.select(25);
+        assert_eq!(menu.visible_offset, 1);
+        assert_eq!(CompletionMenu::new(menu.items.clone(), 24).visible_offset, 15);
+    }
+
+    #[test]
+    fn completion_popup_scrollbar_for_long_lists() {
+        let theme = test_theme();
+        let items: Vec<String> = (0..20).map(|i| format!("word{i:02}")).collect();
+        let mut frame = FrameBuffer::new(40, 20);
+        render_completion_popup(&mut frame, &CompletionMenu::new(items.clone(), 15), 5, 3, 40, 20, &theme);
+
+        // Ten rows, items 6..=15 shown, selection on the last row.
+        assert_eq!(&row_chars(&frame, 4)[5..13], " word06 ");
+        assert_eq!(&row_chars(&frame, 13)[5..13], " word15 ");
+        assert_eq!(frame.get(6, 13).unwrap().bg, theme.pmenu_sel.bg);
+        assert_eq!(frame.get(5, 14).unwrap().bg, CellColor::Default);
+
+        // The scrollbar is the column after the text: a five-row thumb
+        // starting three rows down.
+        let bar: Vec<bool> = (4..14).map(|y| frame.get(13, y).unwrap().bg == theme.pmenu_sel.bg).collect();
+        assert_eq!(bar, [false, false, false, true, true, true, true, true, false, false]);
+
+        // A list that fits has no scrollbar.
+        let mut frame = FrameBuffer::new(40, 20);
+        render_completion_popup(&mut frame, &CompletionMenu::new(items[..3].to_vec(), 0), 5, 3, 40, 20, &theme);
+        assert_eq!(frame.get(13, 4).unwrap().bg, CellColor::Default);
+    }
 }
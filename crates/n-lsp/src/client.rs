@@ -0,0 +1,520 @@
+//! [`LspClient`] — one running language server.
+//!
+//! The client writes requests to the server as they're made and reads
+//! responses on a background thread, so nothing here blocks on the server.
+//! Call [`poll`](LspClient::poll) regularly to pick up responses.
+//!
+//! Until the server answers `initialize`, the protocol forbids sending it
+//! anything else, so messages made before then are queued and sent once
+//! the handshake completes. If `initialize` fails instead, the queued
+//! requests fail with it, and so does everything sent afterwards.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use serde_json::{Value, json};
+
+use crate::jsonrpc;
+use crate::protocol::{self, Location};
+
+/// A response (or failure) the editor should act on, from
+/// [`LspClient::poll`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LspEvent {
+    /// Hover text for a [`hover`](LspClient::hover) request, or `None` if
+    /// the server had nothing to say.
+    Hover(Option<String>),
+    /// Target of a [`definition`](LspClient::definition) request, or
+    /// `None` if the server found none.
+    Definition(Option<Location>),
+    /// A request failed, or the server reported an error.
+    Error(String),
+    /// The server closed its output — it has exited.
+    Exited,
+}
+
+/// What a request in flight was for, so its response can be decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Request {
+    Initialize,
+    Hover,
+    Definition,
+    Shutdown,
+}
+
+/// A connection to one language server.
+pub struct LspClient {
+    /// The server process, when the client started it.
+    child: Option<Child>,
+    writer: Box<dyn Write + Send>,
+    /// Messages from the reader thread. `None` marks end of stream.
+    rx: Receiver<Option<Value>>,
+    next_id: u64,
+    /// Requests awaiting a response, by ID.
+    pending: HashMap<u64, Request>,
+    /// The server has answered `initialize`.
+    initialized: bool,
+    /// Messages held back until `initialize` is answered.
+    queued: Vec<Value>,
+    /// The server's error for `initialize`. Nothing more is sent to it.
+    init_error: Option<String>,
+    /// Open documents and the version last sent for each, by URI.
+    documents: HashMap<String, u64>,
+    /// The reader thread has reported end of stream.
+    exited: bool,
+}
+
+impl LspClient {
+    /// Start the server `command` (a program and its arguments, split on
+    /// whitespace) for the workspace at `root`, and begin the
+    /// `initialize` handshake.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the command is empty or can't be started.
+    pub fn start(command: &str, root: &Path) -> io::Result<Self> {
+        let mut words = command.split_whitespace();
+        let program = words
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "empty server command"))?;
+        let mut child = Command::new(program)
+            .args(words)
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            let _ = child.kill();
+            return Err(io::Error::other("server stdio not captured"));
+        };
+        let mut client = Self::connect(Box::new(stdin), BufReader::new(stdout), root)?;
+        client.child = Some(child);
+        Ok(client)
+    }
+
+    /// Speak LSP over an existing connection: `writer` goes to the server
+    /// and `reader` comes from it. Sends `initialize` right away.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `initialize` can't be written.
+    pub fn connect(
+        writer: Box<dyn Write + Send>,
+        mut reader: impl BufRead + Send + 'static,
+        root: &Path,
+    ) -> io::Result<Self> {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            while let Ok(Some(msg)) = jsonrpc::read_message(&mut reader) {
+                if tx.send(Some(msg)).is_err() {
+                    return;
+                }
+            }
+            let _ = tx.send(None);
+        });
+
+        let mut client = Self {
+            child: None,
+            writer,
+            rx,
+            next_id: 1,
+            pending: HashMap::new(),
+            initialized: false,
+            queued: Vec::new(),
+            init_error: None,
+            documents: HashMap::new(),
+            exited: false,
+        };
+        let id = client.next_request_id(Request::Initialize);
+        jsonrpc::write_message(
+            &mut client.writer,
+            &jsonrpc::request(id, "initialize", protocol::initialize_params(root)),
+        )?;
+        Ok(client)
+    }
+
+    /// Whether the server has finished the `initialize` handshake.
+    #[must_use]
+    pub const fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    /// Whether the server has exited.
+    #[must_use]
+    pub const fn has_exited(&self) -> bool {
+        self.exited
+    }
+
+    /// The version last sent for document `uri`, or `None` if it isn't
+    /// open.
+    #[must_use]
+    pub fn document_version(&self, uri: &str) -> Option<u64> {
+        self.documents.get(uri).copied()
+    }
+
+    // -- Documents ------------------------------------------------------------
+
+    /// Send `textDocument/didOpen` for `uri` with its full `text`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message can't be written to the server.
+    pub fn did_open(&mut self, uri: &str, language_id: &str, version: u64, text: &str) -> io::Result<()> {
+        self.documents.insert(uri.to_string(), version);
+        self.send(jsonrpc::notification(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": {
+                    "uri": uri,
+                    "languageId": language_id,
+                    "version": version,
+                    "text": text,
+                },
+            }),
+        ))
+    }
+
+    /// Send `textDocument/didChange` with the full new `text` of `uri`.
+    /// Does nothing for a document that isn't open.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message can't be written to the server.
+    pub fn did_change(&mut self, uri: &str, version: u64, text: &str) -> io::Result<()> {
+        let Some(v) = self.documents.get_mut(uri) else {
+            return Ok(());
+        };
+        *v = version;
+        self.send(jsonrpc::notification(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": uri, "version": version },
+                "contentChanges": [{ "text": text }],
+            }),
+        ))
+    }
+
+    // -- Requests -------------------------------------------------------------
+
+    /// Ask for hover information at `(line, character)` in `uri`. The
+    /// answer arrives later as [`LspEvent::Hover`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request can't be written to the server.
+    pub fn hover(&mut self, uri: &str, line: usize, character: usize) -> io::Result<()> {
+        let id = self.next_request_id(Request::Hover);
+        let params = protocol::text_document_position(uri, line, character);
+        self.send(jsonrpc::request(id, "textDocument/hover", params))
+    }
+
+    /// Ask where the symbol at `(line, character)` in `uri` is defined.
+    /// The answer arrives later as [`LspEvent::Definition`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request can't be written to the server.
+    pub fn definition(&mut self, uri: &str, line: usize, character: usize) -> io::Result<()> {
+        let id = self.next_request_id(Request::Definition);
+        let params = protocol::text_document_position(uri, line, character);
+        self.send(jsonrpc::request(id, "textDocument/definition", params))
+    }
+
+    /// Collect everything the server has sent since the last call, without
+    /// blocking.
+    pub fn poll(&mut self) -> Vec<LspEvent> {
+        let mut events = Vec::new();
+        loop {
+            match self.rx.try_recv() {
+                Ok(Some(msg)) => self.handle_message(&msg, &mut events),
+                Ok(None) | Err(TryRecvError::Disconnected) => {
+                    if !self.exited {
+                        self.exited = true;
+                        events.push(LspEvent::Exited);
+                    }
+                    break;
+                }
+                Err(TryRecvError::Empty) => break,
+            }
+        }
+        events
+    }
+
+    // -- Internals ------------------------------------------------------------
+
+    fn next_request_id(&mut self, kind: Request) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.pending.insert(id, kind);
+        id
+    }
+
+    /// Write `msg` now, or queue it until the handshake is done. Fails
+    /// once the handshake has.
+    fn send(&mut self, msg: Value) -> io::Result<()> {
+        if let Some(err) = &self.init_error {
+            if let Some(id) = msg.get("id").and_then(Value::as_u64) {
+                self.pending.remove(&id);
+            }
+            return Err(io::Error::other(format!("server failed to initialize: {err}")));
+        }
+        if self.initialized {
+            jsonrpc::write_message(&mut self.writer, &msg)
+        } else {
+            self.queued.push(msg);
+            Ok(())
+        }
+    }
+
+    fn handle_message(&mut self, msg: &Value, events: &mut Vec<LspEvent>) {
+        let id = msg.get("id");
+        if let Some(method) = msg.get("method").and_then(Value::as_str) {
+            // A request from the server needs an answer, or some servers
+            // wait for it forever. We support none, so reply with nulls.
+            if let Some(id) = id {
+                let result = if method == "workspace/configuration" {
+                    let n = msg["params"]["items"].as_array().map_or(0, Vec::len);
+                    Value::Array(vec![Value::Null; n])
+                } else {
+                    Value::Null
+                };
+                let _ = jsonrpc::write_message(&mut self.writer, &jsonrpc::response(id, result));
+            }
+            return;
+        }
+
+        let Some(kind) = id.and_then(Value::as_u64).and_then(|id| self.pending.remove(&id)) else {
+            return;
+        };
+        if let Some(err) = msg.get("error") {
+            let text = err.get("message").and_then(Value::as_str).unwrap_or("request failed");
+            if kind == Request::Initialize {
+                self.fail_initialize(text, events);
+            } else {
+                events.push(LspEvent::Error(text.to_string()));
+            }
+            return;
+        }
+        let result = msg.get("result").unwrap_or(&Value::Null);
+        match kind {
+            Request::Initialize => {
+                self.initialized = true;
+                let mut out = vec![jsonrpc::notification("initialized", json!({}))];
+                out.append(&mut self.queued);
+                for m in &out {
+                    if let Err(e) = jsonrpc::write_message(&mut self.writer, m) {
+                        events.push(LspEvent::Error(e.to_string()));
+                        break;
+                    }
+                }
+            }
+            Request::Hover => events.push(LspEvent::Hover(protocol::parse_hover(result))),
+            Request::Definition => {
+                events.push(LspEvent::Definition(protocol::parse_definition(result)));
+            }
+            Request::Shutdown => {}
+        }
+    }
+
+    /// The server refused `initialize`: fail every queued request with its
+    /// error and drop the queue, then report the failure itself.
+    fn fail_initialize(&mut self, text: &str, events: &mut Vec<LspEvent>) {
+        for msg in self.queued.drain(..) {
+            let Some(id) = msg.get("id").and_then(Value::as_u64) else {
+                continue;
+            };
+            self.pending.remove(&id);
+            let method = msg.get("method").and_then(Value::as_str).unwrap_or("request");
+            events.push(LspEvent::Error(format!("{method} failed: server failed to initialize: {text}")));
+        }
+        events.push(LspEvent::Error(format!("initialize failed: {text}")));
+        self.init_error = Some(text.to_string());
+    }
+
+    /// Ask the server to shut down and exit, then make sure it's gone.
+    fn shutdown(&mut self) {
+        if self.initialized && !self.exited {
+            let id = self.next_request_id(Request::Shutdown);
+            let _ = jsonrpc::write_message(&mut self.writer, &jsonrpc::request(id, "shutdown", Value::Null));
+            let _ = jsonrpc::write_message(&mut self.writer, &jsonrpc::notification("exit", Value::Null));
+        }
+        if let Some(mut child) = self.child.take() {
+            // Give a well-behaved server no chance to linger.
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+impl Drop for LspClient {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    /// A writer whose bytes the test can inspect afterwards.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Shared {
+        /// Every message written so far.
+        fn messages(&self) -> Vec<Value> {
+            let bytes = self.0.lock().unwrap().clone();
+            let mut input = bytes.as_slice();
+            std::iter::from_fn(|| jsonrpc::read_message(&mut input).unwrap()).collect()
+        }
+    }
+
+    /// Frame `msgs` as a server's output stream.
+    fn server_output(msgs: &[Value]) -> io::Cursor<Vec<u8>> {
+        let mut out = Vec::new();
+        for m in msgs {
+            jsonrpc::write_message(&mut out, m).unwrap();
+        }
+        io::Cursor::new(out)
+    }
+
+    /// Poll until the server's output has been fully read.
+    fn poll_all(client: &mut LspClient) -> Vec<LspEvent> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut events = Vec::new();
+        while !events.contains(&LspEvent::Exited) && Instant::now() < deadline {
+            events.extend(client.poll());
+            thread::sleep(Duration::from_millis(1));
+        }
+        events
+    }
+
+    fn ok(id: u64, result: Value) -> Value {
+        jsonrpc::response(&json!(id), result)
+    }
+
+    #[test]
+    fn handshake_queues_messages_until_initialized() {
+        let sent = Shared::default();
+        let reply = server_output(&[ok(1, json!({ "capabilities": {} }))]);
+        let mut c = LspClient::connect(Box::new(sent.clone()), reply, Path::new("/")).unwrap();
+        c.did_open("file:///a.rs", "rust", 1, "fn main() {}").unwrap();
+
+        let methods = |sent: &Shared| {
+            sent.messages().iter().map(|m| m["method"].as_str().unwrap_or("").to_string()).collect::<Vec<_>>()
+        };
+        // Only `initialize` goes out before the server answers.
+        assert_eq!(methods(&sent), ["initialize"]);
+
+        let events = poll_all(&mut c);
+        assert_eq!(events, [LspEvent::Exited]);
+        assert!(c.is_initialized());
+        assert_eq!(methods(&sent), ["initialize", "initialized", "textDocument/didOpen"]);
+        assert_eq!(c.document_version("file:///a.rs"), Some(1));
+    }
+
+    #[test]
+    fn hover_and_definition_responses() {
+        let sent = Shared::default();
+        let reply = server_output(&[
+            ok(1, json!({ "capabilities": {} })),
+            ok(2, json!({ "contents": { "kind": "plaintext", "value": "fn main()" } })),
+            ok(3, json!({ "uri": "file:///b.rs", "range": {
+                "start": { "line": 2, "character": 3 }, "end": { "line": 2, "character": 5 } } })),
+        ]);
+        let mut c = LspClient::connect(Box::new(sent), reply, Path::new("/")).unwrap();
+        c.hover("file:///a.rs", 0, 3).unwrap();
+        c.definition("file:///a.rs", 0, 3).unwrap();
+        let events = poll_all(&mut c);
+        assert_eq!(events[0], LspEvent::Hover(Some("fn main()".to_string())));
+        let LspEvent::Definition(Some(loc)) = &events[1] else {
+            panic!("expected a definition, got {events:?}");
+        };
+        assert_eq!((loc.line, loc.character), (2, 3));
+    }
+
+    #[test]
+    fn error_response_and_server_requests() {
+        let sent = Shared::default();
+        let reply = server_output(&[
+            ok(1, json!({ "capabilities": {} })),
+            json!({ "jsonrpc": "2.0", "id": 9, "method": "workspace/configuration",
+                    "params": { "items": [{}, {}] } }),
+            json!({ "jsonrpc": "2.0", "id": 2, "error": { "code": -32601, "message": "no hover" } }),
+        ]);
+        let mut c = LspClient::connect(Box::new(sent.clone()), reply, Path::new("/")).unwrap();
+        c.hover("file:///a.rs", 0, 0).unwrap();
+        let events = poll_all(&mut c);
+        assert_eq!(events[0], LspEvent::Error("no hover".to_string()));
+        let answer = sent.messages().into_iter().find(|m| m["id"] == 9).unwrap();
+        assert_eq!(answer["result"], json!([null, null]));
+    }
+
+    #[test]
+    fn initialize_error_fails_queued_requests() {
+        let sent = Shared::default();
+        let reply = server_output(&[
+            json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32603, "message": "bad root" } }),
+        ]);
+        let mut c = LspClient::connect(Box::new(sent.clone()), reply, Path::new("/")).unwrap();
+        c.did_open("file:///a.rs", "rust", 1, "fn main() {}").unwrap();
+        c.hover("file:///a.rs", 0, 0).unwrap();
+        c.definition("file:///a.rs", 0, 0).unwrap();
+
+        let events = poll_all(&mut c);
+        assert_eq!(
+            events,
+            [
+                LspEvent::Error("textDocument/hover failed: server failed to initialize: bad root".to_string()),
+                LspEvent::Error("textDocument/definition failed: server failed to initialize: bad root".to_string()),
+                LspEvent::Error("initialize failed: bad root".to_string()),
+                LspEvent::Exited,
+            ]
+        );
+        assert!(!c.is_initialized());
+        assert!(c.pending.is_empty());
+        assert!(c.queued.is_empty());
+
+        // Later requests fail right away instead of queueing.
+        let err = c.hover("file:///a.rs", 0, 0).unwrap_err();
+        assert_eq!(err.to_string(), "server failed to initialize: bad root");
+        assert!(c.pending.is_empty());
+        assert_eq!(sent.messages().len(), 1);
+    }
+
+    #[test]
+    fn did_change_ignores_closed_documents() {
+        let mut c = LspClient::connect(Box::new(Shared::default()), server_output(&[]), Path::new("/")).unwrap();
+        c.did_change("file:///never-opened.rs", 2, "x").unwrap();
+        assert_eq!(c.document_version("file:///never-opened.rs"), None);
+        assert_eq!(poll_all(&mut c), [LspEvent::Exited]);
+        assert!(c.has_exited());
+    }
+
+    #[test]
+    fn start_fails_for_missing_program() {
+        assert!(LspClient::start("n-nvim-no-such-language-server", Path::new(".")).is_err());
+        assert!(LspClient::start("   ", Path::new(".")).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! JSON-RPC 2.0 messages and their wire framing.
+//!
+//! LSP frames each JSON message with HTTP-style headers:
+//!
+//! ```text
+//! Content-Length: 52\r\n
+//! \r\n
+//! {"jsonrpc":"2.0","id":1,"method":"shutdown"}
+//! ```
+//!
+//! Only `Content-Length` matters; other headers (`Content-Type`) are
+//! skipped.
+
+use std::io::{self, BufRead, Write};
+
+use serde_json::{Value, json};
+
+/// A request: a method call that expects a response with the same `id`.
+#[must_use]
+pub fn request(id: u64, method: &str, params: Value) -> Value {
+    let mut msg = json!({ "jsonrpc": "2.0", "id": id, "method": method });
+    msg["params"] = params;
+    msg
+}
+
+/// A notification: a method call with no response.
+#[must_use]
+pub fn notification(method: &str, params: Value) -> Value {
+    let mut msg = json!({ "jsonrpc": "2.0", "method": method });
+    msg["params"] = params;
+    msg
+}
+
+/// A successful response to a request from the other side.
+#[must_use]
+pub fn response(id: &Value, result: Value) -> Value {
+    let mut msg = json!({ "jsonrpc": "2.0", "id": id });
+    msg["result"] = result;
+    msg
+}
+
+/// Write `msg` to `out` with its `Content-Length` header, and flush.
+///
+/// # Errors
+///
+/// Returns any error from writing to `out`.
+pub fn write_message(out: &mut impl Write, msg: &Value) -> io::Result<()> {
+    let body = msg.to_string();
+    write!(out, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    out.flush()
+}
+
+/// Read one framed message from `input`. Returns `Ok(None)` at end of
+/// stream.
+///
+/// # Errors
+///
+/// Returns an error if the stream fails, the headers have no valid
+/// `Content-Length`, or the body isn't JSON.
+pub fn read_message(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut length = None;
+    let mut in_headers = false;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            // A blank line before any header is stray padding; after the
+            // headers it ends them.
+            if in_headers {
+                break;
+            }
+            continue;
+        }
+        in_headers = true;
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let Some(length) = length else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length"));
+    };
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    serde_json::from_slice(&body).map(Some).map_err(io::Error::from)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let msg = request(7, "textDocument/hover", json!({ "x": "é" }));
+        let mut wire = Vec::new();
+        write_message(&mut wire, &msg).unwrap();
+        let text = String::from_utf8(wire.clone()).unwrap();
+        // The length counts bytes, not chars.
+        let body_len = msg.to_string().len();
+        assert!(text.starts_with(&format!("Content-Length: {body_len}\r\n\r\n")));
+        let mut input = wire.as_slice();
+        assert_eq!(read_message(&mut input).unwrap(), Some(msg));
+        assert_eq!(read_message(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn extra_headers_skipped() {
+        let body = r#"{"jsonrpc":"2.0","id":1,"result":null}"#;
+        let wire = format!(
+            "Content-Type: application/vscode-jsonrpc; charset=utf-8\r\ncontent-length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        let msg = read_message(&mut wire.as_bytes()).unwrap().unwrap();
+        assert_eq!(msg["id"], 1);
+    }
+
+    #[test]
+    fn missing_length_is_an_error() {
+        let mut input: &[u8] = b"Content-Type: x\r\n\r\n{}";
+        assert!(read_message(&mut input).is_err());
+    }
+
+    #[test]
+    fn notification_has_no_id() {
+        let n = notification("initialized", json!({}));
+        assert!(n.get("id").is_none());
+        assert_eq!(n["method"], "initialized");
+    }
+}
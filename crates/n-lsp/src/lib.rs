@@ -0,0 +1,31 @@
+//! # n-lsp — Language Server Protocol client for n-nvim
+//!
+//! A minimal LSP client: enough to start a language server, keep it in sync
+//! with the editor's buffers, and ask it for hover text and definitions.
+//!
+//! - **[`jsonrpc`]** — JSON-RPC 2.0 messages and `Content-Length` framing
+//! - **[`protocol`]** — LSP payloads: URIs, UTF-16 columns, hover and
+//!   definition results, language IDs
+//! - **[`client`]** — [`LspClient`]: one running server, spoken to over
+//!   its stdin/stdout
+//!
+//! # Architecture
+//!
+//! ```text
+//! Editor ──request──▶ LspClient ──stdin──▶ server
+//!    ▲                    │
+//!    └──poll() each tick──┘◀──channel◀── reader thread ◀──stdout──
+//! ```
+//!
+//! The editor never blocks on the server. Requests are written straight to
+//! the server's stdin; a reader thread parses its stdout and sends each
+//! message down a channel. The editor calls [`LspClient::poll`] from its
+//! tick handler and gets back [`LspEvent`]s for the responses that have
+//! arrived.
+
+pub mod client;
+pub mod jsonrpc;
+pub mod protocol;
+
+pub use client::{LspClient, LspEvent};
+pub use protocol::Location;
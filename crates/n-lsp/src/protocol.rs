@@ -0,0 +1,270 @@
+//! LSP payloads — building request params and reading results.
+//!
+//! Positions in LSP are `(line, character)` where `character` counts UTF-16
+//! code units, while the editor counts chars. [`char_to_utf16`] and
+//! [`utf16_to_char`] convert between the two for one line of text.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Value, json};
+
+/// A position in a file, as returned by `textDocument/definition`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    /// The file.
+    pub path: PathBuf,
+    /// 0-indexed line.
+    pub line: usize,
+    /// 0-indexed column in UTF-16 code units (see [`utf16_to_char`]).
+    pub character: usize,
+}
+
+/// The LSP language ID for a file, from its extension. `None` for files
+/// no common language server handles.
+#[must_use]
+pub fn language_id(path: &Path) -> Option<&'static str> {
+    Some(match path.extension()?.to_str()? {
+        "rs" => "rust",
+        "go" => "go",
+        "py" | "pyi" => "python",
+        "c" | "h" => "c",
+        "cpp" | "cc" | "cxx" | "hpp" | "hxx" => "cpp",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "javascriptreact",
+        "ts" | "mts" => "typescript",
+        "tsx" => "typescriptreact",
+        "lua" => "lua",
+        "zig" => "zig",
+        "sh" | "bash" => "shellscript",
+        "java" => "java",
+        "rb" => "ruby",
+        "hs" => "haskell",
+        "ml" | "mli" => "ocaml",
+        _ => return None,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// URIs
+// ---------------------------------------------------------------------------
+
+/// `file://` URI for `path`. Relative paths are resolved against the
+/// current directory.
+#[must_use]
+pub fn path_to_uri(path: &Path) -> String {
+    let abs = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let text = abs.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from("file://");
+    if !text.starts_with('/') {
+        // Windows drive path: file:///C:/...
+        uri.push('/');
+    }
+    for b in text.bytes() {
+        if b.is_ascii_alphanumeric() || b"/-_.~:".contains(&b) {
+            uri.push(char::from(b));
+        } else {
+            let _ = write!(uri, "%{b:02X}");
+        }
+    }
+    uri
+}
+
+/// The file path of a `file://` URI. `None` for other schemes.
+#[must_use]
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    let bytes = rest.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| rest.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        if let Some(b) = hex {
+            out.push(b);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    let path = String::from_utf8(out).ok()?;
+    // file:///C:/x → C:/x on Windows.
+    let path = match path.as_bytes() {
+        [b'/', _, b':', ..] if cfg!(windows) => path[1..].to_string(),
+        _ => path,
+    };
+    Some(PathBuf::from(path))
+}
+
+// ---------------------------------------------------------------------------
+// Columns
+// ---------------------------------------------------------------------------
+
+/// UTF-16 column of char column `col` in `line`.
+#[must_use]
+pub fn char_to_utf16(line: &str, col: usize) -> usize {
+    line.chars().take(col).map(char::len_utf16).sum()
+}
+
+/// Char column of UTF-16 column `units` in `line`. A column inside a
+/// surrogate pair maps to that char; past the end maps to the char count.
+#[must_use]
+pub fn utf16_to_char(line: &str, units: usize) -> usize {
+    let mut seen = 0;
+    for (i, ch) in line.chars().enumerate() {
+        seen += ch.len_utf16();
+        if seen > units {
+            return i;
+        }
+    }
+    line.chars().count()
+}
+
+// ---------------------------------------------------------------------------
+// Params
+// ---------------------------------------------------------------------------
+
+/// `InitializeParams` for a workspace rooted at `root`.
+#[must_use]
+pub fn initialize_params(root: &Path) -> Value {
+    json!({
+        "processId": std::process::id(),
+        "clientInfo": { "name": "n-nvim" },
+        "rootUri": path_to_uri(root),
+        "capabilities": {
+            "textDocument": {
+                "synchronization": { "dynamicRegistration": false },
+                "hover": { "contentFormat": ["plaintext", "markdown"] },
+                "definition": { "linkSupport": true },
+            },
+            "general": { "positionEncodings": ["utf-16"] },
+        },
+    })
+}
+
+/// `TextDocumentPositionParams` for hover and definition requests.
+#[must_use]
+pub fn text_document_position(uri: &str, line: usize, character: usize) -> Value {
+    json!({
+        "textDocument": { "uri": uri },
+        "position": { "line": line, "character": character },
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Results
+// ---------------------------------------------------------------------------
+
+/// The text of a `textDocument/hover` result, or `None` when the server
+/// has nothing to show.
+///
+/// Accepts `MarkupContent`, a `MarkedString` or an array of them. Markdown
+/// code fences are dropped so code reads as plain text.
+#[must_use]
+pub fn parse_hover(result: &Value) -> Option<String> {
+    fn piece(v: &Value) -> Option<String> {
+        match v {
+            Value::String(s) => Some(s.clone()),
+            Value::Object(o) => o.get("value")?.as_str().map(str::to_string),
+            _ => None,
+        }
+    }
+    let contents = result.get("contents")?;
+    let pieces: Vec<String> = match contents {
+        Value::Array(items) => items.iter().filter_map(piece).collect(),
+        other => piece(other).into_iter().collect(),
+    };
+    let text = pieces
+        .join("\n\n")
+        .lines()
+        .filter(|l| !l.trim_start().starts_with("```"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let text = text.trim_matches('\n');
+    (!text.trim().is_empty()).then(|| text.to_string())
+}
+
+/// The first location of a `textDocument/definition` result: a
+/// `Location`, an array of them, or an array of `LocationLink`s.
+#[must_use]
+pub fn parse_definition(result: &Value) -> Option<Location> {
+    let first = match result {
+        Value::Array(items) => items.first()?,
+        other => other,
+    };
+    let (uri, range) = match first.get("targetUri") {
+        Some(uri) => (uri, first.get("targetSelectionRange")?),
+        None => (first.get("uri")?, first.get("range")?),
+    };
+    let start = range.get("start")?;
+    Some(Location {
+        path: uri_to_path(uri.as_str()?)?,
+        line: usize::try_from(start.get("line")?.as_u64()?).ok()?,
+        character: usize::try_from(start.get("character")?.as_u64()?).ok()?,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_ids() {
+        assert_eq!(language_id(Path::new("src/main.rs")), Some("rust"));
+        assert_eq!(language_id(Path::new("a.tsx")), Some("typescriptreact"));
+        assert_eq!(language_id(Path::new("notes.txt")), None);
+        assert_eq!(language_id(Path::new("Makefile")), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn uri_round_trip() {
+        let path = Path::new("/tmp/my dir/ü.rs");
+        let uri = path_to_uri(path);
+        assert_eq!(uri, "file:///tmp/my%20dir/%C3%BC.rs");
+        assert_eq!(uri_to_path(&uri).as_deref(), Some(path));
+        assert_eq!(uri_to_path("https://example.com"), None);
+    }
+
+    #[test]
+    fn utf16_columns() {
+        let line = "a😀b";
+        assert_eq!(char_to_utf16(line, 0), 0);
+        assert_eq!(char_to_utf16(line, 2), 3);
+        assert_eq!(utf16_to_char(line, 3), 2);
+        // Inside the surrogate pair.
+        assert_eq!(utf16_to_char(line, 2), 1);
+        assert_eq!(utf16_to_char(line, 99), 3);
+    }
+
+    #[test]
+    fn hover_markup_and_marked_strings() {
+        let markup = json!({ "contents": { "kind": "markdown", "value": "```rust\nfn f()\n```\n\nDocs" } });
+        assert_eq!(parse_hover(&markup).as_deref(), Some("fn f()\n\nDocs"));
+        let marked = json!({ "contents": [{ "language": "rust", "value": "u32" }, "An int"] });
+        assert_eq!(parse_hover(&marked).as_deref(), Some("u32\n\nAn int"));
+        assert_eq!(parse_hover(&json!({ "contents": "" })), None);
+        assert_eq!(parse_hover(&Value::Null), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn definition_location_and_link() {
+        let range = json!({ "start": { "line": 4, "character": 7 }, "end": { "line": 4, "character": 9 } });
+        let loc = json!({ "uri": "file:///src/a.rs", "range": range });
+        let want = Location { path: PathBuf::from("/src/a.rs"), line: 4, character: 7 };
+        assert_eq!(parse_definition(&loc), Some(want.clone()));
+        assert_eq!(parse_definition(&json!([loc])), Some(want.clone()));
+        let link = json!([{ "targetUri": "file:///src/a.rs", "targetRange": range, "targetSelectionRange": range }]);
+        assert_eq!(parse_definition(&link), Some(want));
+        assert_eq!(parse_definition(&json!([])), None);
+        assert_eq!(parse_definition(&Value::Null), None);
+    }
+}
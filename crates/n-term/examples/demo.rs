@@ -85,6 +85,8 @@ impl Demo {
             }
             Event::FocusGained => "Focus: gained".into(),
             Event::FocusLost => "Focus: lost".into(),
+            Event::KeyboardFlags(flags) => format!("Kitty keyboard: flags {flags}"),
+            Event::SyncOutput(supported) => format!("Synchronized output: {supported}"),
         }
     }
 }
@@ -174,6 +174,17 @@ pub fn end_sync(w: &mut impl Write) -> io::Result<()> {
     w.write_all(b"\x1b[?2026l")
 }
 
+/// Ask whether the terminal supports synchronized output (DECRQM for
+/// mode 2026).
+///
+/// A terminal that knows the request replies `CSI ? 2026 ; Ps $ y`
+/// (parsed as [`Event::SyncOutput`](crate::input::Event::SyncOutput)),
+/// with `Ps` 0 or 4 when the mode isn't supported.
+#[inline]
+pub fn query_sync_output(w: &mut impl Write) -> io::Result<()> {
+    w.write_all(b"\x1b[?2026$p")
+}
+
 // ─── Alternate Screen ───────────────────────────────────────────────────────
 
 /// Enter the alternate screen buffer (DEC Private Mode 1049).
@@ -252,6 +263,16 @@ pub fn disable_kitty_keyboard(w: &mut impl Write) -> io::Result<()> {
     w.write_all(b"\x1b[<u")
 }
 
+/// Ask which Kitty keyboard protocol flags are in effect.
+///
+/// A terminal that supports the protocol replies `CSI ? flags u` (parsed
+/// as [`Event::KeyboardFlags`](crate::input::Event::KeyboardFlags)); one
+/// that doesn't ignores the query, so no reply means no support.
+#[inline]
+pub fn query_kitty_keyboard(w: &mut impl Write) -> io::Result<()> {
+    w.write_all(b"\x1b[?u")
+}
+
 // ─── Bracketed Paste ────────────────────────────────────────────────────────
 
 /// Enable bracketed paste mode (DEC 2004).
@@ -617,6 +638,11 @@ mod tests {
         assert_eq!(emit(|w| end_sync(w)), "\x1b[?2026l");
     }
 
+    #[test]
+    fn sync_query() {
+        assert_eq!(emit(query_sync_output), "\x1b[?2026$p");
+    }
+
     // ── Composition ─────────────────────────────────────────────────────
 
     #[test]
@@ -697,6 +723,11 @@ mod tests {
         assert_eq!(emit(|w| disable_kitty_keyboard(w)), "\x1b[<u");
     }
 
+    #[test]
+    fn query_kitty_keyboard_sequence() {
+        assert_eq!(emit(query_kitty_keyboard), "\x1b[?u");
+    }
+
     // ── Bracketed Paste ─────────────────────────────────────────────────
 
     #[test]
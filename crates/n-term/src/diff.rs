@@ -22,7 +22,8 @@
 //   - Row-level skip: entire unchanged rows are detected with a single slice
 //     comparison and skipped without iterating individual cells.
 //   - Cell equality uses our derived PartialEq on the 16-byte Cell struct.
-//   - Synchronized output (DEC 2026) wraps the frame to prevent flicker.
+//   - Synchronized output (DEC 2026) wraps the frame to prevent flicker,
+//     unless the terminal said it doesn't support it (set_sync_output).
 //   - Zero allocation in steady state: the previous-frame buffer is reused
 //     via copy_from() — only the first render or a resize allocates.
 
@@ -81,6 +82,7 @@ pub struct DiffRenderer {
     output: OutputBuffer,
     writer: CellWriter,
     previous: Option<FrameBuffer>,
+    sync_output: bool,
 }
 
 impl DiffRenderer {
@@ -91,9 +93,24 @@ impl DiffRenderer {
             output: OutputBuffer::new(),
             writer: CellWriter::new(),
             previous: None,
+            sync_output: true,
         }
     }
 
+    /// Whether frames are wrapped in synchronized output markers.
+    #[inline]
+    #[must_use]
+    pub const fn sync_output(&self) -> bool {
+        self.sync_output
+    }
+
+    /// Turn the synchronized output markers on or off. On by default —
+    /// terminals that don't know mode 2026 ignore them — and turned off
+    /// when the terminal reports it unsupported.
+    pub const fn set_sync_output(&mut self, on: bool) {
+        self.sync_output = on;
+    }
+
     /// Diff the current frame against the previous and generate ANSI output.
     ///
     /// After calling this, use [`flush`](Self::flush) or
@@ -118,7 +135,9 @@ impl DiffRenderer {
         }
 
         // Synchronized output: terminal buffers until end_sync.
-        ansi::begin_sync(&mut self.output).ok();
+        if self.sync_output {
+            ansi::begin_sync(&mut self.output).ok();
+        }
 
         // Determine if we need a full redraw (first render or size changed).
         let size_matches = self
@@ -170,7 +189,9 @@ impl DiffRenderer {
         // terminal's default rendering (cursor line, shell prompt, etc.).
         ansi::reset(&mut self.output).ok();
 
-        ansi::end_sync(&mut self.output).ok();
+        if self.sync_output {
+            ansi::end_sync(&mut self.output).ok();
+        }
 
         stats.bytes_written = self.output.len();
 
@@ -297,6 +318,19 @@ mod tests {
         assert!(output.contains("\x1b[0m\x1b[?2026l"));
     }
 
+    #[test]
+    fn sync_markers_can_be_turned_off() {
+        let mut renderer = DiffRenderer::new();
+        assert!(renderer.sync_output());
+        renderer.set_sync_output(false);
+        let frame = FrameBuffer::new(10, 5);
+
+        let (_, output) = render_frame(&mut renderer, &frame);
+
+        assert!(!output.contains("\x1b[?2026"));
+        assert!(output.ends_with("\x1b[0m"));
+    }
+
     // ── Identical Frames ────────────────────────────────────────────────
 
     #[test]
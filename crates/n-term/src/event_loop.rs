@@ -39,11 +39,16 @@
 // iteration where no new bytes arrive (timeout fires), we flush pending
 // bytes as literal events. With an 8.3ms timeout, the user experiences
 // at most 8.3ms lag on Escape — imperceptible.
+//
+// Over a slow link (ssh) the rest of a sequence can arrive later than
+// that, so the application may ask for a longer wait with
+// `App::escape_timeout` (the editor's `ttimeoutlen`). Pending bytes are
+// then flushed once that long has passed since the last bytes arrived.
 
 use std::io::{self, Write};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Receiver;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::ansi;
 use crate::buffer::FrameBuffer;
@@ -130,6 +135,20 @@ pub trait App {
         false
     }
 
+    /// Checked after every [`on_tick`](App::on_tick). Return `true` to
+    /// exit the event loop — for work done on a tick (like a timed-out
+    /// key sequence) that ends the application.
+    fn should_quit(&self) -> bool {
+        false
+    }
+
+    /// How long a lone `ESC` waits for the rest of an escape sequence
+    /// before it counts as the Escape key. `None` (the default) uses the
+    /// tick interval.
+    fn escape_timeout(&self) -> Option<Duration> {
+        None
+    }
+
     /// Paint the current application state to the frame buffer.
     ///
     /// Called only when the frame is dirty (input arrived, resize
@@ -282,13 +301,18 @@ impl EventLoop {
         let mut frame = FrameBuffer::new(size.cols, size.rows);
         let mut dirty = true; // First frame always renders.
         let timeout = Duration::from_micros(self.config.tick_interval_us);
+        let mut last_bytes = Instant::now();
 
         loop {
             // ── Receive stdin bytes ──────────────────────────────
             match rx.recv_timeout(timeout) {
                 Ok(bytes) => {
+                    last_bytes = Instant::now();
                     let events = self.parser.advance(&bytes);
                     for event in &events {
+                        if let Event::SyncOutput(supported) = event {
+                            self.renderer.set_sync_output(*supported);
+                        }
                         if app.on_event(event) == Action::Quit {
                             return Ok(());
                         }
@@ -299,7 +323,8 @@ impl EventLoop {
                 }
                 Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
                     // Flush pending escape sequences (lone ESC → Escape key).
-                    if self.parser.has_pending() {
+                    let escape_timeout = app.escape_timeout().unwrap_or(timeout);
+                    if self.parser.has_pending() && last_bytes.elapsed() >= escape_timeout {
                         let events = self.parser.flush();
                         for event in &events {
                             if app.on_event(event) == Action::Quit {
@@ -330,6 +355,9 @@ impl EventLoop {
             if app.on_tick() {
                 dirty = true;
             }
+            if app.should_quit() {
+                return Ok(());
+            }
 
             // ── Render if dirty ──────────────────────────────────
             if dirty {
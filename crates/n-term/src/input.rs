@@ -48,6 +48,13 @@ pub enum Event {
     FocusGained,
     /// Terminal window lost focus (`CSI O`).
     FocusLost,
+    /// The reply to a Kitty keyboard protocol query (`CSI ? flags u`):
+    /// the enhancement flags in effect. Only terminals that support the
+    /// protocol reply, so this also says that keys arrive disambiguated.
+    KeyboardFlags(u8),
+    /// The reply to a synchronized output query (`CSI ? 2026 ; Ps $ y`):
+    /// whether the terminal supports mode 2026.
+    SyncOutput(bool),
 }
 
 /// A keyboard event with key identity, modifiers, and press state.
@@ -497,6 +504,17 @@ fn parse_csi(buf: &[u8]) -> Parsed {
         };
     }
 
+    // ── Kitty keyboard query reply: CSI ? flags u ────────────────────
+    if final_byte == b'u' && params_raw.first() == Some(&b'?') {
+        let flags = parse_csi_params(&params_raw[1..]).first().map_or(0, |p| p.0);
+        return Parsed::Event(Event::KeyboardFlags(u8::try_from(flags).unwrap_or(u8::MAX)), consumed);
+    }
+
+    // ── Mode report: CSI ? mode ; setting $ y ────────────────────────
+    if final_byte == b'y' && params_raw.first() == Some(&b'?') && params_raw.last() == Some(&b'$') {
+        return parse_mode_report(&params_raw[1..params_raw.len() - 1], consumed);
+    }
+
     // ── Kitty keyboard: CSI codepoint [; modifiers[:event_type]] u ───
     if final_byte == b'u' {
         return parse_kitty_key(params_raw, consumed);
@@ -642,6 +660,18 @@ fn parse_sgr_mouse(buf: &[u8]) -> Parsed {
 
 // ── Kitty Keyboard Protocol ────────────────────────────────────────────────
 
+/// Parse a DECRPM mode report (`CSI ? mode ; setting $ y`, `params_raw`
+/// without the `?` and `$`). Only the reply for synchronized output (mode
+/// 2026) is of interest: setting 0 (unknown) or 4 (permanently reset)
+/// means unsupported. Reports for other modes are skipped.
+fn parse_mode_report(params_raw: &[u8], consumed: usize) -> Parsed {
+    let params = parse_csi_params(params_raw);
+    match (params.first().map(|p| p.0), params.get(1).map(|p| p.0)) {
+        (Some(2026), Some(setting)) => Parsed::Event(Event::SyncOutput(!matches!(setting, 0 | 4)), consumed),
+        _ => Parsed::Skip(consumed),
+    }
+}
+
 fn parse_kitty_key(params_raw: &[u8], consumed: usize) -> Parsed {
     // Format: CSI codepoint [; modifiers[:event_type]] u
     let params = parse_csi_params(params_raw);
@@ -1591,6 +1621,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn kitty_query_reply() {
+        assert_eq!(parse_one(b"\x1b[?1u"), Event::KeyboardFlags(1));
+        assert_eq!(parse_one(b"\x1b[?0u"), Event::KeyboardFlags(0));
+        assert_eq!(parse_one(b"\x1b[?31u"), Event::KeyboardFlags(31));
+    }
+
+    #[test]
+    fn sync_output_mode_report() {
+        assert_eq!(parse_one(b"\x1b[?2026;2$y"), Event::SyncOutput(true));
+        assert_eq!(parse_one(b"\x1b[?2026;1$y"), Event::SyncOutput(true));
+        assert_eq!(parse_one(b"\x1b[?2026;0$y"), Event::SyncOutput(false));
+        assert_eq!(parse_one(b"\x1b[?2026;4$y"), Event::SyncOutput(false));
+        // Reports for other modes are consumed without an event.
+        assert!(parse(b"\x1b[?1049;1$y").is_empty());
+    }
+
+    #[test]
+    fn kitty_disambiguates_legacy_collisions() {
+        // Legacy encodings can't tell these apart from Tab / Enter / Esc.
+        assert_eq!(parse_one(b"\x1b[105;5u"), key_mod(KeyCode::Char('i'), Modifiers::CTRL));
+        assert_eq!(parse_one(b"\x1b[13;2u"), key_mod(KeyCode::Enter, Modifiers::SHIFT));
+        assert_eq!(parse_one(b"\x1b[91;5u"), key_mod(KeyCode::Char('['), Modifiers::CTRL));
+        assert_eq!(parse_one(b"\x1b[9;2u"), key_mod(KeyCode::Tab, Modifiers::SHIFT));
+    }
+
     #[test]
     fn kitty_super_modifier() {
         assert_eq!(
@@ -23,5 +23,7 @@ pub mod diff;
 pub mod event_loop;
 pub mod input;
 pub mod output;
+pub mod pty;
 pub mod reader;
 pub mod terminal;
+pub mod vt;
@@ -0,0 +1,253 @@
+// SPDX-License-Identifier: MIT
+#![allow(unsafe_code)]
+//
+// Pseudo-terminals — run a program as if it were attached to a terminal.
+//
+// Safety: opening a pty, making it the child's controlling terminal, and
+// setting its window size are `openpty`, `setsid`, and `ioctl` calls. As in
+// the terminal module, there is no safe alternative; each unsafe block is
+// minimal.
+//
+// The child gets the slave side as stdin, stdout, and stderr, in a new
+// session with the slave as its controlling terminal — so job control,
+// Ctrl-C, and `isatty()` all behave as they would in a real terminal. The
+// editor keeps the master side: writes to it are keystrokes, reads from it
+// are the program's output (escape sequences and all, for `vt::Vt`).
+//
+// Output is read on a background thread, like stdin in the reader module,
+// and arrives as byte chunks on a channel. When the child exits and the
+// slave closes, the read fails (EIO on Linux, EOF elsewhere) and the
+// channel closes.
+//
+// Elsewhere, `Pty::spawn` fails with `Unsupported`.
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, FromRawFd};
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, ExitStatus};
+#[cfg(unix)]
+use std::process::Stdio;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// Output chunk size — matches the stdin reader's.
+const READ_BUF_SIZE: usize = 4096;
+
+/// A child process running on a pseudo-terminal.
+///
+/// Dropping the `Pty` kills the child.
+pub struct Pty {
+    master: File,
+    child: Child,
+}
+
+impl Pty {
+    /// Start `command` on a new `cols × rows` pseudo-terminal. `TERM` is
+    /// set to `xterm-256color` unless the command already sets it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pty can't be opened or the program can't be
+    /// started.
+    #[cfg(unix)]
+    pub fn spawn(mut command: Command, cols: u16, rows: u16) -> io::Result<Self> {
+        let (master, slave) = open_pty(cols, rows)?;
+        if !command.get_envs().any(|(key, _)| key == "TERM") {
+            command.env("TERM", "xterm-256color");
+        }
+        command
+            .stdin(Stdio::from(slave.try_clone()?))
+            .stdout(Stdio::from(slave.try_clone()?))
+            .stderr(Stdio::from(slave));
+        // SAFETY: setsid and ioctl are async-signal-safe; the closure
+        // allocates nothing.
+        unsafe {
+            command.pre_exec(|| {
+                if libc::setsid() < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                if libc::ioctl(0, libc::TIOCSCTTY, 0) < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+        let child = command.spawn()?;
+        // `command` (and with it our copies of the slave) drops here, so
+        // the master sees the hangup once the child is gone.
+        Ok(Self { master, child })
+    }
+
+    /// Pseudo-terminals need unix; elsewhere this always fails.
+    ///
+    /// # Errors
+    ///
+    /// Always returns an `Unsupported` error.
+    #[cfg(not(unix))]
+    pub fn spawn(_command: Command, _cols: u16, _rows: u16) -> io::Result<Self> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "pseudo-terminals need unix"))
+    }
+
+    /// Send bytes to the program, as if typed.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to the pty.
+    pub fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.master.write_all(bytes)?;
+        self.master.flush()
+    }
+
+    /// Change the terminal size the program sees (it gets `SIGWINCH`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `ioctl` fails.
+    #[cfg(unix)]
+    pub fn resize(&self, cols: u16, rows: u16) -> io::Result<()> {
+        let ws = winsize(cols, rows);
+        // SAFETY: TIOCSWINSZ reads a winsize from a valid pointer.
+        let result = unsafe { libc::ioctl(self.master.as_raw_fd(), libc::TIOCSWINSZ, &raw const ws) };
+        if result < 0 { Err(io::Error::last_os_error()) } else { Ok(()) }
+    }
+
+    /// Never called off unix — there is no way to build a `Pty` there.
+    ///
+    /// # Errors
+    ///
+    /// Always returns an `Unsupported` error.
+    #[cfg(not(unix))]
+    pub fn resize(&self, _cols: u16, _rows: u16) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "pseudo-terminals need unix"))
+    }
+
+    /// Spawn a thread that reads the program's output and sends it in
+    /// chunks. The channel closes when the program exits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the master fd can't be duplicated or the thread
+    /// can't be spawned.
+    pub fn reader(&self) -> io::Result<Receiver<Vec<u8>>> {
+        let mut master = self.master.try_clone()?;
+        let (tx, rx) = mpsc::channel();
+        thread::Builder::new().name("pty-reader".into()).spawn(move || {
+            let mut buf = [0u8; READ_BUF_SIZE];
+            loop {
+                match master.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        if tx.send(buf[..n].to_vec()).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(_) => break, // EIO: the slave side closed.
+                }
+            }
+        })?;
+        Ok(rx)
+    }
+
+    /// The program's exit status, if it has exited.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from `waitpid`.
+    pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.child.try_wait()
+    }
+
+    /// The program's process ID.
+    #[must_use]
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+}
+
+impl Drop for Pty {
+    fn drop(&mut self) {
+        if matches!(self.child.try_wait(), Ok(None)) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
+
+#[cfg(unix)]
+const fn winsize(cols: u16, rows: u16) -> libc::winsize {
+    libc::winsize { ws_row: rows, ws_col: cols, ws_xpixel: 0, ws_ypixel: 0 }
+}
+
+/// Open a master/slave pair with the given size. Both ends are
+/// close-on-exec; `Stdio` dups the slave onto the child's fds 0–2.
+#[cfg(unix)]
+fn open_pty(cols: u16, rows: u16) -> io::Result<(File, File)> {
+    let mut master = 0;
+    let mut slave = 0;
+    let mut ws = winsize(cols, rows);
+    // SAFETY: openpty writes two fds; the name and termios pointers may be
+    // null.
+    let result = unsafe {
+        libc::openpty(&raw mut master, &raw mut slave, std::ptr::null_mut(), std::ptr::null(), &raw mut ws)
+    };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: both fds were just opened and are owned by nobody else.
+    let (master, slave) = unsafe { (File::from_raw_fd(master), File::from_raw_fd(slave)) };
+    for fd in [master.as_raw_fd(), slave.as_raw_fd()] {
+        // SAFETY: fcntl on an fd we own.
+        if unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok((master, slave))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    /// Everything the program writes until it exits (or 5 s pass).
+    fn output_of(pty: &Pty) -> String {
+        let rx = pty.reader().unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut out = Vec::new();
+        while let Ok(chunk) = rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            out.extend(chunk);
+        }
+        String::from_utf8_lossy(&out).into_owned()
+    }
+
+    #[test]
+    fn child_sees_a_terminal() {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "[ -t 0 ] && [ -t 1 ] && echo tty:$TERM; stty size"]);
+        let pty = Pty::spawn(cmd, 42, 7).unwrap();
+        let out = output_of(&pty);
+        assert!(out.contains("tty:xterm-256color"), "{out:?}");
+        assert!(out.contains("7 42"), "{out:?}");
+    }
+
+    #[test]
+    fn input_is_echoed_and_read() {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "read line; echo got:$line"]);
+        let mut pty = Pty::spawn(cmd, 80, 24).unwrap();
+        pty.write(b"hello\r").unwrap();
+        let out = output_of(&pty);
+        assert!(out.contains("got:hello"), "{out:?}");
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while pty.try_wait().unwrap().is_none() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(pty.try_wait().unwrap().is_some_and(|s| s.success()));
+    }
+}
@@ -88,6 +88,38 @@ pub fn is_tty() -> bool {
     false
 }
 
+/// Whether the terminal accepts 24-bit `TrueColor` escapes, judging by
+/// `$COLORTERM` and `$TERM`.
+#[must_use]
+pub fn supports_truecolor() -> bool {
+    let term = std::env::var("TERM").ok();
+    let colorterm = std::env::var("COLORTERM").ok();
+    truecolor_from_env(term.as_deref(), colorterm.as_deref())
+}
+
+/// The decision behind [`supports_truecolor`], given the two variables.
+///
+/// `COLORTERM=truecolor` (or `24bit`) and `-direct` terminfo entries are
+/// explicit. Otherwise a `256color` entry, multiplexers (`screen`, `tmux`)
+/// and the Linux console mean no `TrueColor`. Anything else is assumed to
+/// be a modern terminal.
+#[must_use]
+pub fn truecolor_from_env(term: Option<&str>, colorterm: Option<&str>) -> bool {
+    if matches!(colorterm, Some("truecolor" | "24bit")) {
+        return true;
+    }
+    let Some(term) = term else {
+        return true;
+    };
+    if term.ends_with("-direct") {
+        return true;
+    }
+    !(term.contains("256color")
+        || term.starts_with("screen")
+        || term.starts_with("tmux")
+        || term == "linux")
+}
+
 // ─── Panic-Safe Terminal Restore ────────────────────────────────────────────
 
 /// Global backup of original termios for panic recovery.
@@ -259,7 +291,11 @@ impl Terminal {
     /// Enables raw mode (via termios), switches to the alternate screen,
     /// hides the cursor, clears the screen, and enables:
     /// - SGR mouse tracking (drag mode)
-    /// - Kitty keyboard protocol (disambiguate flag)
+    /// - Kitty keyboard protocol (disambiguate flag), followed by a query
+    ///   whose reply ([`Event::KeyboardFlags`](crate::input::Event::KeyboardFlags))
+    ///   arrives only from terminals that support it — the rest ignore both
+    /// - A synchronized output query, answered with
+    ///   [`Event::SyncOutput`](crate::input::Event::SyncOutput)
     /// - Bracketed paste
     /// - Focus reporting
     ///
@@ -287,6 +323,8 @@ impl Terminal {
         ansi::clear_screen(&mut lock)?;
         ansi::enable_mouse(&mut lock, ansi::MouseMode::Drag)?;
         ansi::enable_kitty_keyboard(&mut lock, 1)?;
+        ansi::query_kitty_keyboard(&mut lock)?;
+        ansi::query_sync_output(&mut lock)?;
         ansi::enable_bracketed_paste(&mut lock)?;
         ansi::enable_focus_reporting(&mut lock)?;
         lock.flush()?;
@@ -431,6 +469,17 @@ mod tests {
 
     // ── Size ──────────────────────────────────────────────────────────
 
+    #[test]
+    fn truecolor_detection() {
+        assert!(truecolor_from_env(Some("xterm-256color"), Some("truecolor")));
+        assert!(truecolor_from_env(Some("tmux-256color"), Some("24bit")));
+        assert!(truecolor_from_env(Some("xterm-direct"), None));
+        assert!(truecolor_from_env(Some("xterm-kitty"), None));
+        assert!(!truecolor_from_env(Some("xterm-256color"), None));
+        assert!(!truecolor_from_env(Some("screen"), None));
+        assert!(!truecolor_from_env(Some("linux"), Some("")));
+    }
+
     #[test]
     fn size_area() {
         assert_eq!(Size { cols: 80, rows: 24 }.area(), 1920);
@@ -0,0 +1,845 @@
+// SPDX-License-Identifier: MIT
+//
+// VT100 emulator — the other direction of the ANSI module.
+//
+// `ansi` writes escape sequences to the real terminal; `Vt` reads the ones a
+// program writes and keeps the screen they describe, as a `FrameBuffer` of
+// cells. The editor's `:terminal` feeds it a shell's pty output and shows
+// the result.
+//
+// Coverage is what shells and line-oriented tools use: printable text with
+// deferred wrap, C0 controls (BS, HT, LF, CR), cursor movement, erase and
+// insert/delete (`CSI A–H J K L M P @ X d f`), scroll regions (`CSI r`),
+// SGR colors and attributes, the alternate screen (`CSI ?1049 h/l`), and
+// cursor position reports (`CSI 6 n`). OSC strings (window titles) and
+// charset designations are parsed and dropped.
+//
+// Lines that scroll off the top of the main screen go to a scrollback of
+// plain text, capped at `SCROLLBACK_LINES`.
+
+use crate::buffer::{char_width, FrameBuffer};
+use crate::cell::{Attr, Cell, UnderlineStyle};
+use crate::color::CellColor;
+use crate::input::{KeyCode, KeyEvent, Modifiers};
+
+/// Lines kept after they scroll off the top of the screen.
+pub const SCROLLBACK_LINES: usize = 10_000;
+
+/// Where the parser is within an escape sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Ground,
+    /// After ESC.
+    Escape,
+    /// After `ESC (` / `ESC )` — one charset byte follows.
+    Charset,
+    /// Inside `ESC [ … final`.
+    Csi,
+    /// Inside `ESC ] …`, until BEL or ST.
+    Osc,
+    /// ESC seen inside an OSC string (possible ST).
+    OscEscape,
+}
+
+/// The main screen, put aside while the alternate screen is up.
+struct SavedScreen {
+    screen: FrameBuffer,
+    row: u16,
+    col: u16,
+}
+
+/// A VT100-style terminal emulator over a [`FrameBuffer`].
+pub struct Vt {
+    screen: FrameBuffer,
+    row: u16,
+    col: u16,
+    /// Set after printing in the last column: the next printable character
+    /// wraps to a new line first (DEC "deferred wrap").
+    wrap_pending: bool,
+    saved_cursor: (u16, u16),
+    /// Style for newly printed characters (`ch` unused).
+    pen: Cell,
+    /// Scroll region, inclusive rows.
+    scroll_top: u16,
+    scroll_bottom: u16,
+    cursor_visible: bool,
+    scrollback: Vec<String>,
+    alt: Option<SavedScreen>,
+    state: State,
+    /// Parameter and intermediate bytes of the CSI being parsed.
+    csi: Vec<u8>,
+    /// Bytes of an incomplete UTF-8 character.
+    utf8: Vec<u8>,
+    /// Bytes to send back to the program (e.g. cursor position reports).
+    replies: Vec<u8>,
+}
+
+impl Vt {
+    /// An empty `cols × rows` screen with the cursor at the top left.
+    #[must_use]
+    pub fn new(cols: u16, rows: u16) -> Self {
+        let (cols, rows) = (cols.max(1), rows.max(1));
+        Self {
+            screen: FrameBuffer::new(cols, rows),
+            row: 0,
+            col: 0,
+            wrap_pending: false,
+            saved_cursor: (0, 0),
+            pen: Cell::EMPTY,
+            scroll_top: 0,
+            scroll_bottom: rows - 1,
+            cursor_visible: true,
+            scrollback: Vec::new(),
+            alt: None,
+            state: State::Ground,
+            csi: Vec::new(),
+            utf8: Vec::new(),
+            replies: Vec::new(),
+        }
+    }
+
+    // ─── Queries ────────────────────────────────────────────────────────
+
+    /// The visible screen.
+    #[must_use]
+    pub const fn screen(&self) -> &FrameBuffer {
+        &self.screen
+    }
+
+    /// Cursor position on the screen as `(row, col)`.
+    #[must_use]
+    pub const fn cursor(&self) -> (u16, u16) {
+        (self.row, self.col)
+    }
+
+    /// Whether the program wants the cursor shown (`CSI ?25 h/l`).
+    #[must_use]
+    pub const fn cursor_visible(&self) -> bool {
+        self.cursor_visible
+    }
+
+    /// Lines that have scrolled off the top, oldest first.
+    #[must_use]
+    pub fn scrollback(&self) -> &[String] {
+        &self.scrollback
+    }
+
+    /// Text of screen row `y`, without trailing blanks.
+    #[must_use]
+    pub fn row_text(&self, y: u16) -> String {
+        let Some(row) = self.screen.row(y) else {
+            return String::new();
+        };
+        let text: String = row
+            .iter()
+            .filter(|c| !c.is_continuation())
+            .map(|c| char::from_u32(c.ch).unwrap_or(' '))
+            .collect();
+        text.trim_end().to_string()
+    }
+
+    /// Scrollback followed by the screen, as lines of text. Blank rows at
+    /// the bottom of the screen are left out unless the cursor is on or
+    /// below them, and the cursor's row keeps its blanks up to the cursor
+    /// (so a prompt's trailing space survives).
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        let mut rows: Vec<String> = (0..self.rows()).map(|y| self.row_text(y)).collect();
+        let cursor_row = &mut rows[usize::from(self.row)];
+        let width = crate::buffer::string_width(cursor_row);
+        let col = usize::from(self.col) + usize::from(self.wrap_pending);
+        if width < col {
+            cursor_row.extend(std::iter::repeat_n(' ', col - width));
+        }
+        let last_used = rows.iter().rposition(|r| !r.is_empty()).unwrap_or(0);
+        let keep = last_used.max(usize::from(self.row)) + 1;
+        self.scrollback.iter().cloned().chain(rows.into_iter().take(keep)).collect()
+    }
+
+    /// Take the bytes the emulator wants to send back to the program.
+    pub fn take_replies(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.replies)
+    }
+
+    // ─── Resize ─────────────────────────────────────────────────────────
+
+    /// Change the screen size, keeping the top-left of the old content.
+    /// If the cursor would fall off the bottom, the top rows move to the
+    /// scrollback instead.
+    pub fn resize(&mut self, cols: u16, rows: u16) {
+        let (cols, rows) = (cols.max(1), rows.max(1));
+        let shift = (self.row + 1).saturating_sub(rows);
+        for y in 0..shift {
+            let text = self.row_text(y);
+            self.push_scrollback(text);
+        }
+        let mut screen = FrameBuffer::new(cols, rows);
+        for y in 0..rows {
+            let Some(src) = self.screen.row(y + shift) else {
+                break;
+            };
+            for (x, cell) in src.iter().take(usize::from(cols)).enumerate() {
+                #[allow(clippy::cast_possible_truncation)]
+                screen.set(x as u16, y, *cell);
+            }
+        }
+        self.screen = screen;
+        self.row -= shift;
+        self.col = self.col.min(cols - 1);
+        self.wrap_pending = false;
+        self.scroll_top = 0;
+        self.scroll_bottom = rows - 1;
+    }
+
+    // ─── Input ──────────────────────────────────────────────────────────
+
+    /// Process output from the program.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.byte(b);
+        }
+    }
+
+    fn byte(&mut self, b: u8) {
+        match self.state {
+            State::Ground => self.ground(b),
+            State::Escape => self.escape(b),
+            State::Charset => self.state = State::Ground,
+            State::Csi => match b {
+                0x1B => self.state = State::Escape,
+                0x18 | 0x1A => self.state = State::Ground,
+                0x20..=0x3F => self.csi.push(b),
+                0x40..=0x7E => {
+                    self.state = State::Ground;
+                    self.csi_dispatch(b);
+                }
+                _ => {}
+            },
+            State::Osc => match b {
+                0x07 => self.state = State::Ground,
+                0x1B => self.state = State::OscEscape,
+                _ => {}
+            },
+            State::OscEscape => {
+                self.state = if b == b'\\' { State::Ground } else { State::Osc };
+            }
+        }
+    }
+
+    fn ground(&mut self, b: u8) {
+        if !self.utf8.is_empty() || b >= 0x80 {
+            self.utf8.push(b);
+            match std::str::from_utf8(&self.utf8) {
+                Ok(s) => {
+                    let ch = s.chars().next().unwrap_or(' ');
+                    self.utf8.clear();
+                    self.print(ch);
+                }
+                Err(e) if e.error_len().is_some() => {
+                    self.utf8.clear();
+                    self.print('\u{FFFD}');
+                }
+                Err(_) => {} // Incomplete — wait for more bytes.
+            }
+            return;
+        }
+        match b {
+            0x1B => self.state = State::Escape,
+            0x08 => {
+                self.col = self.col.saturating_sub(1);
+                self.wrap_pending = false;
+            }
+            0x09 => {
+                let next = (self.col / 8 + 1) * 8;
+                self.col = next.min(self.cols() - 1);
+                self.wrap_pending = false;
+            }
+            0x0A..=0x0C => self.line_feed(),
+            0x0D => {
+                self.col = 0;
+                self.wrap_pending = false;
+            }
+            0x20..=0x7E => self.print(char::from(b)),
+            _ => {} // BEL and the other C0 controls.
+        }
+    }
+
+    fn escape(&mut self, b: u8) {
+        self.state = State::Ground;
+        match b {
+            b'[' => {
+                self.csi.clear();
+                self.state = State::Csi;
+            }
+            b']' => self.state = State::Osc,
+            b'(' | b')' | b'*' | b'+' => self.state = State::Charset,
+            b'7' => self.saved_cursor = (self.row, self.col),
+            b'8' => self.restore_cursor(),
+            b'D' => self.line_feed(),
+            b'E' => {
+                self.col = 0;
+                self.line_feed();
+            }
+            b'M' => self.reverse_index(),
+            b'c' => {
+                let (cols, rows) = (self.cols(), self.rows());
+                let scrollback = std::mem::take(&mut self.scrollback);
+                *self = Self::new(cols, rows);
+                self.scrollback = scrollback;
+            }
+            _ => {} // Keypad modes and other escapes we don't model.
+        }
+    }
+
+    // ─── Printing and scrolling ─────────────────────────────────────────
+
+    const fn cols(&self) -> u16 {
+        self.screen.width()
+    }
+
+    const fn rows(&self) -> u16 {
+        self.screen.height()
+    }
+
+    fn print(&mut self, ch: char) {
+        let width = char_width(ch);
+        if width == 0 {
+            return;
+        }
+        if self.wrap_pending || (width == 2 && self.col + 1 >= self.cols()) {
+            if width == 2 && !self.wrap_pending {
+                self.erase_cells(self.row, self.col, self.cols());
+            }
+            self.col = 0;
+            self.line_feed();
+        }
+        let p = self.pen;
+        self.screen.set(self.col, self.row, Cell::styled(ch, p.fg, p.bg, p.attrs, p.underline));
+        if width == 2 {
+            self.screen.set(self.col + 1, self.row, Cell::continuation(p.fg, p.bg, p.attrs));
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let next = self.col + width as u16;
+        if next >= self.cols() {
+            self.col = self.cols() - 1;
+            self.wrap_pending = true;
+        } else {
+            self.col = next;
+        }
+    }
+
+    fn line_feed(&mut self) {
+        self.wrap_pending = false;
+        if self.row == self.scroll_bottom {
+            self.scroll_up(1);
+        } else if self.row + 1 < self.rows() {
+            self.row += 1;
+        }
+    }
+
+    fn reverse_index(&mut self) {
+        self.wrap_pending = false;
+        if self.row == self.scroll_top {
+            self.scroll_down(1);
+        } else {
+            self.row = self.row.saturating_sub(1);
+        }
+    }
+
+    fn push_scrollback(&mut self, line: String) {
+        if self.scrollback.len() == SCROLLBACK_LINES {
+            self.scrollback.remove(0);
+        }
+        self.scrollback.push(line);
+    }
+
+    /// Scroll the region up `n` lines. On the main screen, lines leaving
+    /// the top of the screen go to the scrollback.
+    fn scroll_up(&mut self, n: u16) {
+        if self.scroll_top == 0 && self.alt.is_none() {
+            for y in 0..n.min(self.scroll_bottom + 1) {
+                let text = self.row_text(y);
+                self.push_scrollback(text);
+            }
+        }
+        self.delete_lines(n);
+    }
+
+    fn scroll_down(&mut self, n: u16) {
+        let (top, bottom) = (self.scroll_top, self.scroll_bottom);
+        let n = n.min(bottom - top + 1);
+        for y in (top..=bottom).rev() {
+            if y >= top + n {
+                self.copy_row(y - n, y);
+            } else {
+                self.erase_cells(y, 0, self.cols());
+            }
+        }
+    }
+
+    fn copy_row(&mut self, from: u16, to: u16) {
+        if let Some(src) = self.screen.row(from).map(<[Cell]>::to_vec) {
+            if let Some(dst) = self.screen.row_mut(to) {
+                dst.copy_from_slice(&src);
+            }
+        }
+    }
+
+    /// Blank columns `from..to` of row `y` with the pen's background.
+    fn erase_cells(&mut self, y: u16, from: u16, to: u16) {
+        let blank = Cell::EMPTY.with_bg(self.pen.bg);
+        for x in from..to.min(self.cols()) {
+            self.screen.set(x, y, blank);
+        }
+    }
+
+    fn restore_cursor(&mut self) {
+        (self.row, self.col) = self.saved_cursor;
+        self.row = self.row.min(self.rows() - 1);
+        self.col = self.col.min(self.cols() - 1);
+        self.wrap_pending = false;
+    }
+
+    // ─── CSI ────────────────────────────────────────────────────────────
+
+    fn csi_dispatch(&mut self, final_byte: u8) {
+        let private = self.csi.first().is_some_and(|b| matches!(b, b'?' | b'>' | b'<' | b'='));
+        let params: Vec<u16> = String::from_utf8_lossy(&self.csi)
+            .trim_start_matches(['?', '>', '<', '='])
+            .split(';')
+            .map(|p| p.parse().unwrap_or(0))
+            .collect();
+        let arg = |i: usize| params.get(i).copied().filter(|&n| n > 0).unwrap_or(1);
+        let (rows, cols) = (self.rows(), self.cols());
+        if private && !matches!(final_byte, b'h' | b'l') {
+            // Secondary device attributes and friends: no answer.
+            return;
+        }
+        self.wrap_pending = false;
+        match final_byte {
+            b'A' => self.row = self.row.saturating_sub(arg(0)).max(self.top_bound()),
+            b'B' | b'e' => self.row = (self.row + arg(0)).min(self.bottom_bound()),
+            b'C' | b'a' => self.col = (self.col.saturating_add(arg(0))).min(cols - 1),
+            b'D' => self.col = self.col.saturating_sub(arg(0)),
+            b'E' => {
+                self.row = (self.row + arg(0)).min(self.bottom_bound());
+                self.col = 0;
+            }
+            b'F' => {
+                self.row = self.row.saturating_sub(arg(0)).max(self.top_bound());
+                self.col = 0;
+            }
+            b'G' | b'`' => self.col = (arg(0) - 1).min(cols - 1),
+            b'd' => self.row = (arg(0) - 1).min(rows - 1),
+            b'H' | b'f' => {
+                self.row = (arg(0) - 1).min(rows - 1);
+                self.col = (arg(1) - 1).min(cols - 1);
+            }
+            b'J' => self.erase_display(params.first().copied().unwrap_or(0)),
+            b'K' => match params.first().copied().unwrap_or(0) {
+                0 => self.erase_cells(self.row, self.col, cols),
+                1 => self.erase_cells(self.row, 0, self.col + 1),
+                _ => self.erase_cells(self.row, 0, cols),
+            },
+            b'L' | b'M' if (self.scroll_top..=self.scroll_bottom).contains(&self.row) => {
+                let top = std::mem::replace(&mut self.scroll_top, self.row);
+                if final_byte == b'L' {
+                    self.scroll_down(arg(0));
+                } else {
+                    self.delete_lines(arg(0));
+                }
+                self.scroll_top = top;
+                self.col = 0;
+            }
+            b'@' => self.shift_cells(arg(0), true),
+            b'P' => self.shift_cells(arg(0), false),
+            b'X' => self.erase_cells(self.row, self.col, self.col.saturating_add(arg(0))),
+            b'S' => self.scroll_up(arg(0)),
+            b'T' => self.scroll_down(arg(0)),
+            b'r' => {
+                let top = arg(0) - 1;
+                let bottom = params.get(1).copied().filter(|&n| n > 0).unwrap_or(rows).min(rows) - 1;
+                if top < bottom {
+                    self.scroll_top = top;
+                    self.scroll_bottom = bottom;
+                    self.row = 0;
+                    self.col = 0;
+                }
+            }
+            b'm' => self.sgr(&params),
+            b'n' => match params.first() {
+                Some(5) => self.replies.extend_from_slice(b"\x1b[0n"),
+                Some(6) => {
+                    let report = format!("\x1b[{};{}R", self.row + 1, self.col + 1);
+                    self.replies.extend_from_slice(report.as_bytes());
+                }
+                _ => {}
+            },
+            b'c' => self.replies.extend_from_slice(b"\x1b[?1;2c"),
+            b's' => self.saved_cursor = (self.row, self.col),
+            b'u' => self.restore_cursor(),
+            b'h' | b'l' if private => {
+                let set = final_byte == b'h';
+                for &mode in &params {
+                    match mode {
+                        25 => self.cursor_visible = set,
+                        47 | 1047 | 1049 => self.set_alt_screen(set),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Move the region's lines up `n`, dropping the top ones and blanking
+    /// the bottom ones.
+    fn delete_lines(&mut self, n: u16) {
+        let (top, bottom) = (self.scroll_top, self.scroll_bottom);
+        let n = n.min(bottom - top + 1);
+        for y in top..=bottom {
+            if y + n <= bottom {
+                self.copy_row(y + n, y);
+            } else {
+                self.erase_cells(y, 0, self.cols());
+            }
+        }
+    }
+
+    /// First row the cursor may move up to: the region top if inside it.
+    const fn top_bound(&self) -> u16 {
+        if self.row >= self.scroll_top { self.scroll_top } else { 0 }
+    }
+
+    /// Last row the cursor may move down to: the region bottom if inside it.
+    const fn bottom_bound(&self) -> u16 {
+        if self.row <= self.scroll_bottom { self.scroll_bottom } else { self.rows() - 1 }
+    }
+
+    fn erase_display(&mut self, mode: u16) {
+        let (rows, cols) = (self.rows(), self.cols());
+        match mode {
+            0 => {
+                self.erase_cells(self.row, self.col, cols);
+                for y in self.row + 1..rows {
+                    self.erase_cells(y, 0, cols);
+                }
+            }
+            1 => {
+                for y in 0..self.row {
+                    self.erase_cells(y, 0, cols);
+                }
+                self.erase_cells(self.row, 0, self.col + 1);
+            }
+            3 => self.scrollback.clear(),
+            _ => {
+                for y in 0..rows {
+                    self.erase_cells(y, 0, cols);
+                }
+            }
+        }
+    }
+
+    /// Insert (`@`) or delete (`P`) `n` blank cells at the cursor, shifting
+    /// the rest of the line.
+    fn shift_cells(&mut self, n: u16, insert: bool) {
+        let (row, col, cols) = (self.row, usize::from(self.col), usize::from(self.cols()));
+        let blank = Cell::EMPTY.with_bg(self.pen.bg);
+        let n = usize::from(n).min(cols - col);
+        if let Some(line) = self.screen.row_mut(row) {
+            let tail = &mut line[col..];
+            if insert {
+                tail.rotate_right(n);
+                tail[..n].fill(blank);
+            } else {
+                tail.rotate_left(n);
+                let len = tail.len();
+                tail[len - n..].fill(blank);
+            }
+        }
+    }
+
+    fn set_alt_screen(&mut self, on: bool) {
+        if on && self.alt.is_none() {
+            let blank = FrameBuffer::new(self.cols(), self.rows());
+            let main = std::mem::replace(&mut self.screen, blank);
+            self.alt = Some(SavedScreen { screen: main, row: self.row, col: self.col });
+        } else if !on {
+            if let Some(saved) = self.alt.take() {
+                let (cols, rows) = (self.cols(), self.rows());
+                self.screen = saved.screen;
+                self.row = saved.row;
+                self.col = saved.col;
+                if (cols, rows) != (self.cols(), self.rows()) {
+                    self.resize(cols, rows);
+                }
+            }
+        }
+    }
+
+    /// Select Graphic Rendition — colors and attributes for the pen.
+    fn sgr(&mut self, params: &[u16]) {
+        let mut i = 0;
+        while i < params.len() {
+            let p = params[i];
+            match p {
+                0 => self.pen = Cell::EMPTY,
+                1 => self.pen.attrs |= Attr::BOLD,
+                2 => self.pen.attrs |= Attr::DIM,
+                3 => self.pen.attrs |= Attr::ITALIC,
+                4 => self.pen.underline = UnderlineStyle::Straight,
+                5 => self.pen.attrs |= Attr::SLOW_BLINK,
+                7 => self.pen.attrs |= Attr::INVERSE,
+                8 => self.pen.attrs |= Attr::HIDDEN,
+                9 => self.pen.attrs |= Attr::STRIKETHROUGH,
+                22 => self.pen.attrs.remove(Attr::BOLD | Attr::DIM),
+                23 => self.pen.attrs.remove(Attr::ITALIC),
+                24 => self.pen.underline = UnderlineStyle::None,
+                25 => self.pen.attrs.remove(Attr::SLOW_BLINK),
+                27 => self.pen.attrs.remove(Attr::INVERSE),
+                28 => self.pen.attrs.remove(Attr::HIDDEN),
+                29 => self.pen.attrs.remove(Attr::STRIKETHROUGH),
+                #[allow(clippy::cast_possible_truncation)]
+                30..=37 => self.pen.fg = CellColor::Ansi256((p - 30) as u8),
+                #[allow(clippy::cast_possible_truncation)]
+                40..=47 => self.pen.bg = CellColor::Ansi256((p - 40) as u8),
+                #[allow(clippy::cast_possible_truncation)]
+                90..=97 => self.pen.fg = CellColor::Ansi256((p - 90 + 8) as u8),
+                #[allow(clippy::cast_possible_truncation)]
+                100..=107 => self.pen.bg = CellColor::Ansi256((p - 100 + 8) as u8),
+                39 => self.pen.fg = CellColor::Default,
+                49 => self.pen.bg = CellColor::Default,
+                38 | 48 => {
+                    let (color, used) = extended_color(&params[i + 1..]);
+                    if let Some(color) = color {
+                        if p == 38 {
+                            self.pen.fg = color;
+                        } else {
+                            self.pen.bg = color;
+                        }
+                    }
+                    i += used;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Parse the tail of an SGR `38;…` / `48;…`: `5;n` (256-color) or
+/// `2;r;g;b` (truecolor). Returns the color and how many params it used.
+fn extended_color(rest: &[u16]) -> (Option<CellColor>, usize) {
+    let byte = |i: usize| rest.get(i).and_then(|&v| u8::try_from(v).ok());
+    match rest.first() {
+        Some(5) => (byte(1).map(CellColor::Ansi256), 2),
+        Some(2) => match (byte(1), byte(2), byte(3)) {
+            (Some(r), Some(g), Some(b)) => (Some(CellColor::Rgb(r, g, b)), 4),
+            _ => (None, rest.len()),
+        },
+        _ => (None, rest.len()),
+    }
+}
+
+// ─── Key encoding ───────────────────────────────────────────────────────────
+
+/// The bytes a terminal sends to a program for `key` — the inverse of the
+/// input parser. Keys with no VT encoding produce nothing.
+#[must_use]
+pub fn key_to_bytes(key: &KeyEvent) -> Vec<u8> {
+    let mut out = Vec::new();
+    if key.modifiers.contains(Modifiers::ALT) {
+        out.push(0x1B);
+    }
+    let seq: &[u8] = match key.code {
+        KeyCode::Char(ch) if key.modifiers.contains(Modifiers::CTRL) => {
+            let byte = match ch {
+                'a'..='z' | 'A'..='Z' => ch.to_ascii_lowercase() as u8 - b'a' + 1,
+                '@' | ' ' | '2' => 0,
+                '[' | '3' => 0x1B,
+                '\\' | '4' => 0x1C,
+                ']' | '5' => 0x1D,
+                '^' | '6' => 0x1E,
+                '_' | '7' | '/' => 0x1F,
+                '?' | '8' => 0x7F,
+                _ => return Vec::new(),
+            };
+            out.push(byte);
+            return out;
+        }
+        KeyCode::Char(ch) => {
+            let mut buf = [0; 4];
+            out.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+            return out;
+        }
+        KeyCode::Enter => b"\r",
+        KeyCode::Tab if key.modifiers.contains(Modifiers::SHIFT) => b"\x1b[Z",
+        KeyCode::Tab => b"\t",
+        KeyCode::Backspace => b"\x7f",
+        KeyCode::Escape => b"\x1b",
+        KeyCode::Up => b"\x1b[A",
+        KeyCode::Down => b"\x1b[B",
+        KeyCode::Right => b"\x1b[C",
+        KeyCode::Left => b"\x1b[D",
+        KeyCode::Home => b"\x1b[H",
+        KeyCode::End => b"\x1b[F",
+        KeyCode::Insert => b"\x1b[2~",
+        KeyCode::Delete => b"\x1b[3~",
+        KeyCode::PageUp => b"\x1b[5~",
+        KeyCode::PageDown => b"\x1b[6~",
+        KeyCode::F(n) => match n {
+            1 => b"\x1bOP",
+            2 => b"\x1bOQ",
+            3 => b"\x1bOR",
+            4 => b"\x1bOS",
+            5 => b"\x1b[15~",
+            6 => b"\x1b[17~",
+            7 => b"\x1b[18~",
+            8 => b"\x1b[19~",
+            9 => b"\x1b[20~",
+            10 => b"\x1b[21~",
+            11 => b"\x1b[23~",
+            12 => b"\x1b[24~",
+            _ => return Vec::new(),
+        },
+        _ => return Vec::new(),
+    };
+    out.extend_from_slice(seq);
+    out
+}
+
+// ─── Tests ──────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::KeyEventKind;
+
+    fn vt_with(cols: u16, rows: u16, output: &str) -> Vt {
+        let mut vt = Vt::new(cols, rows);
+        vt.feed(output.as_bytes());
+        vt
+    }
+
+    #[test]
+    fn prints_and_wraps() {
+        let vt = vt_with(5, 3, "hello world");
+        assert_eq!(vt.row_text(0), "hello");
+        assert_eq!(vt.row_text(1), " worl");
+        assert_eq!(vt.row_text(2), "d");
+        assert_eq!(vt.cursor(), (2, 1));
+    }
+
+    #[test]
+    fn deferred_wrap_at_last_column() {
+        // A CR LF right after filling the line must not leave a blank row.
+        let vt = vt_with(5, 3, "hello\r\nx");
+        assert_eq!(vt.row_text(0), "hello");
+        assert_eq!(vt.row_text(1), "x");
+    }
+
+    #[test]
+    fn scrolling_fills_scrollback() {
+        let vt = vt_with(10, 2, "one\r\ntwo\r\nthree\r\nfour");
+        assert_eq!(vt.scrollback(), ["one", "two"]);
+        assert_eq!(vt.lines(), ["one", "two", "three", "four"]);
+    }
+
+    #[test]
+    fn cursor_movement_and_erase() {
+        let vt = vt_with(10, 3, "abcdef\x1b[1;3HX\x1b[K\x1b[2;2Hyz\x1b[1D\x1b[P");
+        assert_eq!(vt.row_text(0), "abX");
+        assert_eq!(vt.row_text(1), " y");
+        let vt = vt_with(10, 3, "abc\r\ndef\x1b[2J");
+        assert_eq!(vt.lines(), ["", "   "]);
+    }
+
+    #[test]
+    fn lines_keep_blanks_before_cursor() {
+        let vt = vt_with(10, 3, "$ ");
+        assert_eq!(vt.row_text(0), "$");
+        assert_eq!(vt.lines(), ["$ "]);
+    }
+
+    #[test]
+    fn backspace_and_tab() {
+        let vt = vt_with(20, 2, "ab\x08c\tx");
+        assert_eq!(vt.row_text(0), "ac      x");
+    }
+
+    #[test]
+    fn sgr_colors_reach_cells() {
+        let vt = vt_with(10, 1, "\x1b[1;31ma\x1b[0;38;5;200;48;2;1;2;3mb\x1b[mc");
+        let row = vt.screen().row(0).unwrap();
+        assert_eq!(row[0].fg, CellColor::Ansi256(1));
+        assert!(row[0].attrs.contains(Attr::BOLD));
+        assert_eq!(row[1].fg, CellColor::Ansi256(200));
+        assert_eq!(row[1].bg, CellColor::Rgb(1, 2, 3));
+        assert!(row[1].attrs.is_empty());
+        assert_eq!(row[2].fg, CellColor::Default);
+    }
+
+    #[test]
+    fn utf8_split_across_feeds() {
+        let mut vt = Vt::new(10, 1);
+        let bytes = "é漢".as_bytes();
+        vt.feed(&bytes[..1]);
+        vt.feed(&bytes[1..3]);
+        vt.feed(&bytes[3..]);
+        assert_eq!(vt.row_text(0), "é漢");
+        assert_eq!(vt.cursor(), (0, 3));
+    }
+
+    #[test]
+    fn osc_title_is_dropped() {
+        let vt = vt_with(10, 1, "\x1b]0;title\x07a\x1b]2;t\x1b\\b");
+        assert_eq!(vt.row_text(0), "ab");
+    }
+
+    #[test]
+    fn cursor_position_report() {
+        let mut vt = vt_with(10, 3, "\x1b[2;4H\x1b[6n");
+        assert_eq!(vt.take_replies(), b"\x1b[2;4R");
+        assert!(vt.take_replies().is_empty());
+    }
+
+    #[test]
+    fn alternate_screen_restores_main() {
+        let vt = vt_with(10, 2, "main\x1b[?1049hfull screen app\x1b[?1049l");
+        assert_eq!(vt.row_text(0), "main");
+        assert_eq!(vt.cursor(), (0, 4));
+        assert!(vt.scrollback().is_empty());
+    }
+
+    #[test]
+    fn scroll_region_keeps_outside_rows() {
+        let vt = vt_with(10, 4, "top\x1b[2;3r\x1b[2;1Ha\r\nb\r\nc\x1b[4;1Hbottom");
+        assert_eq!(vt.row_text(0), "top");
+        assert_eq!(vt.row_text(1), "b");
+        assert_eq!(vt.row_text(2), "c");
+        assert_eq!(vt.row_text(3), "bottom");
+        // Region scrolling below the top row doesn't touch the scrollback.
+        assert!(vt.scrollback().is_empty());
+    }
+
+    #[test]
+    fn resize_keeps_cursor_on_screen() {
+        let mut vt = vt_with(10, 4, "1\r\n2\r\n3\r\n4");
+        vt.resize(5, 2);
+        assert_eq!(vt.scrollback(), ["1", "2"]);
+        assert_eq!(vt.row_text(1), "4");
+        assert_eq!(vt.cursor(), (1, 1));
+    }
+
+    #[test]
+    fn keys_encode_like_a_terminal() {
+        let key = |code, modifiers| KeyEvent { code, modifiers, kind: KeyEventKind::Press };
+        assert_eq!(key_to_bytes(&key(KeyCode::Char('é'), Modifiers::empty())), "é".as_bytes());
+        assert_eq!(key_to_bytes(&key(KeyCode::Char('c'), Modifiers::CTRL)), [3]);
+        assert_eq!(key_to_bytes(&key(KeyCode::Char('\\'), Modifiers::CTRL)), [0x1C]);
+        assert_eq!(key_to_bytes(&key(KeyCode::Char('b'), Modifiers::ALT)), b"\x1bb");
+        assert_eq!(key_to_bytes(&key(KeyCode::Up, Modifiers::empty())), b"\x1b[A");
+        assert_eq!(key_to_bytes(&key(KeyCode::Enter, Modifiers::empty())), b"\r");
+        assert!(key_to_bytes(&key(KeyCode::CapsLock, Modifiers::empty())).is_empty());
+    }
+}
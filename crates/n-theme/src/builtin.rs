@@ -3,6 +3,7 @@
 //! Each preset is a specific combination of pattern, base hue, dark/light,
 //! and seed that produces a distinctive, coherent theme.
 
+use crate::contrast::ContrastLevel;
 use crate::highlight::Theme;
 use crate::pattern::PatternKind;
 
@@ -37,6 +38,27 @@ pub fn builtin_theme(name: &str) -> Option<Theme> {
         "pentagram" => {
             Theme::generate("pentagram", PatternKind::Pentagram, 300.0, true, false, 71)
         }
+        "penrose" => {
+            Theme::generate("penrose", PatternKind::Penrose, 200.0, true, false, 89)
+        }
+        "high-contrast-dark" => Theme::generate_with_contrast(
+            "high-contrast-dark",
+            PatternKind::GoldenRatio,
+            210.0,
+            true,
+            false,
+            42,
+            ContrastLevel::Aaa,
+        ),
+        "high-contrast-light" => Theme::generate_with_contrast(
+            "high-contrast-light",
+            PatternKind::GoldenRatio,
+            210.0,
+            false,
+            false,
+            42,
+            ContrastLevel::Aaa,
+        ),
         _ => return None,
     })
 }
@@ -55,9 +77,22 @@ pub const fn builtin_names() -> &'static [&'static str] {
         "monochrome",
         "triadic",
         "pentagram",
+        "penrose",
+        "high-contrast-dark",
+        "high-contrast-light",
     ]
 }
 
+/// Every builtin theme with its name, in [`builtin_names`] order. Aliases
+/// (`default` for `golden-dark`) appear under each name.
+#[must_use]
+pub fn all_themes() -> Vec<(&'static str, Theme)> {
+    builtin_names()
+        .iter()
+        .filter_map(|&name| builtin_theme(name).map(|theme| (name, theme)))
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -74,6 +109,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn all_themes_lists_every_builtin() {
+        let themes = all_themes();
+        assert_eq!(themes.len(), builtin_names().len());
+        let (name, theme) = &themes[3];
+        assert_eq!((*name, theme.name.as_str()), ("golden-light", "golden-light"));
+    }
+
     #[test]
     fn unknown_returns_none() {
         assert!(builtin_theme("nonexistent").is_none());
@@ -99,6 +142,37 @@ mod tests {
         assert_eq!(t.name, "fibonacci");
     }
 
+    #[test]
+    fn penrose_uses_penrose_pattern() {
+        let t = builtin_theme("penrose").unwrap();
+        assert_eq!(t.pattern, Some(PatternKind::Penrose));
+    }
+
+    #[test]
+    fn high_contrast_themes_meet_aaa() {
+        use crate::contrast::contrast_ratio;
+        for (name, dark) in [("high-contrast-dark", true), ("high-contrast-light", false)] {
+            let t = builtin_theme(name).unwrap();
+            assert_eq!(t.is_dark, dark);
+            assert_eq!(t.contrast_level, ContrastLevel::Aaa);
+            let bg = t.palette.bg1;
+            let p = &t.palette;
+            for (role, c) in [
+                ("fg1", p.fg1),
+                ("ac1", p.ac1),
+                ("error", p.error),
+                ("keyword", t.syntax.keyword),
+                ("string", t.syntax.string),
+                ("function", t.syntax.function),
+            ] {
+                let ratio = contrast_ratio(c, bg);
+                assert!(ratio >= 7.0, "{name} {role}: {ratio:.2}");
+            }
+            let comment = contrast_ratio(t.syntax.comment, bg);
+            assert!(comment >= 4.5, "{name} comment: {comment:.2}");
+        }
+    }
+
     #[test]
     fn each_builtin_is_distinct() {
         let default = builtin_theme("default").unwrap();
@@ -7,12 +7,128 @@
 //! - Comments: kept in a narrow 2.5-3.5:1 range (dark) or 1.5-3.0:1 (light)
 //!   so they're clearly de-emphasized but still legible
 //!
+//! [`ContrastLevel::Aaa`] raises these to WCAG AAA for high-contrast themes:
+//! 7:1 for text and accents, and 4.5:1 (the AAA large-text floor) for
+//! comments, which stay the dimmest text on screen.
+//!
+//! Not every color needs the same floor. Each one is tagged with a
+//! [`ContrastRole`] — body text, UI chrome, or subtle hints — and
+//! [`enforce_contrast`] holds it to that role's minimum in a
+//! [`ContrastRequirements`]. Subtle colors like placeholders then stay
+//! quiet instead of being brightened up to the level of the code.
+//!
 //! The key insight from rlabs: readability enforcement must happen in sRGB
 //! relative luminance space (WCAG definition), but adjustments happen in
 //! OKLCH lightness — because OKLCH adjustments are perceptually uniform.
 
 use n_term::color::{Color, srgb_to_linear};
 
+/// How much contrast a theme's text must have against its background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ContrastLevel {
+    /// WCAG AA, with a margin for body text. The default.
+    #[default]
+    Aa,
+    /// WCAG AAA — for high-contrast themes.
+    Aaa,
+}
+
+impl ContrastLevel {
+    /// Minimum ratio for normal and syntax text.
+    #[must_use]
+    pub const fn text_ratio(self) -> f64 {
+        match self {
+            Self::Aa => 5.5,
+            Self::Aaa => 7.0,
+        }
+    }
+
+    /// Minimum ratio for accents and diagnostics.
+    #[must_use]
+    pub const fn accent_ratio(self) -> f64 {
+        match self {
+            Self::Aa => 4.5,
+            Self::Aaa => 7.0,
+        }
+    }
+
+    /// Target `(min, max)` ratio range for comments.
+    #[must_use]
+    pub const fn comment_range(self, is_dark: bool) -> (f64, f64) {
+        match (self, is_dark) {
+            (Self::Aa, true) => (2.5, 3.5),
+            (Self::Aa, false) => (1.5, 3.0),
+            (Self::Aaa, _) => (4.5, 5.5),
+        }
+    }
+
+    /// Minimum ratio for subtle text: placeholders, disabled items. 3:1 is
+    /// the WCAG floor for UI components; AAA asks for large-text contrast.
+    #[must_use]
+    pub const fn subtle_ratio(self) -> f64 {
+        match self {
+            Self::Aa => 3.0,
+            Self::Aaa => 4.5,
+        }
+    }
+
+    /// The per-role minimum ratios for this level.
+    #[must_use]
+    pub const fn requirements(self) -> ContrastRequirements {
+        ContrastRequirements {
+            text: self.text_ratio(),
+            ui: self.accent_ratio(),
+            subtle: self.subtle_ratio(),
+        }
+    }
+}
+
+/// What a color is used for, which decides how much contrast it needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContrastRole {
+    /// Body text the user reads: code, content.
+    Text,
+    /// UI chrome and signals: status lines, accents, diagnostics.
+    Ui,
+    /// De-emphasized text: placeholders, disabled items.
+    Subtle,
+}
+
+/// Minimum contrast ratio against the background for each [`ContrastRole`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContrastRequirements {
+    /// Minimum ratio for [`ContrastRole::Text`].
+    pub text: f64,
+    /// Minimum ratio for [`ContrastRole::Ui`].
+    pub ui: f64,
+    /// Minimum ratio for [`ContrastRole::Subtle`].
+    pub subtle: f64,
+}
+
+impl ContrastRequirements {
+    /// The minimum ratio for `role`.
+    #[must_use]
+    pub const fn ratio(self, role: ContrastRole) -> f64 {
+        match role {
+            ContrastRole::Text => self.text,
+            ContrastRole::Ui => self.ui,
+            ContrastRole::Subtle => self.subtle,
+        }
+    }
+}
+
+impl Default for ContrastRequirements {
+    fn default() -> Self {
+        ContrastLevel::default().requirements()
+    }
+}
+
+impl From<ContrastLevel> for ContrastRequirements {
+    fn from(level: ContrastLevel) -> Self {
+        level.requirements()
+    }
+}
+
 /// Compute the relative luminance of a color per WCAG 2.1.
 ///
 /// Uses the standard sRGB linearization + weighted sum formula:
@@ -88,6 +204,19 @@ pub fn ensure_readability(fg: Color, bg: Color, min_ratio: f64, is_dark: bool) -
     best
 }
 
+/// Hold `fg` to the minimum contrast `requirements` sets for its `role`
+/// against `bg` (see [`ensure_readability`]).
+#[must_use]
+pub fn enforce_contrast(
+    fg: Color,
+    bg: Color,
+    role: ContrastRole,
+    requirements: ContrastRequirements,
+    is_dark: bool,
+) -> Color {
+    ensure_readability(fg, bg, requirements.ratio(role), is_dark)
+}
+
 /// Adjust a comment color to sit in the sweet spot: visible but clearly
 /// de-emphasized relative to normal text.
 ///
@@ -97,12 +226,21 @@ pub fn ensure_readability(fg: Color, bg: Color, min_ratio: f64, is_dark: bool) -
 /// Also ensures the comment is distinguishable from bg3 (the gutter/sidebar
 /// background) — at least 0.5:1 contrast difference.
 #[must_use]
-pub fn adjust_comment_color(comment: Color, bg1: Color, _bg3: Color, is_dark: bool) -> Color {
-    let (target_min, target_max) = if is_dark {
-        (2.5, 3.5)
-    } else {
-        (1.5, 3.0)
-    };
+pub fn adjust_comment_color(comment: Color, bg1: Color, bg3: Color, is_dark: bool) -> Color {
+    adjust_comment_color_for(comment, bg1, bg3, is_dark, ContrastLevel::Aa)
+}
+
+/// [`adjust_comment_color`] with the target range taken from `level`
+/// (see [`ContrastLevel::comment_range`]).
+#[must_use]
+pub fn adjust_comment_color_for(
+    comment: Color,
+    bg1: Color,
+    _bg3: Color,
+    is_dark: bool,
+    level: ContrastLevel,
+) -> Color {
+    let (target_min, target_max) = level.comment_range(is_dark);
 
     let target_mid = (target_min + target_max) * 0.5;
 
@@ -268,6 +406,34 @@ mod tests {
         assert!(hue_diff < 5.0 || hue_diff > 355.0, "Hue shifted: {hue_diff}");
     }
 
+    // ── enforce_contrast ────────────────────────────────────────────
+
+    #[test]
+    fn enforce_uses_the_role_threshold() {
+        let fg = Color::oklch(0.25, 0.05, 270.0);
+        let bg = Color::oklch(0.15, 0.005, 270.0);
+        let req = ContrastRequirements { text: 7.0, ui: 4.5, subtle: 3.0 };
+        let mut previous = f64::MAX;
+        for role in [ContrastRole::Text, ContrastRole::Ui, ContrastRole::Subtle] {
+            let adjusted = enforce_contrast(fg, bg, role, req, true);
+            let ratio = contrast_ratio(adjusted, bg);
+            assert!(ratio >= req.ratio(role), "{role:?} below its floor: {ratio}");
+            // The search stops near the floor, so lower floors stay dimmer.
+            assert!(ratio < previous, "{role:?} not dimmer than the role above");
+            previous = ratio;
+        }
+    }
+
+    #[test]
+    fn requirements_follow_the_level() {
+        assert_eq!(ContrastRequirements::default(), ContrastLevel::Aa.requirements());
+        for level in [ContrastLevel::Aa, ContrastLevel::Aaa] {
+            let req = ContrastRequirements::from(level);
+            assert_eq!(req, level.requirements());
+            assert!(req.subtle < req.ui && req.ui <= req.text);
+        }
+    }
+
     // ── adjust_comment_color ────────────────────────────────────────
 
     #[test]
@@ -305,4 +471,27 @@ mod tests {
         let hue_diff = (adjusted.h - comment.h).abs();
         assert!(hue_diff < 5.0 || hue_diff > 355.0, "Comment hue shifted: {hue_diff}");
     }
+
+    #[test]
+    fn comment_aaa_is_legible() {
+        let comment = Color::oklch(0.4, 0.03, 120.0);
+        for (bg1, bg3, is_dark) in [
+            (Color::oklch(0.15, 0.005, 270.0), Color::oklch(0.22, 0.008, 270.0), true),
+            (Color::oklch(0.97, 0.002, 0.0), Color::oklch(0.93, 0.005, 0.0), false),
+        ] {
+            let adjusted = adjust_comment_color_for(comment, bg1, bg3, is_dark, ContrastLevel::Aaa);
+            let ratio = contrast_ratio(adjusted, bg1);
+            assert!((4.4..=5.8).contains(&ratio), "AAA comment contrast out of range: {ratio}");
+        }
+    }
+
+    #[test]
+    fn aaa_raises_every_floor() {
+        let (aa, aaa) = (ContrastLevel::Aa, ContrastLevel::Aaa);
+        assert_eq!(ContrastLevel::default(), aa);
+        assert!(aaa.text_ratio() >= 7.0 && aaa.accent_ratio() >= 7.0);
+        assert!(aaa.text_ratio() > aa.text_ratio());
+        assert!(aaa.accent_ratio() > aa.accent_ratio());
+        assert!(aaa.comment_range(true).0 > aa.comment_range(true).1);
+    }
 }
@@ -0,0 +1,235 @@
+//! Theme export — terminal emulator color schemes from generated themes.
+//!
+//! The editor's `normal` group leaves the background to the terminal, so a
+//! generated theme only looks as designed when the terminal's own colors
+//! match it. These functions write the theme's palette in the formats
+//! Alacritty and `WezTerm` load, so the terminal can share the theme.
+//!
+//! Both formats carry the primary background/foreground, the cursor, the
+//! selection and the 16 ANSI colors. The ANSI colors keep their usual
+//! meaning (red is still red) but take their lightness and chroma from the
+//! theme's diagnostic colors, so programs running in the terminal blend in.
+
+use std::fmt::Write as _;
+
+use n_term::color::{CellColor, Color};
+
+use crate::highlight::Theme;
+
+/// A terminal color scheme format for [`export`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Alacritty `[colors]` TOML.
+    Alacritty,
+    /// `WezTerm` color scheme as a Lua table.
+    WezTerm,
+}
+
+impl ExportFormat {
+    /// Parse a format from its name (case-insensitive).
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "alacritty" => Some(Self::Alacritty),
+            "wezterm" => Some(Self::WezTerm),
+            _ => None,
+        }
+    }
+
+    /// Lowercase name of this format.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Alacritty => "alacritty",
+            Self::WezTerm => "wezterm",
+        }
+    }
+}
+
+/// Export `theme` in the given format.
+#[must_use]
+pub fn export(theme: &Theme, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Alacritty => theme_to_alacritty_toml(theme),
+        ExportFormat::WezTerm => theme_to_wezterm_lua(theme),
+    }
+}
+
+/// The colors a terminal scheme needs, as `#rrggbb` strings.
+struct TermColors {
+    background: String,
+    foreground: String,
+    cursor: String,
+    selection: String,
+    /// Black, red, green, yellow, blue, magenta, cyan, white.
+    normal: [String; 8],
+    bright: [String; 8],
+}
+
+/// `#rrggbb` for a resolved cell color, or `fallback` for the default.
+fn cell_hex(color: CellColor, fallback: Color) -> String {
+    color.to_color().unwrap_or(fallback).to_hex()
+}
+
+/// Work out the terminal colors for `theme` from its palette.
+fn term_colors(theme: &Theme) -> TermColors {
+    let p = &theme.palette;
+    let is_dark = theme.is_dark;
+
+    // Gray ramp: "black" and "white" swap roles between dark and light
+    // themes so that text drawn in "white" is always readable.
+    let (black, bright_black, white, bright_white) = if is_dark {
+        (p.bg3, p.fg3, p.fg2, p.fg1)
+    } else {
+        (p.fg1, p.fg2, p.bg3, p.bg1)
+    };
+
+    // Magenta and cyan have no diagnostic role; derive them from `info`.
+    let hues = [
+        black,
+        p.error,
+        p.success,
+        p.warning,
+        p.info,
+        p.info.set_hue(320.0),
+        p.info.set_hue(195.0),
+        white,
+    ];
+    let brighten = |c: Color| if is_dark { c.lighten(0.08) } else { c.darken(0.08) };
+
+    let normal = hues.map(Color::to_hex);
+    let mut bright = hues.map(|c| brighten(c).to_hex());
+    bright[0] = bright_black.to_hex();
+    bright[7] = bright_white.to_hex();
+
+    TermColors {
+        background: p.bg1.to_hex(),
+        foreground: cell_hex(theme.normal.fg, p.fg1),
+        cursor: p.ac1.to_hex(),
+        selection: cell_hex(theme.visual.bg, p.selection),
+        normal,
+        bright,
+    }
+}
+
+/// ANSI color names in index order, as Alacritty spells them.
+const ANSI_NAMES: [&str; 8] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+
+/// Export `theme` as an Alacritty `[colors]` TOML section.
+///
+/// Paste it into `alacritty.toml`, or save it to its own file and list
+/// that under `general.import`.
+#[must_use]
+pub fn theme_to_alacritty_toml(theme: &Theme) -> String {
+    let c = term_colors(theme);
+    let mut out = String::new();
+    let _ = writeln!(out, "# n-nvim theme: {}", theme.name);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "[colors.primary]");
+    let _ = writeln!(out, "background = \"{}\"", c.background);
+    let _ = writeln!(out, "foreground = \"{}\"", c.foreground);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "[colors.cursor]");
+    let _ = writeln!(out, "text = \"{}\"", c.background);
+    let _ = writeln!(out, "cursor = \"{}\"", c.cursor);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "[colors.selection]");
+    let _ = writeln!(out, "text = \"CellForeground\"");
+    let _ = writeln!(out, "background = \"{}\"", c.selection);
+    for (table, colors) in [("normal", &c.normal), ("bright", &c.bright)] {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "[colors.{table}]");
+        for (name, hex) in ANSI_NAMES.iter().zip(colors) {
+            let _ = writeln!(out, "{name} = \"{hex}\"");
+        }
+    }
+    out
+}
+
+/// Export `theme` as a `WezTerm` color scheme: a Lua chunk returning the
+/// scheme table.
+///
+/// Load it with `config.color_schemes = { ['name'] = dofile(path) }` and
+/// select it with `config.color_scheme = 'name'`.
+#[must_use]
+pub fn theme_to_wezterm_lua(theme: &Theme) -> String {
+    let c = term_colors(theme);
+    let list = |colors: &[String; 8]| {
+        colors.iter().map(|h| format!("\"{h}\"")).collect::<Vec<_>>().join(", ")
+    };
+    let mut out = String::new();
+    let _ = writeln!(out, "-- n-nvim theme: {}", theme.name);
+    let _ = writeln!(out, "return {{");
+    let _ = writeln!(out, "  foreground = \"{}\",", c.foreground);
+    let _ = writeln!(out, "  background = \"{}\",", c.background);
+    let _ = writeln!(out, "  cursor_bg = \"{}\",", c.cursor);
+    let _ = writeln!(out, "  cursor_fg = \"{}\",", c.background);
+    let _ = writeln!(out, "  cursor_border = \"{}\",", c.cursor);
+    let _ = writeln!(out, "  selection_bg = \"{}\",", c.selection);
+    let _ = writeln!(out, "  ansi = {{ {} }},", list(&c.normal));
+    let _ = writeln!(out, "  brights = {{ {} }},", list(&c.bright));
+    let _ = writeln!(out, "}}");
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_names_round_trip() {
+        for f in [ExportFormat::Alacritty, ExportFormat::WezTerm] {
+            assert_eq!(ExportFormat::from_name(f.name()), Some(f));
+        }
+        assert_eq!(ExportFormat::from_name("WezTerm"), Some(ExportFormat::WezTerm));
+        assert_eq!(ExportFormat::from_name("kitty"), None);
+    }
+
+    #[test]
+    fn alacritty_has_all_tables() {
+        let theme = Theme::default_theme();
+        let toml = theme_to_alacritty_toml(&theme);
+        for table in ["primary", "cursor", "selection", "normal", "bright"] {
+            assert!(toml.contains(&format!("[colors.{table}]")), "missing {table}");
+        }
+        for name in ANSI_NAMES {
+            assert_eq!(toml.matches(&format!("\n{name} = \"#")).count(), 2, "{name}");
+        }
+        let bg = format!("background = \"{}\"", theme.palette.bg1.to_hex());
+        assert!(toml.contains(&bg));
+    }
+
+    #[test]
+    fn foreground_matches_editor_text() {
+        let theme = Theme::default_theme();
+        let CellColor::Rgb(r, g, b) = theme.normal.fg else {
+            panic!("generated theme has RGB text");
+        };
+        let hex = format!("#{r:02x}{g:02x}{b:02x}");
+        assert!(theme_to_alacritty_toml(&theme).contains(&format!("foreground = \"{hex}\"")));
+        assert!(theme_to_wezterm_lua(&theme).contains(&format!("foreground = \"{hex}\"")));
+    }
+
+    #[test]
+    fn wezterm_has_sixteen_colors() {
+        let lua = theme_to_wezterm_lua(&Theme::default_theme());
+        assert!(lua.starts_with("-- n-nvim theme: default\nreturn {"));
+        assert!(lua.trim_end().ends_with('}'));
+        let ansi = lua.lines().find(|l| l.trim_start().starts_with("ansi =")).unwrap();
+        let brights = lua.lines().find(|l| l.trim_start().starts_with("brights =")).unwrap();
+        assert_eq!(ansi.matches('#').count(), 8);
+        assert_eq!(brights.matches('#').count(), 8);
+    }
+
+    #[test]
+    fn light_theme_swaps_black_and_white() {
+        let theme = crate::builtin::builtin_theme("golden-light").unwrap();
+        let c = term_colors(&theme);
+        assert_eq!(c.normal[0], theme.palette.fg1.to_hex());
+        assert_eq!(c.bright[7], theme.palette.bg1.to_hex());
+    }
+}
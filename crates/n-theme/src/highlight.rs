@@ -4,10 +4,18 @@
 //! layer uses to style every UI element. Colors are pre-resolved to
 //! terminal-ready `CellColor` values during construction so the hot
 //! rendering path never does color math.
+//!
+//! `:highlight` overrides single groups on top of the computed ones (see
+//! [`Theme::set_highlight`] and [`HighlightSpec`]); `:highlight clear`
+//! puts the computed groups back.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
 
 use n_term::cell::{Attr, UnderlineStyle};
 use n_term::color::{CellColor, Color};
 
+use crate::contrast::ContrastLevel;
 use crate::palette::UiPalette;
 use crate::pattern::PatternKind;
 use crate::syntax::SyntaxPalette;
@@ -105,6 +113,8 @@ pub struct Theme {
     pub status_line_nc: HighlightGroup,
     /// Cursor line background.
     pub cursor_line: HighlightGroup,
+    /// Columns marked by `colorcolumn`.
+    pub color_column: HighlightGroup,
     /// Visual selection.
     pub visual: HighlightGroup,
     /// Search matches.
@@ -123,6 +133,17 @@ pub struct Theme {
     pub warning_msg: HighlightGroup,
     /// Normal messages.
     pub msg: HighlightGroup,
+    /// Misspelled words (`:set spell`). Applied on top of the existing cell
+    /// style, so only the underline (and a non-default fg) take effect.
+    pub spell_bad: HighlightGroup,
+    /// Sign column: lines added since the last commit.
+    pub diff_add: HighlightGroup,
+    /// Sign column: lines changed since the last commit.
+    pub diff_change: HighlightGroup,
+    /// Sign column: where lines were deleted since the last commit.
+    pub diff_delete: HighlightGroup,
+    /// The row standing in for a closed fold.
+    pub folded: HighlightGroup,
 
     // ── Mode-specific status lines ────────────────────────────
     /// Status line in insert mode (active window).
@@ -137,12 +158,25 @@ pub struct Theme {
     pub pattern: Option<PatternKind>,
     /// The base hue used to generate this theme (None for terminal).
     pub base_hue: Option<f32>,
+    /// The contrast level text was held to when generating this theme.
+    pub contrast_level: ContrastLevel,
 
     // ── Color sources (for advanced consumers) ────────────────
     /// The full UI palette used to generate this theme.
     pub palette: UiPalette,
     /// The full syntax palette (ready for tree-sitter integration).
     pub syntax: SyntaxPalette,
+
+    /// Set by [`to_256_color`](Self::to_256_color): consumers resolving
+    /// `syntax` colors should reduce them to the 256-color palette too.
+    pub color256: bool,
+
+    /// `:highlight` overrides by group name (as in [`GROUP_NAMES`]). They
+    /// take precedence over the computed groups.
+    pub overrides: HashMap<String, HighlightGroup>,
+    /// The computed value of each overridden group, for
+    /// [`clear_highlights`](Self::clear_highlights).
+    computed: HashMap<String, HighlightGroup>,
 }
 
 /// Resolve a Color to `CellColor`, compositing semi-transparent colors over bg.
@@ -171,14 +205,28 @@ impl Theme {
         few: bool,
         seed: u32,
     ) -> Self {
-        let hues = if few {
-            pattern.generate_few(base_hue)
-        } else {
-            pattern.generate(base_hue)
-        };
+        Self::generate_with_contrast(name, pattern, base_hue, is_dark, few, seed, ContrastLevel::Aa)
+    }
+
+    /// [`generate`](Self::generate) at a given contrast level —
+    /// [`ContrastLevel::Aaa`] gives a WCAG AAA high-contrast theme.
+    #[must_use]
+    pub fn generate_with_contrast(
+        name: &str,
+        pattern: PatternKind,
+        base_hue: f32,
+        is_dark: bool,
+        few: bool,
+        seed: u32,
+        level: ContrastLevel,
+    ) -> Self {
+        let mut hues = pattern.generate_seeded(base_hue, u64::from(seed));
+        if few {
+            hues.truncate(5);
+        }
 
-        let palette = UiPalette::generate(&hues, is_dark, seed);
-        let syntax = SyntaxPalette::generate(
+        let palette = UiPalette::generate_with_contrast(&hues, is_dark, seed, level);
+        let syntax = SyntaxPalette::generate_with_contrast(
             &hues,
             palette.bg1,
             palette.bg3,
@@ -186,11 +234,13 @@ impl Theme {
             palette.ac2.h,
             is_dark,
             seed,
+            level,
         );
 
         let mut theme = Self::from_palette(name, is_dark, palette, syntax);
         theme.pattern = Some(pattern);
         theme.base_hue = Some(base_hue);
+        theme.contrast_level = level;
         theme
     }
 
@@ -251,6 +301,8 @@ impl Theme {
                 underline: UnderlineStyle::None,
             },
 
+            color_column: HighlightGroup::fg_bg(CellColor::Default, line_highlight_cc),
+
             visual: HighlightGroup {
                 fg: CellColor::Default,
                 bg: selection_cc,
@@ -300,6 +352,19 @@ impl Theme {
 
             msg: HighlightGroup::fg_only(p.fg1.to_cell_color()),
 
+            spell_bad: HighlightGroup {
+                fg: CellColor::Default,
+                bg: CellColor::Default,
+                attrs: Attr::empty(),
+                underline: UnderlineStyle::Curly,
+            },
+
+            diff_add: HighlightGroup::fg_only(p.success.to_cell_color()),
+            diff_change: HighlightGroup::fg_only(p.warning.to_cell_color()),
+            diff_delete: HighlightGroup::fg_only(p.error.to_cell_color()),
+
+            folded: HighlightGroup::fg_bg(comment_cc, p.bg2.to_cell_color()),
+
             // Mode-specific status lines: derive from palette hues.
             status_line_insert: HighlightGroup {
                 fg: p.fg1.to_cell_color(),
@@ -322,9 +387,13 @@ impl Theme {
 
             pattern: None, // Set by caller.
             base_hue: None,
+            contrast_level: ContrastLevel::Aa,
 
             palette,
             syntax,
+            color256: false,
+            overrides: HashMap::new(),
+            computed: HashMap::new(),
         }
     }
 
@@ -375,6 +444,8 @@ impl Theme {
                 underline: UnderlineStyle::Straight,
             },
 
+            color_column: HighlightGroup::fg_bg(Default, Ansi256(236)),
+
             visual: HighlightGroup {
                 fg: Default,
                 bg: Default,
@@ -413,6 +484,19 @@ impl Theme {
 
             msg: HighlightGroup::fg_only(Default),
 
+            spell_bad: HighlightGroup {
+                fg: Default,
+                bg: Default,
+                attrs: Attr::empty(),
+                underline: UnderlineStyle::Curly,
+            },
+
+            diff_add: HighlightGroup::fg_only(Ansi256(2)),
+            diff_change: HighlightGroup::fg_only(Ansi256(3)),
+            diff_delete: HighlightGroup::fg_only(Ansi256(1)),
+
+            folded: HighlightGroup::fg_bg(Ansi256(6), Ansi256(236)),
+
             // Mode-specific: ANSI colors for terminal compatibility.
             status_line_insert: HighlightGroup {
                 fg: Ansi256(0),
@@ -435,10 +519,143 @@ impl Theme {
 
             pattern: None,
             base_hue: None,
+            contrast_level: ContrastLevel::Aa,
 
             palette: UiPalette::placeholder(),
             syntax: SyntaxPalette::placeholder(),
+            color256: false,
+            overrides: HashMap::new(),
+            computed: HashMap::new(),
+        }
+    }
+
+    /// This theme for terminals without `TrueColor`: every RGB color in the
+    /// highlight groups is replaced by its nearest ANSI-256 entry (matched
+    /// in Oklab, see [`CellColor::to_ansi256`]). Default and palette colors
+    /// are kept as they are.
+    #[must_use]
+    pub fn to_256_color(&self) -> Self {
+        let mut theme = self.clone();
+        for group in theme.groups_mut() {
+            group.fg = group.fg.to_ansi256();
+            group.bg = group.bg.to_ansi256();
         }
+        theme.color256 = true;
+        theme
+    }
+
+    /// The group named `name` (case-insensitive, see [`GROUP_NAMES`]),
+    /// with its canonical name.
+    #[must_use]
+    pub fn group(&self, name: &str) -> Option<(&'static str, HighlightGroup)> {
+        let i = group_index(name)?;
+        Some((GROUP_NAMES[i], *self.groups()[i]))
+    }
+
+    /// `:highlight {group} {spec}` — override the keys `spec` gives in the
+    /// group named `name`, leaving the rest as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `E411` message if there's no group by that name.
+    pub fn set_highlight(&mut self, name: &str, spec: &HighlightSpec) -> Result<(), String> {
+        let i = group_index(name).ok_or_else(|| format!("E411: highlight group not found: {name}"))?;
+        let key = GROUP_NAMES[i].to_string();
+        let color256 = self.color256;
+        let groups = self.groups_mut();
+        let group = &mut *groups[i];
+        let computed = *group;
+        spec.apply(group, color256);
+        let group = *group;
+        self.computed.entry(key.clone()).or_insert(computed);
+        self.overrides.insert(key, group);
+        Ok(())
+    }
+
+    /// `:highlight clear [group]` — drop the overrides (of one group, or
+    /// all of them), putting the computed groups back.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `E411` message if there's no group named `name`.
+    pub fn clear_highlights(&mut self, name: Option<&str>) -> Result<(), String> {
+        let names: Vec<String> = match name {
+            Some(name) => {
+                let i = group_index(name).ok_or_else(|| format!("E411: highlight group not found: {name}"))?;
+                vec![GROUP_NAMES[i].to_string()]
+            }
+            None => self.computed.keys().cloned().collect(),
+        };
+        for name in names {
+            self.overrides.remove(&name);
+            if let (Some(computed), Some(i)) = (self.computed.remove(&name), group_index(&name)) {
+                *self.groups_mut()[i] = computed;
+            }
+        }
+        Ok(())
+    }
+
+    /// Every highlight group, in [`GROUP_NAMES`] order.
+    const fn groups(&self) -> [&HighlightGroup; 25] {
+        [
+            &self.normal,
+            &self.line_nr,
+            &self.cursor_line_nr,
+            &self.non_text,
+            &self.status_line,
+            &self.status_line_nc,
+            &self.cursor_line,
+            &self.color_column,
+            &self.visual,
+            &self.search,
+            &self.inc_search,
+            &self.vert_split,
+            &self.pmenu_sel,
+            &self.pmenu,
+            &self.error_msg,
+            &self.warning_msg,
+            &self.msg,
+            &self.spell_bad,
+            &self.diff_add,
+            &self.diff_change,
+            &self.diff_delete,
+            &self.folded,
+            &self.status_line_insert,
+            &self.status_line_visual,
+            &self.status_line_replace,
+        ]
+    }
+
+    /// Every highlight group, for whole-theme transforms. In
+    /// [`GROUP_NAMES`] order.
+    const fn groups_mut(&mut self) -> [&mut HighlightGroup; 25] {
+        [
+            &mut self.normal,
+            &mut self.line_nr,
+            &mut self.cursor_line_nr,
+            &mut self.non_text,
+            &mut self.status_line,
+            &mut self.status_line_nc,
+            &mut self.cursor_line,
+            &mut self.color_column,
+            &mut self.visual,
+            &mut self.search,
+            &mut self.inc_search,
+            &mut self.vert_split,
+            &mut self.pmenu_sel,
+            &mut self.pmenu,
+            &mut self.error_msg,
+            &mut self.warning_msg,
+            &mut self.msg,
+            &mut self.spell_bad,
+            &mut self.diff_add,
+            &mut self.diff_change,
+            &mut self.diff_delete,
+            &mut self.folded,
+            &mut self.status_line_insert,
+            &mut self.status_line_visual,
+            &mut self.status_line_replace,
+        ]
     }
 
     /// Generate a theme using the current timestamp as seed — each call
@@ -473,6 +690,186 @@ impl Theme {
     }
 }
 
+// ---------------------------------------------------------------------------
+// :highlight
+// ---------------------------------------------------------------------------
+
+/// The `:highlight` names of the theme's groups. `StatusLineInsert`,
+/// `StatusLineVisual` and `StatusLineReplace` are this editor's own.
+pub const GROUP_NAMES: [&str; 25] = [
+    "Normal",
+    "LineNr",
+    "CursorLineNr",
+    "NonText",
+    "StatusLine",
+    "StatusLineNC",
+    "CursorLine",
+    "ColorColumn",
+    "Visual",
+    "Search",
+    "IncSearch",
+    "VertSplit",
+    "PmenuSel",
+    "Pmenu",
+    "ErrorMsg",
+    "WarningMsg",
+    "MsgArea",
+    "SpellBad",
+    "DiffAdd",
+    "DiffChange",
+    "DiffDelete",
+    "Folded",
+    "StatusLineInsert",
+    "StatusLineVisual",
+    "StatusLineReplace",
+];
+
+/// Position of the group `name` in [`GROUP_NAMES`], ignoring case.
+/// `WinSeparator` is another name for `VertSplit`.
+fn group_index(name: &str) -> Option<usize> {
+    let name = if name.eq_ignore_ascii_case("WinSeparator") { "VertSplit" } else { name };
+    GROUP_NAMES.iter().position(|g| g.eq_ignore_ascii_case(name))
+}
+
+/// The keys of a `:highlight` command.
+///
+/// They are `guifg=#rrggbb guibg=#rrggbb gui=bold,italic ctermfg=N
+/// ctermbg=N`. A key not given leaves that part of the group alone;
+/// `NONE` as a color means the terminal's default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HighlightSpec {
+    pub guifg: Option<CellColor>,
+    pub guibg: Option<CellColor>,
+    /// Attributes and underline style from `gui=` (`cterm=` too).
+    pub gui: Option<(Attr, UnderlineStyle)>,
+    pub ctermfg: Option<CellColor>,
+    pub ctermbg: Option<CellColor>,
+}
+
+impl HighlightSpec {
+    /// Parse the `key=value` words after the group name.
+    ///
+    /// # Errors
+    ///
+    /// Returns a Vim-style message for a word without `=` (`E416`), an
+    /// unknown key (`E423`) or a bad value (`E418`).
+    pub fn parse(args: &str) -> Result<Self, String> {
+        let mut spec = Self::default();
+        for word in args.split_whitespace() {
+            let (key, value) = word.split_once('=').ok_or_else(|| format!("E416: Missing equal sign: {word}"))?;
+            let bad = || format!("E418: Illegal value: {value}");
+            match key.to_ascii_lowercase().as_str() {
+                "guifg" => spec.guifg = Some(parse_hex_color(value).ok_or_else(bad)?),
+                "guibg" => spec.guibg = Some(parse_hex_color(value).ok_or_else(bad)?),
+                "ctermfg" => spec.ctermfg = Some(parse_cterm_color(value).ok_or_else(bad)?),
+                "ctermbg" => spec.ctermbg = Some(parse_cterm_color(value).ok_or_else(bad)?),
+                "gui" | "cterm" => spec.gui = Some(parse_attrs(value).ok_or_else(bad)?),
+                _ => return Err(format!("E423: Illegal argument: {key}")),
+            }
+        }
+        Ok(spec)
+    }
+
+    /// Apply the keys given to `group`. In 256-color mode the `cterm`
+    /// colors win (the `gui` ones are reduced to the nearest entry);
+    /// otherwise the `gui` ones do.
+    pub fn apply(&self, group: &mut HighlightGroup, color256: bool) {
+        let pick = |gui: Option<CellColor>, cterm: Option<CellColor>| {
+            if color256 {
+                cterm.or_else(|| gui.map(CellColor::to_ansi256))
+            } else {
+                gui.or(cterm)
+            }
+        };
+        if let Some(fg) = pick(self.guifg, self.ctermfg) {
+            group.fg = fg;
+        }
+        if let Some(bg) = pick(self.guibg, self.ctermbg) {
+            group.bg = bg;
+        }
+        if let Some((attrs, underline)) = self.gui {
+            group.attrs = attrs;
+            group.underline = underline;
+        }
+    }
+}
+
+/// A group as `:highlight` shows it: the keys that would recreate it, or
+/// `cleared` for one with nothing set.
+#[must_use]
+pub fn describe(group: &HighlightGroup) -> String {
+    let mut out = String::new();
+    let mut color = |key: &str, c: CellColor| match c {
+        CellColor::Rgb(r, g, b) => {
+            let _ = write!(out, " gui{key}=#{r:02x}{g:02x}{b:02x}");
+        }
+        CellColor::Ansi256(n) => {
+            let _ = write!(out, " cterm{key}={n}");
+        }
+        CellColor::Default => {}
+    };
+    color("fg", group.fg);
+    color("bg", group.bg);
+    let mut attrs: Vec<&str> = ATTR_NAMES
+        .iter()
+        .filter(|(_, a)| !a.is_empty() && group.attrs.contains(*a))
+        .map(|(n, _)| *n)
+        .collect();
+    match group.underline {
+        UnderlineStyle::None => {}
+        UnderlineStyle::Curly => attrs.push("undercurl"),
+        _ => attrs.push("underline"),
+    }
+    if !attrs.is_empty() {
+        let _ = write!(out, " gui={}", attrs.join(","));
+    }
+    if out.is_empty() { "cleared".to_string() } else { out[1..].to_string() }
+}
+
+/// `gui=` attribute names. `underline` and `undercurl` set the underline
+/// style instead.
+const ATTR_NAMES: [(&str, Attr); 6] = [
+    ("bold", Attr::BOLD),
+    ("italic", Attr::ITALIC),
+    ("reverse", Attr::INVERSE),
+    ("inverse", Attr::INVERSE),
+    ("strikethrough", Attr::STRIKETHROUGH),
+    ("NONE", Attr::empty()),
+];
+
+/// `#rrggbb`, or `NONE` for the terminal's default.
+fn parse_hex_color(value: &str) -> Option<CellColor> {
+    if value.eq_ignore_ascii_case("NONE") {
+        return Some(CellColor::Default);
+    }
+    let hex = value.strip_prefix('#').filter(|h| h.len() == 6)?;
+    let n = u32::from_str_radix(hex, 16).ok()?;
+    let [_, r, g, b] = n.to_be_bytes();
+    Some(CellColor::Rgb(r, g, b))
+}
+
+/// A 256-color palette number, or `NONE`.
+fn parse_cterm_color(value: &str) -> Option<CellColor> {
+    if value.eq_ignore_ascii_case("NONE") {
+        return Some(CellColor::Default);
+    }
+    value.parse().ok().map(CellColor::Ansi256)
+}
+
+/// A comma-separated `gui=` list (`bold,italic`, or `NONE`).
+fn parse_attrs(value: &str) -> Option<(Attr, UnderlineStyle)> {
+    let mut attrs = Attr::empty();
+    let mut underline = UnderlineStyle::None;
+    for name in value.split(',') {
+        match name.to_ascii_lowercase().as_str() {
+            "underline" => underline = UnderlineStyle::Straight,
+            "undercurl" => underline = UnderlineStyle::Curly,
+            lower => attrs |= ATTR_NAMES.iter().find(|(n, _)| n.eq_ignore_ascii_case(lower))?.1,
+        }
+    }
+    Some((attrs, underline))
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -481,6 +878,46 @@ impl Theme {
 mod tests {
     use super::*;
 
+    #[test]
+    fn highlight_spec_parses_keys() {
+        let spec = HighlightSpec::parse("guifg=#1e1e2e guibg=NONE gui=bold,undercurl ctermfg=4").unwrap();
+        assert_eq!(spec.guifg, Some(CellColor::Rgb(0x1e, 0x1e, 0x2e)));
+        assert_eq!(spec.guibg, Some(CellColor::Default));
+        assert_eq!(spec.gui, Some((Attr::BOLD, UnderlineStyle::Curly)));
+        assert_eq!(spec.ctermfg, Some(CellColor::Ansi256(4)));
+        assert_eq!(spec.ctermbg, None);
+        assert!(HighlightSpec::parse("guifg=red").unwrap_err().starts_with("E418"));
+        assert!(HighlightSpec::parse("gui=shiny").unwrap_err().starts_with("E418"));
+        assert!(HighlightSpec::parse("font=x").unwrap_err().starts_with("E423"));
+        assert!(HighlightSpec::parse("guifg").unwrap_err().starts_with("E416"));
+    }
+
+    #[test]
+    fn highlight_overrides_and_clear() {
+        let mut t = Theme::default_theme();
+        let computed = t.normal;
+        let spec = HighlightSpec::parse("guibg=#1e1e2e ctermbg=235").unwrap();
+        t.set_highlight("normal", &spec).unwrap();
+        assert_eq!(t.normal.bg, CellColor::Rgb(0x1e, 0x1e, 0x2e));
+        assert_eq!(t.normal.fg, computed.fg);
+        assert_eq!(t.overrides.get("Normal"), Some(&t.normal));
+        assert_eq!(t.group("Normal").map(|(n, g)| (n, describe(&g).contains("guibg=#1e1e2e"))), Some(("Normal", true)));
+
+        // A second override still clears back to the computed group.
+        t.set_highlight("Normal", &HighlightSpec::parse("gui=bold").unwrap()).unwrap();
+        assert_eq!(t.normal.attrs, Attr::BOLD);
+        t.clear_highlights(None).unwrap();
+        assert_eq!(t.normal, computed);
+        assert!(t.overrides.is_empty());
+
+        // 256-color mode takes the cterm value.
+        let mut t = t.to_256_color();
+        t.set_highlight("WinSeparator", &spec).unwrap();
+        assert_eq!(t.vert_split.bg, CellColor::Ansi256(235));
+        assert!(t.set_highlight("Bogus", &spec).unwrap_err().starts_with("E411"));
+        assert_eq!(describe(&HighlightGroup::default()), "cleared");
+    }
+
     #[test]
     fn default_theme_is_dark() {
         let t = Theme::default_theme();
@@ -532,6 +969,14 @@ mod tests {
         assert!(t.pmenu_sel.attrs.contains(Attr::BOLD));
     }
 
+    #[test]
+    fn diff_signs_use_status_hues() {
+        let t = Theme::default_theme();
+        assert_eq!(t.diff_add.fg, t.palette.success.to_cell_color());
+        assert_eq!(t.diff_change.fg, t.palette.warning.to_cell_color());
+        assert_eq!(t.diff_delete.fg, t.palette.error.to_cell_color());
+    }
+
     #[test]
     fn error_msg_has_bold() {
         let t = Theme::default_theme();
@@ -592,6 +1037,39 @@ mod tests {
         assert!(t.non_text.attrs.contains(Attr::DIM));
     }
 
+    #[test]
+    fn spell_bad_is_curly() {
+        assert_eq!(Theme::default_theme().spell_bad.underline, UnderlineStyle::Curly);
+        assert_eq!(Theme::terminal().spell_bad.underline, UnderlineStyle::Curly);
+    }
+
+    #[test]
+    fn to_256_color_leaves_no_rgb() {
+        let t = Theme::default_theme().to_256_color();
+        assert!(t.color256);
+        let mut t2 = t.clone();
+        for g in t2.groups_mut() {
+            assert!(!matches!(g.fg, CellColor::Rgb(..)), "{g:?}");
+            assert!(!matches!(g.bg, CellColor::Rgb(..)), "{g:?}");
+        }
+        // Attributes and default colors survive.
+        assert_eq!(t.status_line_insert.attrs, Attr::BOLD);
+        assert_eq!(t.spell_bad.fg, CellColor::Default);
+    }
+
+    #[test]
+    fn to_256_color_picks_nearest() {
+        let mut t = Theme::terminal();
+        t.normal.bg = CellColor::Rgb(0, 0, 0);
+        t.normal.fg = CellColor::Rgb(255, 0, 0);
+        let t = t.to_256_color();
+        // Exact matches in the palette map to themselves.
+        assert_eq!(t.normal.bg.to_srgb(), Some((0.0, 0.0, 0.0)));
+        assert_eq!(t.normal.fg.to_srgb(), Some((1.0, 0.0, 0.0)));
+        // The terminal theme is already ANSI-only and stays unchanged.
+        assert_eq!(t.line_nr, Theme::terminal().line_nr);
+    }
+
     #[test]
     fn vert_split_is_dim() {
         let t = Theme::default_theme();
@@ -17,7 +17,7 @@
 //! palette.rs:  assign hues to UI color roles (BG/FG/AC/diagnostics)
 //!     │
 //!     ▼
-//! contrast.rs: enforce WCAG readability (>= 5.5:1 for text)
+//! contrast.rs: enforce WCAG readability (>= 5.5:1 for text, 7:1 at AAA)
 //!     │
 //!     ▼
 //! syntax.rs:   generate 30+ syntax token colors (grouped by family)
@@ -26,6 +26,9 @@
 //! highlight.rs: assemble Theme with named HighlightGroups
 //! ```
 //!
+//! `export.rs` writes a finished theme as an Alacritty or `WezTerm` color
+//! scheme, so the terminal around the editor can match it.
+//!
 //! # Color Space
 //!
 //! All generation happens in OKLCH (perceptually uniform). Colors are
@@ -45,10 +48,12 @@
 
 pub mod builtin;
 pub mod contrast;
+pub mod export;
 pub mod highlight;
 pub mod palette;
 pub mod pattern;
 pub mod syntax;
 
+pub use contrast::{ContrastLevel, ContrastRequirements, ContrastRole};
 pub use highlight::{HighlightGroup, Theme};
 pub use pattern::PatternKind;
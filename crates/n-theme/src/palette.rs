@@ -3,10 +3,15 @@
 //! Takes hue arrays from [`pattern`] and generates a complete UI color palette
 //! with properly constrained lightness and chroma for backgrounds, foregrounds,
 //! accent colors, and diagnostics.
+//!
+//! Every readable color is tagged with a [`ContrastRole`] and held to that
+//! role's minimum contrast against `bg1`: body text to the highest, UI
+//! chrome and diagnostics to less, and tertiary text to a floor low enough
+//! that it stays de-emphasized.
 
 use n_term::color::Color;
 
-use crate::contrast::ensure_readability;
+use crate::contrast::{ContrastLevel, ContrastRequirements, ContrastRole, enforce_contrast};
 
 // ---------------------------------------------------------------------------
 // Xorshift32 — a minimal deterministic PRNG
@@ -112,6 +117,25 @@ impl UiPalette {
     /// - `seed`: deterministic seed for subtle random variations
     #[must_use]
     pub fn generate(hues: &[f32], is_dark: bool, seed: u32) -> Self {
+        Self::generate_with_contrast(hues, is_dark, seed, ContrastLevel::Aa)
+    }
+
+    /// [`generate`](Self::generate) with text, accents and diagnostics held
+    /// to the given contrast `level` against `bg1`.
+    #[must_use]
+    pub fn generate_with_contrast(hues: &[f32], is_dark: bool, seed: u32, level: ContrastLevel) -> Self {
+        Self::generate_with_requirements(hues, is_dark, seed, level.requirements())
+    }
+
+    /// [`generate`](Self::generate) with each color held to the minimum
+    /// contrast `requirements` sets for its [`ContrastRole`].
+    #[must_use]
+    pub fn generate_with_requirements(
+        hues: &[f32],
+        is_dark: bool,
+        seed: u32,
+        requirements: ContrastRequirements,
+    ) -> Self {
         let mut rng = Xorshift32::new(seed);
         let base_hue = hues[0];
 
@@ -123,49 +147,58 @@ impl UiPalette {
         };
 
         if is_dark {
-            Self::generate_dark(base_hue, ac2_hue, &mut rng)
+            Self::generate_dark(base_hue, ac2_hue, &mut rng, requirements)
         } else {
-            Self::generate_light(base_hue, ac2_hue, &mut rng)
+            Self::generate_light(base_hue, ac2_hue, &mut rng, requirements)
         }
     }
 
-    fn generate_dark(base_hue: f32, ac2_hue: f32, rng: &mut Xorshift32) -> Self {
+    fn generate_dark(base_hue: f32, ac2_hue: f32, rng: &mut Xorshift32, req: ContrastRequirements) -> Self {
         // Backgrounds: very low chroma, dark.
         let bg1 = Color::oklch(rng.range_f32(0.14, 0.17), rng.range_f32(0.002, 0.008), base_hue).to_gamut();
         let bg2 = Color::oklch(bg1.l + rng.range_f32(0.02, 0.04), rng.range_f32(0.003, 0.010), base_hue).to_gamut();
         let bg3 = Color::oklch(bg2.l + rng.range_f32(0.02, 0.04), rng.range_f32(0.004, 0.012), base_hue).to_gamut();
 
         // Foregrounds: near-achromatic, bright.
-        let fg1 = Color::oklch(rng.range_f32(0.90, 0.97), rng.range_f32(0.000, 0.010), base_hue).to_gamut();
-        let fg2 = Color::oklch(rng.range_f32(0.75, 0.85), rng.range_f32(0.000, 0.008), base_hue).to_gamut();
-        let fg3 = Color::oklch(rng.range_f32(0.55, 0.65), rng.range_f32(0.000, 0.008), base_hue).to_gamut();
+        let fg1 = enforce_contrast(
+            Color::oklch(rng.range_f32(0.90, 0.97), rng.range_f32(0.000, 0.010), base_hue).to_gamut(),
+            bg1, ContrastRole::Text, req, true,
+        );
+        let fg2 = enforce_contrast(
+            Color::oklch(rng.range_f32(0.75, 0.85), rng.range_f32(0.000, 0.008), base_hue).to_gamut(),
+            bg1, ContrastRole::Ui, req, true,
+        );
+        let fg3 = enforce_contrast(
+            Color::oklch(rng.range_f32(0.55, 0.65), rng.range_f32(0.000, 0.008), base_hue).to_gamut(),
+            bg1, ContrastRole::Subtle, req, true,
+        );
 
         // Accent colors: moderate chroma.
-        let ac1 = ensure_readability(
+        let ac1 = enforce_contrast(
             Color::oklch(rng.range_f32(0.70, 0.80), rng.range_f32(0.10, 0.16), base_hue).to_gamut(),
-            bg1, 4.5, true,
+            bg1, ContrastRole::Ui, req, true,
         );
-        let ac2 = ensure_readability(
+        let ac2 = enforce_contrast(
             Color::oklch(rng.range_f32(0.70, 0.80), rng.range_f32(0.10, 0.16), ac2_hue).to_gamut(),
-            bg1, 4.5, true,
+            bg1, ContrastRole::Ui, req, true,
         );
 
         // Diagnostics: fixed semantic hues.
-        let error = ensure_readability(
+        let error = enforce_contrast(
             Color::oklch(0.70, 0.18, rng.range_f32(24.0, 32.0)).to_gamut(),
-            bg1, 4.5, true,
+            bg1, ContrastRole::Ui, req, true,
         );
-        let warning = ensure_readability(
+        let warning = enforce_contrast(
             Color::oklch(0.78, 0.14, rng.range_f32(70.0, 85.0)).to_gamut(),
-            bg1, 4.5, true,
+            bg1, ContrastRole::Ui, req, true,
         );
-        let info = ensure_readability(
+        let info = enforce_contrast(
             Color::oklch(0.72, 0.12, rng.range_f32(240.0, 270.0)).to_gamut(),
-            bg1, 4.5, true,
+            bg1, ContrastRole::Ui, req, true,
         );
-        let success = ensure_readability(
+        let success = enforce_contrast(
             Color::oklch(0.72, 0.14, rng.range_f32(140.0, 155.0)).to_gamut(),
-            bg1, 4.5, true,
+            bg1, ContrastRole::Ui, req, true,
         );
 
         // UI surfaces.
@@ -181,43 +214,52 @@ impl UiPalette {
         }
     }
 
-    fn generate_light(base_hue: f32, ac2_hue: f32, rng: &mut Xorshift32) -> Self {
+    fn generate_light(base_hue: f32, ac2_hue: f32, rng: &mut Xorshift32, req: ContrastRequirements) -> Self {
         // Backgrounds: very low chroma, light.
         let bg1 = Color::oklch(rng.range_f32(0.96, 0.98), rng.range_f32(0.002, 0.006), base_hue).to_gamut();
         let bg2 = Color::oklch(bg1.l - rng.range_f32(0.02, 0.04), rng.range_f32(0.003, 0.010), base_hue).to_gamut();
         let bg3 = Color::oklch(bg2.l - rng.range_f32(0.02, 0.04), rng.range_f32(0.004, 0.012), base_hue).to_gamut();
 
         // Foregrounds: near-achromatic, dark.
-        let fg1 = Color::oklch(rng.range_f32(0.10, 0.18), rng.range_f32(0.000, 0.010), base_hue).to_gamut();
-        let fg2 = Color::oklch(rng.range_f32(0.25, 0.35), rng.range_f32(0.000, 0.008), base_hue).to_gamut();
-        let fg3 = Color::oklch(rng.range_f32(0.45, 0.55), rng.range_f32(0.000, 0.008), base_hue).to_gamut();
+        let fg1 = enforce_contrast(
+            Color::oklch(rng.range_f32(0.10, 0.18), rng.range_f32(0.000, 0.010), base_hue).to_gamut(),
+            bg1, ContrastRole::Text, req, false,
+        );
+        let fg2 = enforce_contrast(
+            Color::oklch(rng.range_f32(0.25, 0.35), rng.range_f32(0.000, 0.008), base_hue).to_gamut(),
+            bg1, ContrastRole::Ui, req, false,
+        );
+        let fg3 = enforce_contrast(
+            Color::oklch(rng.range_f32(0.45, 0.55), rng.range_f32(0.000, 0.008), base_hue).to_gamut(),
+            bg1, ContrastRole::Subtle, req, false,
+        );
 
         // Accent colors.
-        let ac1 = ensure_readability(
+        let ac1 = enforce_contrast(
             Color::oklch(rng.range_f32(0.45, 0.55), rng.range_f32(0.12, 0.18), base_hue).to_gamut(),
-            bg1, 4.5, false,
+            bg1, ContrastRole::Ui, req, false,
         );
-        let ac2 = ensure_readability(
+        let ac2 = enforce_contrast(
             Color::oklch(rng.range_f32(0.45, 0.55), rng.range_f32(0.12, 0.18), ac2_hue).to_gamut(),
-            bg1, 4.5, false,
+            bg1, ContrastRole::Ui, req, false,
         );
 
         // Diagnostics.
-        let error = ensure_readability(
+        let error = enforce_contrast(
             Color::oklch(0.55, 0.18, rng.range_f32(24.0, 32.0)).to_gamut(),
-            bg1, 4.5, false,
+            bg1, ContrastRole::Ui, req, false,
         );
-        let warning = ensure_readability(
+        let warning = enforce_contrast(
             Color::oklch(0.50, 0.14, rng.range_f32(70.0, 85.0)).to_gamut(),
-            bg1, 4.5, false,
+            bg1, ContrastRole::Ui, req, false,
         );
-        let info = ensure_readability(
+        let info = enforce_contrast(
             Color::oklch(0.50, 0.12, rng.range_f32(240.0, 270.0)).to_gamut(),
-            bg1, 4.5, false,
+            bg1, ContrastRole::Ui, req, false,
         );
-        let success = ensure_readability(
+        let success = enforce_contrast(
             Color::oklch(0.50, 0.14, rng.range_f32(140.0, 155.0)).to_gamut(),
-            bg1, 4.5, false,
+            bg1, ContrastRole::Ui, req, false,
         );
 
         // UI surfaces.
@@ -359,6 +401,31 @@ mod tests {
         assert!(p.error.in_srgb_gamut(), "error out of gamut");
     }
 
+    #[test]
+    fn each_role_meets_its_own_floor() {
+        let req = ContrastRequirements { text: 7.0, ui: 5.0, subtle: 3.5 };
+        for is_dark in [true, false] {
+            let p = UiPalette::generate_with_requirements(&[270.0, 47.5], is_dark, 42, req);
+            for (name, color, role) in [
+                ("fg1", p.fg1, ContrastRole::Text),
+                ("fg2", p.fg2, ContrastRole::Ui),
+                ("fg3", p.fg3, ContrastRole::Subtle),
+                ("ac1", p.ac1, ContrastRole::Ui),
+                ("error", p.error, ContrastRole::Ui),
+            ] {
+                let ratio = contrast_ratio(color, p.bg1);
+                assert!(ratio >= req.ratio(role) - 0.01, "{name} below its floor: {ratio}");
+            }
+        }
+    }
+
+    #[test]
+    fn subtle_text_stays_dimmer_than_body_text() {
+        for p in [dark_palette(), light_palette()] {
+            assert!(contrast_ratio(p.fg3, p.bg1) < contrast_ratio(p.fg1, p.bg1));
+        }
+    }
+
     #[test]
     fn diagnostics_distinct_hues() {
         let p = dark_palette();
@@ -9,7 +9,8 @@
 pub enum PatternKind {
     /// Golden angle (137.508) spacing — nature's favorite.
     GoldenRatio,
-    /// Fibonacci sequence reciprocal spacing.
+    /// Golden-angle steps, as in Fibonacci phyllotaxis; the seed nudges
+    /// each step (see [`generate_fibonacci_hues`]).
     Fibonacci,
     /// 120-degree spacing (3 colors).
     Triadic,
@@ -45,6 +46,9 @@ pub enum PatternKind {
     HarmonicResonance,
     /// 5x5 grid with phi multipliers.
     PhiGrid,
+    /// Five-fold star with aperiodic phi offsets, after Penrose tilings
+    /// (see [`generate_penrose_hues`]).
+    Penrose,
     /// Single hue only.
     Monochromatic,
 }
@@ -58,6 +62,19 @@ impl PatternKind {
         generate(self, base_hue)
     }
 
+    /// Like [`generate`](Self::generate), but lets `seed` vary patterns
+    /// that take one ([`Fibonacci`](Self::Fibonacci) and
+    /// [`Penrose`](Self::Penrose)). A seed of
+    /// 0 gives the same hues as `generate`.
+    #[must_use]
+    pub fn generate_seeded(self, base_hue: f32, seed: u64) -> Vec<f32> {
+        match self {
+            Self::Fibonacci => generate_fibonacci_hues(FIBONACCI_COUNT, base_hue, seed),
+            Self::Penrose => generate_penrose_hues(PENROSE_COUNT, base_hue, seed),
+            _ => generate(self, base_hue),
+        }
+    }
+
     /// Generate a cohesive subset (first 5 hues) for simpler palettes.
     #[must_use]
     pub fn generate_few(self, base_hue: f32) -> Vec<f32> {
@@ -89,6 +106,7 @@ impl PatternKind {
             Self::SeedOfLife => "seed-of-life",
             Self::HarmonicResonance => "harmonic-resonance",
             Self::PhiGrid => "phi-grid",
+            Self::Penrose => "penrose",
             Self::Monochromatic => "monochromatic",
         }
     }
@@ -110,7 +128,7 @@ impl PatternKind {
             Self::FlowerOfLife, Self::Metatron, Self::SacredSpirals,
             Self::SriYantra, Self::Torus, Self::SolfeggioAll,
             Self::DivineProportion, Self::SeedOfLife, Self::HarmonicResonance,
-            Self::PhiGrid, Self::Monochromatic,
+            Self::PhiGrid, Self::Penrose, Self::Monochromatic,
         ]
     }
 }
@@ -121,18 +139,67 @@ fn norm(h: f32) -> f32 {
     if h < 0.0 { h + 360.0 } else { h }
 }
 
+/// Golden angle = 360 / phi^2 ≈ 137.508.
+const GOLDEN_ANGLE: f32 = 137.507_76;
+
+/// Number of hues in the [`PatternKind::Fibonacci`] pattern.
+const FIBONACCI_COUNT: usize = 10;
+
+/// Generate `n` hues by repeatedly adding the golden angle to `base_hue`.
+///
+/// Successive hues always land in the largest remaining gap on the wheel,
+/// so colors never cluster however many are taken. Each 4-bit nibble of
+/// `seed` widens one step by up to 3 degrees — enough to vary a theme
+/// without disturbing the spacing. The first hue is always `base_hue`.
+#[must_use]
+pub fn generate_fibonacci_hues(n: usize, base_hue: f32, seed: u64) -> Vec<f32> {
+    let mut hues = Vec::with_capacity(n);
+    let mut hue = norm(base_hue);
+    for i in 0..n {
+        if i > 0 {
+            let nibble = (seed >> ((i - 1) % 16 * 4)) & 0xF;
+            hue = norm((nibble as f32).mul_add(0.2, hue + GOLDEN_ANGLE));
+        }
+        hues.push(hue);
+    }
+    hues
+}
+
+/// Number of hues in the [`PatternKind::Penrose`] pattern.
+const PENROSE_COUNT: usize = 10;
+
+/// Generate `n` hues from the five-fold symmetry of a Penrose tiling.
+///
+/// Hues come in rounds of five, 72 degrees apart like the edges of a
+/// pentagrid. Round `j` is rotated by `frac(j / phi)` of a 72-degree sector
+/// — an irrational rotation, so no round ever lines up with an earlier one
+/// and the hues fill the wheel evenly without repeating. Each nibble of
+/// `seed` nudges one round by up to 3 degrees. The first hue is always
+/// `base_hue`.
+#[must_use]
+pub fn generate_penrose_hues(n: usize, base_hue: f32, seed: u64) -> Vec<f32> {
+    const INV_PHI: f32 = 0.618_034;
+    (0..n)
+        .map(|k| {
+            let round = k / 5;
+            let offset = if round == 0 {
+                0.0
+            } else {
+                let nibble = (seed >> ((round - 1) % 16 * 4)) & 0xF;
+                (round as f32 * INV_PHI).fract().mul_add(72.0, nibble as f32 * 0.2)
+            };
+            norm(((k % 5) as f32).mul_add(72.0, offset + base_hue))
+        })
+        .collect()
+}
+
 /// Core pattern generation dispatch.
 fn generate(kind: PatternKind, base: f32) -> Vec<f32> {
     match kind {
         PatternKind::GoldenRatio => {
-            // Golden angle = 360 / phi^2 ≈ 137.508
-            const GOLDEN_ANGLE: f32 = 137.507_76;
             (0..8).map(|i| norm((i as f32).mul_add(GOLDEN_ANGLE, base))).collect()
         }
-        PatternKind::Fibonacci => {
-            let fibs: [f32; 10] = [1.0, 1.0, 2.0, 3.0, 5.0, 8.0, 13.0, 21.0, 34.0, 55.0];
-            fibs.iter().map(|&f| norm(base + 360.0 / f)).collect()
-        }
+        PatternKind::Fibonacci => generate_fibonacci_hues(FIBONACCI_COUNT, base, 0),
         PatternKind::Triadic => {
             vec![norm(base), norm(base + 120.0), norm(base + 240.0), norm(base + 60.0)]
         }
@@ -298,6 +365,7 @@ fn generate(kind: PatternKind, base: f32) -> Vec<f32> {
             }
             v
         }
+        PatternKind::Penrose => generate_penrose_hues(PENROSE_COUNT, base, 0),
         PatternKind::Monochromatic => {
             vec![norm(base)]
         }
@@ -325,7 +393,8 @@ mod tests {
             PatternKind::SriYantra, PatternKind::Torus,
             PatternKind::SolfeggioAll, PatternKind::DivineProportion,
             PatternKind::SeedOfLife, PatternKind::HarmonicResonance,
-            PatternKind::PhiGrid, PatternKind::Monochromatic,
+            PatternKind::PhiGrid, PatternKind::Penrose,
+            PatternKind::Monochromatic,
         ];
         for kind in all {
             let hues = kind.generate(270.0);
@@ -384,6 +453,60 @@ mod tests {
         assert_eq!(PatternKind::Fibonacci.generate(0.0).len(), 10);
     }
 
+    /// Smallest distance between any two hues, around the wheel.
+    fn min_gap(hues: &[f32]) -> f32 {
+        let mut min = f32::MAX;
+        for (i, a) in hues.iter().enumerate() {
+            for b in &hues[i + 1..] {
+                let d = (a - b).abs();
+                min = min.min(d.min(360.0 - d));
+            }
+        }
+        min
+    }
+
+    /// Fibonacci hues start at the base and never cluster.
+    #[test]
+    fn fibonacci_hues_spread() {
+        for seed in [0, 1, 42, 0xFFFF_FFFF, u64::MAX] {
+            for base in [0.0, 137.0, 300.0] {
+                let hues = generate_fibonacci_hues(8, base, seed);
+                assert_eq!(hues.len(), 8);
+                assert!((hues[0] - base).abs() < 0.01, "seed={seed} base={base}");
+                let gap = min_gap(&hues);
+                assert!(gap >= 15.0, "seed={seed} base={base} gap={gap}");
+            }
+        }
+    }
+
+    /// The seed varies the hues; seed 0 matches the plain pattern.
+    #[test]
+    fn fibonacci_seeded() {
+        let plain = PatternKind::Fibonacci.generate(200.0);
+        assert_eq!(PatternKind::Fibonacci.generate_seeded(200.0, 0), plain);
+        assert_ne!(PatternKind::Fibonacci.generate_seeded(200.0, 37), plain);
+        // Other patterns ignore the seed.
+        assert_eq!(
+            PatternKind::Triadic.generate_seeded(200.0, 37),
+            PatternKind::Triadic.generate(200.0),
+        );
+    }
+
+    /// Penrose hues are all distinct, start at the base and keep apart.
+    #[test]
+    fn penrose_hues_spread() {
+        for seed in [0, 7, 89, u64::MAX] {
+            for n in [5, 10, 15, 20] {
+                let hues = generate_penrose_hues(n, 90.0, seed);
+                assert_eq!(hues.len(), n);
+                assert!((hues[0] - 90.0).abs() < 0.01);
+                let gap = min_gap(&hues);
+                assert!(gap > 5.0, "seed={seed} n={n} gap={gap}");
+            }
+        }
+        assert_eq!(PatternKind::Penrose.generate(0.0).len(), 10);
+    }
+
     /// Triadic produces 4 hues.
     #[test]
     fn triadic_count() {
@@ -10,7 +10,7 @@
 
 use n_term::color::Color;
 
-use crate::contrast::{adjust_comment_color, ensure_readability};
+use crate::contrast::{ContrastLevel, adjust_comment_color_for, ensure_readability};
 
 // ---------------------------------------------------------------------------
 // Xorshift32 (same as palette.rs, duplicated to avoid cross-module dep)
@@ -144,9 +144,26 @@ impl SyntaxPalette {
         ac2_hue: f32,
         is_dark: bool,
         seed: u32,
+    ) -> Self {
+        Self::generate_with_contrast(hues, bg1, bg3, ac1_hue, ac2_hue, is_dark, seed, ContrastLevel::Aa)
+    }
+
+    /// [`generate`](Self::generate) with every token color held to the
+    /// given contrast `level` against `bg1`.
+    #[must_use]
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_with_contrast(
+        hues: &[f32],
+        bg1: Color,
+        bg3: Color,
+        ac1_hue: f32,
+        ac2_hue: f32,
+        is_dark: bool,
+        seed: u32,
+        level: ContrastLevel,
     ) -> Self {
         let mut rng = Xorshift32::new(seed.wrapping_add(0x5678));
-        let min_ratio = 5.5;
+        let min_ratio = level.text_ratio();
 
         // Pick diverse hues from the pattern FIRST (before make_color borrows rng).
         let method_hue = *rng.pick(hues);
@@ -167,7 +184,7 @@ impl SyntaxPalette {
         let comment_chroma = rng.range_f32(0.01, 0.04);
         let comment_hue = *rng.pick(hues);
         let comment_base = Color::oklch(0.50, comment_chroma, comment_hue);
-        let comment = adjust_comment_color(comment_base, bg1, bg3, is_dark);
+        let comment = adjust_comment_color_for(comment_base, bg1, bg3, is_dark, level);
 
         // Helper: make a readable color at a given hue with random variation.
         let mut make_color = |hue: f32| -> Color {